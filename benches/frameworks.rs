@@ -0,0 +1,234 @@
+//! Benchmarks that compile entire frameworks.
+//!
+//! The sources of Bootstrap, Bulma and Foundation are too large to check in,
+//! so each framework is cloned at a pinned tag into `benches/frameworks/<name>`
+//! the first time the benchmark runs. The benchmark fails if a framework can't
+//! be cloned, e.g. because there is no network access, or if its directory
+//! isn't a checkout of the pinned tag. `many_extends` approximates the
+//! `@extend` graph of such a framework.
+//!
+//! To catch regressions, save a baseline on the base branch and compare
+//! against it:
+//!
+//! ```text
+//! cargo bench --features bench --bench frameworks -- --save-baseline main
+//! cargo bench --features bench --bench frameworks -- --baseline main
+//! ```
+//!
+//! The second run fails if any benchmark got slower than the baseline by more
+//! than `GRASS_BENCH_MAX_REGRESSION` percent (5 by default), even at the lower
+//! bound of criterion's confidence interval.
+use std::{
+    env,
+    fmt::Write,
+    fs, iter,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use criterion::{black_box, Criterion};
+
+/// A framework to benchmark
+struct Framework {
+    name: &'static str,
+    repository: &'static str,
+    tag: &'static str,
+    /// The path of its entry point, relative to its checkout
+    entry: &'static str,
+}
+
+const FRAMEWORKS: [Framework; 3] = [
+    Framework {
+        name: "bootstrap",
+        repository: "https://github.com/twbs/bootstrap.git",
+        tag: "v4.6.2",
+        entry: "scss/bootstrap.scss",
+    },
+    Framework {
+        name: "bulma",
+        repository: "https://github.com/jgthms/bulma.git",
+        tag: "0.9.4",
+        entry: "bulma.sass",
+    },
+    Framework {
+        name: "foundation",
+        repository: "https://github.com/foundation/foundation-sites.git",
+        tag: "v6.7.5",
+        entry: "assets/foundation.scss",
+    },
+];
+
+/// The number of placeholder selectors in `many_extends`
+const PLACEHOLDERS: usize = 40;
+
+/// The number of style rules in `many_extends`, each of which extends
+/// `.component` and two of the placeholder selectors
+const COMPONENTS: usize = 200;
+
+/// The maximum slowdown, in percent, if `GRASS_BENCH_MAX_REGRESSION` isn't set
+const DEFAULT_MAX_REGRESSION: f64 = 5.0;
+
+fn many_extends_input() -> String {
+    let mut input =
+        String::from(".component {\n  display: block;\n\n  &:hover {\n    opacity: 0.5;\n  }\n}\n");
+
+    for i in 0..PLACEHOLDERS {
+        write!(
+            input,
+            "\n%placeholder-{0} {{\n  color: red;\n\n  .parent-{0} & {{\n    color: blue;\n  }}\n}}\n",
+            i
+        )
+        .unwrap();
+    }
+
+    for i in 0..COMPONENTS {
+        write!(
+            input,
+            "\n.component-{} {{\n  @extend .component;\n  @extend %placeholder-{};\n  width: {}px;\n\n  .child-{} > & {{\n    @extend %placeholder-{};\n  }}\n}}\n",
+            i,
+            i % PLACEHOLDERS,
+            i,
+            i % 10,
+            i * 3 % PLACEHOLDERS
+        )
+        .unwrap();
+    }
+
+    input
+}
+
+fn many_extends(c: &mut Criterion) {
+    let input = many_extends_input();
+    c.bench_function("many_extends", |b| {
+        b.iter(|| grass::from_string(black_box(input.clone()), &Default::default()))
+    });
+}
+
+/// Clones `framework` into `dir` if it isn't there yet, panicking if that
+/// fails or `dir` doesn't contain the pinned version of it
+fn fetch(framework: &Framework, dir: &Path) {
+    if !dir.exists() {
+        eprintln!("fetching {} {}", framework.name, framework.tag);
+        let cloned = Command::new("git")
+            .args(&["clone", "--quiet", "--depth", "1", "--branch"])
+            .arg(framework.tag)
+            .arg(framework.repository)
+            .arg(dir)
+            .status()
+            .map_or(false, |status| status.success());
+        if !cloned {
+            let _ = fs::remove_dir_all(dir);
+            panic!(
+                "could not clone {} {} from {}",
+                framework.name, framework.tag, framework.repository
+            );
+        }
+    }
+
+    let tag = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(&["describe", "--tags", "--exact-match"])
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if tag.as_deref().map(str::trim) != Some(framework.tag) {
+        panic!(
+            "{} is not a checkout of {} {}; remove it to fetch it again",
+            dir.display(),
+            framework.name,
+            framework.tag
+        );
+    }
+}
+
+fn frameworks(c: &mut Criterion) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/frameworks");
+
+    for framework in &FRAMEWORKS {
+        let dir = root.join(framework.name);
+        fetch(framework, &dir);
+
+        let path = dir.join(framework.entry);
+        let path = path.to_str().unwrap();
+        let options = grass::Options::default().load_path(&dir);
+
+        // a framework that fails to compile would only measure how quickly
+        // the error is found
+        if let Err(e) = grass::from_path(path, &options) {
+            panic!("failed to compile {}: {}", framework.name, e);
+        }
+
+        c.bench_function(framework.name, |b| {
+            b.iter(|| grass::from_path(black_box(path), &options))
+        });
+    }
+}
+
+/// The directory criterion writes its results to
+fn criterion_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(dir);
+    }
+    env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(
+            || Path::new(env!("CARGO_MANIFEST_DIR")).join("target"),
+            PathBuf::from,
+        )
+        .join("criterion")
+}
+
+/// Returns the benchmarks that got slower than the baseline they were
+/// compared against by more than the maximum regression, along with the
+/// lower bound of their slowdown in percent
+///
+/// Benchmarks that weren't compared against anything are ignored.
+fn regressions(max_regression: f64) -> Vec<(&'static str, f64)> {
+    let dir = criterion_dir();
+    let mut regressions = Vec::new();
+
+    let names = iter::once("many_extends").chain(FRAMEWORKS.iter().map(|framework| framework.name));
+    for name in names {
+        let estimates = match fs::read_to_string(dir.join(name).join("change/estimates.json")) {
+            Ok(estimates) => estimates,
+            Err(..) => continue,
+        };
+        let estimates: serde_json::Value = serde_json::from_str(&estimates).unwrap();
+        let lower_bound = estimates["mean"]["confidence_interval"]["lower_bound"]
+            .as_f64()
+            .unwrap()
+            * 100.0;
+
+        if lower_bound > max_regression {
+            regressions.push((name, lower_bound));
+        }
+    }
+
+    regressions
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args();
+
+    many_extends(&mut c);
+    frameworks(&mut c);
+
+    c.final_summary();
+
+    let max_regression =
+        env::var("GRASS_BENCH_MAX_REGRESSION").map_or(DEFAULT_MAX_REGRESSION, |max| {
+            max.parse()
+                .expect("GRASS_BENCH_MAX_REGRESSION must be a number")
+        });
+
+    let regressions = regressions(max_regression);
+    if !regressions.is_empty() {
+        for (name, slowdown) in regressions {
+            eprintln!(
+                "{} regressed by at least {:.1}%, more than the maximum of {}%",
+                name, slowdown, max_regression
+            );
+        }
+        process::exit(1);
+    }
+}
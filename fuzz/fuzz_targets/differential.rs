@@ -0,0 +1,15 @@
+#![no_main]
+use grass::differential::{compare, DartSass};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let options = grass::Options::default();
+
+        if let Some(difference) = compare(s, &options, &DartSass::from_env()).unwrap() {
+            if difference.is_miscompilation() {
+                panic!("{}", difference);
+            }
+        }
+    }
+});
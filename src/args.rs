@@ -1,11 +1,10 @@
-use std::collections::HashMap;
-
 use codemap::{Span, Spanned};
 
 use crate::{
     common::Identifier,
     error::SassResult,
-    value::Value,
+    hash::HashMap,
+    value::{ArgList, Value},
     {Cow, Token},
 };
 
@@ -61,7 +60,7 @@ impl CallArg {
 
 impl CallArgs {
     pub fn new(span: Span) -> Self {
-        CallArgs(HashMap::new(), span)
+        CallArgs(HashMap::default(), span)
     }
 
     pub fn to_css_string(self) -> SassResult<Spanned<String>> {
@@ -170,7 +169,53 @@ impl CallArgs {
     }
 
     pub fn max_args(&self, max: usize) -> SassResult<()> {
-        let len = self.len();
+        self.check_arg_count(max, self.len())
+    }
+
+    /// Like `max_args`, but only counts positional arguments
+    ///
+    /// Named arguments that don't match a parameter are reported separately
+    /// by `no_unknown_named_args`
+    pub fn max_positional_args(&self, max: usize) -> SassResult<()> {
+        let positional = self
+            .0
+            .keys()
+            .filter(|arg| matches!(arg, CallArg::Positional(..)))
+            .count();
+        self.check_arg_count(max, positional)
+    }
+
+    /// Returns an error naming any named arguments that have not yet been
+    /// consumed
+    pub fn no_unknown_named_args(&self) -> SassResult<()> {
+        let mut names: Vec<String> = self
+            .0
+            .keys()
+            .filter_map(|arg| match arg {
+                CallArg::Named(name) => Some(format!("${}", name)),
+                CallArg::Positional(..) => None,
+            })
+            .collect();
+
+        names.sort();
+
+        let last = match names.pop() {
+            Some(last) => last,
+            None => return Ok(()),
+        };
+
+        if names.is_empty() {
+            return Err((format!("No argument named {}.", last), self.span()).into());
+        }
+
+        Err((
+            format!("No arguments named {} or {}.", names.join(", "), last),
+            self.span(),
+        )
+            .into())
+    }
+
+    fn check_arg_count(&self, max: usize, len: usize) -> SassResult<()> {
         if len > max {
             let mut err = String::with_capacity(50);
             err.push_str(&format!("Only {} argument", max));
@@ -218,6 +263,37 @@ impl CallArgs {
         })
     }
 
+    /// Collect the remaining arguments into the argument list bound to a
+    /// rest parameter
+    ///
+    /// Named arguments become the keywords of the argument list, ordered by
+    /// where they appear in the source
+    pub fn into_arg_list(self) -> SassResult<ArgList> {
+        let mut positional = Vec::new();
+        let mut keywords = Vec::new();
+
+        for (arg, value) in self.0 {
+            match arg {
+                CallArg::Positional(idx) => positional.push((idx, value?)),
+                CallArg::Named(name) => keywords.push((name, value?)),
+            }
+        }
+
+        positional.sort_by_key(|(idx, _)| *idx);
+        keywords.sort_by(|(name1, value1), (name2, value2)| {
+            value1
+                .span
+                .low()
+                .cmp(&value2.span.low())
+                .then_with(|| name1.as_str().cmp(name2.as_str()))
+        });
+
+        Ok(ArgList::new(
+            positional.into_iter().map(|(_, value)| value).collect(),
+            keywords,
+        ))
+    }
+
     pub fn get_variadic(self) -> SassResult<Vec<Spanned<Value>>> {
         let mut vals = Vec::new();
         let mut args = match self
@@ -0,0 +1,260 @@
+use std::mem;
+
+use crate::parse::Stmt;
+
+use super::{media::MediaRule, SupportsRule, UnknownAtRule};
+
+/// Which of the rules enclosing an `@at-root` rule its contents stay inside
+/// of, such as `(without: media)` or `(with: supports)`
+///
+/// Names are either the names of at-rules, `rule` for style rules, or `all`
+#[derive(Debug, Clone)]
+pub(crate) struct AtRootQuery {
+    /// Whether `names` are the rules that are kept, as written with `with`,
+    /// rather than the rules that are left, as written with `without`
+    include: bool,
+    names: Vec<String>,
+}
+
+impl AtRootQuery {
+    pub const fn new(include: bool, names: Vec<String>) -> Self {
+        AtRootQuery { include, names }
+    }
+
+    /// Whether the contents are moved out of at-rules called `name`
+    pub fn excludes_name(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == "all" || n == name) != self.include
+    }
+
+    /// Whether the contents are moved out of the style rules enclosing them
+    pub fn excludes_style_rules(&self) -> bool {
+        self.excludes_name("rule")
+    }
+}
+
+impl Default for AtRootQuery {
+    /// `(without: rule)`, the query of an `@at-root` rule without one
+    fn default() -> Self {
+        AtRootQuery::new(false, vec!["rule".to_owned()])
+    }
+}
+
+/// An at-rule enclosing an `@at-root` rule, without its body, so that it can
+/// be recreated around the contents of the `@at-root` rule if they stay
+/// inside of it
+#[derive(Debug, Clone)]
+enum Parent {
+    Media(MediaRule),
+    Supports(SupportsRule),
+    UnknownAtRule(UnknownAtRule),
+}
+
+impl Parent {
+    fn name(&self) -> &str {
+        match self {
+            Parent::Media(..) => "media",
+            Parent::Supports(..) => "supports",
+            Parent::UnknownAtRule(rule) => &rule.name,
+        }
+    }
+
+    fn with_body(&self, body: Vec<Stmt>) -> Stmt {
+        match self.clone() {
+            Parent::Media(mut media) => {
+                media.body = body;
+                Stmt::Media(Box::new(media))
+            }
+            Parent::Supports(mut supports) => {
+                supports.body = body;
+                Stmt::Supports(Box::new(supports))
+            }
+            Parent::UnknownAtRule(mut rule) => {
+                rule.body = body;
+                Stmt::UnknownAtRule(Box::new(rule))
+            }
+        }
+    }
+}
+
+/// Replaces each `@at-root` rule with its contents, moving them out of the
+/// rules its query excludes
+///
+/// Like `dart-sass`, the contents are placed in the outermost run of
+/// enclosing at-rules that the query keeps, just after the statement that
+/// contained the `@at-root` rule. Any at-rules nested deeper than that which
+/// the query keeps are recreated around the contents.
+///
+/// Style rules and at-rules are emitted after the style rules that they're
+/// nested in, in order, so style rules don't enclose anything here, and
+/// contents that stay within an at-rule can stay where they are in the style
+/// rules nested in it. When the query keeps style rules, the contents were
+/// already nested in the enclosing style rule while parsing.
+pub(crate) fn hoist_at_root_rules(stmts: Vec<Stmt>) -> Vec<Stmt> {
+    let mut escaped = Vec::new();
+    let stmts = Hoister::default().body(stmts, 0, &mut escaped);
+    debug_assert!(escaped.is_empty());
+    stmts
+}
+
+#[derive(Default)]
+struct Hoister {
+    /// The at-rules enclosing the current statement that it will be emitted
+    /// inside of, outermost first, along with how many at-rules each is
+    /// nested in
+    parents: Vec<(Parent, usize)>,
+}
+
+impl Hoister {
+    /// Resolves the `@at-root` rules in `stmts`, which are nested in `level`
+    /// at-rules
+    ///
+    /// Contents that must move further out than `level` are added to
+    /// `escaped`, along with the level they move to.
+    fn body(
+        &mut self,
+        stmts: Vec<Stmt>,
+        level: usize,
+        escaped: &mut Vec<(usize, Stmt)>,
+    ) -> Vec<Stmt> {
+        let mut out = Vec::with_capacity(stmts.len());
+        for stmt in stmts {
+            let mut inner = Vec::new();
+            match stmt {
+                Stmt::RuleSet {
+                    selector,
+                    body,
+                    span,
+                } => {
+                    let body = self.body(body, level, &mut inner);
+                    out.push(Stmt::RuleSet {
+                        selector,
+                        body,
+                        span,
+                    });
+                }
+                Stmt::Media(mut media) => {
+                    let body = mem::take(&mut media.body);
+                    // a merged media rule replaces the one enclosing it
+                    let popped = if media.is_merged {
+                        let outermost_media = self
+                            .parents
+                            .iter()
+                            .rposition(|(parent, _)| !matches!(parent, Parent::Media(..)))
+                            .map_or(0, |idx| idx + 1);
+                        self.parents.split_off(outermost_media)
+                    } else {
+                        Vec::new()
+                    };
+                    media.body =
+                        self.enclosed(Parent::Media((*media).clone()), body, level, &mut inner);
+                    self.parents.extend(popped);
+                    out.push(Stmt::Media(media));
+                }
+                Stmt::Supports(mut supports) => {
+                    let body = mem::take(&mut supports.body);
+                    supports.body = self.enclosed(
+                        Parent::Supports((*supports).clone()),
+                        body,
+                        level,
+                        &mut inner,
+                    );
+                    out.push(Stmt::Supports(supports));
+                }
+                Stmt::UnknownAtRule(mut rule) if rule.has_body => {
+                    let body = mem::take(&mut rule.body);
+                    rule.body = self.enclosed(
+                        Parent::UnknownAtRule((*rule).clone()),
+                        body,
+                        level,
+                        &mut inner,
+                    );
+                    out.push(Stmt::UnknownAtRule(rule));
+                }
+                Stmt::AtRoot { query, body } => {
+                    self.at_root(&query, body, level, &mut out, &mut inner);
+                }
+                stmt => out.push(stmt),
+            }
+
+            for (target, stmt) in inner {
+                if target == level {
+                    out.push(stmt);
+                } else {
+                    escaped.push((target, stmt));
+                }
+            }
+        }
+        out
+    }
+
+    /// Resolves the body of `parent`, which is nested in `level` at-rules
+    fn enclosed(
+        &mut self,
+        parent: Parent,
+        body: Vec<Stmt>,
+        level: usize,
+        escaped: &mut Vec<(usize, Stmt)>,
+    ) -> Vec<Stmt> {
+        self.parents.push((parent, level));
+        let body = self.body(body, level + 1, escaped);
+        self.parents.pop();
+        body
+    }
+
+    fn at_root(
+        &mut self,
+        query: &AtRootQuery,
+        body: Vec<Stmt>,
+        level: usize,
+        out: &mut Vec<Stmt>,
+        escaped: &mut Vec<(usize, Stmt)>,
+    ) {
+        let kept = self
+            .parents
+            .iter()
+            .take_while(|(parent, _)| !query.excludes_name(parent.name()))
+            .count();
+
+        let target = match kept.checked_sub(1) {
+            Some(idx) => self.parents[idx].1 + 1,
+            None => 0,
+        };
+
+        let outer = self.parents.split_off(kept);
+        let copies = outer
+            .iter()
+            .filter(|(parent, _)| !query.excludes_name(parent.name()))
+            .map(|(parent, _)| parent.clone())
+            .collect::<Vec<Parent>>();
+        self.parents.extend(
+            copies
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(idx, parent)| (parent, target + idx)),
+        );
+
+        let mut inner = Vec::new();
+        let mut stmts = self.body(body, target + copies.len(), &mut inner);
+
+        self.parents.truncate(kept);
+        self.parents.extend(outer);
+
+        for (idx, copy) in copies.iter().enumerate().rev() {
+            stmts = vec![copy.with_body(stmts)];
+            let copy_level = target + idx;
+            let (here, further): (Vec<_>, Vec<_>) = inner
+                .into_iter()
+                .partition(|(level, _)| *level == copy_level);
+            stmts.extend(here.into_iter().map(|(_, stmt)| stmt));
+            inner = further;
+        }
+
+        if target == level {
+            out.extend(stmts);
+        } else {
+            escaped.extend(stmts.into_iter().map(|stmt| (target, stmt)));
+        }
+        escaped.extend(inner);
+    }
+}
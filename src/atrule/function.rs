@@ -1,14 +1,26 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    cell::RefCell,
+    hash::{Hash, Hasher},
+    rc::Weak,
+};
 
 use codemap::Span;
 
-use crate::{args::FuncArgs, Token};
+use crate::{args::FuncArgs, scope::Scope, Token};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Function {
     pub args: FuncArgs,
     pub body: Vec<Token>,
     pub declared_at_root: bool,
+
+    /// The global scope of the module this function was declared in, if it
+    /// was declared at the root of a module loaded with `@use`
+    ///
+    /// The function is evaluated with this as its global scope, so that it
+    /// sees the module's variables and functions rather than those of the
+    /// stylesheet that calls it.
+    pub closure: Option<Weak<RefCell<Scope>>>,
     pos: Span,
 }
 
@@ -33,6 +45,7 @@ impl Function {
             body,
             pos,
             declared_at_root,
+            closure: None,
         }
     }
 }
@@ -65,10 +65,36 @@ pub enum AtRuleKind {
     Keyframes,
     Media,
 
+    /// Declares a cascade layer, or adds styles to one
+    Layer,
+
+    /// Applies its content to elements whose container matches a condition
+    Container,
+
     /// An unknown at-rule
     Unknown(String),
 }
 
+impl AtRuleKind {
+    /// Whether this at-rule is specific to Sass, and so isn't allowed in
+    /// plain CSS
+    ///
+    /// `@import` is allowed, although it's always a plain CSS import there.
+    pub fn is_sass_specific(&self) -> bool {
+        !matches!(
+            self,
+            Self::Import
+                | Self::Charset
+                | Self::Supports
+                | Self::Keyframes
+                | Self::Media
+                | Self::Layer
+                | Self::Container
+                | Self::Unknown(..)
+        )
+    }
+}
+
 impl TryFrom<&Spanned<String>> for AtRuleKind {
     type Error = Box<SassError>;
     fn try_from(c: &Spanned<String>) -> Result<Self, Box<SassError>> {
@@ -93,6 +119,8 @@ impl TryFrom<&Spanned<String>> for AtRuleKind {
             "supports" => return Ok(Self::Supports),
             "content" => return Ok(Self::Content),
             "media" => return Ok(Self::Media),
+            "layer" => return Ok(Self::Layer),
+            "container" => return Ok(Self::Container),
             "else" => return Err(("This at-rule is not allowed here.", c.span).into()),
             "" => return Err(("Expected identifier.", c.span).into()),
             _ => {}
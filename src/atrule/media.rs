@@ -1,16 +1,22 @@
 use std::fmt;
 
-use crate::{parse::Stmt, selector::Selector};
+use codemap::Span;
+
+use crate::{error::SassResult, parse::Stmt, selector::Selector};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MediaRule {
     pub super_selector: Selector,
-    pub query: String,
+    pub query: Vec<CssMediaQuery>,
     pub body: Vec<Stmt>,
+    /// Whether `query` is the result of merging this rule's queries with those
+    /// of an enclosing media rule, in which case this rule is emitted after
+    /// the enclosing rule rather than inside of it
+    pub is_merged: bool,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-pub(crate) struct MediaQuery {
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub(crate) struct CssMediaQuery {
     /// The modifier, probably either "not" or "only".
     ///
     /// This may be `None` if no modifier is in use.
@@ -23,40 +29,226 @@ pub(crate) struct MediaQuery {
 
     /// Feature queries, including parentheses.
     pub features: Vec<String>,
+
+    /// Whether `features` are joined by "and" rather than "or"
+    pub conjunction: bool,
 }
 
-#[allow(dead_code)]
-impl MediaQuery {
-    pub fn is_condition(&self) -> bool {
-        self.modifier.is_none() && self.media_type.is_none()
-    }
+/// The result of intersecting two media queries
+enum MediaQueryMergeResult {
+    /// The queries have no intersection
+    Empty,
+    /// The queries have an intersection, but it can't be represented in CSS
+    Unrepresentable,
+    Query(CssMediaQuery),
+}
 
+impl CssMediaQuery {
     pub fn matches_all_types(&self) -> bool {
-        self.media_type.is_none()
-            || self
-                .media_type
-                .as_ref()
-                .map_or(false, |v| v.to_ascii_lowercase() == "all")
+        self.media_type
+            .as_ref()
+            .map_or(true, |v| v.eq_ignore_ascii_case("all"))
     }
 
-    pub fn condition(features: Vec<String>) -> Self {
+    pub fn condition(features: Vec<String>, conjunction: bool) -> Self {
         Self {
             modifier: None,
             media_type: None,
             features,
+            conjunction,
         }
     }
 
-    #[allow(dead_code, unused_variables)]
-    pub fn merge(other: &Self) -> Self {
-        todo!()
+    /// Parses a comma-separated list of media queries from text in which all
+    /// interpolation has already been resolved
+    pub fn parse_list(list: &str, span: Span) -> SassResult<Vec<Self>> {
+        let mut queries = Vec::new();
+        let mut scanner = QueryScanner {
+            chars: list.chars().collect(),
+            idx: 0,
+            span,
+        };
+
+        loop {
+            scanner.whitespace();
+            queries.push(scanner.parse_query()?);
+            scanner.whitespace();
+            if !scanner.scan_char(',') {
+                break;
+            }
+        }
+
+        if scanner.idx != scanner.chars.len() {
+            return Err(("expected \"{\".", span).into());
+        }
+
+        Ok(queries)
+    }
+
+    /// Merges two lists of media queries, returning the queries matched by
+    /// both lists
+    ///
+    /// Returns `None` if the intersection can't be represented in CSS, and an
+    /// empty list if the lists have no intersection.
+    pub fn merge_lists(queries1: &[Self], queries2: &[Self]) -> Option<Vec<Self>> {
+        let mut queries = Vec::new();
+
+        for query1 in queries1 {
+            for query2 in queries2 {
+                match query1.merge(query2) {
+                    MediaQueryMergeResult::Empty => continue,
+                    MediaQueryMergeResult::Unrepresentable => return None,
+                    MediaQueryMergeResult::Query(query) => queries.push(query),
+                }
+            }
+        }
+
+        Some(queries)
+    }
+
+    fn merge(&self, other: &Self) -> MediaQueryMergeResult {
+        if !self.conjunction || !other.conjunction {
+            return MediaQueryMergeResult::Unrepresentable;
+        }
+
+        let our_modifier = self.modifier.as_ref().map(|m| m.to_ascii_lowercase());
+        let our_type = self.media_type.as_ref().map(|t| t.to_ascii_lowercase());
+        let their_modifier = other.modifier.as_ref().map(|m| m.to_ascii_lowercase());
+        let their_type = other.media_type.as_ref().map(|t| t.to_ascii_lowercase());
+
+        if our_type.is_none() && their_type.is_none() {
+            return MediaQueryMergeResult::Query(Self::condition(
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+                true,
+            ));
+        }
+
+        let we_are_negated = our_modifier.as_deref() == Some("not");
+        let they_are_negated = their_modifier.as_deref() == Some("not");
+
+        let (modifier, media_type, features) = if we_are_negated != they_are_negated {
+            if our_type == their_type {
+                let (negative_features, positive_features) = if we_are_negated {
+                    (&self.features, &other.features)
+                } else {
+                    (&other.features, &self.features)
+                };
+
+                // If the negative features are a subset of the positive features, the
+                // query is empty. For example, `not screen and (color)` has no
+                // intersection with `screen and (color) and (grid)`.
+                //
+                // However, `not screen and (color)` *does* intersect with `screen and
+                // (grid)`, because it means `not (screen and (color))` and so it allows
+                // a screen with no color but with a grid.
+                return if negative_features
+                    .iter()
+                    .all(|feat| positive_features.contains(feat))
+                {
+                    MediaQueryMergeResult::Empty
+                } else {
+                    MediaQueryMergeResult::Unrepresentable
+                };
+            } else if self.matches_all_types() || other.matches_all_types() {
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+
+            if we_are_negated {
+                (their_modifier, their_type, other.features.clone())
+            } else {
+                (our_modifier, our_type, self.features.clone())
+            }
+        } else if we_are_negated {
+            // CSS has no way of representing "neither screen nor print".
+            if our_type != their_type {
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+
+            let (more_features, fewer_features) = if self.features.len() > other.features.len() {
+                (&self.features, &other.features)
+            } else {
+                (&other.features, &self.features)
+            };
+
+            // If one set of features is a superset of the other, use those features
+            // because they're strictly narrower.
+            if fewer_features
+                .iter()
+                .all(|feat| more_features.contains(feat))
+            {
+                (our_modifier, our_type, more_features.clone())
+            } else {
+                // Otherwise, there's no way to represent the intersection.
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+        } else if self.matches_all_types() {
+            // Omit the type if either input query did, since that indicates that they
+            // aren't targeting a browser that requires "all and".
+            let media_type = if other.matches_all_types() && our_type.is_none() {
+                None
+            } else {
+                their_type
+            };
+            (
+                their_modifier,
+                media_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        } else if other.matches_all_types() {
+            (
+                our_modifier,
+                our_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        } else if our_type != their_type {
+            return MediaQueryMergeResult::Empty;
+        } else {
+            (
+                our_modifier.or(their_modifier),
+                our_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        // preserve the case of the original queries
+        MediaQueryMergeResult::Query(CssMediaQuery {
+            media_type: if media_type == self.media_type.as_ref().map(|t| t.to_ascii_lowercase()) {
+                self.media_type.clone()
+            } else {
+                other.media_type.clone()
+            },
+            modifier: if modifier == self.modifier.as_ref().map(|m| m.to_ascii_lowercase()) {
+                self.modifier.clone()
+            } else {
+                other.modifier.clone()
+            },
+            features,
+            conjunction: true,
+        })
     }
 }
 
-impl fmt::Display for MediaQuery {
+impl fmt::Display for CssMediaQuery {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(modifier) = &self.modifier {
             f.write_str(modifier)?;
+            f.write_str(" ")?;
         }
         if let Some(media_type) = &self.media_type {
             f.write_str(media_type)?;
@@ -64,6 +256,193 @@ impl fmt::Display for MediaQuery {
                 f.write_str(" and ")?;
             }
         }
-        f.write_str(&self.features.join(" and "))
+        f.write_str(
+            &self
+                .features
+                .join(if self.conjunction { " and " } else { " or " }),
+        )
+    }
+}
+
+/// A minimal scanner over the text of an evaluated media query list
+struct QueryScanner {
+    chars: Vec<char>,
+    idx: usize,
+    span: Span,
+}
+
+impl QueryScanner {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.idx).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.idx += 1;
+        }
+    }
+
+    fn scan_char(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn identifier(&mut self) -> SassResult<String> {
+        let start = self.idx;
+        while matches!(self.peek(), Some(c) if !c.is_whitespace() && !matches!(c, '(' | ')' | ',' | '{'))
+        {
+            self.idx += 1;
+        }
+        if start == self.idx {
+            return Err(("Expected identifier.", self.span).into());
+        }
+        Ok(self.chars[start..self.idx].iter().collect())
+    }
+
+    /// Consumes `ident` if it is next, case-insensitively
+    fn scan_identifier(&mut self, ident: &str) -> bool {
+        let start = self.idx;
+        match self.identifier() {
+            Ok(found) if found.eq_ignore_ascii_case(ident) => true,
+            _ => {
+                self.idx = start;
+                false
+            }
+        }
+    }
+
+    fn parse_query(&mut self) -> SassResult<CssMediaQuery> {
+        let mut modifier = None;
+        let mut media_type = None;
+
+        if self.peek() != Some('(') {
+            let start = self.idx;
+            let identifier1 = self.identifier()?;
+            self.whitespace();
+
+            // a negated condition, such as `not (color)`
+            if identifier1.eq_ignore_ascii_case("not") && self.peek() == Some('(') {
+                self.idx = start;
+                return self.parse_condition();
+            }
+
+            if !matches!(self.peek(), Some(c) if c != '(' && c != ',') {
+                return Ok(CssMediaQuery {
+                    modifier: None,
+                    media_type: Some(identifier1),
+                    features: Vec::new(),
+                    conjunction: true,
+                });
+            }
+
+            let identifier2 = self.identifier()?;
+            self.whitespace();
+
+            if identifier2.eq_ignore_ascii_case("and") {
+                media_type = Some(identifier1);
+            } else {
+                modifier = Some(identifier1);
+                media_type = Some(identifier2);
+
+                if !self.scan_identifier("and") {
+                    return Ok(CssMediaQuery {
+                        modifier,
+                        media_type,
+                        features: Vec::new(),
+                        conjunction: true,
+                    });
+                }
+                self.whitespace();
+            }
+
+            let mut features = Vec::new();
+            loop {
+                features.push(self.parse_feature()?);
+                self.whitespace();
+                if !self.scan_identifier("and") {
+                    break;
+                }
+                self.whitespace();
+            }
+
+            return Ok(CssMediaQuery {
+                modifier,
+                media_type,
+                features,
+                conjunction: true,
+            });
+        }
+
+        self.parse_condition()
+    }
+
+    /// Parses a media condition without a media type, such as `(a) and (b)`
+    /// or `(a) or (b)`
+    fn parse_condition(&mut self) -> SassResult<CssMediaQuery> {
+        let mut features = vec![self.parse_negatable_feature()?];
+        self.whitespace();
+
+        let conjunction = if self.scan_identifier("or") {
+            false
+        } else if self.scan_identifier("and") {
+            true
+        } else {
+            return Ok(CssMediaQuery::condition(features, true));
+        };
+
+        let operator = if conjunction { "and" } else { "or" };
+
+        loop {
+            self.whitespace();
+            features.push(self.parse_negatable_feature()?);
+            self.whitespace();
+            if !self.scan_identifier(operator) {
+                break;
+            }
+        }
+
+        Ok(CssMediaQuery::condition(features, conjunction))
+    }
+
+    fn parse_negatable_feature(&mut self) -> SassResult<String> {
+        if self.scan_identifier("not") {
+            self.whitespace();
+            return Ok(format!("not {}", self.parse_feature()?));
+        }
+
+        self.parse_feature()
+    }
+
+    /// Consumes a parenthesized feature query, including its parentheses
+    fn parse_feature(&mut self) -> SassResult<String> {
+        let start = self.idx;
+        if !self.scan_char('(') {
+            return Err(("expected \"(\".", self.span).into());
+        }
+
+        let mut depth = 1;
+        let mut quote = None;
+        while depth > 0 {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Err(("expected \")\".", self.span).into()),
+            };
+            self.idx += 1;
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(..), '\\') => self.idx += 1,
+                (Some(..), _) => {}
+                (None, '"') | (None, '\'') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth -= 1,
+                (None, _) => {}
+            }
+        }
+
+        Ok(self.chars[start..self.idx].iter().collect())
     }
 }
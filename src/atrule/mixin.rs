@@ -1,5 +1,7 @@
 use std::fmt;
 
+use codemap::Span;
+
 use crate::{
     args::{CallArgs, FuncArgs},
     error::SassResult,
@@ -7,12 +9,16 @@ use crate::{
     Token,
 };
 
-pub(crate) type BuiltinMixin = fn(CallArgs, &mut Parser<'_>) -> SassResult<Vec<Stmt>>;
+pub(crate) type BuiltinMixin =
+    fn(CallArgs, Option<ContentBlock>, &mut Parser<'_>) -> SassResult<Vec<Stmt>>;
 
 #[derive(Clone)]
 pub(crate) enum Mixin {
     UserDefined(UserDefinedMixin),
-    Builtin(BuiltinMixin),
+    Builtin {
+        body: BuiltinMixin,
+        accepts_content_block: bool,
+    },
 }
 
 impl fmt::Debug for Mixin {
@@ -25,7 +31,13 @@ impl fmt::Debug for Mixin {
                 .field("accepts_content_block", &u.accepts_content_block)
                 .field("declared_at_root", &u.declared_at_root)
                 .finish(),
-            Self::Builtin(..) => f.debug_struct("BuiltinMixin").finish(),
+            Self::Builtin {
+                accepts_content_block,
+                ..
+            } => f
+                .debug_struct("BuiltinMixin")
+                .field("accepts_content_block", accepts_content_block)
+                .finish(),
         }
     }
 }
@@ -36,22 +48,52 @@ impl Mixin {
         body: Vec<Token>,
         accepts_content_block: bool,
         declared_at_root: bool,
+        pos: Span,
     ) -> Self {
         Mixin::UserDefined(UserDefinedMixin::new(
             args,
             body,
             accepts_content_block,
             declared_at_root,
+            pos,
         ))
     }
+
+    /// Whether `@include` may pass a content block to this mixin
+    pub fn accepts_content_block(&self) -> bool {
+        match self {
+            Self::UserDefined(u) => u.accepts_content_block,
+            Self::Builtin {
+                accepts_content_block,
+                ..
+            } => *accepts_content_block,
+        }
+    }
+}
+
+/// Mixins are the same if they were declared by the same `@mixin` rule, or
+/// are the same builtin mixin
+impl PartialEq for Mixin {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::UserDefined(u1), Self::UserDefined(u2)) => u1.pos == u2.pos,
+            (Self::Builtin { body: b1, .. }, Self::Builtin { body: b2, .. }) => {
+                *b1 as usize == *b2 as usize
+            }
+            _ => false,
+        }
+    }
 }
 
+impl Eq for Mixin {}
+
 #[derive(Debug, Clone)]
 pub(crate) struct UserDefinedMixin {
     pub args: FuncArgs,
     pub body: Vec<Token>,
     pub accepts_content_block: bool,
     pub declared_at_root: bool,
+    pos: Span,
 }
 
 impl UserDefinedMixin {
@@ -60,16 +102,29 @@ impl UserDefinedMixin {
         body: Vec<Token>,
         accepts_content_block: bool,
         declared_at_root: bool,
+        pos: Span,
     ) -> Self {
         Self {
             args,
             body,
             accepts_content_block,
             declared_at_root,
+            pos,
         }
     }
 }
 
+/// The content block passed to a mixin by `@include`, before it has been
+/// evaluated
+#[derive(Debug, Clone)]
+pub(crate) struct ContentBlock {
+    /// The literal block, serialized as a list of tokens
+    pub body: Vec<Token>,
+
+    /// The arguments declared with `using`, e.g. `using ($a, $b)`
+    pub args: Option<FuncArgs>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Content {
     /// The literal block, serialized as a list of tokens
@@ -6,4 +6,7 @@ pub(crate) struct UnknownAtRule {
     pub super_selector: Selector,
     pub params: String,
     pub body: Vec<Stmt>,
+
+    /// Whether the rule has a block, as opposed to ending in a semicolon
+    pub has_body: bool,
 }
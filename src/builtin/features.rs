@@ -0,0 +1,53 @@
+//! The language features reported by `feature-exists()`
+//!
+//! <https://sass-lang.com/documentation/modules/meta#feature-exists>
+//!
+//! When support for a feature lands, its entry here should be updated in
+//! the same change.
+
+/// A language feature, and whether it is supported
+struct Feature {
+    name: &'static str,
+    supported: bool,
+}
+
+const FEATURES: &[Feature] = &[
+    // The Sass `@error` directive is supported.
+    Feature {
+        name: "at-error",
+        supported: true,
+    },
+    // Full support for unit arithmetic using units defined in the Values
+    // and Units Level 3 spec.
+    Feature {
+        name: "units-level-3",
+        supported: true,
+    },
+    // A local variable will shadow a global variable unless `!global` is
+    // used.
+    Feature {
+        name: "global-variable-shadowing",
+        supported: true,
+    },
+    // The `@extend` rule will affect selectors nested in pseudo-classes
+    // like `:not()`.
+    Feature {
+        name: "extend-selector-pseudoclass",
+        supported: true,
+    },
+    // The "Custom Properties Level 1" spec is supported. This means that
+    // custom properties are parsed statically, with only interpolation
+    // treated as SassScript.
+    Feature {
+        name: "custom-property",
+        supported: true,
+    },
+];
+
+/// Whether the feature called `name` is supported. Names are case sensitive,
+/// and unknown features are unsupported
+pub(crate) fn feature_exists(name: &str) -> bool {
+    FEATURES
+        .iter()
+        .any(|feature| feature.supported && feature.name == name)
+}
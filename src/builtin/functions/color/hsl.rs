@@ -1,215 +1,155 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    function_string, parse_channels, percentage_or_unitless, Builtin, Channels, GlobalFunctionMap,
+};
 
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 use num_traits::One;
 
 use crate::{
     args::CallArgs,
     color::Color,
     common::QuoteKind,
+    deprecation::Deprecation,
     error::SassResult,
     parse::Parser,
     unit::Unit,
     value::{Number, Value},
+    Cow,
 };
 
 fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    if args.is_empty() {
-        return Err(("Missing argument $channels.", args.span()).into());
-    }
+    args.max_args(4)?;
+    let span = args.span();
 
-    if args.len() == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
-            _ => return Err(("Missing argument $channels.", args.span()).into()),
-        };
+    match args.len() {
+        0 => Err(("Missing argument $channels.", span).into()),
+        1 => match parse_channels(
+            name,
+            ["hue", "saturation", "lightness"],
+            args.get_err(0, "channels")?,
+            span,
+        )? {
+            Channels::Special(v) => Ok(v),
+            Channels::Values(mut channels) => {
+                let lightness = channels.pop().unwrap();
+                let saturation = channels.pop().unwrap();
+                let hue = channels.pop().unwrap();
+                hsl_from_channels(name, hue, saturation, lightness, None, span, parser)
+            }
+        },
+        2 => {
+            let hue = args.get_err(0, "hue")?;
+            let saturation = args.get_err(1, "saturation")?;
 
-        if channels.len() > 3 {
-            return Err((
-                format!(
-                    "Only 3 elements allowed, but {} were passed.",
-                    channels.len()
-                ),
-                args.span(),
-            )
-                .into());
+            if hue.is_special_function() || saturation.is_special_function() {
+                return function_string(name, &[hue, saturation], span);
+            }
+
+            Err(("Missing argument $lightness.", span).into())
+        }
+        _ => {
+            let hue = args.get_err(0, "hue")?;
+            let saturation = args.get_err(1, "saturation")?;
+            let lightness = args.get_err(2, "lightness")?;
+            let alpha = match args.get(3, "alpha") {
+                Some(v) => Some(v?.node),
+                None => None,
+            };
+            hsl_from_channels(name, hue, saturation, lightness, alpha, span, parser)
         }
+    }
+}
 
-        let lightness = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n / Number::from(100),
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$lightness: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $lightness.", args.span()).into()),
-        };
+fn hsl_from_channels(
+    name: &str,
+    hue: Value,
+    saturation: Value,
+    lightness: Value,
+    alpha: Option<Value>,
+    span: Span,
+    parser: &mut Parser<'_>,
+) -> SassResult<Value> {
+    if hue.is_special_function()
+        || saturation.is_special_function()
+        || lightness.is_special_function()
+        || alpha.as_ref().map_or(false, Value::is_special_function)
+    {
+        let mut channels = vec![hue, saturation, lightness];
+        channels.extend(alpha);
+        return function_string(name, &channels, span);
+    }
 
-        let saturation = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n / Number::from(100),
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$saturation: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $saturation.", args.span()).into()),
-        };
+    // the units of the hue, saturation, and lightness are ignored
+    check_hue_unit(&hue, span, parser)?;
+    check_percent(&saturation, "saturation", span, parser)?;
+    check_percent(&lightness, "lightness", span, parser)?;
 
-        let hue = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n,
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $hue.", args.span()).into()),
-        };
+    let hue = assert_number(hue, "hue", span)?;
+    let saturation = assert_number(saturation, "saturation", span)? / Number::from(100);
+    let lightness = assert_number(lightness, "lightness", span)? / Number::from(100);
+    let alpha = match alpha {
+        Some(alpha) => percentage_or_unitless(alpha, 1, "alpha", span)?,
+        None => Number::one(),
+    };
 
-        Ok(Value::Color(Box::new(Color::from_hsla(
-            hue,
-            saturation,
-            lightness,
-            Number::one(),
-        ))))
-    } else {
-        let hue = match args.get_err(0, "hue")? {
-            Value::Dimension(Some(n), ..) => n,
-            Value::Dimension(None, ..) => todo!(),
-            v if v.is_special_function() => {
-                let saturation = args.get_err(1, "saturation")?;
-                let lightness = args.get_err(2, "lightness")?;
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    v.to_css_string(args.span())?,
-                    saturation.to_css_string(args.span())?,
-                    lightness.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let saturation = match args.get_err(1, "saturation")? {
-            Value::Dimension(Some(n), ..) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v if v.is_special_function() => {
-                let lightness = args.get_err(2, "lightness")?;
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    hue,
-                    v.to_css_string(args.span())?,
-                    lightness.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!(
-                        "$saturation: {} is not a number.",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let lightness = match args.get_err(2, "lightness")? {
-            Value::Dimension(Some(n), ..) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v if v.is_special_function() => {
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    hue,
-                    saturation,
-                    v.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!(
-                        "$lightness: {} is not a number.",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let alpha = match args.default_arg(
-            3,
-            "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
-        )? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$alpha: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {}, {}, {})",
-                        name,
-                        hue,
-                        saturation,
-                        lightness,
-                        v.to_css_string(args.span())?
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        Ok(Value::Color(Box::new(Color::from_hsla(
-            hue, saturation, lightness, alpha,
-        ))))
+    Ok(Value::Color(Box::new(Color::from_hsla(
+        hue, saturation, lightness, alpha,
+    ))))
+}
+
+/// Warns that a hue with a unit other than `deg` is deprecated, since the unit
+/// is ignored rather than converted
+fn check_hue_unit(hue: &Value, span: Span, parser: &mut Parser<'_>) -> SassResult<()> {
+    let unit = match hue {
+        Value::Dimension(Some(..), unit, ..) if *unit != Unit::None && *unit != Unit::Deg => unit,
+        _ => return Ok(()),
+    };
+
+    parser.warn_deprecation(
+        Deprecation::FunctionUnits,
+        &Spanned {
+            node: Cow::owned(format!(
+                "$hue: Passing a unit other than deg ({}) is deprecated.\n\nTo preserve current behavior: calc($hue / 1{})\n\nSee https://sass-lang.com/d/function-units",
+                hue.inspect(span)?,
+                unit
+            )),
+            span,
+        },
+    )
+}
+
+/// Warns that a saturation or lightness without the unit `%` is deprecated
+fn check_percent(value: &Value, name: &str, span: Span, parser: &mut Parser<'_>) -> SassResult<()> {
+    let suggestion = match value {
+        Value::Dimension(Some(..), Unit::Percent, ..) => return Ok(()),
+        Value::Dimension(Some(..), Unit::None, ..) => format!("${} * 1%", name),
+        Value::Dimension(Some(..), unit, ..) => format!("calc(${} / 1{} * 1%)", name, unit),
+        _ => return Ok(()),
+    };
+
+    parser.warn_deprecation(
+        Deprecation::FunctionUnits,
+        &Spanned {
+            node: Cow::owned(format!(
+                "${}: Passing a number without unit % ({}) is deprecated.\n\nTo preserve current behavior: {}\n\nMore info: https://sass-lang.com/d/function-units",
+                name,
+                value.inspect(span)?,
+                suggestion
+            )),
+            span,
+        },
+    )
+}
+
+fn assert_number(value: Value, name: &str, span: Span) -> SassResult<Number> {
+    match value {
+        Value::Dimension(Some(n), ..) => Ok(n),
+        Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", span).into()),
+        v => Err((
+            format!("${}: {} is not a number.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
     }
 }
 
@@ -300,7 +240,16 @@ fn lighten(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     };
     let amount = match args.get_err(1, "amount")? {
         Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(None, u, _) => {
+            return Err((
+                format!(
+                    "$amount: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         v => {
             return Err((
                 format!(
@@ -329,7 +278,16 @@ fn darken(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     };
     let amount = match args.get_err(1, "amount")? {
         Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(None, u, _) => {
+            return Err((
+                format!(
+                    "$amount: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         v => {
             return Err((
                 format!(
@@ -347,18 +305,35 @@ fn darken(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 fn saturate(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(2)?;
     if args.len() == 1 {
-        return Ok(Value::String(
-            format!(
-                "saturate({})",
-                args.get_err(0, "amount")?.to_css_string(args.span())?
-            ),
-            QuoteKind::None,
-        ));
+        return match args.get_err(0, "amount")? {
+            v @ Value::Dimension(..) => Ok(Value::String(
+                format!("saturate({})", v.to_css_string(args.span())?),
+                QuoteKind::None,
+            )),
+            v if v.is_special_function() => Ok(Value::String(
+                format!("saturate({})", v.to_css_string(args.span())?),
+                QuoteKind::None,
+            )),
+            v => Err((
+                format!("$amount: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into()),
+        };
     }
 
     let amount = match args.get_err(1, "amount")? {
         Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(None, u, _) => {
+            return Err((
+                format!(
+                    "$amount: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         v => {
             return Err((
                 format!(
@@ -403,7 +378,16 @@ fn desaturate(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     };
     let amount = match args.get_err(1, "amount")? {
         Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(None, u, _) => {
+            return Err((
+                format!(
+                    "$amount: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         v => {
             return Err((
                 format!(
@@ -428,6 +412,12 @@ pub(crate) fn grayscale(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResu
                 QuoteKind::None,
             ))
         }
+        v if v.is_special_function() => {
+            return Ok(Value::String(
+                format!("grayscale({})", v.to_css_string(args.span())?),
+                QuoteKind::None,
+            ))
+        }
         v => {
             return Err((
                 format!("$color: {} is not a color.", v.inspect(args.span())?),
@@ -463,9 +453,18 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
             ..
         })) => Some(bound!(args, "weight", n, u, 0, 100) / Number::from(100)),
         Some(Ok(Spanned {
-            node: Value::Dimension(None, ..),
+            node: Value::Dimension(None, u, _),
             ..
-        })) => todo!(),
+        })) => {
+            return Err((
+                format!(
+                    "$weight: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         None => None,
         Some(Ok(v)) => {
             return Err((
@@ -478,12 +477,13 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
                 .into())
         }
     };
+    let is_full_weight = weight.as_ref().map_or(true, Number::is_one);
     match args.get_err(0, "color")? {
         Value::Color(c) => Ok(Value::Color(Box::new(
             c.invert(weight.unwrap_or_else(Number::one)),
         ))),
         Value::Dimension(Some(n), u, _) => {
-            if weight.is_some() {
+            if !is_full_weight {
                 return Err((
                     "Only one argument may be passed to the plain-CSS invert() function.",
                     args.span(),
@@ -498,6 +498,19 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
         Value::Dimension(None, u, _) => {
             Ok(Value::String(format!("invert(NaN{})", u), QuoteKind::None))
         }
+        v if v.is_special_function() => {
+            if !is_full_weight {
+                return Err((
+                    "Only one argument may be passed to the plain-CSS invert() function.",
+                    args.span(),
+                )
+                    .into());
+            }
+            Ok(Value::String(
+                format!("invert({})", v.to_css_string(args.span())?),
+                QuoteKind::None,
+            ))
+        }
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
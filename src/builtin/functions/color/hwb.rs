@@ -0,0 +1,139 @@
+//! Functions in the HWB color space, which are only available through the
+//! `sass:color` module
+
+use super::{parse_channels, percentage_or_unitless, Channels};
+
+use codemap::Span;
+use num_traits::One;
+
+use crate::{
+    args::CallArgs,
+    color::Color,
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::{Number, Value},
+};
+
+pub(crate) fn hwb(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(4)?;
+    let span = args.span();
+
+    if args.len() == 1 {
+        return match parse_channels(
+            "hwb",
+            ["hue", "whiteness", "blackness"],
+            args.get_err(0, "channels")?,
+            span,
+        )? {
+            Channels::Special(v) => Err((
+                format!(
+                    "Expected numeric channels, got \"{}\".",
+                    v.to_css_string(span)?
+                ),
+                span,
+            )
+                .into()),
+            Channels::Values(mut channels) => {
+                let blackness = channels.pop().unwrap();
+                let whiteness = channels.pop().unwrap();
+                let hue = channels.pop().unwrap();
+                hwb_from_channels(hue, whiteness, blackness, None, span)
+            }
+        };
+    }
+
+    let hue = args.get_err(0, "hue")?;
+    let whiteness = args.get_err(1, "whiteness")?;
+    let blackness = args.get_err(2, "blackness")?;
+    let alpha = match args.get(3, "alpha") {
+        Some(v) => Some(v?.node),
+        None => None,
+    };
+    hwb_from_channels(hue, whiteness, blackness, alpha, span)
+}
+
+fn hwb_from_channels(
+    hue: Value,
+    whiteness: Value,
+    blackness: Value,
+    alpha: Option<Value>,
+    span: Span,
+) -> SassResult<Value> {
+    // the unit of the hue is ignored
+    let hue = match hue {
+        Value::Dimension(Some(n), ..) => n,
+        Value::Dimension(None, ..) => return Err(("Infinity or NaN toInt", span).into()),
+        v => return Err((format!("$hue: {} is not a number.", v.inspect(span)?), span).into()),
+    };
+    let whiteness = percentage_channel(whiteness, "whiteness", span)?;
+    let blackness = percentage_channel(blackness, "blackness", span)?;
+    let alpha = match alpha {
+        Some(alpha) => percentage_or_unitless(alpha, 1, "alpha", span)?,
+        None => Number::one(),
+    };
+
+    Ok(Value::Color(Box::new(Color::from_hwb(
+        hue, whiteness, blackness, alpha,
+    ))))
+}
+
+/// Asserts that `value` is a percentage between `0%` and `100%`, returning
+/// it scaled to be between 0 and 1
+fn percentage_channel(value: Value, name: &str, span: Span) -> SassResult<Number> {
+    match value {
+        Value::Dimension(Some(n), Unit::Percent, _) => {
+            if n < Number::from(0) || n > Number::from(100) {
+                return Err((
+                    format!("${}: Expected {}% to be within 0% and 100%.", name, n),
+                    span,
+                )
+                    .into());
+            }
+            Ok(n / Number::from(100))
+        }
+        Value::Dimension(None, Unit::Percent, _) => Err((
+            format!("${}: Expected NaN% to be within 0% and 100%.", name),
+            span,
+        )
+            .into()),
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "${}: Expected {} to have unit \"%\".",
+                name,
+                v.inspect(span)?
+            ),
+            span,
+        )
+            .into()),
+        v => Err((
+            format!("${}: {} is not a number.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn whiteness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(Some(c.whiteness()), Unit::Percent, true)),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn blackness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(Some(c.blackness()), Unit::Percent, true)),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
@@ -1,10 +1,136 @@
+use codemap::Span;
+
 use super::{Builtin, GlobalFunctionMap};
 
+use crate::{
+    common::{Brackets, ListSeparator, QuoteKind},
+    error::SassResult,
+    unit::Unit,
+    value::{Number, Value},
+};
+
 pub mod hsl;
+pub mod hwb;
 pub mod opacity;
 pub mod other;
 pub mod rgb;
 
+/// The result of parsing the single `$channels` argument of `rgb()`, `hsl()`
+/// or `hwb()`
+enum Channels {
+    /// The three channels, in order
+    Values(Vec<Value>),
+    /// The channels contain a special CSS function such as `var()`, so the
+    /// call is emitted as plain CSS
+    Special(Value),
+}
+
+/// Parses `channels`, a space-separated list such as `1 2 3`
+///
+/// `arg_names` are the names of the channels, used in error messages.
+fn parse_channels(
+    name: &str,
+    arg_names: [&str; 3],
+    channels: Value,
+    span: Span,
+) -> SassResult<Channels> {
+    if channels.is_special_function() {
+        return Ok(Channels::Special(function_string(name, &[channels], span)?));
+    }
+
+    let list = match channels {
+        Value::List(list, separator, brackets) => {
+            let is_comma_separated = separator == ListSeparator::Comma;
+            let is_bracketed = brackets == Brackets::Bracketed;
+
+            if is_comma_separated || is_bracketed {
+                let mut message = "$channels must be".to_owned();
+                if is_bracketed {
+                    message.push_str(" an unbracketed");
+                }
+                if is_comma_separated {
+                    message.push_str(if is_bracketed { "," } else { " a" });
+                    message.push_str(" space-separated");
+                }
+                message.push_str(" list.");
+                return Err((message, span).into());
+            }
+
+            list
+        }
+        v => vec![v],
+    };
+
+    if list.len() > 3 {
+        return Err((
+            format!("Only 3 elements allowed, but {} were passed.", list.len()),
+            span,
+        )
+            .into());
+    }
+
+    // a slash-separated alpha channel, such as `rgb(1 2 var(--blue)/0.5)`
+    let has_slash = matches!(
+        list.last(),
+        Some(Value::String(s, QuoteKind::None)) if s.contains('/')
+    );
+
+    if has_slash || (list.len() < 3 && list.iter().any(Value::is_special_function)) {
+        return Ok(Channels::Special(function_string(
+            name,
+            &[Value::List(list, ListSeparator::Space, Brackets::None)],
+            span,
+        )?));
+    }
+
+    if list.len() < 3 {
+        return Err((format!("Missing element ${}.", arg_names[list.len()]), span).into());
+    }
+
+    Ok(Channels::Values(list))
+}
+
+/// Returns the plain CSS function call `name(args...)`
+fn function_string(name: &str, args: &[Value], span: Span) -> SassResult<Value> {
+    let args = args
+        .iter()
+        .map(|arg| arg.to_css_string(span))
+        .collect::<SassResult<Vec<_>>>()?;
+
+    Ok(Value::String(
+        format!("{}({})", name, args.join(", ")),
+        QuoteKind::None,
+    ))
+}
+
+/// Asserts that `value` is a number that is either unitless or a
+/// percentage, returning it scaled so that `100%` is equal to `max`
+fn percentage_or_unitless(value: Value, max: i32, name: &str, span: Span) -> SassResult<Number> {
+    match value {
+        Value::Dimension(Some(n), Unit::None, _) => Ok(n),
+        Value::Dimension(Some(n), Unit::Percent, _) => {
+            Ok(n * Number::from(max) / Number::from(100))
+        }
+        Value::Dimension(None, Unit::None, _) | Value::Dimension(None, Unit::Percent, _) => {
+            Err(("Infinity or NaN toInt", span).into())
+        }
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "${}: Expected {} to have no units or \"%\".",
+                name,
+                v.to_css_string(span)?
+            ),
+            span,
+        )
+            .into()),
+        v => Err((
+            format!("${}: {} is not a number.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     hsl::declare(f);
     opacity::declare(f);
@@ -12,11 +12,24 @@ use crate::{
     value::{Number, Value},
 };
 
+macro_rules! nan_err {
+    ($args:ident, $arg:literal, $unit:expr, $low:literal, $high:literal) => {
+        (
+            format!(
+                "${}: Expected NaN{} to be within {}{} and {}{}.",
+                $arg, $unit, $low, $unit, $high, $unit,
+            ),
+            $args.span(),
+        )
+            .into()
+    };
+}
+
 macro_rules! opt_rgba {
     ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
         let $name = match $args.default_named_arg($arg, Value::Null)? {
             Value::Dimension(Some(n), u, _) => Some(bound!($args, $arg, n, u, $low, $high)),
-            Value::Dimension(None, ..) => todo!(),
+            Value::Dimension(None, u, _) => return Err(nan_err!($args, $arg, u, $low, $high)),
             Value::Null => None,
             v => {
                 return Err((
@@ -35,7 +48,40 @@ macro_rules! opt_hsl {
             Value::Dimension(Some(n), u, _) => {
                 Some(bound!($args, $arg, n, u, $low, $high) / Number::from(100))
             }
-            Value::Dimension(None, ..) => todo!(),
+            Value::Dimension(None, u, _) => return Err(nan_err!($args, $arg, u, $low, $high)),
+            Value::Null => None,
+            v => {
+                return Err((
+                    format!("${}: {} is not a number.", $arg, v.inspect($args.span())?),
+                    $args.span(),
+                )
+                    .into())
+            }
+        };
+    };
+}
+
+/// Like `opt_hsl`, but the amount must be a percentage
+macro_rules! opt_percent {
+    ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
+        let $name = match $args.default_named_arg($arg, Value::Null)? {
+            Value::Dimension(Some(n), Unit::Percent, _) => {
+                Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
+            }
+            Value::Dimension(None, Unit::Percent, _) => {
+                return Err(nan_err!($args, $arg, Unit::Percent, $low, $high))
+            }
+            v @ Value::Dimension(..) => {
+                return Err((
+                    format!(
+                        "${}: Expected {} to have unit \"%\".",
+                        $arg,
+                        v.inspect($args.span())?
+                    ),
+                    $args.span(),
+                )
+                    .into())
+            }
             Value::Null => None,
             v => {
                 return Err((
@@ -49,13 +95,7 @@ macro_rules! opt_hsl {
 }
 
 pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    if args.positional_arg(1).is_some() {
-        return Err((
-            "Only one positional argument is allowed. All other arguments must be passed by name.",
-            args.span(),
-        )
-            .into());
-    }
+    only_one_positional_arg(&mut args)?;
 
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
@@ -72,8 +112,18 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
     opt_rgba!(args, red, "red", 0, 255);
     opt_rgba!(args, green, "green", 0, 255);
     opt_rgba!(args, blue, "blue", 0, 255);
+    let hue = opt_hue(&mut args)?;
+    opt_hsl!(args, saturation, "saturation", 0, 100);
+    opt_hsl!(args, luminance, "lightness", 0, 100);
+    opt_percent!(args, whiteness, "whiteness", 0, 100);
+    opt_percent!(args, blackness, "blackness", 0, 100);
 
-    if red.is_some() || green.is_some() || blue.is_some() {
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
+    check_channel_kinds(&args, has_rgb, hue.is_some(), has_sl, has_wb)?;
+
+    if has_rgb {
         return Ok(Value::Color(Box::new(Color::from_rgba(
             red.unwrap_or_else(|| color.red()),
             green.unwrap_or_else(|| color.green()),
@@ -82,23 +132,17 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
         ))));
     }
 
-    let hue = match args.default_named_arg("hue", Value::Null)? {
-        Value::Dimension(Some(n), ..) => Some(n),
-        Value::Dimension(None, ..) => todo!(),
-        Value::Null => None,
-        v => {
-            return Err((
-                format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-
-    opt_hsl!(args, saturation, "saturation", 0, 100);
-    opt_hsl!(args, luminance, "lightness", 0, 100);
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwb();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            hue.unwrap_or(this_hue),
+            whiteness.unwrap_or(this_whiteness),
+            blackness.unwrap_or(this_blackness),
+            alpha.unwrap_or(this_alpha),
+        ))));
+    }
 
-    if hue.is_some() || saturation.is_some() || luminance.is_some() {
+    if hue.is_some() || has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
@@ -117,6 +161,8 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
 }
 
 pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    only_one_positional_arg(&mut args)?;
+
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
         v => {
@@ -132,8 +178,18 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
     opt_rgba!(args, red, "red", -255, 255);
     opt_rgba!(args, green, "green", -255, 255);
     opt_rgba!(args, blue, "blue", -255, 255);
+    let hue = opt_hue(&mut args)?;
+    opt_hsl!(args, saturation, "saturation", -100, 100);
+    opt_hsl!(args, luminance, "lightness", -100, 100);
+    opt_percent!(args, whiteness, "whiteness", -100, 100);
+    opt_percent!(args, blackness, "blackness", -100, 100);
 
-    if red.is_some() || green.is_some() || blue.is_some() {
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
+    check_channel_kinds(&args, has_rgb, hue.is_some(), has_sl, has_wb)?;
+
+    if has_rgb {
         return Ok(Value::Color(Box::new(Color::from_rgba(
             color.red() + red.unwrap_or_else(Number::zero),
             color.green() + green.unwrap_or_else(Number::zero),
@@ -142,23 +198,17 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
         ))));
     }
 
-    let hue = match args.default_named_arg("hue", Value::Null)? {
-        Value::Dimension(Some(n), ..) => Some(n),
-        Value::Dimension(None, ..) => todo!(),
-        Value::Null => None,
-        v => {
-            return Err((
-                format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-
-    opt_hsl!(args, saturation, "saturation", -100, 100);
-    opt_hsl!(args, luminance, "lightness", -100, 100);
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwb();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            this_hue + hue.unwrap_or_else(Number::zero),
+            this_whiteness + whiteness.unwrap_or_else(Number::zero),
+            this_blackness + blackness.unwrap_or_else(Number::zero),
+            this_alpha + alpha.unwrap_or_else(Number::zero),
+        ))));
+    }
 
-    if hue.is_some() || saturation.is_some() || luminance.is_some() {
+    if hue.is_some() || has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
@@ -178,7 +228,7 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
 }
 
 #[allow(clippy::cognitive_complexity)]
-// todo: refactor into rgb and hsl?
+// todo: refactor into rgb, hsl and hwb?
 pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     pub(crate) fn scale(val: Number, by: Number, max: Number) -> Number {
         if by.is_zero() {
@@ -187,6 +237,8 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
         val.clone() + (if by.is_positive() { max - val } else { val }) * by
     }
 
+    only_one_positional_arg(&mut args)?;
+
     let span = args.span();
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
@@ -199,42 +251,21 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
         }
     };
 
-    macro_rules! opt_scale_arg {
-        ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
-            let $name = match $args.default_named_arg($arg, Value::Null)? {
-                Value::Dimension(Some(n), Unit::Percent, _) => {
-                    Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
-                }
-                Value::Dimension(None, ..) => todo!(),
-                v @ Value::Dimension(..) => {
-                    return Err((
-                        format!(
-                            "${}: Expected {} to have unit \"%\".",
-                            $arg,
-                            v.inspect($args.span())?
-                        ),
-                        $args.span(),
-                    )
-                        .into())
-                }
-                Value::Null => None,
-                v => {
-                    return Err((
-                        format!("${}: {} is not a number.", $arg, v.inspect($args.span())?),
-                        $args.span(),
-                    )
-                        .into())
-                }
-            };
-        };
-    }
+    opt_percent!(args, alpha, "alpha", -100, 100);
+    opt_percent!(args, red, "red", -100, 100);
+    opt_percent!(args, green, "green", -100, 100);
+    opt_percent!(args, blue, "blue", -100, 100);
+    opt_percent!(args, saturation, "saturation", -100, 100);
+    opt_percent!(args, luminance, "lightness", -100, 100);
+    opt_percent!(args, whiteness, "whiteness", -100, 100);
+    opt_percent!(args, blackness, "blackness", -100, 100);
 
-    opt_scale_arg!(args, alpha, "alpha", -100, 100);
-    opt_scale_arg!(args, red, "red", -100, 100);
-    opt_scale_arg!(args, green, "green", -100, 100);
-    opt_scale_arg!(args, blue, "blue", -100, 100);
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
+    check_channel_kinds(&args, has_rgb, false, has_sl, has_wb)?;
 
-    if red.is_some() || green.is_some() || blue.is_some() {
+    if has_rgb {
         return Ok(Value::Color(Box::new(Color::from_rgba(
             scale(
                 color.red(),
@@ -259,10 +290,29 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
         ))));
     }
 
-    opt_scale_arg!(args, saturation, "saturation", -100, 100);
-    opt_scale_arg!(args, luminance, "lightness", -100, 100);
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwb();
+        return Ok(Value::Color(Box::new(Color::from_hwb(
+            this_hue,
+            scale(
+                this_whiteness,
+                whiteness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_blackness,
+                blackness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_alpha,
+                alpha.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+        ))));
+    }
 
-    if saturation.is_some() || luminance.is_some() {
+    if has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
@@ -293,6 +343,69 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
     }))
 }
 
+/// The color adjustment functions accept only the color positionally, so
+/// that it is always clear which channel an amount applies to
+fn only_one_positional_arg(args: &mut CallArgs) -> SassResult<()> {
+    if args.positional_arg(1).is_some() {
+        return Err((
+            "Only one positional argument is allowed. All other arguments must be passed by name.",
+            args.span(),
+        )
+            .into());
+    }
+
+    Ok(())
+}
+
+fn opt_hue(args: &mut CallArgs) -> SassResult<Option<Number>> {
+    match args.default_named_arg("hue", Value::Null)? {
+        Value::Dimension(Some(n), ..) => Ok(Some(n)),
+        Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
+        Value::Null => Ok(None),
+        v => Err((
+            format!("$hue: {} is not a number.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+/// Ensures that every named argument was a channel, and that channels from
+/// more than one of the RGB, HSL and HWB color spaces weren't given
+///
+/// The hue is shared by HSL and HWB, so `has_sl` and `has_wb` refer only to
+/// the saturation and lightness, and the whiteness and blackness.
+fn check_channel_kinds(
+    args: &CallArgs,
+    has_rgb: bool,
+    has_hue: bool,
+    has_sl: bool,
+    has_wb: bool,
+) -> SassResult<()> {
+    args.no_unknown_named_args()?;
+
+    if has_rgb && (has_hue || has_sl || has_wb) {
+        return Err((
+            format!(
+                "RGB parameters may not be passed along with {} parameters.",
+                if has_wb { "HWB" } else { "HSL" }
+            ),
+            args.span(),
+        )
+            .into());
+    }
+
+    if has_sl && has_wb {
+        return Err((
+            "HSL parameters may not be passed along with HWB parameters.",
+            args.span(),
+        )
+            .into());
+    }
+
+    Ok(())
+}
+
 pub(crate) fn ie_hex_str(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     let color = match args.get_err(0, "color")? {
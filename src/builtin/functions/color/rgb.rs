@@ -1,5 +1,8 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    function_string, parse_channels, percentage_or_unitless, Builtin, Channels, GlobalFunctionMap,
+};
 
+use codemap::Span;
 use num_traits::One;
 
 use crate::{
@@ -13,158 +16,48 @@ use crate::{
 };
 
 /// name: Either `rgb` or `rgba` depending on the caller
-// todo: refactor into smaller functions
-#[allow(clippy::cognitive_complexity)]
 fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    if args.is_empty() {
-        return Err(("Missing argument $channels.", args.span()).into());
-    }
-
-    if args.len() == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
-            _ => return Err(("Missing argument $channels.", args.span()).into()),
-        };
-
-        if channels.len() > 3 {
-            return Err((
-                format!(
-                    "Only 3 elements allowed, but {} were passed.",
-                    channels.len()
-                ),
-                args.span(),
-            )
-                .into());
-        }
-
-        let blue = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
+    args.max_args(4)?;
+    let span = args.span();
+
+    match args.len() {
+        0 => Err(("Missing argument $channels.", span).into()),
+        1 => match parse_channels(
+            name,
+            ["red", "green", "blue"],
+            args.get_err(0, "channels")?,
+            span,
+        )? {
+            Channels::Special(v) => Ok(v),
+            Channels::Values(mut channels) => {
+                let blue = channels.pop().unwrap();
                 let green = channels.pop().unwrap();
                 let red = channels.pop().unwrap();
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {}, {})",
-                        name,
-                        red.to_css_string(args.span())?,
-                        green.to_css_string(args.span())?,
-                        v.to_css_string(args.span())?
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$blue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $blue.", args.span()).into()),
-        };
-
-        let green = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
-                let string = match channels.pop() {
-                    Some(red) => format!(
-                        "{}({}, {}, {})",
-                        name,
-                        red.to_css_string(args.span())?,
-                        v.to_css_string(args.span())?,
-                        blue
-                    ),
-                    None => format!("{}({} {})", name, v.to_css_string(args.span())?, blue),
-                };
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$green: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $green.", args.span()).into()),
-        };
-
-        let red = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {}, {})",
-                        name,
-                        v.to_css_string(args.span())?,
-                        green,
-                        blue
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$red: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $red.", args.span()).into()),
-        };
-
-        let color = Color::from_rgba(red, green, blue, Number::one());
+                rgb_from_channels(name, red, green, blue, None, span)
+            }
+        },
+        2 => {
+            let color = args.get_err(0, "color")?;
+            let alpha = args.get_err(1, "alpha")?;
+
+            if color.is_special_function()
+                || (!matches!(color, Value::Color(..)) && alpha.is_special_function())
+            {
+                return function_string(name, &[color, alpha], span);
+            }
+
+            let color = match color {
+                Value::Color(c) => c,
+                v => {
+                    return Err((
+                        format!("$color: {} is not a color.", v.inspect(span)?),
+                        span,
+                    )
+                        .into())
+                }
+            };
 
-        Ok(Value::Color(Box::new(color)))
-    } else if args.len() == 2 {
-        let color = match args.get_err(0, "color")? {
-            Value::Color(c) => c,
-            v if v.is_special_function() => {
-                let alpha = args.get_err(1, "alpha")?;
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {})",
-                        name,
-                        v.to_css_string(args.span())?,
-                        alpha.to_css_string(args.span())?
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            v => {
-                return Err((
-                    format!("$color: {} is not a color.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let alpha = match args.get_err(1, "alpha")? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$alpha: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
+            if alpha.is_special_function() {
                 return Ok(Value::String(
                     format!(
                         "{}({}, {}, {}, {})",
@@ -172,182 +65,57 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
                         color.red(),
                         color.green(),
                         color.blue(),
-                        v.to_css_string(args.span())?
+                        alpha.to_css_string(span)?
                     ),
                     QuoteKind::None,
                 ));
             }
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        Ok(Value::Color(Box::new(color.with_alpha(alpha))))
-    } else {
-        let red = match args.get_err(0, "red")? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$red: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
-                let green = args.get_err(1, "green")?;
-                let blue = args.get_err(2, "blue")?;
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    v.to_css_string(args.span())?,
-                    green.to_css_string(args.span())?,
-                    blue.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!("$red: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let green = match args.get_err(1, "green")? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$green: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
-                let blue = args.get_err(2, "blue")?;
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    red,
-                    v.to_css_string(args.span())?,
-                    blue.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!("$green: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let blue = match args.get_err(2, "blue")? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$blue: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
-                let mut string = format!(
-                    "{}({}, {}, {}",
-                    name,
-                    red,
-                    green,
-                    v.to_css_string(args.span())?
-                );
-                if !args.is_empty() {
-                    string.push_str(", ");
-                    string.push_str(&args.get_err(3, "alpha")?.to_css_string(args.span())?);
-                }
-                string.push(')');
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!("$blue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let alpha = match args.default_arg(
-            3,
-            "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
-        )? {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$alpha: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v if v.is_special_function() => {
-                let string = format!(
-                    "{}({}, {}, {}, {})",
-                    name,
-                    red,
-                    green,
-                    blue,
-                    v.to_css_string(args.span())?
-                );
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        Ok(Value::Color(Box::new(Color::from_rgba(
-            red, green, blue, alpha,
-        ))))
+
+            let alpha = percentage_or_unitless(alpha, 1, "alpha", span)?;
+            Ok(Value::Color(Box::new(color.with_alpha(alpha))))
+        }
+        _ => {
+            let red = args.get_err(0, "red")?;
+            let green = args.get_err(1, "green")?;
+            let blue = args.get_err(2, "blue")?;
+            let alpha = match args.get(3, "alpha") {
+                Some(v) => Some(v?.node),
+                None => None,
+            };
+            rgb_from_channels(name, red, green, blue, alpha, span)
+        }
+    }
+}
+
+fn rgb_from_channels(
+    name: &str,
+    red: Value,
+    green: Value,
+    blue: Value,
+    alpha: Option<Value>,
+    span: Span,
+) -> SassResult<Value> {
+    if red.is_special_function()
+        || green.is_special_function()
+        || blue.is_special_function()
+        || alpha.as_ref().map_or(false, Value::is_special_function)
+    {
+        let mut channels = vec![red, green, blue];
+        channels.extend(alpha);
+        return function_string(name, &channels, span);
     }
+
+    let red = percentage_or_unitless(red, 255, "red", span)?;
+    let green = percentage_or_unitless(green, 255, "green", span)?;
+    let blue = percentage_or_unitless(blue, 255, "blue", span)?;
+    let alpha = match alpha {
+        Some(alpha) => percentage_or_unitless(alpha, 1, "alpha", span)?,
+        None => Number::one(),
+    };
+
+    Ok(Value::Color(Box::new(Color::from_rgba(
+        red, green, blue, alpha,
+    ))))
 }
 
 pub(crate) fn rgb(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -424,7 +192,16 @@ pub(crate) fn mix(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
         Value::Dimension(Some(Number::from(50)), Unit::None, true),
     )? {
         Value::Dimension(Some(n), u, _) => bound!(args, "weight", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(None, u, _) => {
+            return Err((
+                format!(
+                    "$weight: Expected NaN{} to be within 0{} and 100{}.",
+                    u, u, u
+                ),
+                args.span(),
+            )
+                .into())
+        }
         v => {
             return Err((
                 format!(
@@ -11,6 +11,22 @@ use crate::{
     value::{Number, Value},
 };
 
+/// Splits `value` into its elements, separator, and brackets, treating maps
+/// and argument lists as comma-separated lists and any other value as a list
+/// containing only itself
+fn list_parts(value: Value) -> (Vec<Value>, ListSeparator, Brackets) {
+    match value {
+        Value::List(v, sep, brackets) => (v, sep, brackets),
+        Value::ArgList(v) => (
+            v.into_iter().map(|val| val.node).collect(),
+            ListSeparator::Comma,
+            Brackets::None,
+        ),
+        Value::Map(m) => (m.as_list(), ListSeparator::Comma, Brackets::None),
+        v => (vec![v], ListSeparator::Space, Brackets::None),
+    }
+}
+
 pub(crate) fn length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     Ok(Value::Dimension(
@@ -80,16 +96,7 @@ pub(crate) fn list_separator(mut args: CallArgs, parser: &mut Parser<'_>) -> Sas
 
 pub(crate) fn set_nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(3)?;
-    let (mut list, sep, brackets) = match args.get_err(0, "list")? {
-        Value::List(v, sep, b) => (v, sep, b),
-        Value::ArgList(v) => (
-            v.into_iter().map(|val| val.node).collect(),
-            ListSeparator::Comma,
-            Brackets::None,
-        ),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma, Brackets::None),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
-    };
+    let (mut list, sep, brackets) = list_parts(args.get_err(0, "list")?);
     let (n, unit) = match args.get_err(1, "n")? {
         Value::Dimension(Some(num), unit, ..) => (num, unit),
         Value::Dimension(None, u, ..) => {
@@ -138,10 +145,7 @@ pub(crate) fn set_nth(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult
 
 pub(crate) fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(3)?;
-    let (mut list, sep, brackets) = match args.get_err(0, "list")? {
-        Value::List(v, sep, b) => (v, sep, b),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
-    };
+    let (mut list, sep, brackets) = list_parts(args.get_err(0, "list")?);
     let val = args.get_err(1, "val")?;
     let sep = match args.default_arg(
         2,
@@ -152,9 +156,10 @@ pub(crate) fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
             "auto" => sep,
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -176,16 +181,8 @@ pub(crate) fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
 
 pub(crate) fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(4)?;
-    let (mut list1, sep1, brackets) = match args.get_err(0, "list1")? {
-        Value::List(v, sep, brackets) => (v, sep, brackets),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma, Brackets::None),
-        v => (vec![v], ListSeparator::Space, Brackets::None),
-    };
-    let (list2, sep2) = match args.get_err(1, "list2")? {
-        Value::List(v, sep, ..) => (v, sep),
-        Value::Map(m) => (m.as_list(), ListSeparator::Comma),
-        v => (vec![v], ListSeparator::Space),
-    };
+    let (mut list1, sep1, brackets) = list_parts(args.get_err(0, "list1")?);
+    let (list2, sep2, ..) = list_parts(args.get_err(1, "list2")?);
     let sep = match args.default_arg(
         2,
         "separator",
@@ -201,9 +198,10 @@ pub(crate) fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Va
             }
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -1,3 +1,7 @@
+use std::iter;
+
+use codemap::{Span, Spanned};
+
 use super::{Builtin, GlobalFunctionMap};
 
 use crate::{
@@ -8,56 +12,82 @@ use crate::{
     value::{SassMap, Value},
 };
 
+/// Asserts that `value` is a map, treating an empty list as an empty map
+pub(crate) fn assert_map(value: Value, name: &str, span: Span) -> SassResult<SassMap> {
+    match value {
+        Value::Map(m) => Ok(m),
+        Value::List(v, ..) if v.is_empty() => Ok(SassMap::new()),
+        Value::ArgList(v) if v.is_empty() => Ok(SassMap::new()),
+        v => Err((
+            format!("${}: {} is not a map.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+/// Returns the value at the end of the path `keys` through nested maps, or
+/// `None` if an intermediate value is not a map or a key is missing
+fn get_nested(map: SassMap, key: Value, keys: Vec<Spanned<Value>>) -> Option<Value> {
+    let mut value = Value::Map(map);
+    for key in iter::once(key).chain(keys.into_iter().map(|key| key.node)) {
+        value = match value {
+            Value::Map(map) => map.get_ref(&key)?.clone(),
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Replaces the value at the end of the path `keys` through nested maps with
+/// the result of `modify`, which is passed the value being replaced
+///
+/// Any intermediate value that is missing or not a map is replaced with a
+/// new map.
+pub(crate) fn modify_nested(
+    mut map: SassMap,
+    keys: &[Value],
+    modify: impl FnOnce(Option<Value>) -> Value,
+) -> SassMap {
+    let (key, rest) = match keys.split_first() {
+        Some(v) => v,
+        None => return map,
+    };
+
+    let old = map.get_ref(key).cloned();
+    let new = if rest.is_empty() {
+        modify(old)
+    } else {
+        let nested = match old {
+            Some(Value::Map(nested)) => nested,
+            _ => SassMap::new(),
+        };
+        Value::Map(modify_nested(nested, rest, modify))
+    };
+
+    map.insert(key.clone(), new);
+    map
+}
+
 pub(crate) fn map_get(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-    Ok(map.get(&key)?.unwrap_or(Value::Null))
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let keys = args.get_variadic()?;
+    Ok(get_nested(map, key, keys).unwrap_or(Value::Null))
 }
 
 pub(crate) fn map_has_key(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-    Ok(Value::bool(map.get(&key)?.is_some()))
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let keys = args.get_variadic()?;
+    Ok(Value::bool(get_nested(map, key, keys).is_some()))
 }
 
 pub(crate) fn map_keys(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     Ok(Value::List(
         map.keys(),
         ListSeparator::Comma,
@@ -67,18 +97,7 @@ pub(crate) fn map_keys(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResul
 
 pub(crate) fn map_values(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     Ok(Value::List(
         map.values(),
         ListSeparator::Comma,
@@ -86,49 +105,38 @@ pub(crate) fn map_values(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
     ))
 }
 
+/// `map-merge($map1, $keys..., $map2)`
+///
+/// If any keys are passed, `$map2` is merged into the map nested in `$map1`
+/// at that path instead of into `$map1` itself.
 pub(crate) fn map_merge(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
-    let mut map1 = match args.get_err(0, "map1")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map1: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-    let map2 = match args.get_err(1, "map2")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map2: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
+    let span = args.span();
+    let mut map1 = assert_map(args.get_err(0, "map1")?, "map1", span)?;
+
+    if args.len() <= 1 {
+        let map2 = assert_map(args.get_err(1, "map2")?, "map2", span)?;
+        map1.merge(map2);
+        return Ok(Value::Map(map1));
+    }
+
+    let mut keys: Vec<Value> = args
+        .get_variadic()?
+        .into_iter()
+        .map(|arg| arg.node)
+        .collect();
+    let map2 = assert_map(keys.pop().unwrap(), "map2", span)?;
+
+    Ok(Value::Map(modify_nested(map1, &keys, |old| match old {
+        Some(Value::Map(mut nested)) => {
+            nested.merge(map2);
+            Value::Map(nested)
         }
-    };
-    map1.merge(map2);
-    Ok(Value::Map(map1))
+        _ => Value::Map(map2),
+    })))
 }
 
 pub(crate) fn map_remove(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    let mut map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let mut map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     let keys = args.get_variadic()?;
     for key in keys {
         map.remove(&key);
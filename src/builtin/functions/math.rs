@@ -127,9 +127,8 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
             return Err((format!("$limit: NaN{} is not an int.", u), args.span()).into())
         }
         Value::Null => {
-            let mut rng = rand::thread_rng();
             return Ok(Value::Dimension(
-                Some(Number::from(rng.gen_range(0.0, 1.0))),
+                Some(Number::from(parser.random.rng().gen_range(0.0, 1.0))),
                 Unit::None,
                 true,
             ));
@@ -170,9 +169,8 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
         }
     };
 
-    let mut rng = rand::thread_rng();
     Ok(Value::Dimension(
-        Some(Number::from(rng.gen_range(0, limit) + 1)),
+        Some(Number::from(parser.random.rng().gen_range(0, limit) + 1)),
         Unit::None,
         true,
     ))
@@ -4,11 +4,14 @@ use codemap::Spanned;
 
 use crate::{
     args::CallArgs,
+    builtin::features,
     common::{Identifier, QuoteKind},
+    deprecation::Deprecation,
     error::SassResult,
     parse::Parser,
     unit::Unit,
     value::{SassFunction, Value},
+    Cow,
 };
 
 fn if_(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -23,25 +26,7 @@ fn if_(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
 pub(crate) fn feature_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "feature")? {
-        #[allow(clippy::match_same_arms)]
-        Value::String(s, _) => Ok(match s.as_str() {
-            // A local variable will shadow a global variable unless
-            // `!global` is used.
-            "global-variable-shadowing" => Value::True,
-            // the @extend rule will affect selectors nested in pseudo-classes
-            // like :not()
-            "extend-selector-pseudoclass" => Value::True,
-            // Full support for unit arithmetic using units defined in the
-            // [Values and Units Level 3][] spec.
-            "units-level-3" => Value::True,
-            // The Sass `@error` directive is supported.
-            "at-error" => Value::True,
-            // The "Custom Properties Level 1" spec is supported. This means
-            // that custom properties are parsed statically, with only
-            // interpolation treated as SassScript.
-            "custom-property" => Value::False,
-            _ => Value::False,
-        }),
+        Value::String(s, _) => Ok(Value::bool(features::feature_exists(&s))),
         v => Err((
             format!("$feature: {} is not a string.", v.inspect(args.span())?),
             args.span(),
@@ -141,7 +126,7 @@ pub(crate) fn global_variable_exists(
         parser
             .modules
             .get(module_name.into(), args.span())?
-            .var_exists(name)
+            .var_exists(name, args.span())?
     } else {
         parser.global_scope.var_exists(name)
     }))
@@ -176,7 +161,7 @@ pub(crate) fn mixin_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
         parser
             .modules
             .get(module_name.into(), args.span())?
-            .mixin_exists(name)
+            .mixin_exists(name, args.span())?
     } else {
         parser.scopes.mixin_exists(name, parser.global_scope)
     }))
@@ -212,8 +197,13 @@ pub(crate) fn function_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> Sa
         parser
             .modules
             .get(module_name.into(), args.span())?
-            .fn_exists(name)
+            .fn_exists(name, args.span())?
     } else {
+        #[cfg(feature = "plugins")]
+        if parser.options.plugin_function(name.as_str()).is_some() {
+            return Ok(Value::True);
+        }
+
         parser.scopes.fn_exists(name, parser.global_scope)
     }))
 }
@@ -243,7 +233,7 @@ pub(crate) fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
         }
     };
 
-    let func = match if let Some(module_name) = module {
+    if let Some(module_name) = module {
         if css {
             return Err((
                 "$css and $module may not both be passed at once.",
@@ -252,29 +242,66 @@ pub(crate) fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
                 .into());
         }
 
-        parser
+        return match parser
             .modules
             .get(module_name.into(), args.span())?
             .get_fn(Spanned {
                 node: name,
                 span: args.span(),
-            })?
-    } else {
-        parser.scopes.get_fn(name, parser.global_scope)
-    } {
+            })? {
+            Some(func) => Ok(Value::FunctionRef(func)),
+            None => Err((format!("Function not found: {}", name), args.span()).into()),
+        };
+    }
+
+    if css {
+        return Ok(Value::FunctionRef(SassFunction::Plain(name)));
+    }
+
+    let func = match lookup_function(name, parser) {
         Some(f) => f,
-        None => match GLOBAL_FUNCTIONS.get(name.as_str()) {
-            Some(f) => SassFunction::Builtin(f.clone(), name),
-            None => return Err((format!("Function not found: {}", name), args.span()).into()),
-        },
+        None => return Err((format!("Function not found: {}", name), args.span()).into()),
     };
 
     Ok(Value::FunctionRef(func))
 }
 
+/// Finds the function that would be called by `name(...)` in the current
+/// scope, other than a plain CSS function
+fn lookup_function(name: Identifier, parser: &Parser<'_>) -> Option<SassFunction> {
+    if let Some(f) = parser.scopes.get_fn(name, parser.global_scope) {
+        return Some(f);
+    }
+
+    #[cfg(feature = "plugins")]
+    if let Some(f) = parser.options.plugin_function(name.as_str()) {
+        return Some(SassFunction::Plugin(f, name));
+    }
+
+    GLOBAL_FUNCTIONS
+        .get(name.as_str())
+        .map(|f| SassFunction::Builtin(f.clone(), name))
+}
+
 pub(crate) fn call(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     let func = match args.get_err(0, "function")? {
         Value::FunctionRef(f) => f,
+        Value::String(name, quotes) => {
+            let recommendation = Value::String(name.clone(), quotes).inspect(args.span())?;
+            parser.warn_deprecation(
+                Deprecation::CallString,
+                &Spanned {
+                    node: Cow::owned(format!(
+                        "Passing a string to call() is deprecated and will be illegal in Dart Sass 2.0.0.\n\nRecommendation: call(get-function({}))",
+                        recommendation
+                    )),
+                    span: args.span(),
+                },
+            )?;
+
+            let name = Identifier::from(name);
+            lookup_function(name, parser).unwrap_or(SassFunction::Plain(name))
+        }
         v => {
             return Err((
                 format!(
@@ -304,11 +331,19 @@ pub(crate) fn content_exists(args: CallArgs, parser: &mut Parser<'_>) -> SassRes
     ))
 }
 
-#[allow(unused_variables)]
-pub(crate) fn keywords(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+pub(crate) fn keywords(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
-    drop(args);
-    todo!("builtin function `keywords` blocked on better handling of call args")
+    match args.get_err(0, "args")? {
+        Value::ArgList(args) => Ok(Value::Map(args.keywords_map())),
+        v => Err((
+            format!(
+                "$args: {} is not an argument list.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
@@ -23,21 +23,20 @@ pub(crate) fn is_superselector(mut args: CallArgs, parser: &mut Parser<'_>) -> S
 
 pub(crate) fn simple_selectors(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
-    // todo: Value::to_compound_selector
     let selector = args
         .get_err(0, "selector")?
         .to_selector(parser, "selector", false)?;
 
     if selector.0.components.len() != 1 {
-        return Err(("$selector: expected selector.", args.span()).into());
+        return Err(("$selector: expected no more input.", args.span()).into());
     }
 
-    let compound = if let Some(ComplexSelectorComponent::Compound(compound)) =
-        selector.0.components[0].components.first().cloned()
-    {
-        compound
-    } else {
-        todo!()
+    let compound = match selector.0.components[0].components.as_slice() {
+        [ComplexSelectorComponent::Compound(compound)] => compound.clone(),
+        [ComplexSelectorComponent::Compound(..), ..] => {
+            return Err(("$selector: expected no more input.", args.span()).into())
+        }
+        _ => return Err(("$selector: expected selector.", args.span()).into()),
     };
 
     Ok(Value::List(
@@ -151,13 +150,13 @@ pub(crate) fn selector_replace(mut args: CallArgs, parser: &mut Parser<'_>) -> S
     args.max_args(3)?;
     let selector = args
         .get_err(0, "selector")?
-        .to_selector(parser, "selector", true)?;
+        .to_selector(parser, "selector", false)?;
     let target = args
         .get_err(1, "original")?
-        .to_selector(parser, "original", true)?;
+        .to_selector(parser, "original", false)?;
     let source = args
         .get_err(2, "replacement")?
-        .to_selector(parser, "replacement", true)?;
+        .to_selector(parser, "replacement", false)?;
     Ok(Extender::replace(selector.0, source.0, target.0, args.span())?.to_sass_list())
 }
 
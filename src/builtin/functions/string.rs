@@ -3,9 +3,6 @@ use super::{Builtin, GlobalFunctionMap};
 use num_bigint::BigInt;
 use num_traits::{Signed, ToPrimitive, Zero};
 
-#[cfg(feature = "random")]
-use rand::{distributions::Alphanumeric, thread_rng, Rng};
-
 use crate::{
     args::CallArgs,
     common::QuoteKind,
@@ -18,6 +15,7 @@ use crate::{
 pub(crate) fn to_upper_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "string")? {
+        // Only ASCII letters are converted, as required by the Sass spec
         Value::String(mut i, q) => {
             i.make_ascii_uppercase();
             Ok(Value::String(i, q))
@@ -33,6 +31,7 @@ pub(crate) fn to_upper_case(mut args: CallArgs, parser: &mut Parser<'_>) -> Sass
 pub(crate) fn to_lower_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "string")? {
+        // Only ASCII letters are converted, as required by the Sass spec
         Value::String(mut i, q) => {
             i.make_ascii_lowercase();
             Ok(Value::String(i, q))
@@ -208,8 +207,13 @@ pub(crate) fn str_index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResu
         }
     };
 
+    // `find` returns a byte offset, but indices in Sass count code points
     Ok(match s1.find(&substr) {
-        Some(v) => Value::Dimension(Some(Number::from(v + 1)), Unit::None, true),
+        Some(v) => Value::Dimension(
+            Some(Number::from(s1[..v].chars().count() + 1)),
+            Unit::None,
+            true,
+        ),
         None => Value::Null,
     })
 }
@@ -314,14 +318,9 @@ pub(crate) fn str_insert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
 
 #[cfg(feature = "random")]
 #[allow(clippy::needless_pass_by_value)]
-pub(crate) fn unique_id(args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+pub(crate) fn unique_id(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(0)?;
-    let mut rng = thread_rng();
-    let string = std::iter::repeat(())
-        .map(|()| rng.sample(Alphanumeric))
-        .take(7)
-        .collect();
-    Ok(Value::String(string, QuoteKind::None))
+    Ok(Value::String(parser.random.unique_id(), QuoteKind::None))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
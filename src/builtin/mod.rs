@@ -1,3 +1,4 @@
+mod features;
 mod functions;
 pub(crate) mod modules;
 
@@ -1,27 +1,85 @@
-use crate::builtin::{
-    color::{
-        hsl::{complement, grayscale, hue, invert, lightness, saturation},
-        opacity::alpha,
-        other::{adjust_color, change_color, ie_hex_str, scale_color},
-        rgb::{blue, green, mix, red},
+use crate::{
+    args::CallArgs,
+    builtin::{
+        color::{
+            hsl::{complement, grayscale, hue, invert, lightness, saturation},
+            hwb::{blackness, hwb, whiteness},
+            opacity::{alpha, opacity},
+            other::{adjust_color, change_color, ie_hex_str, scale_color},
+            rgb::{blue, green, mix, red},
+        },
+        modules::Module,
     },
-    modules::Module,
+    error::SassResult,
+    parse::Parser,
+    value::Value,
 };
 
+/// Declares a global color function that has no equivalent in `sass:color`,
+/// which throws an error recommending `color.adjust()` instead
+macro_rules! removed_color_fn {
+    ($name:ident, $fn_name:literal, $arg:literal, $sign:literal) => {
+        fn $name(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+            args.max_args(2)?;
+            let span = args.span();
+
+            let color = args.get_err(0, "color")?;
+            let amount = args.get_err(1, "amount")?;
+
+            Err((
+                format!(
+                    "The function {}() isn't in the sass:color module.\n\nRecommendation: color.adjust({}, ${}: {}{})\n\nMore info: https://sass-lang.com/documentation/functions/color#{}",
+                    $fn_name,
+                    color.inspect(span)?,
+                    $arg,
+                    $sign,
+                    amount.inspect(span)?,
+                    $fn_name
+                ),
+                span,
+            )
+                .into())
+        }
+    };
+}
+
+removed_color_fn!(adjust_hue, "adjust-hue", "hue", "");
+removed_color_fn!(lighten, "lighten", "lightness", "");
+removed_color_fn!(darken, "darken", "lightness", "-");
+removed_color_fn!(saturate, "saturate", "saturation", "");
+removed_color_fn!(desaturate, "desaturate", "saturation", "-");
+removed_color_fn!(opacify, "opacify", "alpha", "");
+removed_color_fn!(fade_in, "fade-in", "alpha", "");
+removed_color_fn!(transparentize, "transparentize", "alpha", "-");
+removed_color_fn!(fade_out, "fade-out", "alpha", "-");
+
 pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("adjust", adjust_color);
+    f.insert_builtin("adjust-hue", adjust_hue);
     f.insert_builtin("alpha", alpha);
+    f.insert_builtin("blackness", blackness);
     f.insert_builtin("blue", blue);
     f.insert_builtin("change", change_color);
     f.insert_builtin("complement", complement);
+    f.insert_builtin("darken", darken);
+    f.insert_builtin("desaturate", desaturate);
+    f.insert_builtin("fade-in", fade_in);
+    f.insert_builtin("fade-out", fade_out);
     f.insert_builtin("grayscale", grayscale);
     f.insert_builtin("green", green);
     f.insert_builtin("hue", hue);
+    f.insert_builtin("hwb", hwb);
     f.insert_builtin("ie-hex-str", ie_hex_str);
     f.insert_builtin("invert", invert);
+    f.insert_builtin("lighten", lighten);
     f.insert_builtin("lightness", lightness);
     f.insert_builtin("mix", mix);
+    f.insert_builtin("opacify", opacify);
+    f.insert_builtin("opacity", opacity);
     f.insert_builtin("red", red);
+    f.insert_builtin("saturate", saturate);
     f.insert_builtin("saturation", saturation);
     f.insert_builtin("scale", scale_color);
+    f.insert_builtin("transparentize", transparentize);
+    f.insert_builtin("whiteness", whiteness);
 }
@@ -1,13 +1,112 @@
-use crate::builtin::{
-    map::{map_get, map_has_key, map_keys, map_merge, map_remove, map_values},
-    modules::Module,
+use std::iter;
+
+use crate::{
+    args::CallArgs,
+    builtin::{
+        map::{
+            assert_map, map_get, map_has_key, map_keys, map_merge, map_remove, map_values,
+            modify_nested,
+        },
+        modules::Module,
+    },
+    error::SassResult,
+    parse::Parser,
+    value::{SassMap, Value},
 };
 
+/// `map.set($map, $keys..., $key, $value)`
+fn set(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+
+    let mut keys: Vec<Value> = match (args.get_named("key"), args.get_named("value")) {
+        (Some(key), Some(value)) => vec![key?.node, value?.node],
+        (Some(..), None) => return Err(("Missing argument $value.", span).into()),
+        (None, Some(..)) => return Err(("Missing argument $key.", span).into()),
+        (None, None) => args
+            .get_variadic()?
+            .into_iter()
+            .map(|arg| arg.node)
+            .collect(),
+    };
+
+    let value = match keys.pop() {
+        Some(value) if !keys.is_empty() => value,
+        Some(..) => return Err(("Expected $args to contain a value.", span).into()),
+        None => return Err(("Expected $args to contain a key.", span).into()),
+    };
+
+    Ok(Value::Map(modify_nested(map, &keys, |_| value)))
+}
+
+/// `value` as a map, treating an empty list as an empty map, or `None` if it
+/// isn't one
+fn try_map(value: &Value) -> Option<SassMap> {
+    match value {
+        Value::Map(map) => Some(map.clone()),
+        Value::List(v, ..) if v.is_empty() => Some(SassMap::new()),
+        Value::ArgList(v) if v.is_empty() => Some(SassMap::new()),
+        _ => None,
+    }
+}
+
+/// Merges `map2` into `map1`, merging the maps they both have at the same key
+/// rather than replacing one with the other
+fn deep_merge_maps(mut map1: SassMap, map2: SassMap) -> SassMap {
+    for (key, value) in map2 {
+        let merged = match (map1.get_ref(&key).and_then(try_map), try_map(&value)) {
+            (Some(nested1), Some(nested2)) => Value::Map(deep_merge_maps(nested1, nested2)),
+            _ => value,
+        };
+        map1.insert(key, merged);
+    }
+    map1
+}
+
+fn deep_merge(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
+    let map1 = assert_map(args.get_err(0, "map1")?, "map1", span)?;
+    let map2 = assert_map(args.get_err(1, "map2")?, "map2", span)?;
+    Ok(Value::Map(deep_merge_maps(map1, map2)))
+}
+
+/// Removes `key` from the map nested in `map` at the path `keys`, leaving
+/// `map` unchanged if there is no map there
+fn remove_nested(mut map: SassMap, keys: &[Value], key: &Value) -> SassMap {
+    match keys.split_first() {
+        Some((first, rest)) => {
+            if let Some(nested) = map.get_ref(first).and_then(try_map) {
+                map.insert(first.clone(), Value::Map(remove_nested(nested, rest, key)));
+            }
+        }
+        None => map.remove(key),
+    }
+    map
+}
+
+/// `map.deep-remove($map, $key, $keys...)`
+fn deep_remove(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let key = args.get_err(1, "key")?;
+
+    let mut keys: Vec<Value> = iter::once(key)
+        .chain(args.get_variadic()?.into_iter().map(|arg| arg.node))
+        .collect();
+    let key = keys.pop().unwrap();
+
+    Ok(Value::Map(remove_nested(map, &keys, &key)))
+}
+
 pub(crate) fn declare(f: &mut Module) {
+    f.insert_builtin("deep-merge", deep_merge);
+    f.insert_builtin("deep-remove", deep_remove);
     f.insert_builtin("get", map_get);
     f.insert_builtin("has-key", map_has_key);
     f.insert_builtin("keys", map_keys);
     f.insert_builtin("merge", map_merge);
     f.insert_builtin("remove", map_remove);
+    f.insert_builtin("set", set);
     f.insert_builtin("values", map_values);
 }
@@ -11,7 +11,7 @@ use crate::{
     },
     common::Op,
     error::SassResult,
-    parse::Parser,
+    parse::{HigherIntermediateValue, Parser, ValueVisitor},
     unit::Unit,
     value::{Number, Value},
 };
@@ -569,6 +569,19 @@ fn atan2(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     )
 }
 
+fn div(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let number1 = args.get_err(0, "number1")?;
+    let number2 = args.get_err(1, "number2")?;
+
+    ValueVisitor::new(parser, args.span()).div(
+        HigherIntermediateValue::Literal(number1),
+        HigherIntermediateValue::Literal(number2),
+        true,
+    )
+}
+
 enum NumberState {
     Zero,
     Finite,
@@ -608,6 +621,7 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("pow", pow);
     f.insert_builtin("hypot", hypot);
     f.insert_builtin("atan2", atan2);
+    f.insert_builtin("div", div);
     #[cfg(feature = "random")]
     f.insert_builtin("random", random);
 
@@ -619,4 +633,24 @@ pub(crate) fn declare(f: &mut Module) {
         "pi",
         Value::Dimension(Some(Number::from(std::f64::consts::PI)), Unit::None, true),
     );
+    f.insert_builtin_var(
+        "epsilon",
+        Value::Dimension(Some(Number::from(f64::EPSILON)), Unit::None, true),
+    );
+    f.insert_builtin_var(
+        "max-safe-integer",
+        Value::Dimension(
+            Some(Number::from(9_007_199_254_740_991_i64)),
+            Unit::None,
+            true,
+        ),
+    );
+    f.insert_builtin_var(
+        "min-safe-integer",
+        Value::Dimension(
+            Some(Number::from(-9_007_199_254_740_991_i64)),
+            Unit::None,
+            true,
+        ),
+    );
 }
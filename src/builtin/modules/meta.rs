@@ -2,6 +2,7 @@ use codemap::Spanned;
 
 use crate::{
     args::CallArgs,
+    atrule::mixin::ContentBlock,
     builtin::{
         meta::{
             call, content_exists, feature_exists, function_exists, get_function,
@@ -9,65 +10,69 @@ use crate::{
         },
         modules::{Module, ModuleConfig},
     },
+    common::Identifier,
     error::SassResult,
     parse::{Parser, Stmt},
-    value::Value,
+    value::{SassMap, SassMixin, Value},
 };
 
-fn load_css(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Vec<Stmt>> {
+// content blocks are rejected before builtin mixins that don't accept them
+// are called
+#[allow(clippy::needless_pass_by_value)]
+fn load_css(
+    mut args: CallArgs,
+    _content: Option<ContentBlock>,
+    parser: &mut Parser<'_>,
+) -> SassResult<Vec<Stmt>> {
     args.max_args(2)?;
 
     let span = args.span();
 
-    // todo: https://github.com/sass/dart-sass/issues/1054
-    let url = match args.get_err(0, "module")? {
+    let url = match args.get_err(0, "url")? {
         Value::String(s, ..) => s,
-        v => {
-            return Err((
-                format!("$module: {} is not a string.", v.inspect(span)?),
-                span,
-            )
-                .into())
-        }
+        v => return Err((format!("$url: {} is not a string.", v.inspect(span)?), span).into()),
     };
 
     let with = match args.default_arg(1, "with", Value::Null)? {
-        Value::Map(map) => Some(map),
-        Value::Null => None,
+        Value::Map(map) => map,
+        Value::Null => SassMap::new(),
         v => return Err((format!("$with: {} is not a map.", v.inspect(span)?), span).into()),
     };
 
-    // todo: tests for `with`
-    if let Some(with) = with {
-        let mut config = ModuleConfig::default();
-
-        for (key, value) in with {
-            let key = match key {
-                Value::String(s, ..) => s,
-                v => {
-                    return Err((
-                        format!("$with key: {} is not a string.", v.inspect(span)?),
-                        span,
-                    )
-                        .into())
-                }
-            };
-
-            config.insert(
-                Spanned {
-                    node: key.into(),
+    let mut config = ModuleConfig::default();
+
+    for (key, value) in with {
+        let key = match key {
+            Value::String(s, ..) => s,
+            v => {
+                return Err((
+                    format!("$with key: {} is not a string.", v.inspect(span)?),
                     span,
-                },
-                value.span(span),
-            )?;
-        }
+                )
+                    .into())
+            }
+        };
+
+        config.insert(
+            Spanned {
+                node: key.into(),
+                span,
+            },
+            value.span(span),
+        )?;
+    }
 
-        let (_, stmts) = parser.load_module(&url, &mut config)?;
+    // the module's CSS is emitted here even if it was already loaded, but its
+    // members are only available through `@use`
+    let (_, stmts) = parser.load_module(&url, &mut config, span, true)?;
 
-        Ok(stmts)
-    } else {
-        parser.parse_single_import(&url, span)
+    // if the config isn't empty here, that means variables were passed to a
+    // builtin module
+    if !config.is_empty() {
+        return Err(("Built-in modules can't be configured.", span).into());
     }
+
+    Ok(stmts)
 }
 
 fn module_functions(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -85,7 +90,10 @@ fn module_functions(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
     };
 
     Ok(Value::Map(
-        parser.modules.get(module.into(), args.span())?.functions(),
+        parser
+            .modules
+            .get(module.into(), args.span())?
+            .functions(args.span())?,
     ))
 }
 
@@ -104,10 +112,110 @@ fn module_variables(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
     };
 
     Ok(Value::Map(
-        parser.modules.get(module.into(), args.span())?.variables(),
+        parser
+            .modules
+            .get(module.into(), args.span())?
+            .variables(args.span())?,
     ))
 }
 
+fn get_mixin(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let span = args.span();
+
+    let name: Identifier = match args.get_err(0, "name")? {
+        Value::String(s, _) => s.into(),
+        v => {
+            return Err((
+                format!("$name: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let module = match args.default_arg(1, "module", Value::Null)? {
+        Value::String(s, ..) => Some(s),
+        Value::Null => None,
+        v => {
+            return Err((
+                format!("$module: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let spanned_name = Spanned { node: name, span };
+
+    let mixin = if let Some(module_name) = module {
+        let module = parser.modules.get(module_name.into(), span)?;
+
+        // private mixins exist, but can't be accessed
+        if module.mixin_exists(name, span)? || name.as_str().starts_with('-') {
+            Some(module.get_mixin(spanned_name)?)
+        } else {
+            None
+        }
+    } else if parser.scopes.mixin_exists(name, parser.global_scope) {
+        Some(parser.scopes.get_mixin(spanned_name, parser.global_scope)?)
+    } else {
+        None
+    };
+
+    match mixin {
+        Some(mixin) => Ok(Value::MixinRef(Box::new(SassMixin::new(name, mixin)))),
+        None => Err((format!("Mixin not found: {}", name), span).into()),
+    }
+}
+
+fn accepts_content(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    match args.get_err(0, "mixin")? {
+        Value::MixinRef(mixin) => Ok(Value::bool(mixin.mixin.accepts_content_block())),
+        v => Err((
+            format!(
+                "$mixin: {} is not a mixin reference.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn apply(
+    mut args: CallArgs,
+    content: Option<ContentBlock>,
+    parser: &mut Parser<'_>,
+) -> SassResult<Vec<Stmt>> {
+    let span = args.span();
+
+    let SassMixin { name, mixin } = match args.get_err(0, "mixin")? {
+        Value::MixinRef(mixin) => *mixin,
+        v => {
+            return Err((
+                format!("$mixin: {} is not a mixin reference.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    if content.is_some() && !mixin.accepts_content_block() {
+        return Err(("Mixin doesn't accept a content block.", span).into());
+    }
+
+    parser.include_mixin(
+        mixin,
+        Spanned { node: name, span },
+        args.decrement(),
+        content,
+    )
+}
+
 pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("feature-exists", feature_exists);
     f.insert_builtin("inspect", inspect);
@@ -122,6 +230,9 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("module-functions", module_functions);
     f.insert_builtin("get-function", get_function);
     f.insert_builtin("call", call);
+    f.insert_builtin("get-mixin", get_mixin);
+    f.insert_builtin("accepts-content", accepts_content);
 
-    f.insert_builtin_mixin("load-css", load_css);
+    f.insert_builtin_mixin("load-css", load_css, false);
+    f.insert_builtin_mixin("apply", apply, true);
 }
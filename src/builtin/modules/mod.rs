@@ -1,4 +1,8 @@
-use std::collections::BTreeMap;
+use std::{
+    cell::{Ref, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+};
 
 use codemap::{Span, Spanned};
 
@@ -21,9 +25,13 @@ mod meta;
 mod selector;
 mod string;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct Module {
-    pub scope: Scope,
+    /// The module's variables, mixins and functions
+    ///
+    /// This is shared with the functions declared at the root of the module,
+    /// which are evaluated with it as their global scope.
+    pub scope: Rc<RefCell<Scope>>,
 
     /// Whether or not this module is builtin
     /// e.g. `"sass:math"`
@@ -31,7 +39,15 @@ pub(crate) struct Module {
 }
 
 #[derive(Debug, Default)]
-pub(crate) struct Modules(BTreeMap<Identifier, Module>);
+pub(crate) struct Modules {
+    /// The modules loaded by this stylesheet, by namespace
+    modules: BTreeMap<Identifier, Module>,
+
+    /// The modules loaded by the modules that this stylesheet loaded, which
+    /// the mixins and functions declared in them may refer to when they're
+    /// called from this stylesheet
+    inherited: BTreeMap<Identifier, Module>,
+}
 
 #[derive(Debug, Default)]
 pub(crate) struct ModuleConfig(BTreeMap<Identifier, Value>);
@@ -63,7 +79,7 @@ impl ModuleConfig {
 
 impl Modules {
     pub fn insert(&mut self, name: Identifier, module: Module, span: Span) -> SassResult<()> {
-        if self.0.contains_key(&name) {
+        if self.modules.contains_key(&name) {
             return Err((
                 format!("There's already a module with namespace \"{}\".", name),
                 span,
@@ -71,13 +87,25 @@ impl Modules {
                 .into());
         }
 
-        self.0.insert(name, module);
+        self.modules.insert(name, module);
 
         Ok(())
     }
 
+    /// Makes the modules loaded by a module that this stylesheet loaded
+    /// available to the members of that module
+    pub fn inherit(&mut self, loaded: Modules) {
+        for (name, module) in loaded.modules.into_iter().chain(loaded.inherited) {
+            self.inherited.entry(name).or_insert(module);
+        }
+    }
+
     pub fn get(&self, name: Identifier, span: Span) -> SassResult<&Module> {
-        match self.0.get(&name) {
+        match self
+            .modules
+            .get(&name)
+            .or_else(|| self.inherited.get(&name))
+        {
             Some(v) => Ok(v),
             None => Err((
                 format!(
@@ -90,8 +118,17 @@ impl Modules {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&Identifier, &Module)> {
+        self.modules.iter()
+    }
+
     pub fn get_mut(&mut self, name: Identifier, span: Span) -> SassResult<&mut Module> {
-        match self.0.get_mut(&name) {
+        let module = match self.modules.get_mut(&name) {
+            Some(module) => Some(module),
+            None => self.inherited.get_mut(&name),
+        };
+
+        match module {
             Some(v) => Ok(v),
             None => Err((
                 format!(
@@ -108,12 +145,28 @@ impl Modules {
 impl Module {
     pub fn new_builtin() -> Self {
         Module {
-            scope: Scope::default(),
+            scope: Rc::default(),
             is_builtin: true,
         }
     }
 
-    pub fn get_var(&self, name: Spanned<Identifier>) -> SassResult<&Value> {
+    pub const fn is_builtin(&self) -> bool {
+        self.is_builtin
+    }
+
+    /// Borrows the module's scope, which isn't possible while one of the
+    /// module's functions is being evaluated with it as its global scope
+    fn borrow_scope(&self, span: Span) -> SassResult<Ref<'_, Scope>> {
+        self.scope.try_borrow().map_err(|_| {
+            (
+                "A module's members can't be accessed while one of its functions is running.",
+                span,
+            )
+                .into()
+        })
+    }
+
+    pub fn get_var(&self, name: Spanned<Identifier>) -> SassResult<Value> {
         if name.node.as_str().starts_with('-') {
             return Err((
                 "Private members can't be accessed from outside their modules.",
@@ -122,8 +175,8 @@ impl Module {
                 .into());
         }
 
-        match self.scope.vars.get(&name.node) {
-            Some(v) => Ok(v),
+        match self.borrow_scope(name.span)?.vars.get(&name.node) {
+            Some(v) => Ok(v.clone()),
             None => Err(("Undefined variable.", name.span).into()),
         }
     }
@@ -141,7 +194,14 @@ impl Module {
                 .into());
         }
 
-        if self.scope.insert_var(name.node, value).is_some() {
+        let mut scope = self.scope.try_borrow_mut().map_err(|_| {
+            (
+                "A module's members can't be accessed while one of its functions is running.",
+                name.span,
+            )
+        })?;
+
+        if scope.insert_var(name.node, value).is_some() {
             Ok(())
         } else {
             Err(("Undefined variable.", name.span).into())
@@ -157,18 +217,29 @@ impl Module {
                 .into());
         }
 
-        match self.scope.mixins.get(&name.node) {
+        match self.borrow_scope(name.span)?.mixins.get(&name.node) {
             Some(v) => Ok(v.clone()),
             None => Err(("Undefined mixin.", name.span).into()),
         }
     }
 
-    pub fn insert_builtin_mixin(&mut self, name: &'static str, mixin: BuiltinMixin) {
-        self.scope.mixins.insert(name.into(), Mixin::Builtin(mixin));
+    pub fn insert_builtin_mixin(
+        &mut self,
+        name: &'static str,
+        mixin: BuiltinMixin,
+        accepts_content_block: bool,
+    ) {
+        self.scope.borrow_mut().mixins.insert(
+            name.into(),
+            Mixin::Builtin {
+                body: mixin,
+                accepts_content_block,
+            },
+        );
     }
 
     pub fn insert_builtin_var(&mut self, name: &'static str, value: Value) {
-        self.scope.vars.insert(name.into(), value);
+        self.scope.borrow_mut().vars.insert(name.into(), value);
     }
 
     pub fn get_fn(&self, name: Spanned<Identifier>) -> SassResult<Option<SassFunction>> {
@@ -180,19 +251,23 @@ impl Module {
                 .into());
         }
 
-        Ok(self.scope.functions.get(&name.node).cloned())
+        Ok(self
+            .borrow_scope(name.span)?
+            .functions
+            .get(&name.node)
+            .cloned())
     }
 
-    pub fn var_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.var_exists(name)
+    pub fn var_exists(&self, name: Identifier, span: Span) -> SassResult<bool> {
+        Ok(!name.as_str().starts_with('-') && self.borrow_scope(span)?.var_exists(name))
     }
 
-    pub fn mixin_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.mixin_exists(name)
+    pub fn mixin_exists(&self, name: Identifier, span: Span) -> SassResult<bool> {
+        Ok(!name.as_str().starts_with('-') && self.borrow_scope(span)?.mixin_exists(name))
     }
 
-    pub fn fn_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.fn_exists(name)
+    pub fn fn_exists(&self, name: Identifier, span: Span) -> SassResult<bool> {
+        Ok(!name.as_str().starts_with('-') && self.borrow_scope(span)?.fn_exists(name))
     }
 
     pub fn insert_builtin(
@@ -202,13 +277,14 @@ impl Module {
     ) {
         let ident = name.into();
         self.scope
+            .borrow_mut()
             .functions
             .insert(ident, SassFunction::Builtin(Builtin::new(function), ident));
     }
 
-    pub fn functions(&self) -> SassMap {
-        SassMap::new_with(
-            self.scope
+    pub fn functions(&self, span: Span) -> SassResult<SassMap> {
+        Ok(SassMap::new_with(
+            self.borrow_scope(span)?
                 .functions
                 .iter()
                 .filter(|(key, _)| !key.as_str().starts_with('-'))
@@ -219,12 +295,12 @@ impl Module {
                     )
                 })
                 .collect::<Vec<(Value, Value)>>(),
-        )
+        ))
     }
 
-    pub fn variables(&self) -> SassMap {
-        SassMap::new_with(
-            self.scope
+    pub fn variables(&self, span: Span) -> SassResult<SassMap> {
+        Ok(SassMap::new_with(
+            self.borrow_scope(span)?
                 .vars
                 .iter()
                 .filter(|(key, _)| !key.as_str().starts_with('-'))
@@ -235,10 +311,25 @@ impl Module {
                     )
                 })
                 .collect::<Vec<(Value, Value)>>(),
-        )
+        ))
     }
 
-    pub const fn new_from_scope(scope: Scope, is_builtin: bool) -> Self {
+    /// Creates a module from the global scope of a stylesheet loaded with
+    /// `@use`, making it the closure of the functions declared in it
+    pub fn new_from_scope(scope: Scope, is_builtin: bool) -> Self {
+        let scope = Rc::new(RefCell::new(scope));
+        let closure = Rc::downgrade(&scope);
+
+        for function in scope.borrow_mut().functions.values_mut() {
+            if let SassFunction::UserDefined(function, ..) = function {
+                // functions brought in with `@use ... as *` already have the
+                // closure of the module they were declared in
+                if function.declared_at_root && function.closure.is_none() {
+                    function.closure = Some(closure.clone());
+                }
+            }
+        }
+
         Module { scope, is_builtin }
     }
 }
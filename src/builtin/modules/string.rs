@@ -1,19 +1,114 @@
-use crate::builtin::{
-    modules::Module,
-    string::{
-        quote, str_index, str_insert, str_length, str_slice, to_lower_case, to_upper_case, unquote,
+use num_traits::{Signed, ToPrimitive};
+
+use crate::{
+    args::CallArgs,
+    builtin::{
+        modules::Module,
+        string::{
+            quote, str_index, str_insert, str_length, str_slice, to_lower_case, to_upper_case,
+            unquote,
+        },
     },
+    common::{Brackets, ListSeparator},
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::Value,
 };
 
 #[cfg(feature = "random")]
 use crate::builtin::string::unique_id;
 
+fn split(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let span = args.span();
+
+    let (string, quotes) = match args.get_err(0, "string")? {
+        Value::String(s, q) => (s, q),
+        v => {
+            return Err((
+                format!("$string: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let separator = match args.get_err(1, "separator")? {
+        Value::String(s, ..) => s,
+        v => {
+            return Err((
+                format!("$separator: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let limit = match args.default_arg(2, "limit", Value::Null)? {
+        Value::Null => None,
+        Value::Dimension(Some(n), Unit::None, _) if n.is_decimal() => {
+            return Err((format!("$limit: {} is not an int.", n), span).into())
+        }
+        Value::Dimension(Some(n), Unit::None, _) if n.is_positive() => {
+            Some(n.to_integer().to_usize().unwrap_or(usize::MAX))
+        }
+        Value::Dimension(Some(n), Unit::None, _) => {
+            return Err((format!("$limit: Must be 1 or greater, was {}.", n), span).into())
+        }
+        Value::Dimension(None, Unit::None, ..) => {
+            return Err(("$limit: NaN is not an int.", span).into())
+        }
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!("$limit: Expected {} to have no units.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$limit: {} is not a number.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let chunks: Vec<String> = if string.is_empty() {
+        Vec::new()
+    } else if separator.is_empty() {
+        string.chars().map(String::from).collect()
+    } else {
+        match limit {
+            Some(limit) => string
+                .splitn(limit.saturating_add(1), separator.as_str())
+                .map(str::to_owned)
+                .collect(),
+            None => string
+                .split(separator.as_str())
+                .map(str::to_owned)
+                .collect(),
+        }
+    };
+
+    Ok(Value::List(
+        chunks
+            .into_iter()
+            .map(|chunk| Value::String(chunk, quotes))
+            .collect(),
+        ListSeparator::Comma,
+        Brackets::Bracketed,
+    ))
+}
+
 pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("quote", quote);
     f.insert_builtin("index", str_index);
     f.insert_builtin("insert", str_insert);
     f.insert_builtin("length", str_length);
     f.insert_builtin("slice", str_slice);
+    f.insert_builtin("split", split);
     f.insert_builtin("to-lower-case", to_lower_case);
     f.insert_builtin("to-upper-case", to_upper_case);
     #[cfg(feature = "random")]
@@ -4,6 +4,9 @@
 //! or the builtin functions `rgb()`, `rgba()`, `hsl()`, and `hsla()`,
 //! all of which can accept 1-4 arguments.
 //!
+//! Colors constructed from HWB values, through `color.hwb()`, are converted
+//! to RGBA, and their whiteness and blackness are computed from it.
+//!
 //! It is necessary to retain the original values with which the
 //! color was constructed.
 //! E.g. `hsla(.999999999999, 100, 100, 1)` should retain its full HSLA
@@ -409,43 +412,13 @@ impl Color {
         };
         let temporary_2 = Number::from(2) * luminance - temporary_1.clone();
         hue /= Number::from(360);
-        let mut temporary_r = hue.clone() + Number::small_ratio(1, 3);
-        let mut temporary_g = hue.clone();
-        let mut temporary_b = hue - Number::small_ratio(1, 3);
-
-        macro_rules! clamp_temp {
-            ($temp:ident) => {
-                if $temp > Number::one() {
-                    $temp -= Number::one();
-                } else if $temp.is_negative() {
-                    $temp += Number::one();
-                }
-            };
-        }
-
-        clamp_temp!(temporary_r);
-        clamp_temp!(temporary_g);
-        clamp_temp!(temporary_b);
-
-        fn channel(temp: Number, temp1: &Number, temp2: &Number) -> Number {
-            Number::from(255)
-                * if Number::from(6) * temp.clone() < Number::one() {
-                    temp2.clone() + (temp1.clone() - temp2.clone()) * Number::from(6) * temp
-                } else if Number::from(2) * temp.clone() < Number::one() {
-                    temp1.clone()
-                } else if Number::from(3) * temp.clone() < Number::from(2) {
-                    temp2.clone()
-                        + (temp1.clone() - temp2.clone())
-                            * (Number::small_ratio(2, 3) - temp)
-                            * Number::from(6)
-                } else {
-                    temp2.clone()
-                }
-        }
+        let temporary_r = hue.clone() + Number::small_ratio(1, 3);
+        let temporary_g = hue.clone();
+        let temporary_b = hue - Number::small_ratio(1, 3);
 
-        let red = channel(temporary_r, &temporary_1, &temporary_2);
-        let green = channel(temporary_g, &temporary_1, &temporary_2);
-        let blue = channel(temporary_b, &temporary_1, &temporary_2);
+        let red = Number::from(255) * hue_to_rgb(&temporary_2, &temporary_1, temporary_r);
+        let green = Number::from(255) * hue_to_rgb(&temporary_2, &temporary_1, temporary_g);
+        let blue = Number::from(255) * hue_to_rgb(&temporary_2, &temporary_1, temporary_b);
 
         let repr = repr(&red, &green, &blue, &alpha);
         Color::new_hsla(red, green, blue, alpha, hsla, repr)
@@ -470,6 +443,89 @@ impl Color {
     }
 }
 
+/// HWB color functions
+/// Algorithms adapted from <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+impl Color {
+    /// Calculate whiteness from RGBA values
+    pub fn whiteness(&self) -> Number {
+        let min = min(self.red(), min(self.green(), self.blue()));
+        min / Number::from(255) * Number::from(100)
+    }
+
+    /// Calculate blackness from RGBA values
+    pub fn blackness(&self) -> Number {
+        let max = max(self.red(), max(self.green(), self.blue()));
+        Number::from(100) - max / Number::from(255) * Number::from(100)
+    }
+
+    /// Returns the hue in degrees, the whiteness and blackness between 0
+    /// and 1, and the alpha channel
+    pub fn as_hwb(&self) -> (Number, Number, Number, Number) {
+        let (hue, ..) = self.as_hsla();
+        (
+            hue,
+            self.whiteness() / Number::from(100),
+            self.blackness() / Number::from(100),
+            self.alpha(),
+        )
+    }
+
+    /// Create RGBA representation from HWB values
+    pub fn from_hwb(hue: Number, whiteness: Number, blackness: Number, alpha: Number) -> Self {
+        let mut hue = hue % Number::from(360);
+        if hue.is_negative() {
+            hue += Number::from(360);
+        }
+        hue /= Number::from(360);
+
+        let mut whiteness = whiteness.clamp(0, 1);
+        let mut blackness = blackness.clamp(0, 1);
+
+        // if the whiteness and blackness add to more than 100%, they are
+        // normalized so that their sum is 100%, producing a gray
+        let sum = whiteness.clone() + blackness.clone();
+        if sum > Number::one() {
+            whiteness /= sum.clone();
+            blackness /= sum;
+        }
+
+        let factor = Number::one() - whiteness.clone() - blackness;
+        let to_rgb = |hue: Number| {
+            ((hue_to_rgb(&Number::zero(), &Number::one(), hue) * factor.clone()
+                + whiteness.clone())
+                * Number::from(255))
+            .round()
+        };
+
+        Color::from_rgba(
+            to_rgb(hue.clone() + Number::small_ratio(1, 3)),
+            to_rgb(hue.clone()),
+            to_rgb(hue - Number::small_ratio(1, 3)),
+            alpha,
+        )
+    }
+}
+
+/// Computes a single RGB channel, between 0 and 1, from a hue between 0
+/// and 1 offset by a third for red and blue
+fn hue_to_rgb(m1: &Number, m2: &Number, mut hue: Number) -> Number {
+    if hue > Number::one() {
+        hue -= Number::one();
+    } else if hue.is_negative() {
+        hue += Number::one();
+    }
+
+    if Number::from(6) * hue.clone() < Number::one() {
+        m1.clone() + (m2.clone() - m1.clone()) * Number::from(6) * hue
+    } else if Number::from(2) * hue.clone() < Number::one() {
+        m2.clone()
+    } else if Number::from(3) * hue.clone() < Number::from(2) {
+        m1.clone() + (m2.clone() - m1.clone()) * (Number::small_ratio(2, 3) - hue) * Number::from(6)
+    } else {
+        m1.clone()
+    }
+}
+
 /// Opacity color functions
 impl Color {
     pub fn alpha(&self) -> Number {
@@ -0,0 +1,433 @@
+//! Compiling many stylesheets that load the same files
+//!
+//! See [`Compiler`](crate::Compiler)
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use codemap::File;
+
+use crate::{
+    fs::{normalize, Fs},
+    hash::HashMap,
+    Deprecation, Input, Options, OutputStyle, Report, ReportOptions, Result, Stats, Syntax, Token,
+};
+
+/// Compiles stylesheets, remembering every file it reads, every path it
+/// looks up, and the tokens every file is parsed into, so that partials and
+/// libraries shared by many stylesheets are only read from the filesystem
+/// and parsed once
+///
+/// A `Compiler` is `Send` and `Sync`, so one can be shared by the threads of
+/// a build tool that compiles many stylesheets in parallel:
+///
+/// ```
+/// use std::{sync::Arc, thread};
+///
+/// let compiler = Arc::new(grass::Compiler::new());
+///
+/// let threads: Vec<_> = ["red", "blue"]
+///     .iter()
+///     .map(|color| {
+///         let compiler = Arc::clone(&compiler);
+///         let input = format!("a {{ color: {}; }}", color);
+///         thread::spawn(move || compiler.from_string(input, &grass::Options::default()))
+///     })
+///     .collect();
+///
+/// for thread in threads {
+///     assert!(thread.join().unwrap().is_ok());
+/// }
+/// ```
+///
+/// Files are remembered until they are [invalidated](Compiler::invalidate)
+/// or [`clear`](Compiler::clear) is called, so changes made to them on disk
+/// in the meantime aren't seen. Every compilation should read from the same
+/// [`Fs`](crate::Fs).
+///
+/// Parsed files are remembered by their canonical path and the syntax they
+/// were parsed in, and are only reused for a file with the same contents.
+/// Each stylesheet is still evaluated separately, along with the modules it
+/// loads, so that compilations can't affect each other.
+#[derive(Debug, Default)]
+pub struct Compiler {
+    cache: FsCache,
+
+    parsed: ParseCache,
+
+    /// The results of incremental compilations, along with the options they
+    /// were compiled with, by the normalized path of the stylesheet that was
+    /// compiled
+    compiled: RwLock<HashMap<PathBuf, (OptionsKey, String, Stats)>>,
+}
+
+impl Compiler {
+    /// Creates a compiler that hasn't read any files yet
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compiles CSS from a path, as [`from_path`](crate::from_path) does
+    #[inline]
+    pub fn from_path(&self, p: &str, options: &Options) -> Result<String> {
+        self.with_cache(options, |options| crate::from_path(p, options))
+    }
+
+    /// Compiles CSS from a string, as [`from_string`](crate::from_string)
+    /// does
+    #[inline]
+    pub fn from_string(&self, input: String, options: &Options) -> Result<String> {
+        self.with_cache(options, |options| crate::from_string(input, options))
+    }
+
+    /// Compiles CSS, additionally collecting the reports selected in
+    /// `reports`, as [`compile_with_reports`](crate::compile_with_reports)
+    /// does
+    #[inline]
+    pub fn compile_with_reports(
+        &self,
+        input: Input<'_>,
+        options: &Options,
+        reports: ReportOptions,
+    ) -> Result<Report> {
+        self.with_cache(options, |options| {
+            crate::compile_with_reports(input, options, reports)
+        })
+    }
+
+    /// Compiles CSS from a path, additionally returning [`Stats`] about the
+    /// compilation, unless it was already compiled this way with the same
+    /// options and none of the files it loaded have been
+    /// [invalidated](Compiler::invalidate) since, in which case the earlier
+    /// result is returned
+    ///
+    /// The files the stylesheet depends on, other than itself, are listed
+    /// in [`Stats::loaded_paths`](Stats::loaded_paths). Options that only
+    /// limit the time or memory a compilation may use, or choose how its
+    /// warnings are logged, are ignored when deciding whether the options
+    /// are the same. Options that are references, such as the
+    /// [`Fs`](crate::Fs), are the same only if they refer to the same value.
+    ///
+    /// ```
+    /// use grass::{Compiler, MemoryFs, Options};
+    ///
+    /// let mut fs = MemoryFs::default();
+    /// fs.add_file("input.scss", "@use \"colors\";\na { color: colors.$primary; }");
+    /// fs.add_file("_colors.scss", "$primary: red;");
+    ///
+    /// let compiler = Compiler::new();
+    /// let (_, stats) = compiler.incremental_from_path("input.scss", &Options::default().fs(&fs))?;
+    /// assert_eq!(1, stats.loaded_paths.len());
+    ///
+    /// fs.add_file("_colors.scss", "$primary: blue;");
+    /// compiler.invalidate(&stats.loaded_paths);
+    ///
+    /// let (css, _) = compiler.incremental_from_path("input.scss", &Options::default().fs(&fs))?;
+    /// assert_eq!("a {\n  color: blue;\n}\n", css);
+    /// # Ok::<(), Box<grass::Error>>(())
+    /// ```
+    pub fn incremental_from_path(&self, p: &str, options: &Options) -> Result<(String, Stats)> {
+        let key = normalize(Path::new(p));
+        let options_key = OptionsKey::new(options);
+        if let Some((compiled_with, css, stats)) = read(&self.compiled).get(&key) {
+            if *compiled_with == options_key {
+                return Ok((css.clone(), stats.clone()));
+            }
+        }
+
+        let report = self.compile_with_reports(
+            Input::Path(p),
+            options,
+            ReportOptions::default().stats(true),
+        )?;
+        let stats = report.stats.unwrap_or_default();
+        write(&self.compiled).insert(key, (options_key, report.css.clone(), stats.clone()));
+        Ok((report.css, stats))
+    }
+
+    /// Forgets the contents of each of the `changed` files, along with the
+    /// result of every incremental compilation that loaded one of them
+    ///
+    /// Paths may be given as they appear in
+    /// [`Stats::loaded_paths`](Stats::loaded_paths), or as absolute paths,
+    /// such as those reported by filesystem notifications. Whether files
+    /// exist is looked up again too, so that files which have been added are
+    /// found.
+    pub fn invalidate<P: AsRef<Path>>(&self, changed: impl IntoIterator<Item = P>) {
+        let changed: Vec<PathBuf> = changed
+            .into_iter()
+            .flat_map(|path| self.cache.identities(path.as_ref()))
+            .collect();
+        let is_changed = |path: &Path| {
+            self.cache
+                .identities(path)
+                .iter()
+                .any(|identity| changed.contains(identity))
+        };
+
+        // `is_changed` reads the canonical paths, so the lock can't be held
+        // while calling it
+        let looked_up: Vec<PathBuf> = read(&self.cache.canonical).keys().cloned().collect();
+        let stale: Vec<PathBuf> = looked_up
+            .into_iter()
+            .filter(|path| is_changed(path))
+            .collect();
+
+        write(&self.compiled).retain(|path, (_, _, stats)| {
+            !is_changed(path) && !stats.loaded_paths.iter().any(|path| is_changed(path))
+        });
+        write(&self.parsed.files).retain(|(path, _), _| !changed.contains(path));
+        write(&self.cache.files).retain(|path, _| !is_changed(path));
+        write(&self.cache.is_file).clear();
+        write(&self.cache.is_dir).clear();
+
+        let mut canonical = write(&self.cache.canonical);
+        for path in stale {
+            canonical.remove(&path);
+        }
+    }
+
+    /// Forgets every file that has been read or parsed, along with the
+    /// result of every incremental compilation, so that the next compilation
+    /// sees any changes to them
+    pub fn clear(&self) {
+        write(&self.compiled).clear();
+        write(&self.parsed.files).clear();
+        write(&self.cache.files).clear();
+        write(&self.cache.is_file).clear();
+        write(&self.cache.is_dir).clear();
+        write(&self.cache.canonical).clear();
+    }
+
+    fn with_cache<T>(&self, options: &Options, compile: impl FnOnce(&Options) -> T) -> T {
+        let fs = CachedFs {
+            fs: options.fs,
+            cache: &self.cache,
+        };
+        compile(&Options {
+            fs: &fs,
+            parse_cache: Some(&self.parsed),
+            ..options.clone()
+        })
+    }
+}
+
+/// The tokens of the files that have been parsed, by canonical path and the
+/// syntax they were parsed in
+///
+/// Tokens hold spans into the [`CodeMap`](codemap::CodeMap) of the
+/// compilation they were parsed in, so they are kept relative to the start
+/// of their file and moved to the copy of the file in each compilation that
+/// reuses them.
+#[derive(Debug, Default)]
+pub(crate) struct ParseCache {
+    files: RwLock<HashMap<(PathBuf, Syntax), ParsedFile>>,
+}
+
+#[derive(Debug)]
+struct ParsedFile {
+    source: String,
+    /// The kind of each token, along with the offsets of its start and end
+    tokens: Vec<(char, u64, u64)>,
+}
+
+impl ParseCache {
+    /// The tokens of `file`, read from `path`, in `syntax`, reusing those of
+    /// the last file parsed from the same path in the same syntax if its
+    /// contents are the same
+    pub(crate) fn tokenize(
+        &self,
+        fs: &dyn Fs,
+        path: &Path,
+        syntax: Syntax,
+        file: &Arc<File>,
+    ) -> Result<Vec<Token>> {
+        // stylesheets compiled from strings have nothing to be found by
+        let key = match fs.canonicalize(path) {
+            Ok(path) => (path, syntax),
+            Err(..) => return syntax.tokenize(file),
+        };
+
+        if let Some(parsed) = read(&self.files).get(&key) {
+            if parsed.source == file.source() {
+                return Ok(parsed
+                    .tokens
+                    .iter()
+                    .map(|&(kind, start, end)| Token::new(file.span.subspan(start, end), kind))
+                    .collect());
+            }
+        }
+
+        let toks = syntax.tokenize(file)?;
+
+        let start = file.span.low();
+        let parsed = ParsedFile {
+            source: file.source().to_owned(),
+            tokens: toks
+                .iter()
+                .map(|tok| (tok.kind, tok.pos.low() - start, tok.pos.high() - start))
+                .collect(),
+        };
+        write(&self.files).insert(key, parsed);
+
+        Ok(toks)
+    }
+}
+
+/// The options that affect the result of a compilation, which an incremental
+/// result is only reused for if they're unchanged
+///
+/// References, such as the filesystem, are compared by address.
+#[derive(Debug, PartialEq)]
+struct OptionsKey {
+    style: OutputStyle,
+    load_paths: Vec<PathBuf>,
+    import_roots: Option<Vec<PathBuf>>,
+    max_nesting_depth: Option<usize>,
+    #[cfg(feature = "plugins")]
+    plugins: Vec<usize>,
+    allows_charset: bool,
+    unicode_error_messages: bool,
+    quiet: bool,
+    verbose: bool,
+    fatal_deprecations: Vec<Deprecation>,
+    silence_deprecations: Vec<Deprecation>,
+    warn_shorthand_conflicts: bool,
+    selector_line_breaks: bool,
+    line_comments: bool,
+    random_seed: Option<u64>,
+    precision: usize,
+    source_url_rewriter: Option<usize>,
+    syntax: Syntax,
+    url: Option<String>,
+    fs: usize,
+    value_formatter: Option<usize>,
+}
+
+impl OptionsKey {
+    fn new(options: &Options) -> Self {
+        Self {
+            style: options.style,
+            load_paths: options
+                .load_paths
+                .iter()
+                .map(|path| path.to_path_buf())
+                .collect(),
+            import_roots: options
+                .import_roots
+                .as_ref()
+                .map(|roots| roots.iter().map(|path| path.to_path_buf()).collect()),
+            max_nesting_depth: options.max_nesting_depth,
+            #[cfg(feature = "plugins")]
+            plugins: options
+                .plugins
+                .iter()
+                .map(|plugin| address(*plugin))
+                .collect(),
+            allows_charset: options.allows_charset,
+            unicode_error_messages: options.unicode_error_messages,
+            quiet: options.quiet,
+            verbose: options.verbose,
+            fatal_deprecations: options.fatal_deprecations.to_vec(),
+            silence_deprecations: options.silence_deprecations.to_vec(),
+            warn_shorthand_conflicts: options.warn_shorthand_conflicts,
+            selector_line_breaks: options.selector_line_breaks,
+            line_comments: options.line_comments,
+            random_seed: options.random_seed,
+            precision: options.precision,
+            source_url_rewriter: options
+                .source_url_rewriter
+                .map(|rewriter| address(rewriter.0)),
+            syntax: options.syntax,
+            url: options.url.map(str::to_owned),
+            fs: address(options.fs),
+            value_formatter: options.value_formatter.map(address),
+        }
+    }
+}
+
+/// The address of the value `value` refers to, ignoring the metadata of
+/// trait objects, which may differ between references to the same value
+fn address<T: ?Sized>(value: &T) -> usize {
+    (value as *const T).cast::<()>() as usize
+}
+
+/// The results of the filesystem calls made while compiling
+#[derive(Debug, Default)]
+struct FsCache {
+    files: RwLock<HashMap<PathBuf, Arc<[u8]>>>,
+    is_file: RwLock<HashMap<PathBuf, bool>>,
+    is_dir: RwLock<HashMap<PathBuf, bool>>,
+    canonical: RwLock<HashMap<PathBuf, PathBuf>>,
+}
+
+impl FsCache {
+    /// The paths that refer to the same file as `path`: `path` itself once
+    /// normalized, and its canonical form if it has been looked up
+    fn identities(&self, path: &Path) -> Vec<PathBuf> {
+        let mut identities = vec![normalize(path)];
+        identities.extend(read(&self.canonical).get(path).cloned());
+        identities
+    }
+}
+
+/// Passes each call on to `fs` the first time it's made, remembering the
+/// result in `cache`
+///
+/// Errors aren't remembered, so that files which couldn't be read are tried
+/// again.
+#[derive(Debug)]
+struct CachedFs<'a> {
+    fs: &'a dyn Fs,
+    cache: &'a FsCache,
+}
+
+impl Fs for CachedFs<'_> {
+    fn is_dir(&self, path: &Path) -> bool {
+        cached(&self.cache.is_dir, path, || Ok(self.fs.is_dir(path))).unwrap_or(false)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        cached(&self.cache.is_file, path, || Ok(self.fs.is_file(path))).unwrap_or(false)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        cached(&self.cache.files, path, || {
+            Ok(Arc::from(self.fs.read(path)?))
+        })
+        .map(|contents| contents.to_vec())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        cached(&self.cache.canonical, path, || self.fs.canonicalize(path))
+    }
+}
+
+/// The value in `map` for `path`, or the result of `get` if there isn't one
+fn cached<T: Clone>(
+    map: &RwLock<HashMap<PathBuf, T>>,
+    path: &Path,
+    get: impl FnOnce() -> io::Result<T>,
+) -> io::Result<T> {
+    if let Some(value) = read(map).get(path) {
+        return Ok(value.clone());
+    }
+
+    let value = get()?;
+    write(map).insert(path.to_path_buf(), value.clone());
+    Ok(value)
+}
+
+// a panic while the lock is held can't leave a map half-updated, so a
+// poisoned lock is still safe to use
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
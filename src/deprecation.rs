@@ -0,0 +1,76 @@
+//! The deprecated features `grass` warns about
+//!
+//! See [`Options::fatal_deprecations`](crate::Options::fatal_deprecations)
+//! and [`Options::silence_deprecations`](crate::Options::silence_deprecations)
+
+/// A deprecated feature of Sass, which is warned about each place it is used
+///
+/// Each deprecation is identified by the same ID as in `dart-sass`, such as
+/// `slash-div`, which is also the suffix of the code of its warnings in
+/// machine-readable [diagnostics](crate::Diagnostic), such as
+/// `deprecated-slash-div`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deprecation {
+    /// Using `/` for division outside of `calc()`, rather than `math.div()`
+    SlashDiv,
+
+    /// Using `@elseif` rather than `@else if`
+    Elseif,
+
+    /// Passing a string to `call()` rather than a function reference
+    CallString,
+
+    /// Loading a stylesheet with `@import` rather than `@use`
+    Import,
+
+    /// Passing a hue with a unit other than `deg`, or a saturation or
+    /// lightness without the unit `%`, to `hsl()` or `hsla()`
+    FunctionUnits,
+}
+
+impl Deprecation {
+    /// Every deprecation `grass` warns about
+    pub const ALL: &'static [Deprecation] = &[
+        Deprecation::SlashDiv,
+        Deprecation::Elseif,
+        Deprecation::CallString,
+        Deprecation::Import,
+        Deprecation::FunctionUnits,
+    ];
+
+    /// The ID of this deprecation, such as `slash-div`
+    #[must_use]
+    #[inline]
+    pub fn id(self) -> &'static str {
+        match self {
+            Deprecation::SlashDiv => "slash-div",
+            Deprecation::Elseif => "elseif",
+            Deprecation::CallString => "call-string",
+            Deprecation::Import => "import",
+            Deprecation::FunctionUnits => "function-units",
+        }
+    }
+
+    /// The deprecation with the given ID, if there is one
+    #[must_use]
+    #[inline]
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|deprecation| deprecation.id() == id)
+    }
+
+    /// The code of warnings about this deprecation in machine-readable
+    /// diagnostics
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            Deprecation::SlashDiv => "deprecated-slash-div",
+            Deprecation::Elseif => "deprecated-elseif",
+            Deprecation::CallString => "deprecated-call-string",
+            Deprecation::Import => "deprecated-import",
+            Deprecation::FunctionUnits => "deprecated-function-units",
+        }
+    }
+}
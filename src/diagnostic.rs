@@ -0,0 +1,390 @@
+//! Machine-readable descriptions of the errors and warnings produced while
+//! compiling, for editor plugins and CI tooling
+
+use std::fmt::Write;
+
+use codemap::SpanLoc;
+
+/// How serious a [`Diagnostic`] is
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Compilation failed
+    Error,
+    /// Emitted by `@warn`, or for a deprecated or suspicious construct
+    Warning,
+    /// Emitted by `@debug`
+    Debug,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Debug => "debug",
+        }
+    }
+}
+
+/// A position in a source file, with line and column numbers starting at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The section of a source file that a [`Diagnostic`] refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceRange {
+    pub start: Position,
+    /// The position just after the end of the range
+    pub end: Position,
+}
+
+/// Another location that is relevant to a [`Diagnostic`], such as the
+/// `@extend` that conflicts with the one an error refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondarySpan {
+    /// A description of how this location relates to the diagnostic
+    pub message: String,
+
+    /// The name of the file the location is in
+    pub file: String,
+
+    pub range: SourceRange,
+}
+
+impl SecondarySpan {
+    pub(crate) fn new(message: String, loc: &SpanLoc) -> Self {
+        SecondarySpan {
+            message,
+            file: loc.file.name().to_owned(),
+            range: SourceRange::from_loc(loc),
+        }
+    }
+}
+
+impl SourceRange {
+    fn from_loc(loc: &SpanLoc) -> Self {
+        SourceRange {
+            start: Position {
+                line: loc.begin.line + 1,
+                column: loc.begin.column + 1,
+            },
+            end: Position {
+                line: loc.end.line + 1,
+                column: loc.end.column + 1,
+            },
+        }
+    }
+}
+
+/// An error, warning, or debug message produced while compiling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// A short identifier for the kind of diagnostic, such as `error` or
+    /// `shorthand-conflict`, which tools may use to filter diagnostics
+    pub code: &'static str,
+
+    pub message: String,
+
+    /// The name of the file the diagnostic refers to, if it refers to one
+    pub file: Option<String>,
+
+    /// The section of `file` the diagnostic refers to
+    pub range: Option<SourceRange>,
+
+    /// Other locations that are relevant to the diagnostic
+    pub secondary: Vec<SecondarySpan>,
+
+    /// Text that would fix the problem if written in place of `range`, if
+    /// there is an obvious fix
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(
+        severity: Severity,
+        code: &'static str,
+        message: String,
+        loc: Option<&SpanLoc>,
+    ) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message,
+            file: loc.map(|loc| loc.file.name().to_owned()),
+            range: loc.map(SourceRange::from_loc),
+            secondary: Vec::new(),
+            suggestion: None,
+        }
+    }
+
+    /// Serializes this diagnostic as a single-line JSON object, such as
+    ///
+    /// ```json
+    /// {"severity":"error","code":"error","message":"Expected expression.","file":"input.scss","range":{"start":{"line":1,"column":8},"end":{"line":1,"column":9}},"secondary":[],"suggestion":null}
+    /// ```
+    ///
+    /// `file` and `range` are `null` if the diagnostic doesn't refer to a
+    /// location in a file. Each element of `secondary` is an object with a
+    /// `message`, `file` and `range`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+
+        json.push_str("{\"severity\":");
+        write_json_string(&mut json, self.severity.as_str());
+        json.push_str(",\"code\":");
+        write_json_string(&mut json, self.code);
+        json.push_str(",\"message\":");
+        write_json_string(&mut json, &self.message);
+
+        json.push_str(",\"file\":");
+        match &self.file {
+            Some(file) => write_json_string(&mut json, file),
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"range\":");
+        match self.range {
+            Some(range) => write_json_range(&mut json, range),
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"secondary\":[");
+        for (idx, secondary) in self.secondary.iter().enumerate() {
+            if idx != 0 {
+                json.push(',');
+            }
+            json.push_str("{\"message\":");
+            write_json_string(&mut json, &secondary.message);
+            json.push_str(",\"file\":");
+            write_json_string(&mut json, &secondary.file);
+            json.push_str(",\"range\":");
+            write_json_range(&mut json, secondary.range);
+            json.push('}');
+        }
+        json.push(']');
+
+        json.push_str(",\"suggestion\":");
+        match &self.suggestion {
+            Some(suggestion) => write_json_string(&mut json, suggestion),
+            None => json.push_str("null"),
+        }
+
+        json.push('}');
+        json
+    }
+
+    /// Formats this diagnostic as a GitHub Actions workflow command, such as
+    ///
+    /// ```text
+    /// ::error file=input.scss,line=1,col=8,endLine=1,endColumn=9,title=error::Expected expression.
+    /// ```
+    ///
+    /// which shows the message as an annotation on the given lines when
+    /// printed by a workflow step. `@debug` messages are written as notices.
+    pub fn to_github(&self) -> String {
+        let command = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Debug => "notice",
+        };
+
+        let mut out = format!("::{} ", command);
+
+        if let Some(file) = &self.file {
+            out.push_str("file=");
+            write_github_property(&mut out, file);
+            out.push(',');
+        }
+
+        if let Some(SourceRange { start, end }) = self.range {
+            let _ = write!(
+                out,
+                "line={},col={},endLine={},endColumn={},",
+                start.line, start.column, end.line, end.column
+            );
+        }
+
+        out.push_str("title=");
+        write_github_property(&mut out, self.code);
+        out.push_str("::");
+        write_github_data(&mut out, &self.message);
+        out
+    }
+}
+
+/// How many warnings of each kind of deprecation are printed, unless
+/// compiling verbosely
+const MAX_REPETITIONS: usize = 5;
+
+impl Diagnostic {
+    pub(crate) fn is_deprecation(&self) -> bool {
+        self.code.starts_with("deprecated-")
+    }
+
+    /// Whether this warning is a deprecation warning of a kind that was
+    /// already emitted `MAX_REPETITIONS` times in `previous`
+    pub(crate) fn is_repetitive(&self, previous: &[Diagnostic]) -> bool {
+        self.is_deprecation()
+            && previous
+                .iter()
+                .filter(|diagnostic| diagnostic.code == self.code)
+                .nth(MAX_REPETITIONS - 1)
+                .is_some()
+    }
+}
+
+/// The number of deprecation warnings in `diagnostics` that weren't printed
+/// because more than `MAX_REPETITIONS` of their kind were emitted
+pub(crate) fn repetitive_deprecations(diagnostics: &[Diagnostic]) -> usize {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+
+    for diagnostic in diagnostics.iter().filter(|d| d.is_deprecation()) {
+        match counts
+            .iter_mut()
+            .find(|(code, ..)| *code == diagnostic.code)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((diagnostic.code, 1)),
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(_, count)| count.saturating_sub(MAX_REPETITIONS))
+        .sum()
+}
+
+/// Formats `diagnostics` as a Checkstyle XML report, as read by many code
+/// review and CI tools
+///
+/// Diagnostics are grouped by file, in the order each file first appears.
+/// Those that don't refer to a file are listed under a file with an empty
+/// name.
+pub fn checkstyle_report(diagnostics: &[Diagnostic]) -> String {
+    let mut files: Vec<(&str, Vec<&Diagnostic>)> = Vec::new();
+
+    for diagnostic in diagnostics {
+        let file = diagnostic.file.as_deref().unwrap_or("");
+        match files.iter_mut().find(|(name, ..)| *name == file) {
+            Some((_, group)) => group.push(diagnostic),
+            None => files.push((file, vec![diagnostic])),
+        }
+    }
+
+    let mut xml =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+
+    for (file, group) in files {
+        xml.push_str("  <file name=\"");
+        write_xml_attribute(&mut xml, file);
+        xml.push_str("\">\n");
+
+        for diagnostic in group {
+            xml.push_str("    <error");
+            if let Some(SourceRange { start, .. }) = diagnostic.range {
+                let _ = write!(xml, " line=\"{}\" column=\"{}\"", start.line, start.column);
+            }
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Debug => "info",
+            };
+            let _ = write!(xml, " severity=\"{}\" message=\"", severity);
+            write_xml_attribute(&mut xml, &diagnostic.message);
+            xml.push_str("\" source=\"grass.");
+            write_xml_attribute(&mut xml, diagnostic.code);
+            xml.push_str("\"/>\n");
+        }
+
+        xml.push_str("  </file>\n");
+    }
+
+    xml.push_str("</checkstyle>\n");
+    xml
+}
+
+/// Escapes the message of a workflow command
+fn write_github_data(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '%' => out.push_str("%25"),
+            '\r' => out.push_str("%0D"),
+            '\n' => out.push_str("%0A"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Escapes a property of a workflow command, which additionally may not
+/// contain the `:` and `,` that delimit properties
+fn write_github_property(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            ':' => out.push_str("%3A"),
+            ',' => out.push_str("%2C"),
+            c => write_github_data(out, c.encode_utf8(&mut [0; 4])),
+        }
+    }
+}
+
+fn write_xml_attribute(xml: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\n' => xml.push_str("&#10;"),
+            '\r' => xml.push_str("&#13;"),
+            '\t' => xml.push_str("&#9;"),
+            c if c.is_control() => {}
+            c => xml.push(c),
+        }
+    }
+}
+
+fn write_json_range(json: &mut String, SourceRange { start, end }: SourceRange) {
+    let _ = write!(
+        json,
+        "{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}",
+        start.line, start.column, end.line, end.column
+    );
+}
+
+fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// How warnings and `@debug` messages are written to stderr
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    /// Human-readable text, in the same format as `dart-sass`
+    Human,
+    /// One JSON object per line, as produced by [`Diagnostic::to_json`]
+    Json,
+    /// One GitHub Actions workflow command per line, as produced by
+    /// [`Diagnostic::to_github`], so that diagnostics are shown as
+    /// annotations on pull requests
+    Github,
+}
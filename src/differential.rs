@@ -0,0 +1,199 @@
+//! Differential testing against the reference implementation, `dart-sass`
+//!
+//! Each input is compiled by both grass and a locally installed `sass`
+//! binary, and any disagreement between the two is reported as a
+//! [`Difference`]. This is used by the `differential` test, which runs a
+//! corpus of stylesheets along with generated ones, and by the
+//! `differential` fuzz target, which saves any input that makes the two
+//! compilers disagree.
+//!
+//! The path to `dart-sass` is read from the `DART_SASS` environment
+//! variable, falling back to `sass` on the `PATH`.
+
+use std::{
+    env, fmt,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::{from_string, Options};
+
+/// A way in which grass and `dart-sass` disagree about an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// Both compilers succeeded, but produced different CSS
+    Output { grass: String, dart_sass: String },
+
+    /// Only grass failed to compile the input
+    GrassError(String),
+
+    /// Only `dart-sass` failed to compile the input
+    DartSassError(String),
+
+    /// Both compilers failed, but with different error messages
+    ErrorMessage { grass: String, dart_sass: String },
+
+    /// grass panicked while compiling the input
+    Panic(String),
+}
+
+impl Difference {
+    /// Whether grass accepted or rejected the input differently than
+    /// `dart-sass`, as opposed to only disagreeing on an error message
+    pub fn is_miscompilation(&self) -> bool {
+        !matches!(self, Difference::ErrorMessage { .. })
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Output { grass, dart_sass } => {
+                writeln!(f, "outputs differ")?;
+                write_diff(f, grass, dart_sass)
+            }
+            Difference::GrassError(err) => write!(f, "only grass errored: {}", err),
+            Difference::DartSassError(err) => write!(f, "only dart-sass errored: {}", err),
+            Difference::ErrorMessage { grass, dart_sass } => write!(
+                f,
+                "error messages differ\n  grass:     {}\n  dart-sass: {}",
+                grass, dart_sass
+            ),
+            Difference::Panic(msg) => write!(f, "grass panicked: {}", msg),
+        }
+    }
+}
+
+/// Writes the lines of `grass` and `dart_sass` that differ, prefixed by `-`
+/// and `+` respectively
+fn write_diff(f: &mut fmt::Formatter<'_>, grass: &str, dart_sass: &str) -> fmt::Result {
+    let grass: Vec<&str> = grass.lines().collect();
+    let dart_sass: Vec<&str> = dart_sass.lines().collect();
+
+    for idx in 0..grass.len().max(dart_sass.len()) {
+        match (grass.get(idx), dart_sass.get(idx)) {
+            (Some(a), Some(b)) if a == b => writeln!(f, "   {}", a)?,
+            (a, b) => {
+                if let Some(a) = a {
+                    writeln!(f, "  -{}", a)?;
+                }
+                if let Some(b) = b {
+                    writeln!(f, "  +{}", b)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A `dart-sass` executable
+#[derive(Debug, Clone)]
+pub struct DartSass {
+    path: PathBuf,
+}
+
+impl DartSass {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        DartSass { path: path.into() }
+    }
+
+    /// Uses the executable named by `DART_SASS`, or else `sass`, without
+    /// checking that it exists
+    pub fn from_env() -> Self {
+        DartSass::new(env::var_os("DART_SASS").unwrap_or_else(|| "sass".into()))
+    }
+
+    /// Like [`DartSass::from_env`], but returns `None` if the executable
+    /// cannot be run
+    pub fn find() -> Option<Self> {
+        let dart_sass = DartSass::from_env();
+        dart_sass.version().ok()?;
+        Some(dart_sass)
+    }
+
+    /// The version reported by `sass --version`
+    pub fn version(&self) -> io::Result<String> {
+        let output = Command::new(&self.path).arg("--version").output()?;
+        if !output.status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    }
+
+    /// Compiles `input` to expanded CSS, returning the first line of the
+    /// error message if compilation fails
+    pub fn compile(&self, input: &str) -> io::Result<Result<String, String>> {
+        let mut child = Command::new(&self.path)
+            .args(&["--stdin", "--no-source-map", "--style=expanded", "--quiet"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(input.as_bytes())?;
+
+        let output = child.wait_with_output()?;
+
+        if output.status.success() {
+            Ok(Ok(String::from_utf8_lossy(&output.stdout).into_owned()))
+        } else {
+            Ok(Err(first_line(&String::from_utf8_lossy(&output.stderr))))
+        }
+    }
+}
+
+/// Compiles `input` with both grass and `dart-sass`, returning how they
+/// disagree, if at all
+pub fn compare(
+    input: &str,
+    options: &Options,
+    dart_sass: &DartSass,
+) -> io::Result<Option<Difference>> {
+    let expected = dart_sass.compile(input)?;
+
+    let actual =
+        match panic::catch_unwind(AssertUnwindSafe(|| from_string(input.to_owned(), options))) {
+            Ok(result) => result.map_err(|e| first_line(&e.to_string())),
+            Err(payload) => {
+                let msg = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| (*s).to_owned())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                return Ok(Some(Difference::Panic(msg)));
+            }
+        };
+
+    Ok(match (actual, expected) {
+        (Ok(grass), Ok(dart_sass)) => {
+            if grass.trim_end() == dart_sass.trim_end() {
+                None
+            } else {
+                Some(Difference::Output { grass, dart_sass })
+            }
+        }
+        (Err(grass), Ok(..)) => Some(Difference::GrassError(grass)),
+        (Ok(..), Err(dart_sass)) => Some(Difference::DartSassError(dart_sass)),
+        (Err(grass), Err(dart_sass)) => {
+            if grass == dart_sass {
+                None
+            } else {
+                Some(Difference::ErrorMessage { grass, dart_sass })
+            }
+        }
+    })
+}
+
+fn first_line(s: &str) -> String {
+    s.lines().next().unwrap_or_default().trim_end().to_owned()
+}
@@ -2,11 +2,13 @@ use std::{
     error::Error,
     fmt::{self, Display},
     io,
-    rc::Rc,
     string::FromUtf8Error,
+    sync::Arc,
 };
 
-use codemap::{Span, SpanLoc};
+use codemap::{CodeMap, Span, SpanLoc};
+
+use crate::diagnostic::{Diagnostic, SecondarySpan, Severity};
 
 pub type SassResult<T> = Result<T, Box<SassError>>;
 
@@ -34,41 +36,273 @@ pub type SassResult<T> = Result<T, Box<SassError>>;
 #[derive(Debug, Clone)]
 pub struct SassError {
     kind: SassErrorKind,
+
+    /// Other locations relevant to this error, with a description of each,
+    /// which are looked up along with the location of the error itself
+    secondary: Vec<(String, Span)>,
+
+    /// Text that would fix this error if written in place of its span
+    suggestion: Option<String>,
 }
 
 impl SassError {
-    pub(crate) fn raw(self) -> (String, Span) {
+    fn new(kind: SassErrorKind) -> Box<Self> {
+        Box::new(SassError {
+            kind,
+            secondary: Vec::new(),
+            suggestion: None,
+        })
+    }
+
+    /// A machine-readable description of this error
+    #[inline]
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = self.diagnostic_without_hints();
+        if let SassErrorKind::ParseError { secondary, .. } = &self.kind {
+            diagnostic.secondary = secondary.clone();
+        }
+        diagnostic.suggestion = self.suggestion.clone();
+        diagnostic
+    }
+
+    fn diagnostic_without_hints(&self) -> Diagnostic {
+        match &self.kind {
+            SassErrorKind::ParseError { message, loc, .. } => {
+                Diagnostic::new(Severity::Error, "error", message.clone(), Some(loc))
+            }
+            SassErrorKind::Raw(message, ..)
+            | SassErrorKind::Traced(message, ..)
+            | SassErrorKind::Nested { message, .. } => {
+                Diagnostic::new(Severity::Error, "error", message.clone(), None)
+            }
+            SassErrorKind::IoError(err) => {
+                Diagnostic::new(Severity::Error, "io", err.to_string(), None)
+            }
+            SassErrorKind::FromUtf8Error(message) => {
+                Diagnostic::new(Severity::Error, "utf-8", message.clone(), None)
+            }
+        }
+    }
+
+    /// The message of this error and the span it points to, or `span` if it
+    /// doesn't point to one, e.g. because it has already been located
+    pub(crate) fn raw(self, span: Span) -> (String, Span) {
         match self.kind {
             SassErrorKind::Raw(string, span) => (string, span),
-            e => todo!("unable to get raw of {:?}", e),
+            SassErrorKind::Traced(string, trace) => (string, trace[0].span),
+            SassErrorKind::Nested { message, span, .. } => (message, span),
+            SassErrorKind::ParseError { message, .. } | SassErrorKind::FromUtf8Error(message) => {
+                (message, span)
+            }
+            SassErrorKind::IoError(err) => (err.to_string(), span),
+        }
+    }
+
+    /// An error that is shown with a stack trace of the mixins, functions
+    /// and imports it was raised within, as for `@error`
+    pub(crate) fn traced(message: String, span: Span) -> Box<Self> {
+        SassError::new(SassErrorKind::Traced(
+            message,
+            vec![Frame { member: None, span }],
+        ))
+    }
+
+    /// Points to another location relevant to this error, such as a
+    /// conflicting declaration, which is described by `message`
+    pub(crate) fn with_secondary_span(mut self: Box<Self>, message: &str, span: Span) -> Box<Self> {
+        self.secondary.push((message.to_owned(), span));
+        self
+    }
+
+    /// Suggests text that would fix this error if written in place of its
+    /// span
+    pub(crate) fn with_suggestion(mut self: Box<Self>, suggestion: String) -> Box<Self> {
+        self.suggestion = Some(suggestion);
+        self
+    }
+
+    /// Adds a frame to the stack trace of this error, if it has one, when
+    /// it propagates out of `member`, which was called at `span`
+    pub(crate) fn in_member(mut self: Box<Self>, member: &str, span: Span) -> Box<Self> {
+        if let SassErrorKind::Traced(_, trace) = &mut self.kind {
+            if let Some(frame) = trace.last_mut() {
+                frame.member = Some(member.to_owned());
+            }
+            trace.push(Frame { member: None, span });
+        }
+        self
+    }
+
+    /// Prefixes the message of a raw error with the location of `from`, as
+    /// `dart-sass` does for errors raised while extending a selector that
+    /// was defined elsewhere
+    pub(crate) fn nested_in(mut self: Box<Self>, from: Span) -> Box<Self> {
+        if let SassErrorKind::Raw(message, span) = self.kind {
+            self.kind = SassErrorKind::Nested {
+                message,
+                from,
+                span,
+            };
         }
+        self
     }
 
-    pub(crate) const fn from_loc(message: String, loc: SpanLoc, unicode: bool) -> Self {
+    /// Looks up the location of a raw error, and of each frame of its stack
+    /// trace
+    pub(crate) fn locate(self, map: &CodeMap, unicode: bool) -> Self {
+        let mut secondary: Vec<SecondarySpan> = self
+            .secondary
+            .iter()
+            .map(|(message, span)| SecondarySpan::new(message.clone(), &map.look_up_span(*span)))
+            .collect();
+
+        let (message, span, trace) = match self.kind {
+            SassErrorKind::Raw(message, span) => (message, span, Vec::new()),
+            SassErrorKind::Nested {
+                message,
+                from,
+                span,
+            } => {
+                let from = map.look_up_span(from);
+                secondary.push(SecondarySpan::new(
+                    "while extending this selector".to_owned(),
+                    &from,
+                ));
+                let message = format!(
+                    "From line {}, column {} of {}: \n{}{}",
+                    from.begin.line + 1,
+                    from.begin.column + 1,
+                    from.file.name(),
+                    highlight(&from, unicode),
+                    message
+                );
+                (message, span, Vec::new())
+            }
+            SassErrorKind::Traced(message, trace) => {
+                (message, trace[0].span, format_trace(map, &trace))
+            }
+            // errors reading a file have no location
+            kind => return SassError { kind, ..self },
+        };
+
         SassError {
             kind: SassErrorKind::ParseError {
                 message,
-                loc,
+                loc: map.look_up_span(span),
                 unicode,
+                trace,
+                secondary,
             },
+            secondary: self.secondary,
+            suggestion: self.suggestion,
         }
     }
 }
 
+/// A mixin, function or imported file that was running when a traced error
+/// was raised, and the location within it that was being evaluated
+///
+/// The outermost frame has no member, as it is the root stylesheet.
+#[derive(Debug, Clone)]
+struct Frame {
+    member: Option<String>,
+    span: Span,
+}
+
+/// Formats a stack trace in the same manner as `dart-sass`, with the
+/// innermost frame first and the locations aligned, e.g.
+///
+/// ```text
+///   _buttons.scss 3:5  size()
+///   input.scss 12:3    root stylesheet
+/// ```
+fn format_trace(map: &CodeMap, trace: &[Frame]) -> Vec<String> {
+    let locations: Vec<String> = trace
+        .iter()
+        .map(|frame| {
+            let loc = map.look_up_span(frame.span);
+            format!(
+                "{} {}:{}",
+                loc.file.name(),
+                loc.begin.line + 1,
+                loc.begin.column + 1
+            )
+        })
+        .collect();
+
+    let width = locations.iter().map(String::len).max().unwrap_or(0);
+
+    trace
+        .iter()
+        .zip(locations)
+        .map(|(frame, location)| {
+            format!(
+                "  {:width$}  {}",
+                location,
+                frame.member.as_deref().unwrap_or("root stylesheet"),
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// The line of source `loc` is on, with `loc` underlined, framed by bars
+/// with a newline after each
+fn highlight(loc: &SpanLoc, unicode: bool) -> String {
+    let first_bar = if unicode { '╷' } else { '|' };
+    let second_bar = if unicode { '│' } else { '|' };
+    let third_bar = if unicode { '│' } else { '|' };
+    let fourth_bar = if unicode { '╵' } else { '|' };
+
+    let line = loc.begin.line + 1;
+    let padding = vec![' '; format!("{}", line).len() + 1]
+        .iter()
+        .collect::<String>();
+
+    format!(
+        "{padding}{}\n{} {} {}\n{padding}{} {}{}\n{padding}{}\n",
+        first_bar,
+        line,
+        second_bar,
+        loc.file.source_line(loc.begin.line),
+        third_bar,
+        vec![' '; loc.begin.column].iter().collect::<String>(),
+        vec!['^'; loc.end.column.max(loc.begin.column) - loc.begin.column.min(loc.end.column)]
+            .iter()
+            .collect::<String>(),
+        fourth_bar,
+        padding = padding,
+    )
+}
+
 #[derive(Debug, Clone)]
 enum SassErrorKind {
     /// A raw error with no additional metadata
     /// It contains only a `String` message and
     /// a span
     Raw(String, Span),
+    /// A raw error with a stack trace, innermost frame first
+    Traced(String, Vec<Frame>),
+    /// A raw error raised while handling the code at `from`, which is
+    /// mentioned in its message
+    Nested {
+        message: String,
+        from: Span,
+        span: Span,
+    },
     ParseError {
         message: String,
         loc: SpanLoc,
         unicode: bool,
+        /// The formatted lines of the stack trace, if the error has one
+        trace: Vec<String>,
+        /// The located `SassError::secondary` spans, along with the span
+        /// of a nested error's `from`
+        secondary: Vec<SecondarySpan>,
     },
-    // we put IoErrors in an `Rc` to allow it to be
-    // cloneable
-    IoError(Rc<io::Error>),
+    // we put IoErrors in an `Arc` to allow it to be
+    // cloneable, and sent between threads
+    IoError(Arc<io::Error>),
     FromUtf8Error(String),
 }
 
@@ -78,48 +312,39 @@ impl Display for SassError {
     // TODO: integrate with codemap-diagnostics
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (message, loc, unicode) = match &self.kind {
+        let (message, loc, unicode, trace) = match &self.kind {
             SassErrorKind::ParseError {
                 message,
                 loc,
                 unicode,
-            } => (message, loc, *unicode),
+                trace,
+                ..
+            } => (message, loc, *unicode, trace),
             SassErrorKind::FromUtf8Error(s) => return writeln!(f, "Error: {}", s),
             SassErrorKind::IoError(s) => return writeln!(f, "Error: {}", s),
-            SassErrorKind::Raw(..) => todo!(),
+            // errors that haven't been located have no source to show
+            SassErrorKind::Raw(message, ..)
+            | SassErrorKind::Traced(message, ..)
+            | SassErrorKind::Nested { message, .. } => return writeln!(f, "Error: {}", message),
         };
 
-        let first_bar = if unicode { '╷' } else { '|' };
-        let second_bar = if unicode { '│' } else { '|' };
-        let third_bar = if unicode { '│' } else { '|' };
-        let fourth_bar = if unicode { '╵' } else { '|' };
-
         let line = loc.begin.line + 1;
         let col = loc.begin.column + 1;
         writeln!(f, "Error: {}", message)?;
-        let padding = vec![' '; format!("{}", line).len() + 1]
-            .iter()
-            .collect::<String>();
-        writeln!(f, "{}{}", padding, first_bar)?;
-        writeln!(
-            f,
-            "{} {} {}",
-            line,
-            second_bar,
-            loc.file.source_line(loc.begin.line)
-        )?;
-        writeln!(
-            f,
-            "{}{} {}{}",
-            padding,
-            third_bar,
-            vec![' '; loc.begin.column].iter().collect::<String>(),
-            vec!['^'; loc.end.column.max(loc.begin.column) - loc.begin.column.min(loc.end.column)]
-                .iter()
-                .collect::<String>()
-        )?;
-        writeln!(f, "{}{}", padding, fourth_bar)?;
-        writeln!(f, "./{}:{}:{}", loc.file.name(), line, col)?;
+        f.write_str(&highlight(loc, unicode))?;
+        if !trace.is_empty() {
+            for line in trace {
+                writeln!(f, "{}", line)?;
+            }
+            return Ok(());
+        }
+        // URLs with a scheme, such as those produced by
+        // `Options::source_url_rewriter`, aren't relative paths
+        if loc.file.name().contains(':') {
+            writeln!(f, "{}:{}:{}", loc.file.name(), line, col)?;
+        } else {
+            writeln!(f, "./{}:{}:{}", loc.file.name(), line, col)?;
+        }
         Ok(())
     }
 }
@@ -127,39 +352,31 @@ impl Display for SassError {
 impl From<io::Error> for Box<SassError> {
     #[inline]
     fn from(error: io::Error) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::IoError(Rc::new(error)),
-        })
+        SassError::new(SassErrorKind::IoError(Arc::new(error)))
     }
 }
 
 impl From<FromUtf8Error> for Box<SassError> {
     #[inline]
     fn from(error: FromUtf8Error) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::FromUtf8Error(format!(
-                "Invalid UTF-8 character \"\\x{:X?}\"",
-                error.as_bytes()[0]
-            )),
-        })
+        SassError::new(SassErrorKind::FromUtf8Error(format!(
+            "Invalid UTF-8 character \"\\x{:X?}\"",
+            error.as_bytes()[error.utf8_error().valid_up_to()]
+        )))
     }
 }
 
 impl From<(&str, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (&str, Span)) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0.to_owned(), error.1),
-        })
+        SassError::new(SassErrorKind::Raw(error.0.to_owned(), error.1))
     }
 }
 
 impl From<(String, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (String, Span)) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0, error.1),
-        })
+        SassError::new(SassErrorKind::Raw(error.0, error.1))
     }
 }
 
@@ -0,0 +1,200 @@
+//! The public variables of a stylesheet and the modules it loads, as they
+//! are after compilation
+//!
+//! See [`ReportOptions::exports`](crate::ReportOptions::exports)
+//!
+//! With the `serde` feature, these types implement `Serialize` and
+//! `Deserialize`, so that they can be written to or read from formats such
+//! as JSON. A [`SassValue`] is represented by its variant name in kebab
+//! case, so that `1px` is `{"number": {"value": 1.0, "unit": "px"}}` in
+//! JSON. Maps are represented as lists of key-value pairs, because their
+//! keys may be any value.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    builtin::modules::Modules,
+    common::{Brackets, ListSeparator, QuoteKind},
+    scope::Scope,
+    value::Value,
+};
+
+/// A Sass value, converted to a form that can be inspected outside of grass
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SassValue {
+    Null,
+    Bool(bool),
+
+    /// A number, along with its unit as it would be written after the
+    /// number, such as `px`, `px*px` or `px/s`, or an empty string if it
+    /// has none
+    ///
+    /// `NaN` can't be represented in JSON, and is serialized as `null` by
+    /// `serde_json`.
+    Number {
+        value: f64,
+        unit: String,
+    },
+
+    String {
+        text: String,
+        quoted: bool,
+    },
+
+    /// A color, with red, green and blue channels between `0` and `255`,
+    /// and an alpha channel between `0` and `1`
+    Color {
+        red: f64,
+        green: f64,
+        blue: f64,
+        alpha: f64,
+    },
+
+    List {
+        items: Vec<SassValue>,
+        separator: Separator,
+        bracketed: bool,
+    },
+
+    /// A map, with its entries in the order they were defined
+    Map(Vec<(SassValue, SassValue)>),
+
+    /// A function reference, as returned by `get-function()`, holding the
+    /// name of the function
+    Function(String),
+
+    /// A mixin reference, as returned by `meta.get-mixin()`, holding the
+    /// name of the mixin
+    Mixin(String),
+
+    /// An unevaluated call to a CSS math function such as `calc()`, as it
+    /// would be written in CSS
+    Calculation(String),
+}
+
+/// The separator between the items of a [`SassValue::List`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Separator {
+    Space,
+    Comma,
+    Slash,
+}
+
+/// The public variables of a module
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModuleExports {
+    /// The values of the module's variables after compilation, keyed by
+    /// their names without the `$`
+    ///
+    /// Underscores in names are normalized to hyphens, and private
+    /// variables, whose names begin with `-` or `_`, are omitted.
+    pub variables: BTreeMap<String, SassValue>,
+}
+
+/// The public variables of a compiled stylesheet and of the modules it
+/// loads with `@use`
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Exports {
+    /// The global variables of the stylesheet itself
+    pub root: ModuleExports,
+
+    /// The user-defined modules loaded by the stylesheet, keyed by their
+    /// namespace, with underscores normalized to hyphens
+    ///
+    /// Builtin modules such as `sass:math` and modules loaded with `as *`
+    /// are omitted. The variables of modules loaded with `as *` are
+    /// included in [`Exports::root`] instead.
+    pub modules: BTreeMap<String, ModuleExports>,
+}
+
+impl Exports {
+    pub(crate) fn new(global_scope: &Scope, modules: &Modules) -> Self {
+        Exports {
+            root: ModuleExports::new(global_scope),
+            modules: modules
+                .iter()
+                .filter(|(_, module)| !module.is_builtin())
+                .map(|(name, module)| {
+                    (name.to_string(), ModuleExports::new(&module.scope.borrow()))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ModuleExports {
+    fn new(scope: &Scope) -> Self {
+        ModuleExports {
+            variables: scope
+                .vars
+                .iter()
+                .filter(|(name, _)| !name.as_str().starts_with('-'))
+                .map(|(name, value)| (name.to_string(), SassValue::from(value)))
+                .collect(),
+        }
+    }
+}
+
+impl From<&Value> for SassValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => SassValue::Null,
+            Value::True => SassValue::Bool(true),
+            Value::False => SassValue::Bool(false),
+            Value::Important => SassValue::String {
+                text: "!important".to_owned(),
+                quoted: false,
+            },
+            Value::Dimension(num, unit, _) => SassValue::Number {
+                value: num
+                    .clone()
+                    .and_then(|num| num.as_float())
+                    .unwrap_or(f64::NAN),
+                unit: unit.to_string(),
+            },
+            Value::String(text, quotes) => SassValue::String {
+                text: text.clone(),
+                quoted: *quotes == QuoteKind::Quoted,
+            },
+            Value::Color(color) => SassValue::Color {
+                red: color.red().as_float().unwrap_or(f64::NAN),
+                green: color.green().as_float().unwrap_or(f64::NAN),
+                blue: color.blue().as_float().unwrap_or(f64::NAN),
+                alpha: color.alpha().as_float().unwrap_or(f64::NAN),
+            },
+            Value::List(items, separator, brackets) => SassValue::List {
+                items: items.iter().map(SassValue::from).collect(),
+                separator: match separator {
+                    ListSeparator::Space => Separator::Space,
+                    ListSeparator::Comma => Separator::Comma,
+                    ListSeparator::Slash => Separator::Slash,
+                },
+                bracketed: *brackets == Brackets::Bracketed,
+            },
+            Value::ArgList(args) => SassValue::List {
+                items: args.iter().map(|arg| SassValue::from(&arg.node)).collect(),
+                separator: Separator::Comma,
+                bracketed: false,
+            },
+            Value::Map(map) => SassValue::Map(
+                map.iter()
+                    .map(|(key, value)| (SassValue::from(key), SassValue::from(value)))
+                    .collect(),
+            ),
+            Value::FunctionRef(function) => SassValue::Function(function.name().to_string()),
+            Value::MixinRef(mixin) => SassValue::Mixin(mixin.name.to_string()),
+            Value::Calculation(calc) => SassValue::Calculation(calc.to_string()),
+        }
+    }
+}
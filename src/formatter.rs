@@ -0,0 +1,70 @@
+//! Overriding how values are written in the CSS output
+//!
+//! See [`Options::value_formatter`](crate::Options::value_formatter)
+
+use std::fmt;
+
+use crate::{common::QuoteKind, exports::SassValue, value::Value};
+
+/// Overrides how the values of declarations are written in the CSS output,
+/// for output targets with needs grass doesn't otherwise cater to
+///
+/// The formatter is offered the whole value of each declaration, then each
+/// item of it if it is a list, and so on, and the first text it returns is
+/// written in place of that value. Values it declines are written as usual.
+/// Because values are replaced when a declaration is evaluated, the
+/// replacements are written the same way in every output style.
+///
+/// For example, to write every opaque color as an `rgb()` triple:
+///
+/// ```
+/// use grass::{SassValue, ValueFormatter};
+///
+/// #[derive(Debug)]
+/// struct RgbColors;
+///
+/// impl ValueFormatter for RgbColors {
+///     fn format(&self, value: &SassValue) -> Option<String> {
+///         match value {
+///             SassValue::Color { red, green, blue, alpha } if *alpha == 1.0 => {
+///                 Some(format!("rgb({}, {}, {})", red, green, blue))
+///             }
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let css = grass::from_string(
+///     "a { border: 1px solid red; }".to_string(),
+///     &grass::Options::default().value_formatter(&RgbColors),
+/// )
+/// .unwrap();
+///
+/// assert_eq!("a {\n  border: 1px solid rgb(255, 0, 0);\n}\n", css);
+/// ```
+pub trait ValueFormatter: fmt::Debug {
+    /// Returns the text to write in place of `value`, or `None` to write it
+    /// as usual. Unquoted strings, such as `url(image.png)`, are passed as
+    /// [`SassValue::String`]s.
+    fn format(&self, value: &SassValue) -> Option<String>;
+}
+
+/// Replaces `value`, or the items of it if it is a list, with the text
+/// `formatter` returns for them
+pub(crate) fn format_value(formatter: &dyn ValueFormatter, value: Value) -> Value {
+    if let Some(text) = formatter.format(&SassValue::from(&value)) {
+        return Value::String(text, QuoteKind::None);
+    }
+
+    match value {
+        Value::List(items, separator, brackets) => Value::List(
+            items
+                .into_iter()
+                .map(|item| format_value(formatter, item))
+                .collect(),
+            separator,
+            brackets,
+        ),
+        value => value,
+    }
+}
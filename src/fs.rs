@@ -0,0 +1,143 @@
+//! Where stylesheets are loaded from
+//!
+//! See [`Options::fs`](crate::Options::fs)
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::{Component, Path, PathBuf},
+};
+
+/// The filesystem that the stylesheet being compiled and every file it
+/// loads with `@import`, `@use`, or `meta.load-css()` are read from
+///
+/// Implementing this allows stylesheets to be compiled without touching the
+/// disk, such as from files bundled into a binary or when running as
+/// WebAssembly. [`MemoryFs`] holds files in memory:
+///
+/// ```
+/// use grass::MemoryFs;
+///
+/// let mut fs = MemoryFs::default();
+/// fs.add_file("input.scss", "@use \"lib/colors\";\na { color: colors.$primary; }\n");
+/// fs.add_file("lib/_colors.scss", "$primary: red;\n");
+///
+/// let css = grass::from_path("input.scss", &grass::Options::default().fs(&fs)).unwrap();
+/// assert_eq!("a {\n  color: red;\n}\n", css);
+/// ```
+pub trait Fs: fmt::Debug {
+    /// Whether `path` is a directory
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Whether `path` is a file
+    fn is_file(&self, path: &Path) -> bool;
+
+    /// Reads the contents of the file at `path`
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// The canonical form of `path`, so that the same file reached through
+    /// different paths is recognized, or an error if `path` doesn't exist
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+}
+
+/// The real filesystem, as accessed through `std::fs`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdFs;
+
+impl Fs for StdFs {
+    #[inline]
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    #[inline]
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    #[inline]
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    #[inline]
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::canonicalize(path)
+    }
+}
+
+/// A filesystem held in memory, containing only the files added to it
+///
+/// Paths are compared after removing `.` components and resolving `..`
+/// components, so `a/../b.scss` refers to the file added as `b.scss`.
+/// Directories exist wherever files have been added inside of them.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFs {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFs {
+    /// Adds a file at `path`, replacing any file that was already there
+    #[inline]
+    pub fn add_file(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+        self.files.insert(normalize(path.as_ref()), contents.into());
+    }
+}
+
+impl Fs for MemoryFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        self.files
+            .keys()
+            .any(|file| file != &path && file.starts_with(&path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.is_file(path) || self.is_dir(path) {
+            Ok(normalize(path))
+        } else {
+            Err(not_found(path))
+        }
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::NotFound,
+        format!("{}: no such file or directory", path.display()),
+    )
+}
+
+/// Removes `.` components from `path` and resolves `..` components
+/// lexically, without following symbolic links
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if normalized.file_name().is_some() {
+                    normalized.pop();
+                } else {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
@@ -0,0 +1,83 @@
+//! The hash maps and sets used while compiling
+//!
+//! With the `determinism-audit` feature, these hash their keys with a seed
+//! that can be changed between compilations, so that output which depends on
+//! the order they are iterated in can be caught by compiling with several
+//! seeds and comparing the results.
+
+use std::hash::{Hash, Hasher};
+
+#[cfg(feature = "determinism-audit")]
+use std::hash::BuildHasher;
+
+#[cfg(not(feature = "determinism-audit"))]
+pub(crate) use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "determinism-audit")]
+pub(crate) use audit::{set_seed, HashMap, HashSet};
+
+/// Hashes `value` on its own, with the same seed as the maps and sets created
+/// on this thread when the `determinism-audit` feature is enabled
+pub(crate) fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    #[cfg(not(feature = "determinism-audit"))]
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    #[cfg(feature = "determinism-audit")]
+    let mut hasher = audit::SeededState::default().build_hasher();
+
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(feature = "determinism-audit")]
+mod audit {
+    use std::{
+        cell::Cell,
+        collections::hash_map::DefaultHasher,
+        hash::{BuildHasher, Hasher},
+    };
+
+    thread_local!(static SEED: Cell<u64> = Cell::new(0));
+
+    pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, SeededState>;
+    pub(crate) type HashSet<T> = std::collections::HashSet<T, SeededState>;
+
+    /// Sets the seed used by the maps and sets created on this thread, until
+    /// the returned guard is dropped
+    pub(crate) fn set_seed(seed: u64) -> SeedGuard {
+        SeedGuard {
+            previous: SEED.with(|cell| cell.replace(seed)),
+        }
+    }
+
+    #[must_use]
+    pub(crate) struct SeedGuard {
+        previous: u64,
+    }
+
+    impl Drop for SeedGuard {
+        fn drop(&mut self) {
+            SEED.with(|cell| cell.set(self.previous));
+        }
+    }
+
+    /// Hashes keys with the seed that was set when the map was created, so
+    /// that a map keeps working if the seed changes while it is alive
+    #[derive(Debug, Clone)]
+    pub(crate) struct SeededState(u64);
+
+    impl Default for SeededState {
+        fn default() -> Self {
+            Self(SEED.with(Cell::get))
+        }
+    }
+
+    impl BuildHasher for SeededState {
+        type Hasher = DefaultHasher;
+
+        fn build_hasher(&self) -> DefaultHasher {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_u64(self.0);
+            hasher
+        }
+    }
+}
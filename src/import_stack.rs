@@ -0,0 +1,134 @@
+//! Detection of stylesheets that load themselves, either directly or through
+//! a chain of other stylesheets, and of modules that have already been loaded
+//!
+//! Without this, an `@import` or `@use` cycle would recurse until the stack
+//! overflows.
+
+use std::path::{Path, PathBuf};
+
+use codemap::{CodeMap, Span};
+
+use crate::{builtin::modules::Module, error::SassResult, fs::Fs, hash::HashMap};
+
+/// A stylesheet that is being loaded
+#[derive(Debug)]
+struct Frame {
+    /// The canonical path of the stylesheet, so that the same file reached
+    /// through different relative paths is recognized
+    path: PathBuf,
+
+    /// The `@import` or `@use` that loaded the stylesheet, or `None` if it
+    /// is the stylesheet being compiled
+    span: Option<Span>,
+}
+
+/// The stylesheets that are currently being loaded, outermost first
+#[derive(Debug, Default)]
+pub(crate) struct ImportStack {
+    frames: Vec<Frame>,
+
+    /// The modules loaded with `@use`, by canonical path, so that each
+    /// module is only evaluated once
+    modules: HashMap<PathBuf, Module>,
+}
+
+impl ImportStack {
+    /// Creates a stack for compiling the stylesheet at `path`, which isn't
+    /// a file at all when compiling a string
+    pub fn new(path: &Path, fs: &dyn Fs) -> Self {
+        Self {
+            frames: fs
+                .canonicalize(path)
+                .map(|path| vec![Frame { path, span: None }])
+                .unwrap_or_default(),
+            modules: HashMap::default(),
+        }
+    }
+
+    /// Records that the stylesheet at `path` is being loaded by the rule at
+    /// `span`, or returns an error describing the cycle if it is already
+    /// being loaded
+    ///
+    /// Every successful call must be followed by a call to
+    /// [`pop`](ImportStack::pop) once the stylesheet has been loaded.
+    pub fn push(
+        &mut self,
+        path: &Path,
+        span: Span,
+        map: &CodeMap,
+        fs: &dyn Fs,
+        unicode: bool,
+    ) -> SassResult<()> {
+        let path = canonicalize(path, fs);
+
+        if let Some(start) = self.frames.iter().position(|frame| frame.path == path) {
+            return Err((self.describe_cycle(start, span, map, unicode), span).into());
+        }
+
+        self.frames.push(Frame {
+            path,
+            span: Some(span),
+        });
+
+        Ok(())
+    }
+
+    pub fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    /// The module at `path`, if it has already been loaded
+    ///
+    /// The module shares its members with every other copy of it, so
+    /// changes made to its variables through one namespace are seen through
+    /// all of them.
+    pub fn loaded_module(&self, path: &Path, fs: &dyn Fs) -> Option<Module> {
+        self.modules.get(&canonicalize(path, fs)).cloned()
+    }
+
+    pub fn insert_module(&mut self, path: &Path, fs: &dyn Fs, module: Module) {
+        self.modules.insert(canonicalize(path, fs), module);
+    }
+
+    /// Lists the files in the cycle beginning with the stylesheet at
+    /// `start`, followed by the location of each rule that continues it,
+    /// ending with the rule at `span` that closes it
+    fn describe_cycle(&self, start: usize, span: Span, map: &CodeMap, unicode: bool) -> String {
+        let imports: Vec<Span> = self.frames[start + 1..]
+            .iter()
+            .filter_map(|frame| frame.span)
+            .chain(Some(span))
+            .collect();
+
+        let files: Vec<String> = imports
+            .iter()
+            .map(|span| map.look_up_span(*span).file.name().to_owned())
+            .collect();
+
+        let arrow = if unicode { " → " } else { " -> " };
+
+        let mut message = format!(
+            "This file is already being loaded: {}{}{}",
+            files.join(arrow),
+            arrow,
+            files[0]
+        );
+
+        for (idx, import) in imports.iter().enumerate() {
+            let loc = map.look_up_span(*import);
+            message.push_str(&format!(
+                "\n  {} {}:{}  loads {}",
+                files[idx],
+                loc.begin.line + 1,
+                loc.begin.column + 1,
+                files.get(idx + 1).unwrap_or(&files[0])
+            ));
+        }
+
+        message
+    }
+}
+
+fn canonicalize(path: &Path, fs: &dyn Fs) -> PathBuf {
+    fs.canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,362 @@
+//! The indented syntax, which is translated to SCSS before it is parsed
+//!
+//! Each line is a statement. A line followed by more deeply indented lines
+//! opens a block containing them, and any other line ends with a semicolon.
+//! The braces and semicolons are inserted as tokens with the position of
+//! the line they belong to, so that errors still point into the original
+//! source.
+//!
+//! <https://sass-lang.com/documentation/syntax#the-indented-syntax>
+
+use codemap::Span;
+
+use crate::{error::SassResult, utils::is_name_start, Token};
+
+/// A statement, or a loud comment, along with how deeply it is indented
+struct Line {
+    indent: usize,
+    toks: Vec<Token>,
+
+    /// A silent comment at the end of the line, which is kept after the
+    /// semicolon or brace that ends the statement
+    comment: Vec<Token>,
+
+    is_comment: bool,
+}
+
+/// Translates the tokens of a stylesheet in the indented syntax to SCSS
+pub(crate) fn to_scss(toks: Vec<Token>) -> SassResult<Vec<Token>> {
+    let lines = statements(physical_lines(toks));
+
+    if let Some(first) = lines.first() {
+        if first.indent > 0 {
+            return Err((
+                "Indenting at the beginning of the document is illegal.",
+                first.toks[0].pos,
+            )
+                .into());
+        }
+    }
+
+    let mut scss = Vec::new();
+    // the indentation of each line that opened a block that is still open
+    let mut open_blocks: Vec<(usize, Span)> = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        close_blocks(&mut scss, &mut open_blocks, line.indent);
+
+        let end = line.toks.last().unwrap().pos;
+
+        if line.is_comment {
+            scss.extend(line.toks.iter().copied());
+            scss.push(synthetic('\n', end));
+            continue;
+        }
+
+        scss.extend(translate_statement(&line.toks));
+
+        let opens_block = lines
+            .get(idx + 1)
+            .map_or(false, |next| next.indent > line.indent);
+
+        if opens_block {
+            scss.push(synthetic(' ', end));
+            scss.push(synthetic('{', end));
+            open_blocks.push((line.indent, end));
+        } else {
+            scss.push(synthetic(';', end));
+        }
+
+        if !line.comment.is_empty() {
+            scss.push(synthetic(' ', end));
+            scss.extend(line.comment.iter().copied());
+        }
+
+        scss.push(synthetic('\n', end));
+    }
+
+    close_blocks(&mut scss, &mut open_blocks, 0);
+
+    Ok(scss)
+}
+
+fn synthetic(kind: char, pos: Span) -> Token {
+    Token { kind, pos }
+}
+
+fn synthetic_str(s: &str, pos: Span) -> impl Iterator<Item = Token> + '_ {
+    s.chars().map(move |kind| synthetic(kind, pos))
+}
+
+/// Closes every open block that a line indented by `indent` is outside of
+fn close_blocks(scss: &mut Vec<Token>, open_blocks: &mut Vec<(usize, Span)>, indent: usize) {
+    while let Some(&(block_indent, pos)) = open_blocks.last() {
+        if block_indent < indent {
+            break;
+        }
+        open_blocks.pop();
+        scss.push(synthetic('}', pos));
+        scss.push(synthetic('\n', pos));
+    }
+}
+
+/// Splits `toks` into lines, without their newlines
+fn physical_lines(toks: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut lines = vec![Vec::new()];
+    for tok in toks {
+        if tok.kind == '\n' {
+            lines.push(Vec::new());
+        } else {
+            lines.last_mut().unwrap().push(tok);
+        }
+    }
+    lines
+}
+
+fn indentation(line: &[Token]) -> usize {
+    line.iter()
+        .take_while(|tok| tok.kind == ' ' || tok.kind == '\t')
+        .count()
+}
+
+fn is_blank(line: &[Token]) -> bool {
+    line.iter().all(|tok| tok.kind.is_whitespace())
+}
+
+fn trim_end(mut toks: Vec<Token>) -> Vec<Token> {
+    while toks.last().map_or(false, |tok| tok.kind.is_whitespace()) {
+        toks.pop();
+    }
+    toks
+}
+
+fn starts_with(toks: &[Token], s: &str) -> bool {
+    toks.len() >= s.len() && toks.iter().zip(s.chars()).all(|(tok, c)| tok.kind == c)
+}
+
+fn ends_with(toks: &[Token], s: &str) -> bool {
+    toks.len() >= s.len() && starts_with(&toks[toks.len() - s.len()..], s)
+}
+
+/// Groups lines into statements, dropping blank lines and silent comments
+///
+/// A statement continues onto the next line if it ends inside of
+/// parentheses or brackets, or with a comma, as a selector list may. A
+/// comment continues onto every following line that is indented more
+/// deeply than it.
+fn statements(lines: Vec<Vec<Token>>) -> Vec<Line> {
+    let mut statements = Vec::new();
+    let mut lines = lines.into_iter().peekable();
+
+    while let Some(line) = lines.next() {
+        if is_blank(&line) {
+            continue;
+        }
+
+        let indent = indentation(&line);
+        let mut toks = trim_end(line[indent..].to_vec());
+
+        if starts_with(&toks, "//") || starts_with(&toks, "/*") {
+            let is_loud = starts_with(&toks, "/*");
+            while let Some(next) = lines.peek() {
+                if !is_blank(next) && indentation(next) <= indent {
+                    break;
+                }
+                let next = trim_end(lines.next().unwrap());
+                if is_loud && !next.is_empty() {
+                    // like `dart-sass`, each line after the first starts
+                    // with ` * `, which takes the place of three columns of
+                    // its indentation
+                    let next_indent = indentation(&next);
+                    let pos = toks.last().unwrap().pos;
+                    toks.push(synthetic('\n', pos));
+                    toks.extend(synthetic_str(" * ", pos));
+                    let extra = (next_indent - indent).saturating_sub(3);
+                    toks.extend(std::iter::repeat(synthetic(' ', pos)).take(extra));
+                    toks.extend(next[next_indent..].iter().copied());
+                }
+            }
+
+            if is_loud {
+                if !ends_with(&toks, "*/") {
+                    let pos = toks.last().unwrap().pos;
+                    toks.extend(synthetic_str(" */", pos));
+                }
+                statements.push(Line {
+                    indent,
+                    toks,
+                    comment: Vec::new(),
+                    is_comment: true,
+                });
+            }
+            continue;
+        }
+
+        let (mut code, mut comment) = split_comment(toks);
+        let mut depth = nesting_depth(&code);
+
+        while depth > 0 || code.last().map_or(false, |tok| tok.kind == ',') {
+            let next = match lines.next() {
+                Some(next) => next,
+                None => break,
+            };
+            if is_blank(&next) {
+                continue;
+            }
+            let pos = code.last().unwrap().pos;
+            let next = trim_end(next[indentation(&next)..].to_vec());
+            let (next_code, next_comment) = split_comment(next);
+            depth += nesting_depth(&next_code);
+            code.push(synthetic('\n', pos));
+            code.extend(next_code);
+            comment = next_comment;
+        }
+
+        if code.is_empty() {
+            continue;
+        }
+
+        statements.push(Line {
+            indent,
+            toks: trim_end(code),
+            comment,
+            is_comment: false,
+        });
+    }
+
+    statements
+}
+
+/// Calls `f` with the index of each token in `toks` that isn't inside of a
+/// string, along with how deeply it is nested in parentheses and brackets,
+/// until `f` returns `false`
+fn for_each_unquoted(toks: &[Token], mut f: impl FnMut(usize, isize) -> bool) {
+    let mut quote = None;
+    let mut depth = 0;
+    let mut idx = 0;
+
+    while idx < toks.len() {
+        let kind = toks[idx].kind;
+        match quote {
+            Some(q) => {
+                if kind == '\\' {
+                    idx += 1;
+                } else if kind == q {
+                    quote = None;
+                }
+            }
+            None => {
+                if !f(idx, depth) {
+                    return;
+                }
+                match kind {
+                    '"' | '\'' => quote = Some(kind),
+                    '(' | '[' => depth += 1,
+                    ')' | ']' => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+        idx += 1;
+    }
+}
+
+/// How many more parentheses and brackets `toks` opens than it closes
+fn nesting_depth(toks: &[Token]) -> isize {
+    let mut depth = 0;
+    for_each_unquoted(toks, |idx, _| {
+        match toks[idx].kind {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            _ => {}
+        }
+        true
+    });
+    depth
+}
+
+/// Splits a silent comment off of the end of a line
+///
+/// `//` only starts a comment outside of strings and parentheses, after
+/// whitespace, so that URLs such as `url(http://example.com)` are kept.
+fn split_comment(mut toks: Vec<Token>) -> (Vec<Token>, Vec<Token>) {
+    let mut start = None;
+    for_each_unquoted(&toks, |idx, depth| {
+        let is_comment = depth == 0
+            && toks[idx].kind == '/'
+            && toks.get(idx + 1).map_or(false, |tok| tok.kind == '/')
+            && (idx == 0 || toks[idx - 1].kind.is_whitespace());
+        if is_comment {
+            start = Some(idx);
+        }
+        !is_comment
+    });
+
+    match start {
+        Some(start) => {
+            let comment = toks.split_off(start);
+            (trim_end(toks), comment)
+        }
+        None => (toks, Vec::new()),
+    }
+}
+
+/// Translates the shorthands for mixins and unquoted imports to SCSS
+fn translate_statement(toks: &[Token]) -> Vec<Token> {
+    let first = toks[0];
+    let starts_name = toks.get(1).map_or(false, |tok| is_name_start(tok.kind));
+
+    match first.kind {
+        '=' if starts_name => synthetic_str("@mixin ", first.pos)
+            .chain(toks[1..].iter().copied())
+            .collect(),
+        '+' if starts_name => synthetic_str("@include ", first.pos)
+            .chain(toks[1..].iter().copied())
+            .collect(),
+        '@' if starts_with(toks, "@import")
+            && toks.get(7).map_or(false, |tok| tok.kind.is_whitespace()) =>
+        {
+            let mut scss = toks[..7].to_vec();
+            scss.push(toks[7]);
+            scss.extend(quote_imports(&toks[8..]));
+            scss
+        }
+        _ => toks.to_vec(),
+    }
+}
+
+/// Quotes each of the comma-separated URLs of an `@import` rule that isn't
+/// already quoted or a `url()`
+fn quote_imports(toks: &[Token]) -> Vec<Token> {
+    let mut commas = Vec::new();
+    for_each_unquoted(toks, |idx, depth| {
+        if depth == 0 && toks[idx].kind == ',' {
+            commas.push(idx);
+        }
+        true
+    });
+
+    let mut scss = Vec::new();
+    let mut start = 0;
+    for end in commas.into_iter().chain(std::iter::once(toks.len())) {
+        let item = &toks[start..end];
+        let leading = indentation(item);
+        let url = trim_end(item[leading..].to_vec());
+
+        scss.extend(item[..leading].iter().copied());
+        match url.first() {
+            Some(tok) if tok.kind != '"' && tok.kind != '\'' && !starts_with(&url, "url(") => {
+                let end_pos = url.last().unwrap().pos;
+                scss.push(synthetic('"', tok.pos));
+                scss.extend(url.iter().copied());
+                scss.push(synthetic('"', end_pos));
+            }
+            _ => scss.extend(url.iter().copied()),
+        }
+        if let Some(comma) = toks.get(end) {
+            scss.push(*comma);
+        }
+        start = end + 1;
+    }
+    scss
+}
@@ -0,0 +1,131 @@
+//! An insertion-ordered map, for the collections whose contents are output in
+//! the order they were added, such as the extensions of a selector or the
+//! members of a module
+//!
+//! With the `indexmap` feature, this is the `indexmap` crate's map. Otherwise,
+//! a minimal implementation of the subset of its API used here is provided,
+//! with the same semantics, so that output does not depend on which
+//! implementation is chosen.
+
+#[cfg(feature = "indexmap")]
+pub(crate) use indexmap::IndexMap;
+
+#[cfg(not(feature = "indexmap"))]
+pub(crate) use minimal::IndexMap;
+
+#[cfg(not(feature = "indexmap"))]
+mod minimal {
+    use std::{collections::HashMap, fmt, hash::Hash, iter::FromIterator, vec};
+
+    #[derive(Clone)]
+    pub(crate) struct IndexMap<K, V> {
+        indices: HashMap<K, usize>,
+        entries: Vec<(K, V)>,
+    }
+
+    impl<K: Hash + Eq + Clone, V> IndexMap<K, V> {
+        pub fn new() -> Self {
+            Self {
+                indices: HashMap::new(),
+                entries: Vec::new(),
+            }
+        }
+
+        pub fn get(&self, key: &K) -> Option<&V> {
+            self.indices.get(key).map(|&idx| &self.entries[idx].1)
+        }
+
+        pub fn contains_key(&self, key: &K) -> bool {
+            self.indices.contains_key(key)
+        }
+
+        pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+            match self.indices.get(key) {
+                Some(&idx) => Some(&mut self.entries[idx].1),
+                None => None,
+            }
+        }
+
+        /// Inserts a key-value pair, keeping the original position of `key` if it
+        /// was already present
+        pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+            if let Some(&idx) = self.indices.get(&key) {
+                return Some(std::mem::replace(&mut self.entries[idx].1, value));
+            }
+
+            self.indices.insert(key.clone(), self.entries.len());
+            self.entries.push((key, value));
+            None
+        }
+
+        /// Removes `key` by swapping it with the last element, which matches
+        /// `indexmap::IndexMap::remove`
+        pub fn remove(&mut self, key: &K) -> Option<V> {
+            let idx = self.indices.remove(key)?;
+            let (_, value) = self.entries.swap_remove(idx);
+
+            if let Some((moved, _)) = self.entries.get(idx) {
+                *self.indices.get_mut(moved).unwrap() = idx;
+            }
+
+            Some(value)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+            self.entries.iter().map(|(key, value)| (key, value))
+        }
+
+        pub fn values(&self) -> impl Iterator<Item = &V> {
+            self.entries.iter().map(|(_, value)| value)
+        }
+
+        pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+            self.entries.iter_mut().map(|(_, value)| value)
+        }
+    }
+
+    impl<K: Hash + Eq + Clone, V> Default for IndexMap<K, V> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Formats the entries in order, like `indexmap::IndexMap`, leaving out the
+    /// indices, which are in no particular order
+    impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IndexMap<K, V> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_map()
+                .entries(self.entries.iter().map(|(key, value)| (key, value)))
+                .finish()
+        }
+    }
+
+    impl<K: Hash + Eq + Clone, V> Extend<(K, V)> for IndexMap<K, V> {
+        fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+            for (key, value) in iter {
+                self.insert(key, value);
+            }
+        }
+    }
+
+    impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for IndexMap<K, V> {
+        fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+            let mut map = Self::new();
+            map.extend(iter);
+            map
+        }
+    }
+
+    impl<K, V> IntoIterator for IndexMap<K, V> {
+        type Item = (K, V);
+        type IntoIter = vec::IntoIter<(K, V)>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.entries.into_iter()
+        }
+    }
+}
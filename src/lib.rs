@@ -90,29 +90,57 @@ grass input.scss
 )]
 #![cfg_attr(feature = "nightly", feature(track_caller))]
 #![cfg_attr(feature = "profiling", inline(never))]
-use std::{fs, path::Path};
-
-#[cfg(feature = "wasm")]
-use wasm_bindgen::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 pub(crate) use beef::lean::Cow;
 
-use codemap::CodeMap;
+use codemap::{CodeMap, File, Span};
 
 use peekmore::PeekMore;
 
+#[cfg(not(feature = "wasm"))]
+pub use crate::compiler::Compiler;
+#[cfg(not(feature = "wasm"))]
+use crate::compiler::ParseCache;
+pub use crate::deprecation::Deprecation;
+pub use crate::diagnostic::{
+    checkstyle_report, Diagnostic, DiagnosticFormat, Position, SecondarySpan, Severity, SourceRange,
+};
 pub use crate::error::{SassError as Error, SassResult as Result};
+pub use crate::exports::{Exports, ModuleExports, SassValue, Separator};
+pub use crate::formatter::ValueFormatter;
+pub use crate::fs::{Fs, MemoryFs, StdFs};
+#[cfg(feature = "memory-limit")]
+pub use crate::limits::CountingAllocator;
+pub use crate::logger::{Logger, NullLogger, StderrLogger};
+pub use crate::number::{Number, ParseNumberError};
+#[cfg(feature = "plugins")]
+pub use crate::plugin::{Plugin, PluginFunction, GRASS_PLUGIN_ABI_VERSION};
+pub use crate::syntax::Syntax;
 pub(crate) use crate::token::Token;
+#[cfg(not(feature = "wasm"))]
+pub use crate::write::{write_css, WriteOptions};
 use crate::{
     builtin::modules::{ModuleConfig, Modules},
-    lexer::Lexer,
+    hash::HashSet,
+    import_stack::ImportStack,
+    limits::Limits,
+    mixin_cache::MixinCache,
     output::Css,
     parse::{
         common::{ContextFlags, NeverEmptyVec},
         Parser,
     },
+    random::Random,
     scope::{Scope, Scopes},
     selector::{Extender, Selector},
+    value::{set_precision, DEFAULT_PRECISION},
 };
 
 mod args;
@@ -120,21 +148,51 @@ mod atrule;
 mod builtin;
 mod color;
 mod common;
+#[cfg(not(feature = "wasm"))]
+mod compiler;
+mod deprecation;
+mod diagnostic;
+#[cfg(feature = "differential")]
+pub mod differential;
 mod error;
+mod exports;
+mod formatter;
+mod fs;
+mod hash;
+mod import_stack;
+mod indented;
+mod index_map;
 mod interner;
 mod lexer;
+mod limits;
+mod logger;
+mod mixin_cache;
+mod number;
 mod output;
 mod parse;
+#[cfg(feature = "plugins")]
+mod plugin;
+#[cfg(all(feature = "plugins", not(unix)))]
+compile_error!(
+    "The `plugins` feature loads libraries with `dlopen`, so it is only supported on unix."
+);
+mod random;
 mod scope;
 mod selector;
+mod shorthand;
 mod style;
+mod syntax;
 mod token;
 mod unit;
 mod utils;
 mod value;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(not(feature = "wasm"))]
+mod write;
 
 #[non_exhaustive]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputStyle {
     /// The default style, this mode writes each
     /// selector and declaration on its own line.
@@ -150,13 +208,51 @@ pub enum OutputStyle {
 /// The simplest usage is `grass::Options::default()`;
 /// however, a builder pattern is also exposed to offer
 /// more control.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Options<'a> {
     style: OutputStyle,
     load_paths: Vec<&'a Path>,
+    import_roots: Option<Vec<&'a Path>>,
+    timeout: Option<Duration>,
+    max_nesting_depth: Option<usize>,
+    #[cfg(feature = "memory-limit")]
+    memory_limit: Option<usize>,
+    #[cfg(feature = "plugins")]
+    plugins: Vec<&'a Plugin>,
     allows_charset: bool,
     unicode_error_messages: bool,
     quiet: bool,
+    verbose: bool,
+    fatal_deprecations: &'a [Deprecation],
+    silence_deprecations: &'a [Deprecation],
+    warn_shorthand_conflicts: bool,
+    selector_line_breaks: bool,
+    line_comments: bool,
+    cache_mixins: bool,
+    random_seed: Option<u64>,
+    precision: usize,
+    source_url_rewriter: Option<SourceUrlRewriter<'a>>,
+    syntax: Syntax,
+    url: Option<&'a str>,
+    diagnostic_format: DiagnosticFormat,
+    logger: &'a dyn Logger,
+    fs: &'a dyn Fs,
+    value_formatter: Option<&'a dyn ValueFormatter>,
+    /// The files parsed by the [`Compiler`] compiling with these options
+    #[cfg(not(feature = "wasm"))]
+    parse_cache: Option<&'a ParseCache>,
+}
+
+/// Wraps the callback given to
+/// [`Options::source_url_rewriter`](Options::source_url_rewriter), which
+/// cannot itself implement `Debug`
+#[derive(Clone, Copy)]
+struct SourceUrlRewriter<'a>(&'a dyn Fn(&str) -> String);
+
+impl fmt::Debug for SourceUrlRewriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SourceUrlRewriter")
+    }
 }
 
 impl Default for Options<'_> {
@@ -165,9 +261,34 @@ impl Default for Options<'_> {
         Self {
             style: OutputStyle::Expanded,
             load_paths: Vec::new(),
+            import_roots: None,
+            timeout: None,
+            max_nesting_depth: None,
+            #[cfg(feature = "memory-limit")]
+            memory_limit: None,
+            #[cfg(feature = "plugins")]
+            plugins: Vec::new(),
             allows_charset: true,
             unicode_error_messages: true,
             quiet: false,
+            verbose: false,
+            fatal_deprecations: &[],
+            silence_deprecations: &[],
+            warn_shorthand_conflicts: false,
+            selector_line_breaks: true,
+            line_comments: false,
+            cache_mixins: false,
+            random_seed: None,
+            precision: DEFAULT_PRECISION,
+            source_url_rewriter: None,
+            syntax: Syntax::Scss,
+            url: None,
+            diagnostic_format: DiagnosticFormat::Human,
+            logger: &StderrLogger,
+            fs: &StdFs,
+            value_formatter: None,
+            #[cfg(not(feature = "wasm"))]
+            parse_cache: None,
         }
     }
 }
@@ -202,6 +323,197 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Print every deprecation warning. Otherwise, a deprecation warning
+    /// is printed once for each place it is emitted from, only the first 5
+    /// of each kind of deprecation warning are printed, and the number of
+    /// warnings that were omitted is printed once compilation finishes.
+    /// Warnings from `@warn` are always printed.
+    ///
+    /// By default, this value is `false` and repetitive deprecation warnings
+    /// are omitted.
+    #[must_use]
+    #[inline]
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Deprecations to treat as errors rather than warnings, so that a
+    /// stylesheet can be kept free of a deprecated feature before support
+    /// for it is removed.
+    ///
+    /// A deprecation that is both fatal and silenced is fatal.
+    ///
+    /// By default, every deprecation is a warning.
+    #[must_use]
+    #[inline]
+    pub fn fatal_deprecations(mut self, deprecations: &'a [Deprecation]) -> Self {
+        self.fatal_deprecations = deprecations;
+        self
+    }
+
+    /// Deprecations to not warn about, such as those used by a dependency
+    /// that can't yet be migrated. Other warnings are still printed.
+    ///
+    /// By default, every deprecation is warned about.
+    #[must_use]
+    #[inline]
+    pub fn silence_deprecations(mut self, deprecations: &'a [Deprecation]) -> Self {
+        self.silence_deprecations = deprecations;
+        self
+    }
+
+    /// The format in which warnings and the output of `@debug` are passed
+    /// to the [`logger`](Options::logger). `DiagnosticFormat::Json` writes each one as a JSON object
+    /// on its own line, as described by [`Diagnostic`](Diagnostic), so that
+    /// it can be read by editor plugins and CI tooling, and
+    /// `DiagnosticFormat::Github` writes each one as a GitHub Actions
+    /// workflow command, so that it is shown on pull requests. Errors are
+    /// returned rather than written, and can be converted with
+    /// [`Error::diagnostic`](Error::diagnostic).
+    ///
+    /// By default, this value is `DiagnosticFormat::Human`.
+    #[must_use]
+    #[inline]
+    pub fn diagnostic_format(mut self, diagnostic_format: DiagnosticFormat) -> Self {
+        self.diagnostic_format = diagnostic_format;
+        self
+    }
+
+    /// Where warnings and the output of `@debug` are sent, so that programs
+    /// embedding grass can capture them rather than have them printed. The
+    /// logger isn't called when [`quiet`](Options::quiet) is set.
+    ///
+    /// By default, this value is [`StderrLogger`](StderrLogger), which
+    /// writes each message to stderr.
+    #[must_use]
+    #[inline]
+    pub fn logger(mut self, logger: &'a dyn Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// The filesystem that the stylesheet being compiled and every file it
+    /// loads are read from, so that programs embedding grass can supply
+    /// stylesheets without them being on disk. Load paths and import roots
+    /// are looked up in this filesystem too.
+    ///
+    /// By default, this value is [`StdFs`](StdFs), which reads from disk.
+    #[must_use]
+    #[inline]
+    pub fn fs(mut self, fs: &'a dyn Fs) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Override how the values of declarations are written, such as to
+    /// write colors in a particular format, or to rewrite the paths of
+    /// `url()`s through an asset pipeline. See
+    /// [`ValueFormatter`](ValueFormatter).
+    ///
+    /// By default, there is no formatter and values are written as usual.
+    #[must_use]
+    #[inline]
+    pub fn value_formatter(mut self, value_formatter: &'a dyn ValueFormatter) -> Self {
+        self.value_formatter = Some(value_formatter);
+        self
+    }
+
+    /// Emit a warning when a style rule declares a shorthand property, such
+    /// as `margin`, after one of the longhand properties it overrides, such
+    /// as `margin-top`, or the other way around. Declarations included by
+    /// mixins are checked as part of the rule that includes them.
+    ///
+    /// By default, this value is `false` and no such warnings are emitted.
+    #[must_use]
+    #[inline]
+    pub fn warn_shorthand_conflicts(mut self, warn_shorthand_conflicts: bool) -> Self {
+        self.warn_shorthand_conflicts = warn_shorthand_conflicts;
+        self
+    }
+
+    /// Whether to preserve line breaks between the selectors of a selector
+    /// list. As in dart-sass, a selector that was preceded by a line break
+    /// in the source, such as `b` in `a,\nb`, is written on its own line.
+    ///
+    /// Set this to `false` to write every selector list on a single line,
+    /// as in `a, b`.
+    ///
+    /// By default, this value is `true`.
+    #[must_use]
+    #[inline]
+    pub fn selector_line_breaks(mut self, selector_line_breaks: bool) -> Self {
+        self.selector_line_breaks = selector_line_breaks;
+        self
+    }
+
+    /// Precede each style rule with a comment noting the file and line it
+    /// was written on, such as `/* line 3, src/_buttons.scss */`.
+    ///
+    /// This is useful for tracing CSS back to its source where source maps
+    /// can't be used, such as in pipelines that inline CSS into emails, or
+    /// in old browsers.
+    ///
+    /// By default, this value is `false`.
+    #[must_use]
+    #[inline]
+    pub fn line_comments(mut self, line_comments: bool) -> Self {
+        self.line_comments = line_comments;
+        self
+    }
+
+    /// Reuse the CSS produced by including a mixin when it is included again
+    /// with the same arguments in the same context, such as the same parent
+    /// selector, rather than evaluating its body again. This can greatly
+    /// reduce compilation time for stylesheets that generate many utility
+    /// classes from the same mixins.
+    ///
+    /// Only mixins whose output can depend on nothing but their arguments
+    /// are cached. A mixin is never cached if it accepts a content block,
+    /// includes other mixins, uses `@extend`, `@warn` or `@debug`, sets
+    /// global variables, reads variables other than its parameters and its
+    /// own local variables, or calls a user-defined function or a function
+    /// such as `random()`.
+    ///
+    /// By default, this value is `false` and every include is evaluated.
+    #[must_use]
+    #[inline]
+    pub fn cache_mixins(mut self, cache_mixins: bool) -> Self {
+        self.cache_mixins = cache_mixins;
+        self
+    }
+
+    /// Seed the random number generator used by `random()` and
+    /// `unique-id()`, so that compiling the same stylesheet with the same
+    /// seed always produces the same output. This is useful for
+    /// reproducible builds.
+    ///
+    /// The values produced for a given seed may change between versions
+    /// of grass.
+    ///
+    /// By default, there is no seed and the generator is seeded randomly
+    /// for each compilation.
+    #[must_use]
+    #[inline]
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// The number of digits after the decimal point that numbers are written
+    /// with. Numbers are stored as exact fractions, so this only affects the
+    /// output, and `1/3 * 3 == 1` regardless of precision.
+    ///
+    /// A precision of `0` rounds numbers to the nearest integer.
+    ///
+    /// By default, this value is `10`, which matches `dart-sass`.
+    #[must_use]
+    #[inline]
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
     /// All Sass implementations allow users to provide
     /// load paths: paths on the filesystem that Sass
     /// will look in when locating modules. For example,
@@ -234,6 +546,250 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Restrict `@import` and `@use` to files inside the given directory,
+    /// which is useful when compiling untrusted input.
+    ///
+    /// Once any import root has been given, every file loaded by `@import`
+    /// or `@use` must be inside one of the import roots, otherwise compilation
+    /// fails with an error. This applies to files found relative to the current
+    /// file as well as to files found in load paths. Symbolic links are resolved
+    /// before checking.
+    ///
+    /// By default, imports are not restricted.
+    ///
+    /// This method will append a single directory to the list.
+    #[must_use]
+    #[inline]
+    pub fn import_root(mut self, path: &'a Path) -> Self {
+        self.import_roots.get_or_insert_with(Vec::new).push(path);
+        self
+    }
+
+    /// Append multiple import roots
+    ///
+    /// Passing an empty slice restricts imports without allowing any
+    /// directories, so that no files may be imported at all.
+    ///
+    /// See [`Options::import_root`](Options::import_root) for more information about import roots
+    #[must_use]
+    #[inline]
+    pub fn import_roots(mut self, paths: &'a [&'a Path]) -> Self {
+        self.import_roots
+            .get_or_insert_with(Vec::new)
+            .extend_from_slice(paths);
+        self
+    }
+
+    /// Fail compilation with an error if it takes longer than `timeout`,
+    /// putting a hard upper bound on the time spent compiling untrusted input.
+    ///
+    /// The timeout is checked before each statement and each loop iteration
+    /// is evaluated, and repeatedly while selectors are extended, so a
+    /// compilation may overrun it by the time taken to evaluate a single
+    /// statement.
+    ///
+    /// By default, there is no timeout.
+    #[must_use]
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail compilation with an error if style rules are nested more than
+    /// `depth` levels deep, where a style rule at the root of a stylesheet
+    /// has a depth of 1
+    ///
+    /// Style rules in imported files and mixins count towards the depth of
+    /// the style rule they are included in.
+    ///
+    /// By default, nesting depth is not limited.
+    #[must_use]
+    #[inline]
+    pub fn max_nesting_depth(mut self, depth: usize) -> Self {
+        self.max_nesting_depth = Some(depth);
+        self
+    }
+
+    /// Fail compilation with an error once it has allocated more than
+    /// `bytes` bytes of memory, rather than letting runaway input (e.g. huge
+    /// loops or `@extend` explosions) exhaust the memory of the host process.
+    ///
+    /// This limit is best-effort: it is checked before each statement and
+    /// each loop iteration is evaluated and repeatedly while selectors are
+    /// extended, and it only takes effect if
+    /// [`CountingAllocator`](CountingAllocator) is installed as the global
+    /// allocator. Only allocations made by the thread compiling the
+    /// stylesheet are counted.
+    ///
+    /// By default, memory usage is not limited.
+    #[cfg(feature = "memory-limit")]
+    #[must_use]
+    #[inline]
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Make the functions provided by a [`Plugin`](Plugin) available to
+    /// stylesheets
+    ///
+    /// Plugin functions take precedence over builtin functions, but not over
+    /// functions defined with `@function`. If multiple plugins provide a
+    /// function with the same name, the one added first is used.
+    #[cfg(feature = "plugins")]
+    #[must_use]
+    #[inline]
+    pub fn plugin(mut self, plugin: &'a Plugin) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Rewrite the URL each source file is referred to by in error messages,
+    /// warnings, `@debug` output, [`Diagnostic`](Diagnostic)s,
+    /// [`SelectorMapping`](SelectorMapping)s and [`Stats`](Stats), such as
+    /// to strip an absolute prefix or to add a `webpack://` scheme
+    ///
+    /// The callback is called once for each file as it is loaded, with the
+    /// path the file was loaded from, or `stdin` for a stylesheet compiled
+    /// from a string, and returns the URL to use instead.
+    ///
+    /// ```
+    /// # use grass::Options;
+    /// let rewrite = |url: &str| format!("webpack:///{}", url);
+    /// let options = Options::default().source_url_rewriter(&rewrite);
+    ///
+    /// let err = grass::from_string("a { b: }".to_string(), &options).unwrap_err();
+    /// assert_eq!(err.diagnostic().file.as_deref(), Some("webpack:///stdin"));
+    /// ```
+    ///
+    /// By default, files are referred to by the path they were loaded from.
+    #[must_use]
+    #[inline]
+    pub fn source_url_rewriter(mut self, rewriter: &'a dyn Fn(&str) -> String) -> Self {
+        self.source_url_rewriter = Some(SourceUrlRewriter(rewriter));
+        self
+    }
+
+    /// The URL a source file loaded from `path` is referred to by
+    pub(crate) fn source_url(&self, path: String) -> String {
+        match self.source_url_rewriter {
+            Some(SourceUrlRewriter(rewrite)) => rewrite(&path),
+            None => path,
+        }
+    }
+
+    /// The syntax of a stylesheet compiled from a string
+    ///
+    /// Files it loads are read in the syntax implied by their extension, and
+    /// a stylesheet compiled from a path is read in the syntax implied by
+    /// the path's extension.
+    ///
+    /// ```
+    /// # use grass::{Options, Syntax};
+    /// let css = grass::compile_string(
+    ///     "a\n  color: red\n".to_string(),
+    ///     &Options::default().syntax(Syntax::Indented),
+    /// )?;
+    /// assert_eq!(css, "a {\n  color: red;\n}\n");
+    /// # Ok::<(), Box<grass::Error>>(())
+    /// ```
+    ///
+    /// By default, stylesheets compiled from strings are SCSS.
+    #[must_use]
+    #[inline]
+    pub fn syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// The URL of a stylesheet compiled from a string, as if it had been
+    /// read from that path
+    ///
+    /// The stylesheet is referred to by this URL in error messages and
+    /// warnings, and relative `@import`s and `@use`s are resolved from the
+    /// directory containing it.
+    ///
+    /// ```
+    /// # use grass::{MemoryFs, Options};
+    /// let mut fs = MemoryFs::default();
+    /// fs.add_file("styles/_colors.scss", "$primary: red;");
+    ///
+    /// let options = Options::default().fs(&fs).url("styles/input.scss");
+    /// let css = grass::compile_string(
+    ///     "@use \"colors\";\na { color: colors.$primary; }".to_string(),
+    ///     &options,
+    /// )?;
+    /// assert_eq!(css, "a {\n  color: red;\n}\n");
+    ///
+    /// let err = grass::compile_string("a { b: }".to_string(), &options).unwrap_err();
+    /// assert_eq!(err.diagnostic().file.as_deref(), Some("styles/input.scss"));
+    /// # Ok::<(), Box<grass::Error>>(())
+    /// ```
+    ///
+    /// By default, stylesheets compiled from strings are referred to as
+    /// `stdin`, and relative loads are resolved from the current directory.
+    #[must_use]
+    #[inline]
+    pub fn url(mut self, url: &'a str) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// The tokens of `file`, read from `path`, in `syntax`
+    #[cfg(not(feature = "wasm"))]
+    pub(crate) fn tokenize(
+        &self,
+        syntax: Syntax,
+        path: &Path,
+        file: &Arc<File>,
+    ) -> Result<Vec<Token>> {
+        match self.parse_cache {
+            Some(cache) => cache.tokenize(self.fs, path, syntax, file),
+            None => syntax.tokenize(file),
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    pub(crate) fn tokenize(
+        &self,
+        syntax: Syntax,
+        _: &Path,
+        file: &Arc<File>,
+    ) -> Result<Vec<Token>> {
+        syntax.tokenize(file)
+    }
+
+    /// Adds a stylesheet compiled from a string to `map`, returning it along
+    /// with the path that loads are resolved relative to
+    pub(crate) fn string_input(&self, map: &mut CodeMap, input: String) -> (Arc<File>, &'a Path) {
+        let file = map.add_file(self.source_url(self.url.unwrap_or("stdin").into()), input);
+        (file, Path::new(self.url.unwrap_or("")))
+    }
+
+    #[cfg(feature = "plugins")]
+    pub(crate) fn plugin_function(&self, name: &str) -> Option<crate::plugin::PluginFn> {
+        self.plugins.iter().find_map(|plugin| plugin.get(name))
+    }
+
+    /// Passes a warning or `@debug` message to the logger in the configured
+    /// format, unless compiling quietly
+    pub(crate) fn log(&self, diagnostic: &Diagnostic, human: &str) {
+        if self.quiet {
+            return;
+        }
+
+        let formatted = match self.diagnostic_format {
+            DiagnosticFormat::Human => human.to_owned(),
+            DiagnosticFormat::Json => diagnostic.to_json(),
+            DiagnosticFormat::Github => diagnostic.to_github(),
+        };
+        match diagnostic.severity {
+            Severity::Debug => self.logger.debug(diagnostic, &formatted),
+            Severity::Warning | Severity::Error => self.logger.warn(diagnostic, &formatted),
+        }
+    }
+
     /// This flag tells Sass whether to emit a `@charset`
     /// declaration or a UTF-8 byte-order mark.
     ///
@@ -263,60 +819,475 @@ impl<'a> Options<'a> {
     }
 }
 
+/// Logs how many deprecation warnings weren't printed because too many of
+/// their kind were emitted, unless compiling verbosely
+fn report_repetitive_deprecations(options: &Options, stats: &Stats) {
+    if options.verbose {
+        return;
+    }
+
+    let omitted = diagnostic::repetitive_deprecations(&stats.diagnostics);
+    if omitted == 0 {
+        return;
+    }
+
+    let message = format!(
+        "{} repetitive deprecation warnings omitted.\nRun in verbose mode to see all warnings.",
+        omitted
+    );
+    let human = format!("Warning: {}", message);
+    options.log(
+        &Diagnostic::new(Severity::Warning, "omitted-warnings", message, None),
+        &human,
+    );
+}
+
 fn raw_to_parse_error(map: &CodeMap, err: Error, unicode: bool) -> Box<Error> {
-    let (message, span) = err.raw();
-    Box::new(Error::from_loc(message, map.look_up_span(span), unicode))
+    Box::new(err.locate(map, unicode))
 }
 
-/// Compile CSS from a path
+/// A style rule in the source, and the selector it produced in the output
 ///
-/// ```
-/// fn main() -> Result<(), Box<grass::Error>> {
-///     let sass = grass::from_path("input.scss", &grass::Options::default())?;
-///     Ok(())
-/// }
-/// ```
-/// (grass does not currently allow files or paths that are not valid UTF-8)
-#[cfg_attr(feature = "profiling", inline(never))]
-#[cfg_attr(not(feature = "profiling"), inline)]
-#[cfg(not(feature = "wasm"))]
-pub fn from_path(p: &str, options: &Options) -> Result<String> {
-    let mut map = CodeMap::new();
-    let file = map.add_file(p.into(), String::from_utf8(fs::read(p)?)?);
+/// This makes it possible to answer "where did this selector in the CSS
+/// come from?" without generating source maps.
+///
+/// See [`ReportOptions::selector_map`](ReportOptions::selector_map)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorMapping {
+    /// The name of the file containing the style rule
+    pub file: String,
+    /// The 1-based line on which the style rule's selector begins
+    pub line: usize,
+    /// The 1-based column on which the style rule's selector begins
+    pub column: usize,
+    /// The selector of the style rule as written in the source
+    pub source: String,
+    /// The selector emitted for the style rule, after nesting has been
+    /// resolved and `@extend` has been applied
+    ///
+    /// Placeholder selectors are not emitted, so this is empty if the
+    /// style rule consisted only of placeholders
+    pub output: String,
+}
+
+/// Placeholder selectors and `@extend`s that have no effect on the output
+///
+/// This helps to find code that can be removed, such as the unused parts of
+/// a fork of a CSS framework.
+///
+/// See [`ReportOptions::dead_code`](ReportOptions::dead_code)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeadCode {
+    /// Placeholder selectors that are never the target of an `@extend`, and
+    /// so never appear in the output, located at the first style rule each
+    /// appears in
+    pub unextended_placeholders: Vec<DeadSelector>,
+
+    /// `@extend !optional` rules whose target never appears in a style rule,
+    /// located at the `@extend`
+    ///
+    /// An `@extend` that isn't optional and never matches is an error, so
+    /// is never reported here.
+    pub unmatched_extends: Vec<DeadSelector>,
+}
+
+/// A selector reported by [`DeadCode`](DeadCode), and where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadSelector {
+    /// The name of the file containing the selector
+    pub file: String,
+    /// The 1-based line on which the style rule or `@extend` begins
+    pub line: usize,
+    /// The 1-based column on which the style rule or `@extend` begins
+    pub column: usize,
+    /// The placeholder selector, or the target of the `@extend`
+    pub selector: String,
+}
+
+impl DeadSelector {
+    fn new(map: &CodeMap, span: Span, selector: String) -> Self {
+        let loc = map.look_up_span(span);
+        DeadSelector {
+            file: loc.file.name().to_owned(),
+            line: loc.begin.line + 1,
+            column: loc.begin.column + 1,
+            selector,
+        }
+    }
+}
+
+/// Statistics collected while compiling a stylesheet
+///
+/// See [`ReportOptions::stats`](ReportOptions::stats)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The deepest level of style rule nesting reached in each file, keyed by
+    /// file name. A style rule at the root of a stylesheet has a depth of 1
+    ///
+    /// Files that contain no style rules are omitted.
+    pub max_nesting_depth: BTreeMap<String, usize>,
+
+    /// The warnings and `@debug` messages emitted while compiling, in the
+    /// order they were emitted. These are recorded even if
+    /// [`Options::quiet`](Options::quiet) is set, but a deprecation warning
+    /// emitted more than once from the same place is recorded once.
+    pub diagnostics: Vec<Diagnostic>,
+
+    /// The files loaded by `@import`, `@use`, and `meta.load-css()`, as they
+    /// were found relative to the current directory or a load path. The
+    /// stylesheet being compiled isn't included.
+    pub loaded_paths: BTreeSet<PathBuf>,
+
+    /// The code and location of each deprecation warning in `diagnostics`
+    pub(crate) deprecation_locations: HashSet<(&'static str, Span)>,
+}
+
+/// The stylesheet to compile with [`compile_with_reports`](compile_with_reports)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input<'a> {
+    /// The path of a file, which is read through [`Options::fs`](Options::fs)
+    /// in the syntax implied by its extension
+    Path(&'a str),
+
+    /// A stylesheet in the [`syntax`](Options::syntax) given in the options,
+    /// compiled as if it were read from their [`url`](Options::url)
+    String(String),
+}
+
+/// The reports that [`compile_with_reports`](compile_with_reports) collects
+/// in addition to the CSS
+///
+/// None are collected by default, and collecting a report may make
+/// compilation slower.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReportOptions {
+    selector_map: bool,
+    stats: bool,
+    dead_code: bool,
+    exports: bool,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+impl ReportOptions {
+    /// Collect a [`SelectorMapping`](SelectorMapping) for every style rule
+    /// encountered, in source order, as [`Report::selector_map`]
+    #[must_use]
+    #[inline]
+    pub fn selector_map(mut self, selector_map: bool) -> Self {
+        self.selector_map = selector_map;
+        self
+    }
+
+    /// Collect [`Stats`](Stats) about the compilation, as [`Report::stats`]
+    #[must_use]
+    #[inline]
+    pub fn stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Collect the placeholder selectors and `@extend`s that have no effect
+    /// on the output, as [`Report::dead_code`]
+    #[must_use]
+    #[inline]
+    pub fn dead_code(mut self, dead_code: bool) -> Self {
+        self.dead_code = dead_code;
+        self
+    }
+
+    /// Collect the values of the public variables of the stylesheet and of
+    /// the modules it loads with `@use`, as they are after compilation, as
+    /// [`Report::exports`]
+    #[must_use]
+    #[inline]
+    pub fn exports(mut self, exports: bool) -> Self {
+        self.exports = exports;
+        self
+    }
+}
+
+/// The result of [`compile_with_reports`](compile_with_reports)
+///
+/// Each report is `None` unless it was requested in the
+/// [`ReportOptions`](ReportOptions).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// The compiled CSS
+    pub css: String,
+
+    /// The [`SelectorMapping`](SelectorMapping) of every style rule
+    /// encountered, in source order
+    pub selector_map: Option<Vec<SelectorMapping>>,
+
+    /// Statistics collected while compiling
+    pub stats: Option<Stats>,
+
+    /// The placeholder selectors and `@extend`s that have no effect on the
+    /// output
+    pub dead_code: Option<DeadCode>,
+
+    /// The public variables of the stylesheet and of the modules it loads
+    /// with `@use`
+    pub exports: Option<Exports>,
+}
+
+/// A stylesheet being compiled, along with everything collected while
+/// compiling it
+struct Compilation<'a> {
+    map: CodeMap,
+    file: Arc<File>,
+    /// The path loads are resolved relative to
+    path: &'a Path,
+    syntax: Syntax,
+    extender: Extender,
+    stats: Stats,
+    /// Where the public variables are written after compilation, if they're
+    /// wanted
+    exports: Option<Exports>,
+}
+
+impl<'a> Compilation<'a> {
+    fn new(input: Input<'a>, options: &Options<'a>) -> Result<Self> {
+        let mut map = CodeMap::new();
+        let (file, path, syntax) = match input {
+            Input::Path(p) => {
+                let file = map.add_file(
+                    options.source_url(p.into()),
+                    String::from_utf8(options.fs.read(p.as_ref())?)?,
+                );
+                (file, Path::new(p), Syntax::for_path(p.as_ref()))
+            }
+            Input::String(source) => {
+                let (file, path) = options.string_input(&mut map, source);
+                (file, path, options.syntax)
+            }
+        };
+
+        Ok(Self::from_file(map, file, path, syntax))
+    }
+
+    fn from_file(map: CodeMap, file: Arc<File>, path: &'a Path, syntax: Syntax) -> Self {
+        let extender = Extender::new(file.span.subspan(0, 0));
+        Compilation {
+            map,
+            file,
+            path,
+            syntax,
+            extender,
+            stats: Stats::default(),
+            exports: None,
+        }
+    }
+}
+
+fn compile(compilation: &mut Compilation, options: &Options) -> Result<String> {
+    #[cfg(feature = "determinism-audit")]
+    {
+        // `random()` and `unique-id()` must return the same values each time
+        let options = &Options {
+            random_seed: Some(options.random_seed.unwrap_or_else(|| {
+                use std::hash::{BuildHasher, Hasher};
+                std::collections::hash_map::RandomState::new()
+                    .build_hasher()
+                    .finish()
+            })),
+            ..options.clone()
+        };
+
+        let css = compile_once(compilation, options)?;
+        audit_determinism(compilation, options, &css)?;
+        Ok(css)
+    }
+
+    #[cfg(not(feature = "determinism-audit"))]
+    compile_once(compilation, options)
+}
+
+/// The number of additional times each stylesheet is compiled by the
+/// `determinism-audit` feature, each with a different hash seed
+#[cfg(feature = "determinism-audit")]
+const AUDIT_SEEDS: u64 = 8;
+
+/// Compiles the stylesheet again with several hash seeds, returning an error
+/// if any of the results differ from `css`
+#[cfg(feature = "determinism-audit")]
+fn audit_determinism(compilation: &mut Compilation, options: &Options, css: &str) -> Result<()> {
+    let options = &Options {
+        logger: &NullLogger,
+        ..options.clone()
+    };
+
+    for seed in 1..=AUDIT_SEEDS {
+        let _seed = hash::set_seed(seed);
+        let mut audit = Compilation::from_file(
+            std::mem::replace(&mut compilation.map, CodeMap::new()),
+            Arc::clone(&compilation.file),
+            compilation.path,
+            compilation.syntax,
+        );
+        let other = compile_once(&mut audit, options);
+        compilation.map = audit.map;
+        let other = other?;
+
+        if other != css {
+            let line = css
+                .lines()
+                .zip(other.lines())
+                .take_while(|(expected, found)| expected == found)
+                .count();
+            let message = format!(
+                "Output depends on the iteration order of a hash map: compiling with seed {} \
+                 changes line {} of the CSS.\n  expected: {}\n     found: {}",
+                seed,
+                line + 1,
+                css.lines().nth(line).unwrap_or(""),
+                other.lines().nth(line).unwrap_or("")
+            );
+            return Err(raw_to_parse_error(
+                &compilation.map,
+                *Box::<Error>::from((message, compilation.file.span.subspan(0, 0))),
+                options.unicode_error_messages,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn compile_once(compilation: &mut Compilation, options: &Options) -> Result<String> {
+    let Compilation {
+        map,
+        file,
+        path,
+        syntax,
+        extender,
+        stats,
+        exports,
+    } = compilation;
+    let (path, syntax) = (*path, *syntax);
+
     let empty_span = file.span.subspan(0, 0);
+    let _precision = set_precision(options.precision);
+    let mut global_scope = Scope::new();
+    let mut modules = Modules::default();
+    let limits = Limits::new(options);
+    extender.set_limits(limits);
+
+    let toks = options
+        .tokenize(syntax, path, file)
+        .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
 
     let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
-            .into_iter()
-            .peekmore(),
-        map: &mut map,
-        path: p.as_ref(),
+        toks: &mut toks.into_iter().peekmore(),
+        map,
+        path,
         scopes: &mut Scopes::new(),
-        global_scope: &mut Scope::new(),
+        global_scope: &mut global_scope,
         super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
         span_before: empty_span,
         content: &mut Vec::new(),
-        flags: ContextFlags::empty(),
+        flags: ContextFlags::empty().for_syntax(syntax),
         at_root: true,
         at_root_has_selector: false,
-        extender: &mut Extender::new(empty_span),
+        extender,
         content_scopes: &mut Scopes::new(),
         options,
-        modules: &mut Modules::default(),
+        modules: &mut modules,
         module_config: &mut ModuleConfig::default(),
+        media_queries: &mut None,
+        stats,
+        mixin_cache: &mut MixinCache::default(),
+        random: &mut Random::new(options.random_seed),
+        import_stack: &mut ImportStack::new(path, options.fs),
+        limits,
     }
     .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+    .and_then(|stmts| {
+        extender.check_unsatisfied_extensions()?;
+        Ok(stmts)
+    });
+
+    report_repetitive_deprecations(options, stats);
+
+    let stmts = stmts.map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
+
+    if let Some(exports) = exports.as_mut() {
+        *exports = Exports::new(&global_scope, &modules);
+    }
+
+    Css::from_stmts(
+        stmts,
+        false,
+        options.allows_charset,
+        options.selector_line_breaks,
+        options.line_comments,
+    )
+    .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?
+    .pretty_print(map, options.style)
+    .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))
+}
+
+fn selector_map(map: &CodeMap, extender: &Extender) -> Vec<SelectorMapping> {
+    extender
+        .rule_origins()
+        .iter()
+        .map(|(span, selector)| {
+            let loc = map.look_up_span(*span);
+            SelectorMapping {
+                file: loc.file.name().to_owned(),
+                line: loc.begin.line + 1,
+                column: loc.begin.column + 1,
+                source: loc
+                    .file
+                    .source_slice(*span)
+                    .trim_end_matches('{')
+                    .trim()
+                    .to_owned(),
+                output: selector
+                    .clone()
+                    .into_selector()
+                    .remove_placeholders()
+                    .to_string(),
+            }
+        })
+        .collect()
+}
+
+fn dead_code(map: &CodeMap, extender: &Extender) -> DeadCode {
+    DeadCode {
+        unextended_placeholders: extender
+            .unextended_placeholders()
+            .into_iter()
+            .map(|(span, placeholder)| DeadSelector::new(map, span, placeholder.to_string()))
+            .collect(),
+        unmatched_extends: extender
+            .unmatched_extensions()
+            .into_iter()
+            .map(|(span, target)| DeadSelector::new(map, span, target.to_string()))
+            .collect(),
+    }
+}
 
-    Css::from_stmts(stmts, false, options.allows_charset)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+/// Compile CSS from a path
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let sass = grass::from_path("input.scss", &grass::Options::default())?;
+///     Ok(())
+/// }
+/// ```
+/// (grass does not currently allow files or paths that are not valid UTF-8)
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_path(p: &str, options: &Options) -> Result<String> {
+    compile(&mut Compilation::new(Input::Path(p), options)?, options)
 }
 
 /// Compile CSS from a string
 ///
+/// This is the same as [`compile_string`](compile_string).
+///
 /// ```
 /// fn main() -> Result<(), Box<grass::Error>> {
 ///     let sass = grass::from_string("a { b { color: &; } }".to_string(), &grass::Options::default())?;
@@ -328,72 +1299,98 @@ pub fn from_path(p: &str, options: &Options) -> Result<String> {
 #[cfg_attr(not(feature = "profiling"), inline)]
 #[cfg(not(feature = "wasm"))]
 pub fn from_string(p: String, options: &Options) -> Result<String> {
-    let mut map = CodeMap::new();
-    let file = map.add_file("stdin".into(), p);
-    let empty_span = file.span.subspan(0, 0);
-    let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
-            .into_iter()
-            .peekmore(),
-        map: &mut map,
-        path: Path::new(""),
-        scopes: &mut Scopes::new(),
-        global_scope: &mut Scope::new(),
-        super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
-        span_before: empty_span,
-        content: &mut Vec::new(),
-        flags: ContextFlags::empty(),
-        at_root: true,
-        at_root_has_selector: false,
-        extender: &mut Extender::new(empty_span),
-        content_scopes: &mut Scopes::new(),
-        options,
-        modules: &mut Modules::default(),
-        module_config: &mut ModuleConfig::default(),
-    }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
-
-    Css::from_stmts(stmts, false, options.allows_charset)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+    compile_string(p, options)
 }
 
-#[cfg(feature = "wasm")]
-#[wasm_bindgen]
-pub fn from_string(p: String) -> std::result::Result<String, JsValue> {
-    let mut map = CodeMap::new();
-    let file = map.add_file("stdin".into(), p);
-    let empty_span = file.span.subspan(0, 0);
+/// Compile CSS from a string, in the [`syntax`](Options::syntax) and as if
+/// it were read from the [`url`](Options::url) given in `options`
+///
+/// ```
+/// use grass::{Options, Syntax};
+///
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = Options::default()
+///         .syntax(Syntax::Indented)
+///         .url("styles/input.sass");
+///     let css = grass::compile_string("a\n  b\n    color: &\n".to_string(), &options)?;
+///     assert_eq!(css, "a b {\n  color: a b;\n}\n");
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn compile_string(source: String, options: &Options) -> Result<String> {
+    compile(
+        &mut Compilation::new(Input::String(source), options)?,
+        options,
+    )
+}
 
-    let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
-            .into_iter()
-            .peekmore(),
-        map: &mut map,
-        path: Path::new(""),
-        scopes: &mut Scopes::new(),
-        global_scope: &mut Scope::new(),
-        super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
-        span_before: empty_span,
-        content: &mut Vec::new(),
-        flags: ContextFlags::empty(),
-        at_root: true,
-        at_root_has_selector: false,
-        extender: &mut Extender::new(empty_span),
-        content_scopes: &mut Scopes::new(),
-        options: &Options::default(),
-        modules: &mut Modules::default(),
-        module_config: &mut ModuleConfig::default(),
+/// Compile CSS, additionally collecting the reports selected in `reports`
+///
+/// ```
+/// use grass::{Input, Options, ReportOptions};
+///
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let report = grass::compile_with_reports(
+///         Input::String("a { b { color: red; } }\n%unused { color: red; }".to_string()),
+///         &Options::default(),
+///         ReportOptions::default()
+///             .selector_map(true)
+///             .stats(true)
+///             .dead_code(true),
+///     )?;
+///     assert_eq!(report.css, "a b {\n  color: red;\n}\n");
+///
+///     let selectors = report.selector_map.unwrap();
+///     assert_eq!(selectors[1].source, "b");
+///     assert_eq!(selectors[1].output, "a b");
+///
+///     assert_eq!(report.stats.unwrap().max_nesting_depth["stdin"], 2);
+///     assert_eq!(report.dead_code.unwrap().unextended_placeholders[0].selector, "%unused");
+///     assert!(report.exports.is_none());
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn compile_with_reports(
+    input: Input<'_>,
+    options: &Options,
+    reports: ReportOptions,
+) -> Result<Report> {
+    let mut compilation = Compilation::new(input, options)?;
+    if reports.selector_map {
+        compilation.extender.track_rule_origins();
     }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?;
+    if reports.dead_code {
+        compilation.extender.track_placeholder_origins();
+    }
+    if reports.exports {
+        compilation.exports = Some(Exports::default());
+    }
+
+    let css = compile(&mut compilation, options)?;
 
-    Ok(Css::from_stmts(stmts, false, true)
-        .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?)
+    Ok(Report {
+        css,
+        selector_map: if reports.selector_map {
+            Some(selector_map(&compilation.map, &compilation.extender))
+        } else {
+            None
+        },
+        stats: if reports.stats {
+            Some(compilation.stats)
+        } else {
+            None
+        },
+        dead_code: if reports.dead_code {
+            Some(dead_code(&compilation.map, &compilation.extender))
+        } else {
+            None
+        },
+        exports: compilation.exports,
+    })
 }
@@ -0,0 +1,128 @@
+//! Best-effort resource limits, used to stop runaway compilations of
+//! untrusted input before they take down the host process.
+//!
+//! Limits are enforced at checkpoints in the evaluator (before each statement
+//! and before each iteration of a loop) and in the extender (for each
+//! extension and each selector it extends, since a single `@extend` can
+//! produce an exponential number of selectors), so they bound the work done
+//! between checkpoints rather than being exact.
+
+use std::time::Instant;
+
+use codemap::Span;
+
+use crate::{error::SassResult, Options};
+
+#[cfg(feature = "memory-limit")]
+pub use allocator::CountingAllocator;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Limits {
+    /// The point in time after which compilation should be aborted
+    deadline: Option<Instant>,
+
+    /// The number of bytes the compiling thread had allocated when compilation
+    /// began, and the number of additional bytes it may allocate
+    #[cfg(feature = "memory-limit")]
+    memory: Option<(isize, usize)>,
+}
+
+impl Limits {
+    pub fn new(options: &Options) -> Self {
+        Self {
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+            #[cfg(feature = "memory-limit")]
+            memory: options
+                .memory_limit
+                .map(|limit| (allocator::allocated_bytes(), limit)),
+        }
+    }
+
+    /// Returns an error if any limit has been exceeded
+    pub fn check(self, span: Span) -> SassResult<()> {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() > deadline {
+                return Err(("Compilation timed out.", span).into());
+            }
+        }
+
+        #[cfg(feature = "memory-limit")]
+        if let Some((start, limit)) = self.memory {
+            let allocated = allocator::allocated_bytes().saturating_sub(start);
+            if allocated > 0 && allocated as usize > limit {
+                return Err(("Memory limit exceeded.", span).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "memory-limit")]
+mod allocator {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    thread_local!(static ALLOCATED: Cell<isize> = const { Cell::new(0) });
+
+    /// The number of bytes currently allocated by this thread, as tracked by
+    /// `CountingAllocator`
+    ///
+    /// Memory freed by a thread other than the one that allocated it is
+    /// attributed to the freeing thread, so this may be negative
+    pub(super) fn allocated_bytes() -> isize {
+        ALLOCATED.try_with(Cell::get).unwrap_or(0)
+    }
+
+    fn track(delta: isize) {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get().wrapping_add(delta)));
+    }
+
+    /// A global allocator that keeps track of how much memory each thread
+    /// has allocated, which is required for
+    /// [`Options::memory_limit`](crate::Options::memory_limit) to take effect
+    ///
+    /// ```
+    /// #[global_allocator]
+    /// static ALLOCATOR: grass::CountingAllocator = grass::CountingAllocator;
+    /// ```
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        #[inline]
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                track(layout.size() as isize);
+            }
+            ptr
+        }
+
+        #[inline]
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                track(layout.size() as isize);
+            }
+            ptr
+        }
+
+        #[inline]
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            track(-(layout.size() as isize));
+        }
+
+        #[inline]
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                track(new_size as isize - layout.size() as isize);
+            }
+            new_ptr
+        }
+    }
+}
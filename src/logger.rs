@@ -0,0 +1,77 @@
+//! Where warnings and `@debug` messages are sent
+//!
+//! See [`Options::logger`](crate::Options::logger)
+
+use std::fmt;
+
+use crate::diagnostic::Diagnostic;
+
+/// Receives the warnings and `@debug` messages emitted while compiling
+///
+/// Each message is passed both as a [`Diagnostic`], which describes its
+/// location in a structured form, and formatted as configured by
+/// [`Options::diagnostic_format`](crate::Options::diagnostic_format).
+///
+/// Loggers are shared by reference, so one that records messages must use
+/// interior mutability:
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// use grass::{Diagnostic, Logger};
+///
+/// #[derive(Debug, Default)]
+/// struct Recorder(RefCell<Vec<String>>);
+///
+/// impl Logger for Recorder {
+///     fn warn(&self, diagnostic: &Diagnostic, _formatted: &str) {
+///         self.0.borrow_mut().push(diagnostic.message.clone());
+///     }
+///
+///     fn debug(&self, _diagnostic: &Diagnostic, _formatted: &str) {}
+/// }
+///
+/// let recorder = Recorder::default();
+/// grass::from_string(
+///     "@warn \"careful\";".to_string(),
+///     &grass::Options::default().logger(&recorder),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(vec!["careful".to_string()], recorder.0.into_inner());
+/// ```
+pub trait Logger: fmt::Debug {
+    /// Called for each warning, including those emitted by `@warn`
+    fn warn(&self, diagnostic: &Diagnostic, formatted: &str);
+
+    /// Called for each `@debug` rule
+    fn debug(&self, diagnostic: &Diagnostic, formatted: &str);
+}
+
+/// Writes each message to stderr on its own line
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrLogger;
+
+impl Logger for StderrLogger {
+    #[inline]
+    fn warn(&self, _diagnostic: &Diagnostic, formatted: &str) {
+        eprintln!("{}", formatted);
+    }
+
+    #[inline]
+    fn debug(&self, _diagnostic: &Diagnostic, formatted: &str) {
+        eprintln!("{}", formatted);
+    }
+}
+
+/// Discards every message
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullLogger;
+
+impl Logger for NullLogger {
+    #[inline]
+    fn warn(&self, _diagnostic: &Diagnostic, _formatted: &str) {}
+
+    #[inline]
+    fn debug(&self, _diagnostic: &Diagnostic, _formatted: &str) {}
+}
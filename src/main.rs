@@ -1,13 +1,21 @@
 use std::{
-    fs::OpenOptions,
-    io::{stdin, stdout, BufWriter, Read, Write},
-    path::Path,
+    collections::BTreeSet,
+    fs,
+    io::{self, stdin, stdout, BufWriter, Read, Write},
+    iter,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
 };
 
-use clap::{arg_enum, App, AppSettings, Arg};
+use clap::{arg_enum, value_t, App, AppSettings, Arg, ArgMatches};
+use notify::{DebouncedEvent, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
 
 #[cfg(not(feature = "wasm"))]
-use grass::{from_path, from_string, Options};
+use grass::{
+    checkstyle_report, compile_with_reports, write_css, Deprecation, DiagnosticFormat, Error,
+    Input, Options, OutputStyle, ReportOptions, Stats, Syntax, WriteOptions,
+};
 
 arg_enum! {
     #[derive(PartialEq, Debug)]
@@ -25,12 +33,24 @@ arg_enum! {
     }
 }
 
+arg_enum! {
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    pub enum Format {
+        Human,
+        Json,
+        Github,
+        Checkstyle,
+    }
+}
+
 #[cfg(feature = "wasm")]
 fn main() {}
 
 #[cfg(not(feature = "wasm"))]
 #[cfg_attr(feature = "profiling", inline(never))]
 fn main() -> std::io::Result<()> {
+    let deprecation_ids: Vec<&str> = Deprecation::ALL.iter().map(|d| d.id()).collect();
+
     let matches = App::new("grass")
         .setting(AppSettings::ColoredHelp)
         .version(env!("CARGO_PKG_VERSION"))
@@ -44,7 +64,6 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("INDENTED")
                 .long("indented")
-                .hidden(true)
                 .help("Use the indented syntax for input from stdin"),
         )
         .arg(
@@ -59,10 +78,7 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("STYLE")
                 .short("s")
-                // this is required for compatibility with ruby sass
-                .short("t")
                 .long("style")
-                .hidden(true)
                 .help("Minified or expanded output")
                 .default_value("expanded")
                 .case_insensitive(true)
@@ -90,8 +106,7 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("NO_SOURCE_MAP")
                 .long("no-source-map")
-                .hidden(true)
-                .help("Whether to generate source maps."),
+                .help("Don't generate source maps. grass never generates them."),
         )
         .arg(
             Arg::with_name("SOURCE_MAP_URLS")
@@ -119,20 +134,17 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("WATCH")
                 .long("watch")
-                .hidden(true)
                 .help("Watch stylesheets and recompile when they change."),
         )
         .arg(
             Arg::with_name("POLL")
                 .long("poll")
-                .hidden(true)
                 .help("Manually check for changes rather than using a native watcher. Only valid with --watch.")
                 .requires("WATCH"),
         )
         .arg(
             Arg::with_name("NO_STOP_ON_ERROR")
                 .long("no-stop-on-error")
-                .hidden(true)
                 .help("Continue to compile more files after error is encountered.")
         )
         .arg(
@@ -161,21 +173,103 @@ fn main() -> std::io::Result<()> {
                 .help("Don't print warnings."),
         )
         .arg(
-            Arg::with_name("INPUT")
-                .required_unless("STDIN")
-                .help("SCSS files"),
+            Arg::with_name("VERBOSE")
+                .long("verbose")
+                .help("Print all deprecation warnings even when they're repetitive."),
         )
         .arg(
-            Arg::with_name("OUTPUT")
-                .help("Output SCSS file")
+            Arg::with_name("FATAL_DEPRECATION")
+                .long("fatal-deprecation")
+                .help("Treat this deprecation as an error. May be passed multiple times.")
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1)
+                .possible_values(&deprecation_ids),
+        )
+        .arg(
+            Arg::with_name("SILENCE_DEPRECATION")
+                .long("silence-deprecation")
+                .help("Don't warn about this deprecation. May be passed multiple times.")
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1)
+                .possible_values(&deprecation_ids),
+        )
+        .arg(
+            Arg::with_name("MAX_NESTING_DEPTH")
+                .long("max-nesting-depth")
+                .takes_value(true)
+                .help("Fail if style rules are nested more deeply than this."),
+        )
+        .arg(
+            Arg::with_name("STATS")
+                .long("stats")
+                .help("Print the maximum nesting depth reached in each file."),
+        )
+        .arg(
+            Arg::with_name("WARN_SHORTHAND_CONFLICTS")
+                .long("warn-shorthand-conflicts")
+                .help("Warn when a shorthand property and one of its longhands override each other within a rule."),
+        )
+        .arg(
+            Arg::with_name("NO_SELECTOR_LINE_BREAKS")
+                .long("no-selector-line-breaks")
+                .help("Write each selector list on a single line, even if it spans multiple lines in the source."),
+        )
+        .arg(
+            Arg::with_name("LINE_COMMENTS")
+                .short("l")
+                .long("line-comments")
+                .help("Precede each style rule with a comment noting the file and line it was written on."),
+        )
+        .arg(
+            Arg::with_name("JSON_ERRORS")
+                .long("json-errors")
+                .conflicts_with("FORMAT")
+                .help("Print errors, warnings and debug messages as JSON objects, one per line. Equivalent to --format=json."),
+        )
+        .arg(
+            Arg::with_name("FORMAT")
+                .long("format")
+                .takes_value(true)
+                .default_value("human")
+                .case_insensitive(true)
+                .possible_values(&Format::variants())
+                .help("How errors, warnings and debug messages are printed. `github` prints GitHub Actions annotations, and `checkstyle` prints a single Checkstyle XML report once compilation finishes."),
+        )
+        .arg(
+            Arg::with_name("RANDOM_SEED")
+                .long("random-seed")
+                .takes_value(true)
+                .help("Seed random() and unique-id(), so that the same input always produces the same output."),
         )
-
-        // Hidden, legacy arguments
         .arg(
             Arg::with_name("PRECISION")
                 .long("precision")
-                .hidden(true)
                 .takes_value(true)
+                .help("The number of digits after the decimal point that numbers are written with. Defaults to 10."),
+        )
+        .arg(
+            Arg::with_name("CREATE_DIRS")
+                .long("create-dirs")
+                .help("Create the directory of the output file if it doesn't exist."),
+        )
+        .arg(
+            Arg::with_name("NO_OVERWRITE")
+                .long("no-overwrite")
+                .help("Fail rather than replace the output file if it already exists."),
+        )
+        .arg(
+            Arg::with_name("ATOMIC")
+                .long("atomic")
+                .help("Write to a temporary file, then rename it over the output file, so that the output file is never partially written."),
+        )
+        .arg(
+            Arg::with_name("INPUT")
+                .required_unless("STDIN")
+                .multiple(true)
+                .help("SCSS file, or - to read from stdin, followed by the CSS file to write to rather than stdout. \
+                       Alternatively, pairs of files or directories written as INPUT:OUTPUT"),
         )
         .get_matches();
 
@@ -183,47 +277,480 @@ fn main() -> std::io::Result<()> {
         .values_of("LOAD_PATH")
         .map_or_else(Vec::new, |vals| vals.map(Path::new).collect());
 
-    let options = &Options::default()
-        .load_paths(&load_paths)
-        .quiet(matches.is_present("QUIET"))
-        .unicode_error_messages(!matches.is_present("NO_UNICODE"))
-        .allows_charset(!matches.is_present("NO_CHARSET"));
-
-    let (mut stdout_write, mut file_write);
-    let buf_out: &mut dyn Write = if let Some(path) = matches.value_of("OUTPUT") {
-        file_write = BufWriter::new(
-            OpenOptions::new()
-                .create(true)
-                .write(true)
-                .truncate(true)
-                .open(path)?,
-        );
-        &mut file_write
+    // clap has already checked that these are the IDs of deprecations
+    let deprecations = |name: &str| -> Vec<Deprecation> {
+        matches.values_of(name).map_or_else(Vec::new, |vals| {
+            vals.filter_map(Deprecation::from_id).collect()
+        })
+    };
+    let fatal_deprecations = deprecations("FATAL_DEPRECATION");
+    let silence_deprecations = deprecations("SILENCE_DEPRECATION");
+
+    let mut options = Options::default();
+
+    if let Some(depth) = matches.value_of("MAX_NESTING_DEPTH") {
+        options = options.max_nesting_depth(depth.parse().unwrap_or_else(|_| {
+            eprintln!("--max-nesting-depth must be a non-negative integer.");
+            std::process::exit(1)
+        }));
+    }
+
+    if let Some(precision) = matches.value_of("PRECISION") {
+        options = options.precision(precision.parse().unwrap_or_else(|_| {
+            eprintln!("--precision must be a non-negative integer.");
+            std::process::exit(1)
+        }));
+    }
+
+    if let Some(seed) = matches.value_of("RANDOM_SEED") {
+        options = options.random_seed(seed.parse().unwrap_or_else(|_| {
+            eprintln!("--random-seed must be a non-negative integer.");
+            std::process::exit(1)
+        }));
+    }
+
+    let format = if matches.is_present("JSON_ERRORS") {
+        Format::Json
     } else {
-        stdout_write = BufWriter::new(stdout());
-        &mut stdout_write
+        value_t!(matches, "FORMAT", Format).unwrap_or_else(|e| e.exit())
     };
+    let checkstyle = format == Format::Checkstyle;
 
-    buf_out.write_all(
-        if let Some(name) = matches.value_of("INPUT") {
-            from_path(name, &options)
-        } else if matches.is_present("STDIN") {
-            from_string(
-                {
-                    let mut buffer = String::new();
-                    stdin().read_to_string(&mut buffer)?;
-                    buffer
-                },
-                options,
-            )
+    let options = &options
+        .load_paths(&load_paths)
+        // the checkstyle report is printed once compilation finishes
+        .quiet(matches.is_present("QUIET") || checkstyle)
+        .verbose(matches.is_present("VERBOSE"))
+        .fatal_deprecations(&fatal_deprecations)
+        .silence_deprecations(&silence_deprecations)
+        .warn_shorthand_conflicts(matches.is_present("WARN_SHORTHAND_CONFLICTS"))
+        .unicode_error_messages(!matches.is_present("NO_UNICODE"))
+        .allows_charset(!matches.is_present("NO_CHARSET"))
+        .selector_line_breaks(!matches.is_present("NO_SELECTOR_LINE_BREAKS"))
+        .line_comments(matches.is_present("LINE_COMMENTS"))
+        .syntax(if matches.is_present("INDENTED") {
+            Syntax::Indented
         } else {
-            unreachable!()
+            Syntax::Scss
+        })
+        .style(
+            match value_t!(matches, "STYLE", Style).unwrap_or_else(|e| e.exit()) {
+                Style::Expanded => OutputStyle::Expanded,
+                Style::Compressed => OutputStyle::Compressed,
+            },
+        )
+        .diagnostic_format(match format {
+            Format::Json => DiagnosticFormat::Json,
+            Format::Github => DiagnosticFormat::Github,
+            Format::Human | Format::Checkstyle => DiagnosticFormat::Human,
+        });
+
+    let write_options = WriteOptions::default()
+        .create_dirs(matches.is_present("CREATE_DIRS"))
+        .overwrite(!matches.is_present("NO_OVERWRITE"))
+        .atomic(matches.is_present("ATOMIC"));
+
+    let report = |stats: &Stats| {
+        if checkstyle {
+            let diagnostics = if matches.is_present("QUIET") {
+                &[]
+            } else {
+                stats.diagnostics.as_slice()
+            };
+            eprint!("{}", checkstyle_report(diagnostics));
+        }
+
+        if matches.is_present("STATS") {
+            for (file, depth) in &stats.max_nesting_depth {
+                eprintln!("{}: maximum nesting depth {}", file, depth);
+            }
+        }
+    };
+
+    let paths: Vec<&str> = matches
+        .values_of("INPUT")
+        .map_or_else(Vec::new, Iterator::collect);
+
+    if paths.iter().any(|path| split_pair(path).is_some()) {
+        if matches.is_present("STDIN") {
+            eprintln!("--stdin may not be used with \":\" arguments.");
+            std::process::exit(64)
         }
-        .unwrap_or_else(|e| {
-            eprintln!("{}", e);
+
+        let mut entries = Vec::new();
+        for path in paths {
+            let (input, output) = split_pair(path).unwrap_or_else(|| {
+                eprintln!("Positional and \":\" arguments may not both be used.");
+                std::process::exit(64)
+            });
+            add_entries(Path::new(input), Path::new(output), &mut entries).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input, e);
+                std::process::exit(1)
+            });
+        }
+
+        if matches.is_present("WATCH") {
+            start_watching(entries, options, &write_options, format, &report, &matches);
+            return Ok(());
+        }
+
+        let mut failed = false;
+        for entry in &mut entries {
+            match entry.compile(options, &write_options, format) {
+                Some(stats) => report(&stats),
+                None => {
+                    failed = true;
+                    if !matches.is_present("NO_STOP_ON_ERROR") {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if failed {
             std::process::exit(1)
-        })
-        .as_bytes(),
-    )?;
+        }
+        return Ok(());
+    }
+
+    if paths.len() > 2 {
+        eprintln!("Only two positional args may be passed.");
+        std::process::exit(64)
+    }
+
+    // like `sass`, the only path given with `--stdin` is the output file
+    let (input, output) = if matches.is_present("STDIN") {
+        if paths.len() > 1 {
+            eprintln!("Only one argument is allowed with --stdin.");
+            std::process::exit(64)
+        }
+        (None, paths.get(0).copied())
+    } else {
+        match paths.get(0).copied() {
+            Some("-") => (None, paths.get(1).copied()),
+            input => (input, paths.get(1).copied()),
+        }
+    };
+
+    if matches.is_present("WATCH") {
+        let (input, output) = match (input, output) {
+            (None, _) => {
+                eprintln!("--watch is not allowed when reading from stdin.");
+                std::process::exit(64)
+            }
+            (_, None) => {
+                eprintln!("--watch is not allowed when printing to stdout.");
+                std::process::exit(64)
+            }
+            (Some(input), Some(output)) => (input, output),
+        };
+
+        let entries = vec![Entry::new(input.to_owned(), output.to_owned())];
+        start_watching(entries, options, &write_options, format, &report, &matches);
+        return Ok(());
+    }
+
+    let input = if let Some(name) = input {
+        Input::Path(name)
+    } else {
+        let mut buffer = String::new();
+        stdin().read_to_string(&mut buffer)?;
+        Input::String(buffer)
+    };
+
+    let (css, stats) = compile_with_stats(input, options).unwrap_or_else(|e| {
+        print_error(&e, format);
+        std::process::exit(1)
+    });
+
+    report(&stats);
+
+    // the output file is only written once compilation has succeeded, so
+    // that an error never leaves it empty
+    if let Some(path) = output {
+        write_css(Path::new(path), &css, &write_options).unwrap_or_else(|e| {
+            eprintln!("Error writing {}: {}", path, e);
+            std::process::exit(1)
+        });
+    } else {
+        BufWriter::new(stdout()).write_all(css.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Splits an `input:output` argument, ignoring the colon after a Windows
+/// drive letter
+#[cfg(not(feature = "wasm"))]
+fn split_pair(arg: &str) -> Option<(&str, &str)> {
+    let start = if cfg!(windows) && arg.as_bytes().get(1) == Some(&b':') {
+        2
+    } else {
+        0
+    };
+    let idx = arg[start..].find(':')? + start;
+    Some((&arg[..idx], &arg[idx + 1..]))
+}
+
+/// Adds the stylesheet `input` to `entries`, or if it's a directory, every
+/// stylesheet in it or its subdirectories other than partials, written to the
+/// same relative paths within `output`
+#[cfg(not(feature = "wasm"))]
+fn add_entries(input: &Path, output: &Path, entries: &mut Vec<Entry>) -> io::Result<()> {
+    if !input.is_dir() {
+        entries.push(Entry::new(
+            input.to_string_lossy().into_owned(),
+            output.to_string_lossy().into_owned(),
+        ));
+        return Ok(());
+    }
+
+    let mut children = fs::read_dir(input)?
+        .map(|child| child.map(|child| child.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    children.sort();
+
+    for child in children {
+        let name = match child.file_name() {
+            Some(name) => Path::new(name),
+            None => continue,
+        };
+
+        if child.is_dir() {
+            add_entries(&child, &output.join(name), entries)?;
+        } else if is_entry_point(&child) {
+            add_entries(&child, &output.join(name).with_extension("css"), entries)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `path` is a stylesheet that is compiled when its directory is,
+/// rather than a partial
+#[cfg(not(feature = "wasm"))]
+fn is_entry_point(path: &Path) -> bool {
+    let is_partial = path
+        .file_name()
+        .map_or(true, |name| name.to_string_lossy().starts_with('_'));
+    let is_stylesheet = path
+        .extension()
+        .map_or(false, |ext| ext == "scss" || ext == "css");
+
+    !is_partial && is_stylesheet
+}
+
+#[cfg(not(feature = "wasm"))]
+fn start_watching(
+    entries: Vec<Entry>,
+    options: &Options,
+    write_options: &WriteOptions,
+    format: Format,
+    report: &dyn Fn(&Stats),
+    matches: &ArgMatches,
+) {
+    let watched = if matches.is_present("POLL") {
+        watch::<PollWatcher>(entries, options, write_options, format, report)
+    } else {
+        watch::<RecommendedWatcher>(entries, options, write_options, format, report)
+    };
+    watched.unwrap_or_else(|e| {
+        eprintln!("Error watching for changes: {}", e);
+        std::process::exit(1)
+    });
+}
+
+/// Compiles `input`, returning the statistics about it along with the css
+#[cfg(not(feature = "wasm"))]
+fn compile_with_stats(input: Input<'_>, options: &Options) -> grass::Result<(String, Stats)> {
+    let report = compile_with_reports(input, options, ReportOptions::default().stats(true))?;
+    Ok((report.css, report.stats.unwrap_or_default()))
+}
+
+#[cfg(not(feature = "wasm"))]
+fn print_error(e: &Error, format: Format) {
+    match format {
+        Format::Human => eprintln!("{}", e),
+        Format::Json => eprintln!("{}", e.diagnostic().to_json()),
+        Format::Github => eprintln!("{}", e.diagnostic().to_github()),
+        Format::Checkstyle => eprint!("{}", checkstyle_report(&[e.diagnostic()])),
+    }
+}
+
+/// A stylesheet compiled to a file, along with the files it depends on
+#[cfg(not(feature = "wasm"))]
+struct Entry {
+    input: String,
+    output: String,
+    /// The stylesheet itself and every file it loaded the last time it was
+    /// compiled, as given by `watched_path`
+    dependencies: BTreeSet<PathBuf>,
+    failed: bool,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl Entry {
+    fn new(input: String, output: String) -> Self {
+        Entry {
+            input,
+            output,
+            dependencies: BTreeSet::new(),
+            failed: false,
+        }
+    }
+
+    /// Compiles the stylesheet and writes it to its output file, printing
+    /// any error rather than returning it
+    fn compile(
+        &mut self,
+        options: &Options,
+        write_options: &WriteOptions,
+        format: Format,
+    ) -> Option<Stats> {
+        let (css, stats) = match compile_with_stats(Input::Path(&self.input), options) {
+            Ok(compiled) => compiled,
+            Err(e) => {
+                print_error(&e, format);
+                self.failed = true;
+                return None;
+            }
+        };
+
+        self.dependencies = iter::once(Path::new(&self.input))
+            .chain(stats.loaded_paths.iter().map(PathBuf::as_path))
+            .map(watched_path)
+            .collect();
+
+        match write_css(Path::new(&self.output), &css, write_options) {
+            Ok(()) => {
+                self.failed = false;
+                Some(stats)
+            }
+            Err(e) => {
+                self.failed = true;
+                eprintln!("Error writing {}: {}", self.output, e);
+                None
+            }
+        }
+    }
+
+    /// Compiles the stylesheet as `--watch` does, noting when it was
+    /// successfully written
+    fn recompile(
+        &mut self,
+        options: &Options,
+        write_options: &WriteOptions,
+        format: Format,
+        report: &dyn Fn(&Stats),
+    ) {
+        if let Some(stats) = self.compile(options, write_options, format) {
+            report(&stats);
+            eprintln!("Compiled {} to {}.", self.input, self.output);
+        }
+    }
+
+    /// Whether the stylesheet must be recompiled because `path` changed
+    ///
+    /// A stylesheet that failed to compile may not have loaded all of its
+    /// dependencies, so it is recompiled when any stylesheet other than an
+    /// output file changes.
+    fn is_affected_by(&self, path: &Path, outputs: &BTreeSet<PathBuf>) -> bool {
+        let is_stylesheet = path
+            .extension()
+            .map_or(false, |ext| ext == "scss" || ext == "css");
+
+        self.dependencies.contains(path)
+            || (self.failed && is_stylesheet && !outputs.contains(path))
+    }
+}
+
+/// The absolute path that a watcher reports changes to `path` at, even once
+/// the file has been removed
+#[cfg(not(feature = "wasm"))]
+fn watched_path(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if dir != Path::new("") => dir,
+        _ => Path::new("."),
+    };
+    match (dir.canonicalize(), path.file_name()) {
+        (Ok(dir), Some(name)) => dir.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Compiles each entry, then recompiles the entries affected by each change
+/// to their dependencies until the process is killed
+///
+/// The directories containing the dependencies are watched, rather than the
+/// files themselves, so that files which editors replace when saving them
+/// are still tracked.
+#[cfg(not(feature = "wasm"))]
+fn watch<W: Watcher>(
+    mut entries: Vec<Entry>,
+    options: &Options,
+    write_options: &WriteOptions,
+    format: Format,
+    report: &dyn Fn(&Stats),
+) -> notify::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = W::new(tx, Duration::from_millis(100))?;
+    let mut watched_dirs = BTreeSet::new();
+
+    let outputs: BTreeSet<PathBuf> = entries
+        .iter()
+        .map(|entry| watched_path(Path::new(&entry.output)))
+        .collect();
+
+    for entry in &mut entries {
+        entry.recompile(options, write_options, format, report);
+    }
+    watch_dependencies(&mut watcher, &entries, &mut watched_dirs)?;
+
+    eprintln!("Sass is watching for changes. Press Ctrl-C to stop.\n");
+
+    for event in rx {
+        let changed = match event {
+            DebouncedEvent::Create(path)
+            | DebouncedEvent::Write(path)
+            | DebouncedEvent::Remove(path) => vec![path],
+            DebouncedEvent::Rename(from, to) => vec![from, to],
+            DebouncedEvent::Error(e, _) => {
+                eprintln!("Error watching for changes: {}", e);
+                continue;
+            }
+            _ => continue,
+        };
+
+        for entry in &mut entries {
+            if changed
+                .iter()
+                .any(|path| entry.is_affected_by(&watched_path(path), &outputs))
+            {
+                entry.recompile(options, write_options, format, report);
+            }
+        }
+        watch_dependencies(&mut watcher, &entries, &mut watched_dirs)?;
+    }
+
+    Ok(())
+}
+
+/// Starts watching the directories of any dependencies that were loaded for
+/// the first time
+#[cfg(not(feature = "wasm"))]
+fn watch_dependencies<W: Watcher>(
+    watcher: &mut W,
+    entries: &[Entry],
+    watched_dirs: &mut BTreeSet<PathBuf>,
+) -> notify::Result<()> {
+    for dependency in entries.iter().flat_map(|entry| &entry.dependencies) {
+        if let Some(dir) = dependency.parent() {
+            if !watched_dirs.contains(dir) {
+                watcher.watch(dir, RecursiveMode::NonRecursive)?;
+                watched_dirs.insert(dir.to_path_buf());
+            }
+        }
+    }
+
     Ok(())
 }
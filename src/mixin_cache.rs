@@ -0,0 +1,209 @@
+//! Caching of the CSS produced by `@include`, enabled by
+//! [`Options::cache_mixins`](crate::Options::cache_mixins)
+//!
+//! An include is only cached if the mixin's output can depend on nothing
+//! but its arguments and the context it is included in. This is determined
+//! by a conservative scan of the mixin's body, which rejects any mixin that
+//!
+//! - accepts a content block, or includes another mixin
+//! - uses `@extend`, `@import`, `@use`, `@forward`, `@warn` or `@debug`
+//! - assigns a variable with `!global`
+//! - reads a variable that is neither a parameter nor declared in the body
+//! - calls a function whose result may change between includes, such as
+//!   `random()` or a user-defined function
+
+use std::collections::{HashMap, HashSet};
+
+use codemap::Span;
+
+use crate::{common::Identifier, parse::Stmt, utils::is_name, Token};
+
+/// At-rules with effects beyond the CSS emitted by the mixin itself
+const IMPURE_AT_RULES: &[&str] = &[
+    "content", "debug", "extend", "forward", "import", "include", "use", "warn",
+];
+
+/// Builtin functions whose result depends on more than their arguments
+const IMPURE_FUNCTIONS: &[&str] = &[
+    "call",
+    "content-exists",
+    "function-exists",
+    "get-function",
+    "get-mixin",
+    "global-variable-exists",
+    "load-css",
+    "mixin-exists",
+    "random",
+    "unique-id",
+    "variable-exists",
+];
+
+/// The names a mixin body refers to that must be checked against the scope
+/// of each include before its output may be cached
+#[derive(Debug, Default)]
+pub(crate) struct MixinDependencies {
+    /// The functions called by the mixin, along with the namespace they are
+    /// called through, if any
+    pub functions: Vec<(Option<Identifier>, Identifier)>,
+
+    /// The namespaces of the module variables read by the mixin
+    pub variable_namespaces: Vec<Identifier>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MixinCache {
+    /// The dependencies of each mixin body, keyed by the position of the
+    /// body, or `None` if the body can never be cached
+    dependencies: HashMap<Span, Option<MixinDependencies>>,
+
+    /// The statements produced by each include
+    outputs: HashMap<String, Vec<Stmt>>,
+}
+
+impl MixinCache {
+    /// Returns the dependencies of the mixin with the given body and
+    /// parameters, or `None` if its output can never be cached
+    pub fn dependencies(
+        &mut self,
+        body: &[Token],
+        params: &[Identifier],
+    ) -> Option<&MixinDependencies> {
+        let span = body.first()?.pos;
+        self.dependencies
+            .entry(span)
+            .or_insert_with(|| scan_body(body, params))
+            .as_ref()
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<Stmt>> {
+        self.outputs.get(key).cloned()
+    }
+
+    pub fn insert(&mut self, key: String, stmts: Vec<Stmt>) {
+        self.outputs.insert(key, stmts);
+    }
+}
+
+/// Scans the body of a mixin for anything that would make its output
+/// depend on more than its arguments
+fn scan_body(body: &[Token], params: &[Identifier]) -> Option<MixinDependencies> {
+    let chars: Vec<char> = body.iter().map(|tok| tok.kind).collect();
+    let mut dependencies = MixinDependencies::default();
+    let mut declared: HashSet<Identifier> = params.iter().copied().collect();
+
+    // whether only whitespace has been seen since the start of the statement
+    let mut at_statement_start = true;
+    // whether we are between `@each` or `@for` and `in` or `from`, where
+    // variables are declared rather than read
+    let mut in_loop_header = false;
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        match chars[idx] {
+            '@' => {
+                let name = read_name(&chars, idx + 1);
+                idx += 1 + name.chars().count();
+                let name = name.to_ascii_lowercase();
+                if IMPURE_AT_RULES.contains(&name.as_str()) {
+                    return None;
+                }
+                in_loop_header = name == "each" || name == "for";
+                at_statement_start = false;
+            }
+            '!' => {
+                let name = read_name(&chars, idx + 1);
+                if name.eq_ignore_ascii_case("global") {
+                    return None;
+                }
+                idx += 1 + name.chars().count();
+                at_statement_start = false;
+            }
+            '$' => {
+                let name = read_name(&chars, idx + 1);
+                let end = idx + 1 + name.chars().count();
+
+                if let Some(namespace) = namespace_before(&chars, idx) {
+                    dependencies
+                        .variable_namespaces
+                        .push(Identifier::from(namespace));
+                } else {
+                    let name = Identifier::from(name);
+                    let is_declaration = in_loop_header
+                        || (at_statement_start && next_non_whitespace(&chars, end) == Some(':'));
+                    if is_declaration {
+                        declared.insert(name);
+                    } else if !declared.contains(&name) {
+                        return None;
+                    }
+                }
+
+                idx = end;
+                at_statement_start = false;
+            }
+            '{' | '}' | ';' => {
+                idx += 1;
+                at_statement_start = true;
+                in_loop_header = false;
+            }
+            c if c.is_whitespace() => idx += 1,
+            c if is_name(c) && !c.is_ascii_digit() => {
+                let name = read_name(&chars, idx);
+                let end = idx + name.chars().count();
+
+                if in_loop_header && (name == "in" || name == "from") {
+                    in_loop_header = false;
+                } else if chars.get(end) == Some(&'(') {
+                    let name = Identifier::from(name);
+                    if IMPURE_FUNCTIONS.contains(&name.as_str()) {
+                        return None;
+                    }
+                    let namespace = namespace_before(&chars, idx).map(Identifier::from);
+                    dependencies.functions.push((namespace, name));
+                }
+
+                idx = end;
+                at_statement_start = false;
+            }
+            _ => {
+                idx += 1;
+                at_statement_start = false;
+            }
+        }
+    }
+
+    Some(dependencies)
+}
+
+fn read_name(chars: &[char], start: usize) -> String {
+    chars
+        .iter()
+        .skip(start)
+        .take_while(|c| is_name(**c))
+        .collect()
+}
+
+/// Returns the namespace preceding the name at `idx`, as in `math.div`
+fn namespace_before(chars: &[char], idx: usize) -> Option<String> {
+    if idx == 0 || chars[idx - 1] != '.' {
+        return None;
+    }
+
+    let start = chars[..idx - 1]
+        .iter()
+        .rposition(|c| !is_name(*c))
+        .map_or(0, |pos| pos + 1);
+
+    if start == idx - 1 {
+        return None;
+    }
+
+    Some(chars[start..idx - 1].iter().collect())
+}
+
+fn next_non_whitespace(chars: &[char], start: usize) -> Option<char> {
+    chars
+        .iter()
+        .skip(start)
+        .copied()
+        .find(|c| !c.is_whitespace())
+}
@@ -0,0 +1,249 @@
+//! Sass numbers, for tools that process values alongside grass
+//!
+//! See [`Number`](crate::Number)
+
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use num_traits::{ToPrimitive, Zero};
+
+use crate::{
+    utils::ParsedNumber,
+    value::{self, set_precision},
+};
+
+/// A unitless number, stored and written exactly as grass stores and writes
+/// the numbers in a stylesheet
+///
+/// Numbers are stored exactly, as fractions, so arithmetic doesn't
+/// accumulate floating point error, and they are written the way
+/// `dart-sass` writes them, rounded to 10 digits after the decimal point.
+///
+/// For example, to scale a spacing system the way a stylesheet would:
+///
+/// ```
+/// use grass::Number;
+///
+/// let base: Number = "1.5".parse().unwrap();
+/// let scaled = base * Number::from(2) - "0.1".parse().unwrap();
+///
+/// assert_eq!("2.9", scaled.to_string());
+///
+/// let third = Number::from(1).checked_div(&Number::from(3)).unwrap();
+/// assert_eq!("0.3333333333", third.to_string());
+/// assert!(!third.is_int());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Number(value::Number);
+
+impl Number {
+    /// Whether the number has no fractional part
+    #[inline]
+    pub fn is_int(&self) -> bool {
+        !self.0.is_decimal()
+    }
+
+    /// The number as an `i64`, or `None` if it has a fractional part or is
+    /// out of range
+    #[inline]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    /// The nearest `f64` to the number, or infinity if it is out of range
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.0.clone().as_float().unwrap_or_else(|| {
+            if self.0 < value::Number::zero() {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }
+        })
+    }
+
+    /// Divides the number by `other`, or returns `None` if `other` is zero
+    #[inline]
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.0.is_zero() {
+            return None;
+        }
+
+        Some(Self(self.0.clone() / other.0.clone()))
+    }
+
+    /// Writes the number with `precision` digits after the decimal point,
+    /// as configured by [`Options::precision`](crate::Options::precision)
+    #[inline]
+    pub fn to_string_with_precision(&self, precision: usize) -> String {
+        let _precision = set_precision(precision);
+        self.0.to_string()
+    }
+}
+
+impl fmt::Display for Number {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_with_precision(value::DEFAULT_PRECISION))
+    }
+}
+
+impl From<i64> for Number {
+    #[inline]
+    fn from(n: i64) -> Self {
+        Self(value::Number::from(n))
+    }
+}
+
+impl From<f64> for Number {
+    /// Converts `n` exactly, so `0.1` becomes the fraction nearest to it
+    /// that an `f64` can represent
+    ///
+    /// # Panics
+    ///
+    /// If `n` is infinite or NaN
+    #[inline]
+    fn from(n: f64) -> Self {
+        Self(value::Number::from(n))
+    }
+}
+
+impl Add for Number {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for Number {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl Mul for Number {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+impl Neg for Number {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl PartialEq<i64> for Number {
+    #[inline]
+    fn eq(&self, other: &i64) -> bool {
+        self.to_i64() == Some(*other)
+    }
+}
+
+impl PartialOrd<i64> for Number {
+    #[inline]
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        Some(self.0.cmp(&value::Number::from(*other)))
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    /// Parses a number written the way a stylesheet would write it, such
+    /// as `12`, `-.5`, or `1.5e-3`, without a unit
+    fn from_str(s: &str) -> Result<Self, ParseNumberError> {
+        let (negative, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (mantissa, exponent) = match s.find(|c| c == 'e' || c == 'E') {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        let (whole, dec) = match mantissa.find('.') {
+            Some(idx) => (&mantissa[..idx], Some(&mantissa[idx + 1..])),
+            None => (mantissa, None),
+        };
+
+        if !is_digits(whole) || (whole.is_empty() && dec.is_none()) {
+            return Err(ParseNumberError("Expected digit."));
+        }
+
+        let mut num = whole.to_owned();
+        let mut dec_len = 0;
+        if let Some(dec) = dec {
+            if dec.is_empty() || !is_digits(dec) {
+                return Err(ParseNumberError("Expected digit."));
+            }
+            num.push_str(dec);
+            dec_len = dec.len();
+        }
+
+        let mut times_ten = String::new();
+        let mut times_ten_is_postive = true;
+        if let Some(exponent) = exponent {
+            let digits = match exponent.strip_prefix('-') {
+                Some(digits) => {
+                    times_ten_is_postive = false;
+                    digits
+                }
+                None => exponent,
+            };
+
+            if digits.is_empty() || !is_digits(digits) {
+                return Err(ParseNumberError("Expected digit."));
+            } else if digits.len() > 2 {
+                return Err(ParseNumberError(if times_ten_is_postive {
+                    "Exponent too large."
+                } else {
+                    "Exponent too negative."
+                }));
+            }
+
+            times_ten.push_str(digits);
+        }
+
+        let n = ParsedNumber::new(num, dec_len, times_ten, times_ten_is_postive)
+            .into_number()
+            .ok_or(ParseNumberError("Exponent too large."))?;
+
+        Ok(Self(if negative { -n } else { n }))
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The error returned when a string isn't a valid Sass number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNumberError(&'static str);
+
+impl fmt::Display for ParseNumberError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Error for ParseNumberError {}
@@ -1,10 +1,11 @@
 //! # Convert from SCSS AST to CSS
 use std::{io::Write, mem};
 
-use codemap::CodeMap;
+use codemap::{CodeMap, Span};
 
 use crate::{
     atrule::{
+        hoist_at_root_rules,
         keyframes::{Keyframes, KeyframesRuleSet, KeyframesSelector},
         media::MediaRule,
         SupportsRule, UnknownAtRule,
@@ -13,24 +14,40 @@ use crate::{
     parse::Stmt,
     selector::Selector,
     style::Style,
+    OutputStyle,
 };
 
 #[derive(Debug, Clone)]
-struct ToplevelUnknownAtRule {
+struct CssUnknownAtRule {
     name: String,
     params: String,
-    body: Vec<Stmt>,
+    body: Vec<CssStmt>,
+    has_body: bool,
 }
 
+/// A node of the CSS tree that evaluated statements are converted to before
+/// being written, where nested rules have bubbled up out of style rules
 #[derive(Debug, Clone)]
-enum Toplevel {
-    RuleSet(Selector, Vec<BlockEntry>),
+enum CssStmt {
+    /// A style rule, and where it was written
+    RuleSet(Selector, Vec<BlockEntry>, Span),
     MultilineComment(String),
-    UnknownAtRule(Box<ToplevelUnknownAtRule>),
-    Keyframes(Box<Keyframes>),
+    UnknownAtRule(Box<CssUnknownAtRule>),
+    Keyframes {
+        rule: String,
+        name: String,
+        body: Vec<CssStmt>,
+    },
     KeyframesRuleSet(Vec<KeyframesSelector>, Vec<BlockEntry>),
-    Media { query: String, body: Vec<Stmt> },
-    Supports { params: String, body: Vec<Stmt> },
+    Media {
+        query: String,
+        body: Vec<CssStmt>,
+        is_merged: bool,
+    },
+    Supports {
+        params: String,
+        body: Vec<CssStmt>,
+    },
     Newline,
     // todo: do we actually need a toplevel style variant?
     Style(Style),
@@ -41,31 +58,56 @@ enum Toplevel {
 enum BlockEntry {
     Style(Style),
     MultilineComment(String),
+    /// An at-rule without a body, such as `@foo bar;`, which is emitted in
+    /// place rather than being hoisted out of its style rule
+    UnknownAtRule {
+        name: String,
+        params: String,
+    },
 }
 
 impl BlockEntry {
-    pub fn to_string(&self) -> SassResult<String> {
+    pub fn to_string(&self, indentation: &str) -> SassResult<String> {
         match self {
-            BlockEntry::Style(s) => s.to_string(),
+            BlockEntry::Style(s) => s.to_string(indentation),
             BlockEntry::MultilineComment(s) => Ok(format!("/*{}*/", s)),
+            BlockEntry::UnknownAtRule { name, params } => Ok(if params.is_empty() {
+                format!("@{};", name)
+            } else {
+                format!("@{} {};", name, params)
+            }),
         }
     }
 }
 
-impl Toplevel {
-    const fn new_rule(selector: Selector) -> Self {
-        Toplevel::RuleSet(selector, Vec::new())
+impl CssStmt {
+    const fn new_rule(selector: Selector, span: Span) -> Self {
+        CssStmt::RuleSet(selector, Vec::new(), span)
     }
 
     fn new_keyframes_rule(selector: Vec<KeyframesSelector>) -> Self {
-        Toplevel::KeyframesRuleSet(selector, Vec::new())
+        CssStmt::KeyframesRuleSet(selector, Vec::new())
+    }
+
+    /// Whether this block would not produce any output
+    fn is_invisible(&self) -> bool {
+        match self {
+            CssStmt::Newline => true,
+            CssStmt::RuleSet(_, body, _) | CssStmt::KeyframesRuleSet(_, body) => body.is_empty(),
+            CssStmt::Media { body, .. } | CssStmt::Supports { body, .. } => {
+                body.iter().all(CssStmt::is_invisible)
+            }
+            _ => false,
+        }
     }
 
     fn push_style(&mut self, s: Style) {
-        if s.value.is_null() {
+        // an empty custom property is still meaningful, as `--a: ;` guarantees
+        // that `var(--a)` is valid
+        if s.value.is_null() && !s.parsed_as_custom_property {
             return;
         }
-        if let Toplevel::RuleSet(_, entries) | Toplevel::KeyframesRuleSet(_, entries) = self {
+        if let CssStmt::RuleSet(_, entries, _) | CssStmt::KeyframesRuleSet(_, entries) = self {
             entries.push(BlockEntry::Style(s));
         } else {
             panic!()
@@ -73,28 +115,45 @@ impl Toplevel {
     }
 
     fn push_comment(&mut self, s: String) {
-        if let Toplevel::RuleSet(_, entries) | Toplevel::KeyframesRuleSet(_, entries) = self {
+        if let CssStmt::RuleSet(_, entries, _) | CssStmt::KeyframesRuleSet(_, entries) = self {
             entries.push(BlockEntry::MultilineComment(s));
         } else {
             panic!()
         }
     }
+
+    fn push_unknown_at_rule(&mut self, name: String, params: String) {
+        if let CssStmt::RuleSet(_, entries, _) = self {
+            entries.push(BlockEntry::UnknownAtRule { name, params });
+        } else {
+            panic!()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct Css {
-    blocks: Vec<Toplevel>,
+    blocks: Vec<CssStmt>,
     in_at_rule: bool,
     allows_charset: bool,
-    plain_imports: Vec<Toplevel>,
+    selector_line_breaks: bool,
+    line_comments: bool,
+    plain_imports: Vec<CssStmt>,
 }
 
 impl Css {
-    pub const fn new(in_at_rule: bool, allows_charset: bool) -> Self {
+    pub const fn new(
+        in_at_rule: bool,
+        allows_charset: bool,
+        selector_line_breaks: bool,
+        line_comments: bool,
+    ) -> Self {
         Css {
             blocks: Vec::new(),
             in_at_rule,
             allows_charset,
+            selector_line_breaks,
+            line_comments,
             plain_imports: Vec::new(),
         }
     }
@@ -103,100 +162,152 @@ impl Css {
         s: Vec<Stmt>,
         in_at_rule: bool,
         allows_charset: bool,
+        selector_line_breaks: bool,
+        line_comments: bool,
     ) -> SassResult<Self> {
-        Css::new(in_at_rule, allows_charset).parse_stylesheet(s)
+        Css::new(
+            in_at_rule,
+            allows_charset,
+            selector_line_breaks,
+            line_comments,
+        )
+        .parse_stylesheet(hoist_at_root_rules(s))
     }
 
-    fn parse_stmt(&mut self, stmt: Stmt) -> SassResult<Vec<Toplevel>> {
+    /// Creates an empty stylesheet nested within this one, with the same
+    /// output options
+    const fn nested(&self, in_at_rule: bool) -> Self {
+        Css::new(
+            in_at_rule,
+            self.allows_charset,
+            self.selector_line_breaks,
+            self.line_comments,
+        )
+    }
+
+    /// Converts the body of an at-rule, which is written as a stylesheet
+    /// nested within this one
+    fn body(&self, stmts: Vec<Stmt>) -> SassResult<Vec<CssStmt>> {
+        Ok(self.nested(true).parse_stylesheet(stmts)?.blocks)
+    }
+
+    /// The stylesheet nested within this one that writes the converted body
+    /// of an at-rule
+    fn nested_body(&self, blocks: Vec<CssStmt>) -> Self {
+        Css {
+            blocks,
+            ..self.nested(true)
+        }
+    }
+
+    fn parse_stmt(&mut self, stmt: Stmt) -> SassResult<Vec<CssStmt>> {
         Ok(match stmt {
-            Stmt::RuleSet { selector, body } => {
+            Stmt::RuleSet {
+                selector,
+                body,
+                span,
+            } => {
                 if body.is_empty() {
                     return Ok(Vec::new());
                 }
-                let selector = selector.into_selector().remove_placeholders();
-                if selector.is_empty() {
-                    return Ok(Vec::new());
+                let mut selector = selector.into_selector().remove_placeholders();
+                if !self.selector_line_breaks {
+                    selector.remove_line_breaks();
                 }
-                let mut vals = vec![Toplevel::new_rule(selector)];
+                // a rule whose selector is made up entirely of placeholders is
+                // not emitted, but the rules nested within it may still be
+                let is_invisible = selector.is_empty();
+                let mut vals = vec![CssStmt::new_rule(selector, span)];
                 for rule in body {
                     match rule {
                         Stmt::RuleSet { .. } => vals.extend(self.parse_stmt(rule)?),
                         Stmt::Style(s) => vals.first_mut().unwrap().push_style(s),
                         Stmt::Comment(s) => vals.first_mut().unwrap().push_comment(s),
-                        Stmt::Media(m) => {
-                            let MediaRule { query, body, .. } = *m;
-                            vals.push(Toplevel::Media { query, body })
+                        // at-rules with bodies bubble up out of the style rule
+                        Stmt::Media(..) | Stmt::Supports(..) | Stmt::Keyframes(..) => {
+                            vals.extend(self.parse_stmt(rule)?)
                         }
-                        Stmt::Supports(s) => {
-                            let SupportsRule { params, body } = *s;
-                            vals.push(Toplevel::Supports { params, body })
+                        Stmt::UnknownAtRule(ref u) if u.has_body => {
+                            vals.extend(self.parse_stmt(rule)?)
                         }
                         Stmt::UnknownAtRule(u) => {
-                            let UnknownAtRule {
-                                params, body, name, ..
-                            } = *u;
-                            vals.push(Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
-                                params,
-                                body,
-                                name,
-                            })))
+                            let UnknownAtRule { params, name, .. } = *u;
+                            vals.first_mut().unwrap().push_unknown_at_rule(name, params)
                         }
                         Stmt::Return(..) => unreachable!(),
-                        Stmt::AtRoot { body } => {
-                            body.into_iter().try_for_each(|r| -> SassResult<()> {
-                                vals.append(&mut self.parse_stmt(r)?);
-                                Ok(())
-                            })?
-                        }
-                        Stmt::Keyframes(k) => {
-                            let Keyframes { rule, name, body } = *k;
-                            vals.push(Toplevel::Keyframes(Box::new(Keyframes {
-                                rule,
-                                name,
-                                body,
-                            })))
-                        }
+                        Stmt::AtRoot { .. } => unreachable!("@at-root: {:?}", rule),
                         k @ Stmt::KeyframesRuleSet(..) => {
                             unreachable!("@keyframes ruleset {:?}", k)
                         }
-                        Stmt::Import(s) => self.plain_imports.push(Toplevel::Import(s)),
+                        Stmt::Import(s) => self.plain_imports.push(CssStmt::Import(s)),
                     };
                 }
+                if is_invisible {
+                    vals.remove(0);
+                }
                 vals
             }
-            Stmt::Comment(s) => vec![Toplevel::MultilineComment(s)],
+            Stmt::Comment(s) => vec![CssStmt::MultilineComment(s)],
             Stmt::Import(s) => {
-                self.plain_imports.push(Toplevel::Import(s));
+                self.plain_imports.push(CssStmt::Import(s));
                 Vec::new()
             }
-            Stmt::Style(s) => vec![Toplevel::Style(s)],
+            Stmt::Style(s) => vec![CssStmt::Style(s)],
             Stmt::Media(m) => {
-                let MediaRule { query, body, .. } = *m;
-                vec![Toplevel::Media { query, body }]
+                let MediaRule {
+                    query,
+                    body,
+                    is_merged,
+                    ..
+                } = *m;
+                vec![CssStmt::Media {
+                    query: query
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    body: self.body(body)?,
+                    is_merged,
+                }]
             }
             Stmt::Supports(s) => {
                 let SupportsRule { params, body } = *s;
-                vec![Toplevel::Supports { params, body }]
+                vec![CssStmt::Supports {
+                    params,
+                    body: self.body(body)?,
+                }]
             }
             Stmt::UnknownAtRule(u) => {
                 let UnknownAtRule {
-                    params, body, name, ..
+                    params,
+                    body,
+                    name,
+                    has_body,
+                    ..
                 } = *u;
-                vec![Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
+                vec![CssStmt::UnknownAtRule(Box::new(CssUnknownAtRule {
                     params,
                     name,
-                    body,
+                    body: self.body(body)?,
+                    has_body,
                 }))]
             }
             Stmt::Return(..) => unreachable!("@return: {:?}", stmt),
             Stmt::AtRoot { .. } => unreachable!("@at-root: {:?}", stmt),
-            Stmt::Keyframes(k) => vec![Toplevel::Keyframes(k)],
+            Stmt::Keyframes(k) => {
+                let Keyframes { rule, name, body } = *k;
+                vec![CssStmt::Keyframes {
+                    rule,
+                    name,
+                    body: self.body(body)?,
+                }]
+            }
             Stmt::KeyframesRuleSet(k) => {
                 let KeyframesRuleSet { body, selector } = *k;
                 if body.is_empty() {
                     return Ok(Vec::new());
                 }
-                let mut vals = vec![Toplevel::new_keyframes_rule(selector)];
+                let mut vals = vec![CssStmt::new_keyframes_rule(selector)];
                 for rule in body {
                     match rule {
                         Stmt::Style(s) => vals.first_mut().unwrap().push_style(s),
@@ -211,38 +322,57 @@ impl Css {
 
     fn parse_stylesheet(mut self, stmts: Vec<Stmt>) -> SassResult<Css> {
         let mut is_first = true;
+        // the end of the leading run of imports and comments, where plain
+        // imports that come later in the file are moved to
+        let mut end_of_imports = 0;
+        let mut in_imports = true;
         for stmt in stmts {
             let v = self.parse_stmt(stmt)?;
+            if in_imports {
+                self.blocks.append(&mut self.plain_imports);
+                end_of_imports = self.blocks.len();
+                if v.iter().all(|s| matches!(s, CssStmt::MultilineComment(..))) {
+                    self.blocks.extend(v);
+                    end_of_imports = self.blocks.len();
+                    continue;
+                }
+                in_imports = false;
+            }
             // this is how we print newlines between unrelated styles
             // it could probably be refactored
             if !v.is_empty() {
-                if let Some(Toplevel::MultilineComment(..)) = v.first() {
+                if let Some(CssStmt::MultilineComment(..)) = v.first() {
                 } else if is_first {
                     is_first = false;
-                } else {
-                    self.blocks.push(Toplevel::Newline);
+                } else if !self.in_at_rule {
+                    self.blocks.push(CssStmt::Newline);
                 }
                 self.blocks.extend(v);
             }
         }
 
         // move plain imports to top of file
-        self.plain_imports.append(&mut self.blocks);
-        mem::swap(&mut self.plain_imports, &mut self.blocks);
+        self.blocks
+            .splice(end_of_imports..end_of_imports, self.plain_imports.drain(..));
 
         Ok(self)
     }
 
-    pub fn pretty_print(mut self, map: &CodeMap) -> SassResult<String> {
+    /// Writes the stylesheet, preceded by a `@charset` declaration, or a
+    /// byte-order mark in compressed mode, if it contains any non-ASCII
+    /// characters
+    pub fn pretty_print(mut self, map: &CodeMap, style: OutputStyle) -> SassResult<String> {
         let mut string = Vec::new();
         let allows_charset = self.allows_charset;
         self._inner_pretty_print(&mut string, map, 0)?;
-        if allows_charset && string.iter().any(|s| !s.is_ascii()) {
-            return Ok(format!("@charset \"UTF-8\";\n{}", unsafe {
-                String::from_utf8_unchecked(string)
-            }));
+        let string = unsafe { String::from_utf8_unchecked(string) };
+        if allows_charset && !string.is_ascii() {
+            return Ok(match style {
+                OutputStyle::Expanded => format!("@charset \"UTF-8\";\n{}", string),
+                OutputStyle::Compressed => format!("\u{FEFF}{}", string),
+            });
         }
-        Ok(unsafe { String::from_utf8_unchecked(string) })
+        Ok(string)
     }
 
     fn _inner_pretty_print(
@@ -252,11 +382,13 @@ impl Css {
         nesting: usize,
     ) -> SassResult<()> {
         let mut has_written = false;
-        let padding = vec![' '; nesting * 2].iter().collect::<String>();
+        let padding = " ".repeat(nesting * 2);
+        // the indentation of the declarations in a style rule
+        let style_padding = format!("{}  ", padding);
         let mut should_emit_newline = false;
         for block in mem::take(&mut self.blocks) {
             match block {
-                Toplevel::RuleSet(selector, styles) => {
+                CssStmt::RuleSet(selector, styles, span) => {
                     if styles.is_empty() {
                         continue;
                     }
@@ -265,13 +397,29 @@ impl Css {
                         should_emit_newline = false;
                         writeln!(buf)?;
                     }
+                    // selectors that follow a line break are indented to
+                    // the same level as the first
+                    if self.line_comments {
+                        let loc = map.look_up_pos(span.low());
+                        writeln!(
+                            buf,
+                            "{}/* line {}, {} */",
+                            padding,
+                            loc.position.line + 1,
+                            loc.file.name()
+                        )?;
+                    }
+                    let mut selector = selector.to_string();
+                    if selector.contains('\n') {
+                        selector = selector.replace('\n', &format!("\n{}", padding));
+                    }
                     writeln!(buf, "{}{} {{", padding, selector)?;
                     for style in styles {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        writeln!(buf, "{}{}", style_padding, style.to_string(&style_padding)?)?;
                     }
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::KeyframesRuleSet(selector, body) => {
+                CssStmt::KeyframesRuleSet(selector, body) => {
                     if body.is_empty() {
                         continue;
                     }
@@ -288,20 +436,25 @@ impl Css {
                             .join(", ")
                     )?;
                     for style in body {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        writeln!(buf, "{}{}", style_padding, style.to_string(&style_padding)?)?;
                     }
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::MultilineComment(s) => {
+                CssStmt::MultilineComment(s) => {
                     has_written = true;
                     writeln!(buf, "{}/*{}*/", padding, s)?;
                 }
-                Toplevel::Import(s) => {
+                CssStmt::Import(s) => {
                     has_written = true;
                     writeln!(buf, "{}@import {};", padding, s)?;
                 }
-                Toplevel::UnknownAtRule(u) => {
-                    let ToplevelUnknownAtRule { params, name, body } = *u;
+                CssStmt::UnknownAtRule(u) => {
+                    let CssUnknownAtRule {
+                        params,
+                        name,
+                        body,
+                        has_body,
+                    } = *u;
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
@@ -313,22 +466,22 @@ impl Css {
                         write!(buf, "{}@{} {}", padding, name, params)?;
                     }
 
-                    if body.is_empty() {
+                    if !has_body {
                         writeln!(buf, ";")?;
                         continue;
-                    } else {
-                        writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
-                        buf,
-                        map,
-                        nesting + 1,
-                    )?;
+                    if body.iter().all(CssStmt::is_invisible) {
+                        writeln!(buf, " {{}}")?;
+                        continue;
+                    }
+
+                    writeln!(buf, " {{")?;
+                    self.nested_body(body)
+                        ._inner_pretty_print(buf, map, nesting + 1)?;
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::Keyframes(k) => {
-                    let Keyframes { rule, name, body } = *k;
+                CssStmt::Keyframes { rule, name, body } => {
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
@@ -347,14 +500,15 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
-                        buf,
-                        map,
-                        nesting + 1,
-                    )?;
+                    self.nested_body(body)
+                        ._inner_pretty_print(buf, map, nesting + 1)?;
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::Supports { params, body } => {
+                CssStmt::Supports { params, body } => {
+                    if body.iter().all(CssStmt::is_invisible) {
+                        continue;
+                    }
+
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
@@ -366,37 +520,48 @@ impl Css {
                         write!(buf, "{}@supports {}", padding, params)?;
                     }
 
-                    if body.is_empty() {
-                        writeln!(buf, ";")?;
-                        continue;
-                    } else {
-                        writeln!(buf, " {{")?;
-                    }
-
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
-                        buf,
-                        map,
-                        nesting + 1,
-                    )?;
+                    writeln!(buf, " {{")?;
+                    self.nested_body(body)
+                        ._inner_pretty_print(buf, map, nesting + 1)?;
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::Media { query, body } => {
-                    if body.is_empty() {
+                CssStmt::Media { query, body, .. } => {
+                    if body.iter().all(CssStmt::is_invisible) {
                         continue;
                     }
 
-                    writeln!(buf, "{}@media {} {{", padding, query)?;
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
-                        buf,
-                        map,
-                        nesting + 1,
-                    )?;
-                    writeln!(buf, "{}}}", padding)?;
+                    let mut group = Vec::new();
+
+                    for block in body {
+                        // media rules whose queries have been merged with this rule's are
+                        // emitted after it, splitting this rule if anything follows them
+                        if let CssStmt::Media {
+                            is_merged: true, ..
+                        } = block
+                        {
+                            self.print_media_group(
+                                buf,
+                                map,
+                                nesting,
+                                &query,
+                                mem::take(&mut group),
+                            )?;
+                            Css {
+                                blocks: vec![block],
+                                ..self.nested(self.in_at_rule)
+                            }
+                            ._inner_pretty_print(buf, map, nesting)?;
+                        } else {
+                            group.push(block);
+                        }
+                    }
+
+                    self.print_media_group(buf, map, nesting, &query, group)?;
                 }
-                Toplevel::Style(s) => {
-                    writeln!(buf, "{}{}", padding, s.to_string()?)?;
+                CssStmt::Style(s) => {
+                    writeln!(buf, "{}{}", padding, s.to_string(&padding)?)?;
                 }
-                Toplevel::Newline => {
+                CssStmt::Newline => {
                     if has_written {
                         should_emit_newline = true;
                     }
@@ -406,4 +571,26 @@ impl Css {
         }
         Ok(())
     }
+
+    fn print_media_group(
+        &self,
+        buf: &mut Vec<u8>,
+        map: &CodeMap,
+        nesting: usize,
+        query: &str,
+        blocks: Vec<CssStmt>,
+    ) -> SassResult<()> {
+        if blocks.iter().all(CssStmt::is_invisible) {
+            return Ok(());
+        }
+
+        let padding = " ".repeat(nesting * 2);
+
+        writeln!(buf, "{}@media {} {{", padding, query)?;
+        self.nested_body(blocks)
+            ._inner_pretty_print(buf, map, nesting + 1)?;
+        writeln!(buf, "{}}}", padding)?;
+
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, mem};
+use std::mem;
 
 use codemap::Span;
 
@@ -6,6 +6,7 @@ use crate::{
     args::{CallArg, CallArgs, FuncArg, FuncArgs},
     common::QuoteKind,
     error::SassResult,
+    hash::HashMap,
     scope::Scope,
     utils::{
         peek_ident_no_interpolation, peek_whitespace_or_comment, read_until_closing_paren,
@@ -146,7 +147,7 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_call_args(&mut self) -> SassResult<CallArgs> {
-        let mut args = HashMap::new();
+        let mut args = HashMap::default();
         self.whitespace_or_comment();
         let mut name = String::new();
 
@@ -251,9 +252,12 @@ impl<'a> Parser<'a> {
                     let val = value?;
                     match val.node {
                         Value::ArgList(v) => {
-                            for arg in v {
+                            for arg in v.elems {
                                 args.insert(CallArg::Positional(args.len()), Ok(arg));
                             }
+                            for (name, arg) in v.keywords {
+                                args.insert(CallArg::Named(name), Ok(arg));
+                            }
                         }
                         Value::List(v, ..) => {
                             for arg in v {
@@ -289,6 +293,17 @@ impl<'a> Parser<'a> {
                             args.insert(CallArg::Positional(args.len()), Ok(val));
                         }
                     }
+
+                    self.whitespace_or_comment();
+
+                    match self.toks.peek() {
+                        Some(Token { kind: ',', .. }) => {
+                            self.toks.next();
+                        }
+                        Some(Token { kind: ')', .. }) => {}
+                        Some(Token { pos, .. }) => return Err(("expected \")\".", *pos).into()),
+                        None => return Err(("expected \")\".", span).into()),
+                    }
                 }
                 Some(Token { kind: '=', .. }) => {
                     self.toks.next();
@@ -371,22 +386,42 @@ impl<'a> Parser<'a> {
             args.max_args(0)?;
             return Ok(scope);
         }
+        let is_variadic = fn_args.0.iter().any(|arg| arg.is_variadic);
+        if !is_variadic {
+            args.max_positional_args(fn_args.len())?;
+        }
         self.scopes.enter_new_scope();
         for (idx, mut arg) in fn_args.0.into_iter().enumerate() {
             if arg.is_variadic {
-                let arg_list = Value::ArgList(args.get_variadic()?);
+                let arg_list = Value::ArgList(args.into_arg_list()?);
                 scope.insert_var(arg.name, arg_list);
-                break;
+                self.scopes.exit_scope();
+                return Ok(scope);
             }
-            let val = match args.get(idx, arg.name) {
-                Some(v) => v,
-                None => match arg.default.as_mut() {
-                    Some(v) => self.parse_value_from_vec(mem::take(v), true),
-                    None => {
-                        return Err(
-                            (format!("Missing argument ${}.", &arg.name), args.span()).into()
+            let val = match args.get_named(arg.name) {
+                Some(v) => {
+                    if args.get_positional(idx).is_some() {
+                        return Err((
+                            format!(
+                                "Argument ${} was passed both by position and by name.",
+                                arg.name
+                            ),
+                            args.span(),
                         )
+                            .into());
                     }
+                    v
+                }
+                None => match args.get_positional(idx) {
+                    Some(v) => v,
+                    None => match arg.default.as_mut() {
+                        Some(v) => self.parse_value_from_vec(mem::take(v), true),
+                        None => {
+                            return Err(
+                                (format!("Missing argument ${}.", &arg.name), args.span()).into()
+                            )
+                        }
+                    },
                 },
             }?
             .node;
@@ -394,6 +429,7 @@ impl<'a> Parser<'a> {
             scope.insert_var(arg.name, val);
         }
         self.scopes.exit_scope();
+        args.no_unknown_named_args()?;
         Ok(scope)
     }
 }
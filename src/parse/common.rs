@@ -2,7 +2,7 @@ use std::ops::{BitAnd, BitOr};
 
 use codemap::Spanned;
 
-use crate::{common::Identifier, interner::InternedString, value::Value};
+use crate::{common::Identifier, interner::InternedString, syntax::Syntax, value::Value};
 
 #[derive(Debug, Clone)]
 pub(crate) struct NeverEmptyVec<T> {
@@ -33,6 +33,10 @@ impl<T> NeverEmptyVec<T> {
     pub fn is_empty(&self) -> bool {
         self.rest.is_empty()
     }
+
+    pub fn len(&self) -> usize {
+        self.rest.len() + 1
+    }
 }
 
 /// A toplevel element beginning with something other than
@@ -42,6 +46,8 @@ impl<T> NeverEmptyVec<T> {
 pub(super) enum SelectorOrStyle {
     Selector(String),
     Style(InternedString, Option<Box<Spanned<Value>>>),
+    /// A declaration like `--color: red`, whose value is kept verbatim
+    CustomProperty(InternedString, Box<Spanned<Value>>),
     ModuleVariableRedeclaration(Identifier),
 }
 
@@ -56,6 +62,7 @@ impl ContextFlags {
     pub const IN_CONTROL_FLOW: ContextFlag = ContextFlag(1 << 2);
     pub const IN_KEYFRAMES: ContextFlag = ContextFlag(1 << 3);
     pub const IN_AT_ROOT_RULE: ContextFlag = ContextFlag(1 << 4);
+    pub const IN_PLAIN_CSS: ContextFlag = ContextFlag(1 << 5);
 
     pub const fn empty() -> Self {
         Self(0)
@@ -80,6 +87,19 @@ impl ContextFlags {
     pub fn in_at_root_rule(self) -> bool {
         (self.0 & Self::IN_AT_ROOT_RULE) != 0
     }
+
+    pub fn in_plain_css(self) -> bool {
+        (self.0 & Self::IN_PLAIN_CSS) != 0
+    }
+
+    /// These flags, along with `IN_PLAIN_CSS` if a file in `syntax` is
+    /// being parsed
+    pub fn for_syntax(self, syntax: Syntax) -> Self {
+        match syntax {
+            Syntax::Css => self | Self::IN_PLAIN_CSS,
+            Syntax::Scss | Syntax::Indented => self,
+        }
+    }
 }
 
 impl BitAnd<ContextFlag> for u8 {
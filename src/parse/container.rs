@@ -0,0 +1,121 @@
+use crate::{
+    atrule::UnknownAtRule, error::SassResult, selector::Selector,
+    utils::peek_ident_no_interpolation, Token,
+};
+
+use super::{Parser, Stmt};
+
+impl<'a> Parser<'a> {
+    pub(super) fn parse_container(&mut self) -> SassResult<Stmt> {
+        if self.flags.in_function() {
+            return Err(("This at-rule is not allowed here.", self.span_before).into());
+        }
+
+        self.whitespace_or_comment();
+
+        let params = if matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+            || self.looking_at_container_query()
+        {
+            self.parse_container_condition()?
+        } else {
+            // the name of the container, or an interpolated condition if
+            // nothing follows it
+            let mut params = self.parse_identifier()?.node;
+            self.whitespace_or_comment();
+            if !matches!(self.toks.peek(), Some(Token { kind: '{', .. })) {
+                params.push(' ');
+                params.push_str(&self.parse_container_condition()?);
+            }
+            params
+        };
+
+        self.whitespace();
+        self.expect_char('{')?;
+
+        let body = self.parse_block()?;
+        let body = self.wrap_in_super_selector(body)?;
+
+        Ok(Stmt::UnknownAtRule(Box::new(UnknownAtRule {
+            name: "container".to_owned(),
+            super_selector: Selector::new(self.span_before),
+            params,
+            body,
+            has_body: true,
+        })))
+    }
+
+    /// Whether the next tokens start a container query rather than the name
+    /// of a container, i.e. are `not` or a function such as `style(`, without
+    /// consuming them
+    fn looking_at_container_query(&mut self) -> bool {
+        let is_query = match peek_ident_no_interpolation(self.toks, false, self.span_before) {
+            Ok(ident) => {
+                ident.node.eq_ignore_ascii_case("not")
+                    || matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+            }
+            Err(..) => false,
+        };
+        self.toks.reset_cursor();
+        is_query
+    }
+
+    /// Parses container queries joined by either `and` or `or`, each of which
+    /// may be negated with `not`, evaluating any SassScript they contain
+    fn parse_container_condition(&mut self) -> SassResult<String> {
+        let mut buf = String::new();
+        let mut operator = None;
+
+        loop {
+            self.whitespace_or_comment();
+            if self.scan_identifier("not")? {
+                self.whitespace_or_comment();
+                buf.push_str("not ");
+            }
+            buf.push_str(&self.parse_container_query_in_parens()?);
+            self.whitespace_or_comment();
+
+            let next = match operator {
+                Some(op) => op,
+                None if self.scan_identifier("and")? => "and",
+                None if self.scan_identifier("or")? => "or",
+                None => break,
+            };
+
+            if operator.is_some() && !self.scan_identifier(next)? {
+                break;
+            }
+
+            operator = Some(next);
+            buf.push(' ');
+            buf.push_str(next);
+            buf.push(' ');
+        }
+
+        Ok(buf)
+    }
+
+    /// Parses a single container query, such as `(min-width: 400px)`,
+    /// `((a) or (b))`, or `style(color: red)`
+    fn parse_container_query_in_parens(&mut self) -> SassResult<String> {
+        match self.toks.peek() {
+            Some(Token { kind: '(', .. }) => {
+                let is_nested = matches!(self.toks.peek_forward(1), Some(Token { kind: '(', .. }));
+                self.toks.reset_cursor();
+
+                if !is_nested {
+                    return self.parse_media_feature();
+                }
+
+                self.toks.next();
+                let condition = self.parse_container_condition()?;
+                self.expect_char(')')?;
+                Ok(format!("({})", condition))
+            }
+            Some(Token { kind: '#', .. }) => self.parse_media_feature(),
+            _ => {
+                let function = self.parse_identifier()?;
+                Ok(format!("{}{}", function.node, self.parse_media_feature()?))
+            }
+        }
+    }
+}
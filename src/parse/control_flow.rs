@@ -1,9 +1,10 @@
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 use num_traits::cast::ToPrimitive;
 use peekmore::PeekMore;
 
 use crate::{
     common::Identifier,
+    deprecation::Deprecation,
     error::SassResult,
     parse::{ContextFlags, Parser, Stmt},
     unit::Unit,
@@ -11,7 +12,7 @@ use crate::{
         peek_ident_no_interpolation, read_until_closing_curly_brace, read_until_open_curly_brace,
     },
     value::{Number, Value},
-    Token,
+    Cow, Token,
 };
 
 impl<'a> Parser<'a> {
@@ -51,8 +52,14 @@ impl<'a> Parser<'a> {
                 options: self.options,
                 modules: self.modules,
                 module_config: self.module_config,
+                media_queries: self.media_queries,
+                stats: self.stats,
+                mixin_cache: self.mixin_cache,
+                random: self.random,
+                import_stack: self.import_stack,
+                limits: self.limits,
             }
-            .parse_stmt()?;
+            .parse_block()?;
             self.scopes.exit_scope();
         } else {
             self.throw_away_until_closing_curly_brace()?;
@@ -60,99 +67,115 @@ impl<'a> Parser<'a> {
 
         loop {
             self.whitespace_or_comment();
-            if let Some(Token { kind: '@', pos }) = self.toks.peek().cloned() {
+            let is_elseif = if let Some(Token { kind: '@', pos }) = self.toks.peek().cloned() {
                 self.toks.peek_forward(1);
                 let ident = peek_ident_no_interpolation(self.toks, false, pos)?;
-                if ident.as_str() != "else" {
-                    self.toks.reset_cursor();
-                    break;
-                }
+                let is_elseif = match ident.node.as_str() {
+                    "else" => false,
+                    "elseif" => {
+                        self.warn_deprecation(
+                            Deprecation::Elseif,
+                            &Spanned {
+                                node: Cow::const_str("@elseif is deprecated and will not be supported in future Sass versions.\n\nRecommendation: @else if"),
+                                span: pos.merge(ident.span),
+                            },
+                        )?;
+                        true
+                    }
+                    _ => {
+                        self.toks.reset_cursor();
+                        break;
+                    }
+                };
                 self.toks.truncate_iterator_to_cursor();
+                is_elseif
             } else {
                 break;
-            }
+            };
             self.whitespace_or_comment();
-            if let Some(tok) = self.toks.peek().cloned() {
-                match tok.kind {
-                    'i' if matches!(
-                        self.toks.peek_forward(1),
-                        Some(Token { kind: 'f', .. }) | Some(Token { kind: 'F', .. })
-                    ) =>
-                    {
-                        self.toks.next();
-                        self.toks.next();
-                        let cond = if found_true {
-                            self.throw_away_until_open_curly_brace()?;
-                            false
-                        } else {
-                            let v = self.parse_value(true, &|_| false)?.node.is_true();
-                            self.expect_char('{')?;
-                            v
-                        };
-                        if cond {
-                            found_true = true;
-                            self.scopes.enter_new_scope();
-                            body = Parser {
-                                toks: self.toks,
-                                map: self.map,
-                                path: self.path,
-                                scopes: self.scopes,
-                                global_scope: self.global_scope,
-                                super_selectors: self.super_selectors,
-                                span_before: self.span_before,
-                                content: self.content,
-                                flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                                at_root: self.at_root,
-                                at_root_has_selector: self.at_root_has_selector,
-                                extender: self.extender,
-                                content_scopes: self.content_scopes,
-                                options: self.options,
-                                modules: self.modules,
-                                module_config: self.module_config,
-                            }
-                            .parse_stmt()?;
-                            self.scopes.exit_scope();
-                        } else {
-                            self.throw_away_until_closing_curly_brace()?;
-                        }
-                        self.whitespace();
+            if is_elseif || self.scan_if_keyword()? {
+                let cond = if found_true {
+                    self.throw_away_until_open_curly_brace()?;
+                    false
+                } else {
+                    let v = self.parse_value(true, &|_| false)?.node.is_true();
+                    self.expect_char('{')?;
+                    v
+                };
+                if cond {
+                    found_true = true;
+                    self.scopes.enter_new_scope();
+                    body = Parser {
+                        toks: self.toks,
+                        map: self.map,
+                        path: self.path,
+                        scopes: self.scopes,
+                        global_scope: self.global_scope,
+                        super_selectors: self.super_selectors,
+                        span_before: self.span_before,
+                        content: self.content,
+                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
+                        at_root: self.at_root,
+                        at_root_has_selector: self.at_root_has_selector,
+                        extender: self.extender,
+                        content_scopes: self.content_scopes,
+                        options: self.options,
+                        modules: self.modules,
+                        module_config: self.module_config,
+                        media_queries: self.media_queries,
+                        stats: self.stats,
+                        mixin_cache: self.mixin_cache,
+                        random: self.random,
+                        import_stack: self.import_stack,
+                        limits: self.limits,
                     }
-                    '{' => {
-                        self.toks.next();
-                        if found_true {
-                            self.throw_away_until_closing_curly_brace()?;
-                            break;
-                        } else {
-                            self.scopes.enter_new_scope();
-                            let tmp = Parser {
-                                toks: self.toks,
-                                map: self.map,
-                                path: self.path,
-                                scopes: self.scopes,
-                                global_scope: self.global_scope,
-                                super_selectors: self.super_selectors,
-                                span_before: self.span_before,
-                                content: self.content,
-                                flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                                at_root: self.at_root,
-                                at_root_has_selector: self.at_root_has_selector,
-                                extender: self.extender,
-                                content_scopes: self.content_scopes,
-                                options: self.options,
-                                modules: self.modules,
-                                module_config: self.module_config,
-                            }
-                            .parse_stmt();
-                            self.scopes.exit_scope();
-                            return tmp;
+                    .parse_block()?;
+                    self.scopes.exit_scope();
+                } else {
+                    self.throw_away_until_closing_curly_brace()?;
+                }
+                self.whitespace();
+                continue;
+            }
+            match self.toks.peek().cloned() {
+                Some(Token { kind: '{', .. }) => {
+                    self.toks.next();
+                    if found_true {
+                        self.throw_away_until_closing_curly_brace()?;
+                        break;
+                    } else {
+                        self.scopes.enter_new_scope();
+                        let tmp = Parser {
+                            toks: self.toks,
+                            map: self.map,
+                            path: self.path,
+                            scopes: self.scopes,
+                            global_scope: self.global_scope,
+                            super_selectors: self.super_selectors,
+                            span_before: self.span_before,
+                            content: self.content,
+                            flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
+                            at_root: self.at_root,
+                            at_root_has_selector: self.at_root_has_selector,
+                            extender: self.extender,
+                            content_scopes: self.content_scopes,
+                            options: self.options,
+                            modules: self.modules,
+                            module_config: self.module_config,
+                            media_queries: self.media_queries,
+                            stats: self.stats,
+                            mixin_cache: self.mixin_cache,
+                            random: self.random,
+                            import_stack: self.import_stack,
+                            limits: self.limits,
                         }
-                    }
-                    _ => {
-                        return Err(("expected \"{\".", tok.pos()).into());
+                        .parse_block();
+                        self.scopes.exit_scope();
+                        return tmp;
                     }
                 }
-            } else {
-                break;
+                Some(tok) => return Err(("expected \"{\".", tok.pos()).into()),
+                None => return Err(("expected \"{\".", self.span_before).into()),
             }
         }
         self.whitespace();
@@ -160,6 +183,28 @@ impl<'a> Parser<'a> {
         Ok(body)
     }
 
+    /// Consume the `if` in `@else if`, which is case-insensitive
+    ///
+    /// Unlike `Parser::scan_identifier`, this does not consume the token
+    /// after the identifier, so that `@else if($a)` is handled correctly
+    fn scan_if_keyword(&mut self) -> SassResult<bool> {
+        if !matches!(
+            self.toks.peek(),
+            Some(Token { kind: 'i', .. }) | Some(Token { kind: 'I', .. })
+        ) {
+            return Ok(false);
+        }
+
+        let ident = peek_ident_no_interpolation(self.toks, false, self.span_before)?;
+        if ident.node.eq_ignore_ascii_case("if") {
+            self.toks.truncate_iterator_to_cursor();
+            Ok(true)
+        } else {
+            self.toks.reset_cursor();
+            Ok(false)
+        }
+    }
+
     pub(super) fn parse_for(&mut self) -> SassResult<Vec<Stmt>> {
         self.whitespace_or_comment();
         self.expect_char('$')?;
@@ -203,13 +248,8 @@ impl<'a> Parser<'a> {
             return Err(("Expected \"to\" or \"through\".", self.span_before).into());
         };
 
-        let from = match from_val.node {
-            Value::Dimension(Some(n), ..) => match n.to_i32() {
-                Some(std::i32::MAX) | Some(std::i32::MIN) | None => {
-                    return Err((format!("{} is not an int.", n), from_val.span).into())
-                }
-                Some(v) => v,
-            },
+        let (from, unit) = match from_val.node {
+            Value::Dimension(Some(n), unit, _) => (for_bound_to_int(&n, from_val.span)?, unit),
             Value::Dimension(None, ..) => return Err(("NaN is not an int.", from_val.span).into()),
             v => {
                 return Err((
@@ -222,16 +262,29 @@ impl<'a> Parser<'a> {
 
         let to_val = self.parse_value(true, &|_| false)?;
         let to = match to_val.node {
-            Value::Dimension(Some(n), ..) => match n.to_i32() {
-                Some(std::i32::MAX) | Some(std::i32::MIN) | None => {
-                    return Err((format!("{} is not an int.", n), to_val.span).into())
+            Value::Dimension(Some(n), to_unit, _) => {
+                if !unit.comparable(&to_unit) {
+                    return Err((
+                        format!("Incompatible units {} and {}.", to_unit, unit),
+                        to_val.span,
+                    )
+                        .into());
                 }
-                Some(v) => v,
-            },
-            Value::Dimension(None, ..) => return Err(("NaN is not an int.", from_val.span).into()),
+
+                // the upper bound is converted to the units of the lower one,
+                // which the loop variable takes
+                let n = if unit == to_unit || unit == Unit::None || to_unit == Unit::None {
+                    n
+                } else {
+                    n.convert(&to_unit, &unit)
+                };
+
+                for_bound_to_int(&n, to_val.span)?
+            }
+            Value::Dimension(None, ..) => return Err(("NaN is not an int.", to_val.span).into()),
             v => {
                 return Err((
-                    format!("{} is not a number.", v.to_css_string(to_val.span)?),
+                    format!("{} is not a number.", v.inspect(to_val.span)?),
                     to_val.span,
                 )
                     .into())
@@ -240,9 +293,12 @@ impl<'a> Parser<'a> {
 
         self.expect_char('{')?;
 
-        let body = read_until_closing_curly_brace(self.toks)?;
+        let mut body = read_until_closing_curly_brace(self.toks)?;
 
-        self.expect_char('}')?;
+        body.push(match self.toks.next() {
+            Some(tok) if tok.kind == '}' => tok,
+            Some(..) | None => return Err(("expected \"}\".", self.span_before).into()),
+        });
 
         let (mut x, mut y);
         // we can't use an inclusive range here
@@ -262,53 +318,16 @@ impl<'a> Parser<'a> {
         for i in iter {
             self.scopes.insert_var_last(
                 var.node,
-                Value::Dimension(Some(Number::from(i)), Unit::None, true),
+                Value::Dimension(Some(Number::from(i)), unit.clone(), true),
             );
+            let mut these_stmts = self.parse_loop_body(&body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
-                    return Ok(these_stmts);
+                    stmts = these_stmts;
+                    break;
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
         }
 
@@ -341,51 +360,14 @@ impl<'a> Parser<'a> {
         let mut val = self.parse_value_from_vec(cond.clone(), true)?;
         self.scopes.enter_new_scope();
         while val.node.is_true() {
+            let mut these_stmts = self.parse_loop_body(&body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
-                    return Ok(these_stmts);
+                    stmts = these_stmts;
+                    break;
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
             val = self.parse_value_from_vec(cond.clone(), true)?;
         }
@@ -441,63 +423,16 @@ impl<'a> Parser<'a> {
         self.scopes.enter_new_scope();
 
         for row in iter {
-            if vars.len() == 1 {
-                self.scopes.insert_var_last(vars[0].node, row);
-            } else {
-                for (var, val) in vars.iter().zip(
-                    row.as_list()
-                        .into_iter()
-                        .chain(std::iter::once(Value::Null).cycle()),
-                ) {
-                    self.scopes.insert_var_last(var.node, val);
-                }
-            }
+            self.bind_each_vars(&vars, row);
 
+            let mut these_stmts = self.parse_loop_body(&body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
-                    return Ok(these_stmts);
+                    stmts = these_stmts;
+                    break;
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
         }
 
@@ -505,4 +440,68 @@ impl<'a> Parser<'a> {
 
         Ok(stmts)
     }
+
+    /// Bind the variables of an `@each` rule to a single element of the list
+    /// being iterated over
+    ///
+    /// A single variable is bound to the element as-is. With multiple variables,
+    /// the element is destructured as a list (map entries are 2-element lists),
+    /// and any variables without a corresponding value are bound to `null`
+    fn bind_each_vars(&mut self, vars: &[Spanned<Identifier>], row: Value) {
+        if vars.len() == 1 {
+            self.scopes.insert_var_last(vars[0].node, row);
+            return;
+        }
+
+        for (var, val) in vars.iter().zip(
+            row.as_list()
+                .into_iter()
+                .chain(std::iter::once(Value::Null).cycle()),
+        ) {
+            self.scopes.insert_var_last(var.node, val);
+        }
+    }
+
+    /// Evaluate a single iteration of the body of `@for`, `@while`, or `@each`
+    ///
+    /// The loop variables are expected to already be bound in the innermost scope
+    fn parse_loop_body(&mut self, body: &[Token]) -> SassResult<Vec<Stmt>> {
+        self.limits.check(self.span_before)?;
+
+        Parser {
+            toks: &mut body.to_vec().into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            content_scopes: self.content_scopes,
+            options: self.options,
+            modules: self.modules,
+            module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
+        }
+        .parse_stmt()
+    }
+}
+
+/// Converts a bound of an `@for` rule to the integer it must be
+fn for_bound_to_int(n: &Number, span: Span) -> SassResult<i32> {
+    match n.to_i32() {
+        Some(std::i32::MAX) | Some(std::i32::MIN) | None => {
+            Err((format!("{} is not an int.", n), span).into())
+        }
+        Some(v) => Ok(v),
+    }
 }
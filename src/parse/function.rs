@@ -1,3 +1,5 @@
+use std::rc::Weak;
+
 use codemap::Spanned;
 use peekmore::PeekMore;
 
@@ -21,7 +23,7 @@ const RESERVED_IDENTIFIERS: [&str; 7] =
 impl<'a> Parser<'a> {
     pub(super) fn parse_function(&mut self) -> SassResult<()> {
         self.whitespace_or_comment();
-        let Spanned { node: name, span } = self.parse_identifier()?;
+        let Spanned { node: name, span } = self.parse_identifier_no_interpolation(false)?;
 
         if self.flags.in_mixin() {
             return Err(("Mixins may not contain function declarations.", span).into());
@@ -85,9 +87,18 @@ impl<'a> Parser<'a> {
             body,
             args: fn_args,
             declared_at_root,
+            closure,
             ..
         } = function;
 
+        let closure = closure.as_ref().and_then(Weak::upgrade);
+        // if the module's scope is already borrowed, one of its functions is
+        // being evaluated, such as when a function calls itself, and so it is
+        // already the global scope
+        let mut module_scope = closure
+            .as_ref()
+            .and_then(|scope| scope.try_borrow_mut().ok());
+
         let scope = self.eval_args(fn_args, args)?;
 
         let mut new_scope = Scopes::new();
@@ -108,7 +119,10 @@ impl<'a> Parser<'a> {
             } else {
                 self.scopes
             },
-            global_scope: self.global_scope,
+            global_scope: match &mut module_scope {
+                Some(scope) => &mut **scope,
+                None => self.global_scope,
+            },
             super_selectors: self.super_selectors,
             span_before: self.span_before,
             content: self.content,
@@ -120,6 +134,12 @@ impl<'a> Parser<'a> {
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
         .parse_stmt()?;
 
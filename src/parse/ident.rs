@@ -13,7 +13,10 @@ use crate::{
 use super::Parser;
 
 impl<'a> Parser<'a> {
-    fn ident_body_no_interpolation(&mut self, unit: bool) -> SassResult<Spanned<String>> {
+    pub(crate) fn ident_body_no_interpolation(
+        &mut self,
+        unit: bool,
+    ) -> SassResult<Spanned<String>> {
         let mut text = String::new();
         while let Some(tok) = self.toks.peek() {
             self.span_before = self.span_before.merge(tok.pos());
@@ -47,7 +50,9 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn interpolated_ident_body(&mut self, buf: &mut String) -> SassResult<()> {
+    /// Returns whether the body contained any interpolation
+    fn interpolated_ident_body(&mut self, buf: &mut String) -> SassResult<bool> {
+        let mut interpolated = false;
         while let Some(tok) = self.toks.peek() {
             match tok.kind {
                 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '\u{80}'..=std::char::MAX => {
@@ -65,6 +70,7 @@ impl<'a> Parser<'a> {
                         // TODO: if ident, interpolate literally
                         let interpolation = self.parse_interpolation()?;
                         buf.push_str(&interpolation.node.to_css_string(interpolation.span)?);
+                        interpolated = true;
                     } else {
                         self.toks.reset_cursor();
                         break;
@@ -73,7 +79,7 @@ impl<'a> Parser<'a> {
                 _ => break,
             }
         }
-        Ok(())
+        Ok(interpolated)
     }
 
     fn escape(&mut self, identifier_start: bool) -> SassResult<String> {
@@ -99,8 +105,12 @@ impl<'a> Parser<'a> {
                 value += as_hex(next.kind);
                 self.toks.next();
             }
-            if matches!(self.toks.peek(), Some(Token { kind: ' ', .. }) | Some(Token { kind: '\n', .. }) | Some(Token { kind: '\t', .. }))
-            {
+            if matches!(
+                self.toks.peek(),
+                Some(Token { kind: ' ', .. })
+                    | Some(Token { kind: '\n', .. })
+                    | Some(Token { kind: '\t', .. })
+            ) {
                 self.toks.next();
             }
         } else {
@@ -129,6 +139,14 @@ impl<'a> Parser<'a> {
     }
 
     pub(crate) fn parse_identifier(&mut self) -> SassResult<Spanned<String>> {
+        Ok(self.parse_identifier_and_interpolated()?.0)
+    }
+
+    /// Parses an identifier, additionally returning whether it contained any
+    /// interpolation
+    pub(crate) fn parse_identifier_and_interpolated(
+        &mut self,
+    ) -> SassResult<(Spanned<String>, bool)> {
         let Token { kind, pos } = self
             .toks
             .peek()
@@ -142,18 +160,24 @@ impl<'a> Parser<'a> {
                 Some(Token { kind: '-', .. }) => {
                     self.toks.next();
                     text.push('-');
-                    self.interpolated_ident_body(&mut text)?;
-                    return Ok(Spanned {
-                        node: text,
-                        span: pos,
-                    });
+                    let interpolated = self.interpolated_ident_body(&mut text)?;
+                    return Ok((
+                        Spanned {
+                            node: text,
+                            span: pos,
+                        },
+                        interpolated,
+                    ));
                 }
                 Some(..) => {}
                 None => {
-                    return Ok(Spanned {
-                        node: text,
-                        span: self.span_before,
-                    })
+                    return Ok((
+                        Spanned {
+                            node: text,
+                            span: self.span_before,
+                        },
+                        false,
+                    ))
                 }
             }
         }
@@ -163,6 +187,7 @@ impl<'a> Parser<'a> {
             None => return Err(("Expected identifier.", self.span_before).into()),
         };
 
+        let mut interpolated = false;
         match first {
             c if is_name_start(c) => {
                 text.push(self.toks.next().unwrap().kind);
@@ -178,15 +203,19 @@ impl<'a> Parser<'a> {
                     Value::String(ref s, ..) => text.push_str(s),
                     v => text.push_str(v.to_css_string(self.span_before)?.borrow()),
                 }
+                interpolated = true;
             }
             _ => return Err(("Expected identifier.", pos).into()),
         }
 
-        self.interpolated_ident_body(&mut text)?;
-        Ok(Spanned {
-            node: text,
-            span: self.span_before,
-        })
+        interpolated |= self.interpolated_ident_body(&mut text)?;
+        Ok((
+            Spanned {
+                node: text,
+                span: self.span_before,
+            },
+            interpolated,
+        ))
     }
 
     pub(crate) fn parse_identifier_no_interpolation(
@@ -1,14 +1,21 @@
-use std::{ffi::OsStr, fs, path::Path, path::PathBuf};
+use std::{
+    ffi::{OsStr, OsString},
+    path::Path,
+    path::PathBuf,
+};
 
 use codemap::{Span, Spanned};
 use peekmore::PeekMore;
 
 use crate::{
-    common::{ListSeparator::Comma, QuoteKind},
+    common::QuoteKind,
+    deprecation::Deprecation,
     error::SassResult,
-    lexer::Lexer,
+    fs::Fs,
+    syntax::Syntax,
+    utils::{is_name_start, peek_ident_no_interpolation},
     value::Value,
-    Token,
+    Cow, Token,
 };
 
 use super::{Parser, Stmt};
@@ -24,13 +31,94 @@ fn is_plain_css_import(url: &str) -> bool {
         || url.starts_with("//")
 }
 
+/// Finds the file that an import of `path` refers to, trying partials,
+/// `.sass` and `.scss` and then `.css` extensions, and then `_index` files
+/// in a directory named `path`
+///
+/// <https://sass-lang.com/documentation/at-rules/import#partials>
+/// <https://sass-lang.com/documentation/at-rules/import#index-files>
+fn resolve_import_path(path: &Path, span: Span, fs: &dyn Fs) -> SassResult<Option<PathBuf>> {
+    let extension = path.extension().and_then(OsStr::to_str);
+    if extension == Some("sass") || extension == Some("scss") || extension == Some("css") {
+        return exactly_one(partial_and_full(path, None), span, fs);
+    }
+
+    if let Some(found) = with_extensions(path, span, fs)? {
+        return Ok(Some(found));
+    }
+
+    // a file without an extension, which `dart-sass` wouldn't find
+    if fs.is_file(path) {
+        return Ok(Some(path.to_path_buf()));
+    }
+
+    with_extensions(&path.join("index"), span, fs)
+}
+
+/// Finds `path` with a `.sass` or `.scss` extension, which are equally
+/// preferred, or else with a `.css` extension
+fn with_extensions(path: &Path, span: Span, fs: &dyn Fs) -> SassResult<Option<PathBuf>> {
+    let mut sass = partial_and_full(path, Some("sass"));
+    sass.extend(partial_and_full(path, Some("scss")));
+
+    if let Some(found) = exactly_one(sass, span, fs)? {
+        return Ok(Some(found));
+    }
+
+    exactly_one(partial_and_full(path, Some("css")), span, fs)
+}
+
+/// `path` as a partial, with an underscore before its file name, followed by
+/// `path` itself, each with `extension` appended if one is given
+fn partial_and_full(path: &Path, extension: Option<&str>) -> Vec<PathBuf> {
+    let name = match path.file_name() {
+        Some(name) => name,
+        None => return Vec::new(),
+    };
+
+    let with_extension = |name: &OsStr| {
+        let mut name = name.to_owned();
+        if let Some(extension) = extension {
+            name.push(".");
+            name.push(extension);
+        }
+        path.with_file_name(name)
+    };
+
+    let mut partial = OsString::from("_");
+    partial.push(name);
+
+    vec![with_extension(&partial), with_extension(name)]
+}
+
+/// The only one of `candidates` that exists, or an error if several do
+fn exactly_one(candidates: Vec<PathBuf>, span: Span, fs: &dyn Fs) -> SassResult<Option<PathBuf>> {
+    let mut found = candidates.into_iter().filter(|path| fs.is_file(path));
+
+    match (found.next(), found.next()) {
+        (Some(first), Some(second)) => Err((
+            format!(
+                "It's not clear which file to import. Found:\n  {}\n  {}",
+                first.display(),
+                second.display()
+            ),
+            span,
+        )
+            .into()),
+        (found, _) => Ok(found),
+    }
+}
+
 impl<'a> Parser<'a> {
     /// Searches the current directory of the file then searches in `load_paths` directories
     /// if the import has not yet been found.
     ///
+    /// Returns an error if more than one file matches in the same directory,
+    /// such as both `_foo.scss` and `foo.scss`.
+    ///
     /// <https://sass-lang.com/documentation/at-rules/import#finding-the-file>
     /// <https://sass-lang.com/documentation/at-rules/import#load-paths>
-    pub(super) fn find_import(&self, path: &Path) -> Option<PathBuf> {
+    pub(super) fn find_import(&self, path: &Path, span: Span) -> SassResult<Option<PathBuf>> {
         let path_buf = if path.is_absolute() {
             // todo: test for absolute path imports
             path.into()
@@ -41,51 +129,55 @@ impl<'a> Parser<'a> {
                 .join(path)
         };
 
-        let name = path_buf.file_name().unwrap_or_else(|| OsStr::new(".."));
-
-        let paths = [
-            path_buf.with_file_name(name).with_extension("scss"),
-            path_buf
-                .with_file_name(format!("_{}", name.to_str().unwrap()))
-                .with_extension("scss"),
-            path_buf.clone(),
-            path_buf.join("index.scss"),
-            path_buf.join("_index.scss"),
-        ];
-
-        for name in &paths {
-            if name.is_file() {
-                return Some(name.to_path_buf());
-            }
+        if let Some(found) = resolve_import_path(&path_buf, span, self.options.fs)? {
+            return Ok(Some(found));
         }
 
-        for path in &self.options.load_paths {
-            let paths: Vec<PathBuf> = if path.is_dir() {
-                vec![
-                    path.join(format!("{}.scss", name.to_str().unwrap())),
-                    path.join(format!("_{}.scss", name.to_str().unwrap())),
-                    path.join("index.scss"),
-                    path.join("_index.scss"),
-                ]
-            } else {
-                vec![
-                    path.to_path_buf(),
-                    path.with_file_name(name).with_extension("scss"),
-                    path.with_file_name(format!("_{}", name.to_str().unwrap()))
-                        .with_extension("scss"),
-                    path.join("index.scss"),
-                    path.join("_index.scss"),
-                ]
-            };
-
-            for name in paths {
-                if name.is_file() {
-                    return Some(name);
-                }
+        for load_path in &self.options.load_paths {
+            if let Some(found) = resolve_import_path(&load_path.join(path), span, self.options.fs)?
+            {
+                return Ok(Some(found));
             }
         }
 
-        None
+        Ok(None)
+    }
+
+    /// Returns an error if imports are restricted to a set of directories
+    /// and `path` is not inside any of them
+    ///
+    /// See [`Options::import_root`](crate::Options::import_root)
+    pub(super) fn check_import_allowed(
+        &self,
+        url: &str,
+        path: &Path,
+        span: Span,
+    ) -> SassResult<()> {
+        let roots = match &self.options.import_roots {
+            Some(roots) => roots,
+            None => return Ok(()),
+        };
+
+        let fs = self.options.fs;
+        let is_allowed = fs.canonicalize(path).map_or(false, |path| {
+            roots.iter().any(|root| {
+                fs.canonicalize(root)
+                    .map_or(false, |root| path.starts_with(root))
+            })
+        });
+
+        if is_allowed {
+            Ok(())
+        } else {
+            Err((
+                format!(
+                    "Can't import \"{}\": it is outside of the allowed import directories.",
+                    url
+                ),
+                span,
+            )
+                .into())
+        }
     }
 
     pub(crate) fn parse_single_import(
@@ -93,16 +185,37 @@ impl<'a> Parser<'a> {
         file_name: &str,
         span: Span,
     ) -> SassResult<Vec<Stmt>> {
+        self.warn_deprecation(
+            Deprecation::Import,
+            &Spanned {
+                node: Cow::const_str("Sass @import rules are deprecated and will be removed in Dart Sass 3.0.0.\n\nMore info and automated migrator: https://sass-lang.com/d/import"),
+                span,
+            },
+        )?;
+
         let path: &Path = file_name.as_ref();
 
-        if let Some(name) = self.find_import(path) {
+        if let Some(name) = self.find_import(path, span)? {
+            self.check_import_allowed(file_name, &name, span)?;
+            self.stats.loaded_paths.insert(name.clone());
+
             let file = self.map.add_file(
-                name.to_string_lossy().into(),
-                String::from_utf8(fs::read(&name)?)?,
+                self.options.source_url(name.to_string_lossy().into()),
+                String::from_utf8(self.options.fs.read(&name)?)?,
             );
-            return Parser {
-                toks: &mut Lexer::new(&file)
-                    .collect::<Vec<Token>>()
+            self.import_stack.push(
+                &name,
+                span,
+                self.map,
+                self.options.fs,
+                self.options.unicode_error_messages,
+            )?;
+
+            let syntax = Syntax::for_path(&name);
+            let stmts = Parser {
+                toks: &mut self
+                    .options
+                    .tokenize(syntax, &name, &file)?
                     .into_iter()
                     .peekmore(),
                 map: self.map,
@@ -112,7 +225,7 @@ impl<'a> Parser<'a> {
                 super_selectors: self.super_selectors,
                 span_before: file.span.subspan(0, 0),
                 content: self.content,
-                flags: self.flags,
+                flags: self.flags.for_syntax(syntax),
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
@@ -120,8 +233,19 @@ impl<'a> Parser<'a> {
                 options: self.options,
                 modules: self.modules,
                 module_config: self.module_config,
+                media_queries: self.media_queries,
+                stats: self.stats,
+                mixin_cache: self.mixin_cache,
+                random: self.random,
+                import_stack: self.import_stack,
+                limits: self.limits,
             }
-            .parse();
+            .parse()
+            .map_err(|err| err.in_member("@import", span));
+
+            self.import_stack.pop();
+
+            return stmts;
         }
 
         Err(("Can't find stylesheet to import.", span).into())
@@ -132,63 +256,189 @@ impl<'a> Parser<'a> {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
-        self.whitespace_or_comment();
+        let mut imports = Vec::new();
 
-        match self.toks.peek() {
-            Some(Token { kind: '\'', .. })
-            | Some(Token { kind: '"', .. })
-            | Some(Token { kind: 'u', .. }) => {}
-            Some(Token { pos, .. }) => return Err(("Expected string.", *pos).into()),
-            None => return Err(("expected more input.", self.span_before).into()),
-        };
-        let Spanned {
-            node: file_name_as_value,
-            span,
-        } = self.parse_value(true, &|_| false)?;
+        loop {
+            self.whitespace_or_comment();
 
-        match file_name_as_value {
-            Value::String(s, QuoteKind::Quoted) => {
-                if is_plain_css_import(&s) {
-                    Ok(vec![Stmt::Import(format!("\"{}\"", s))])
-                } else {
-                    self.parse_single_import(&s, span)
+            match self.toks.peek() {
+                Some(Token { kind: '\'', .. })
+                | Some(Token { kind: '"', .. })
+                | Some(Token { kind: 'u', .. })
+                | Some(Token { kind: 'U', .. }) => {}
+                Some(Token { pos, .. }) => return Err(("Expected string.", *pos).into()),
+                None => return Err(("expected more input.", self.span_before).into()),
+            };
+
+            // interpolated urls can't be resolved until runtime, so they're
+            // always plain CSS imports
+            let is_interpolated = self.looking_at_interpolated_string();
+
+            let Spanned {
+                node: file_name_as_value,
+                span,
+            } = self.parse_value(true, &|toks| {
+                matches!(
+                    toks.peek(),
+                    Some(Token { kind: ',', .. })
+                        | Some(Token { kind: ';', .. })
+                        | Some(Token { kind: ' ', .. })
+                        | Some(Token { kind: '\t', .. })
+                        | Some(Token { kind: '\n', .. })
+                )
+            })?;
+
+            self.whitespace_or_comment();
+            let modifiers = self.parse_import_modifiers()?;
+
+            let plain_import = |url: String| match &modifiers {
+                Some(modifiers) => Stmt::Import(format!("{} {}", url, modifiers)),
+                None => Stmt::Import(url),
+            };
+
+            match file_name_as_value {
+                Value::String(s, QuoteKind::Quoted) => {
+                    if is_plain_css_import(&s)
+                        || modifiers.is_some()
+                        || is_interpolated
+                        || self.flags.in_plain_css()
+                    {
+                        imports.push(plain_import(format!("\"{}\"", s)));
+                    } else {
+                        imports.append(&mut self.parse_single_import(&s, span)?);
+                    }
                 }
+                Value::String(s, QuoteKind::None) => {
+                    if s.get(..4)
+                        .map_or(false, |start| start.eq_ignore_ascii_case("url("))
+                    {
+                        imports.push(plain_import(s));
+                    } else {
+                        imports.append(&mut self.parse_single_import(&s, span)?);
+                    }
+                }
+                _ => return Err(("Expected string.", span).into()),
             }
-            Value::String(s, QuoteKind::None) => {
-                if s.starts_with("url(") {
-                    Ok(vec![Stmt::Import(s)])
-                } else {
-                    self.parse_single_import(&s, span)
+
+            if !self.consume_char_if_exists(',') {
+                break;
+            }
+        }
+
+        Ok(imports)
+    }
+
+    /// Whether the next token begins a quoted string that contains
+    /// interpolation, without consuming it
+    fn looking_at_interpolated_string(&mut self) -> bool {
+        let quote = match self.toks.peek() {
+            Some(Token { kind: '"', .. }) => '"',
+            Some(Token { kind: '\'', .. }) => '\'',
+            _ => return false,
+        };
+
+        let mut is_interpolated = false;
+        let mut after_hash = false;
+        while let Some(Token { kind, .. }) = self.toks.peek_next().copied() {
+            match kind {
+                '{' if after_hash => {
+                    is_interpolated = true;
+                    break;
+                }
+                '\\' => {
+                    self.toks.peek_next();
                 }
+                _ if kind == quote => break,
+                _ => {}
             }
-            Value::List(v, Comma, _) => {
-                let mut list_of_imports: Vec<Stmt> = Vec::new();
-                for file_name_element in v {
-                    match file_name_element {
-                        Value::String(s, QuoteKind::Quoted) => {
-                            if s.ends_with(".css")
-                                || s.starts_with("http://")
-                                || s.starts_with("https://")
-                            {
-                                list_of_imports.push(Stmt::Import(format!("\"{}\"", s)));
-                            } else {
-                                list_of_imports.append(&mut self.parse_single_import(&s, span)?);
-                            }
-                        }
-                        Value::String(s, QuoteKind::None) => {
-                            if s.starts_with("url(") {
-                                list_of_imports.push(Stmt::Import(s));
-                            } else {
-                                list_of_imports.append(&mut self.parse_single_import(&s, span)?);
-                            }
-                        }
-                        _ => return Err(("Expected string.", span).into()),
-                    }
+            after_hash = kind == '#';
+        }
+        self.toks.reset_cursor();
+
+        is_interpolated
+    }
+
+    /// Parses the modifiers that may follow the URL of a plain CSS import,
+    /// such as `layer(base) supports(display: grid) screen`
+    ///
+    /// Returns `None` if there are no modifiers.
+    fn parse_import_modifiers(&mut self) -> SassResult<Option<String>> {
+        let mut buf = String::new();
+
+        loop {
+            let looking_at_identifier = match self.toks.peek() {
+                Some(Token { kind: '#', .. }) => {
+                    let is_interpolation =
+                        matches!(self.toks.peek_forward(1), Some(Token { kind: '{', .. }));
+                    self.toks.reset_cursor();
+                    is_interpolation
+                }
+                Some(Token { kind, .. }) => is_name_start(*kind) || *kind == '-' || *kind == '\\',
+                None => false,
+            };
+
+            if looking_at_identifier {
+                if !buf.is_empty() {
+                    buf.push(' ');
                 }
 
-                Ok(list_of_imports)
+                let name = self.parse_identifier()?.node;
+                buf.push_str(&name);
+
+                if !name.eq_ignore_ascii_case("and") && self.consume_char_if_exists('(') {
+                    buf.push('(');
+                    if name.eq_ignore_ascii_case("supports") {
+                        buf.push_str(&self.parse_import_supports_query()?);
+                    } else {
+                        buf.push_str(&self.parse_supports_declaration_value()?);
+                    }
+                    buf.push(')');
+                    self.whitespace_or_comment();
+                } else {
+                    self.whitespace_or_comment();
+                    if self.consume_char_if_exists(',') {
+                        buf.push_str(", ");
+                        buf.push_str(&self.parse_media_query_list()?);
+                        break;
+                    }
+                }
+            } else if matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
+                if !buf.is_empty() {
+                    buf.push(' ');
+                }
+                buf.push_str(&self.parse_media_query_list()?);
+                break;
+            } else {
+                break;
             }
-            _ => Err(("Expected string.", span).into()),
         }
+
+        Ok(if buf.is_empty() { None } else { Some(buf) })
+    }
+
+    /// Parses the argument to `supports()` in an import, up to and including
+    /// its closing parenthesis
+    ///
+    /// This is either a supports condition or a bare declaration, such as
+    /// `supports(display: grid)`.
+    fn parse_import_supports_query(&mut self) -> SassResult<String> {
+        self.whitespace_or_comment();
+
+        let is_function = match peek_ident_no_interpolation(self.toks, false, self.span_before) {
+            Ok(..) => matches!(self.toks.peek(), Some(Token { kind: '(', .. })),
+            Err(..) => false,
+        };
+        self.toks.reset_cursor();
+
+        if is_function
+            || self.looking_at_negation()
+            || matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+        {
+            let condition = self.parse_supports_condition()?;
+            self.expect_char(')')?;
+            return Ok(condition);
+        }
+
+        self.parse_supports_declaration()
     }
 }
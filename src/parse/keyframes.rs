@@ -54,21 +54,12 @@ impl<'a, 'b> KeyframesSelectorParser<'a, 'b> {
                         return Err(("Expected \"to\" or \"from\".", tok.pos).into());
                     }
                 }
-                '0'..='9' => {
-                    let mut num = eat_whole_number(self.parser.toks);
-
-                    if let Some(Token { kind: '.', .. }) = self.parser.toks.peek() {
-                        self.parser.toks.next();
-                        num.push('.');
-                        num.push_str(&eat_whole_number(self.parser.toks));
-                    }
-
-                    self.parser.expect_char('%')?;
-
-                    selectors.push(KeyframesSelector::Percent(num.into_boxed_str()));
+                '0'..='9' | '+' | '.' => {
+                    selectors.push(KeyframesSelector::Percent(
+                        self.parse_percentage()?.into_boxed_str(),
+                    ));
                 }
                 '{' => break,
-                '\\' => todo!("escaped chars in @keyframes selector"),
                 _ => return Err(("Expected \"to\" or \"from\".", tok.pos).into()),
             }
             self.parser.whitespace_or_comment();
@@ -81,6 +72,58 @@ impl<'a, 'b> KeyframesSelectorParser<'a, 'b> {
         }
         Ok(selectors)
     }
+
+    /// Parses a percentage such as `50%`, `+.5%` or `1e2%`, preserving the
+    /// text as written
+    fn parse_percentage(&mut self) -> SassResult<String> {
+        let mut num = String::new();
+
+        if self.parser.consume_char_if_exists('+') {
+            num.push('+');
+        }
+
+        match self.parser.toks.peek() {
+            Some(tok) if tok.kind.is_ascii_digit() || tok.kind == '.' => {}
+            Some(Token { pos, .. }) => return Err(("Expected number.", *pos).into()),
+            None => return Err(("Expected number.", self.parser.span_before).into()),
+        }
+
+        num.push_str(&eat_whole_number(self.parser.toks));
+
+        if self.parser.consume_char_if_exists('.') {
+            num.push('.');
+            num.push_str(&self.expect_digits()?);
+        }
+
+        if let Some(exponent) = self.consume_one_of('e', 'E') {
+            num.push(exponent);
+            if let Some(sign) = self.consume_one_of('+', '-') {
+                num.push(sign);
+            }
+            num.push_str(&self.expect_digits()?);
+        }
+
+        self.parser.expect_char('%')?;
+
+        Ok(num)
+    }
+
+    fn consume_one_of(&mut self, a: char, b: char) -> Option<char> {
+        match self.parser.toks.peek() {
+            Some(Token { kind, .. }) if *kind == a || *kind == b => {
+                self.parser.toks.next().map(|tok| tok.kind)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect_digits(&mut self) -> SassResult<String> {
+        let digits = eat_whole_number(self.parser.toks);
+        if digits.is_empty() {
+            return Err(("Expected digit.", self.parser.span_before).into());
+        }
+        Ok(digits)
+    }
 }
 
 impl<'a> Parser<'a> {
@@ -174,6 +217,12 @@ impl<'a> Parser<'a> {
                         options: self.options,
                         modules: self.modules,
                         module_config: self.module_config,
+                        media_queries: self.media_queries,
+                        stats: self.stats,
+                        mixin_cache: self.mixin_cache,
+                        random: self.random,
+                        import_stack: self.import_stack,
+                        limits: self.limits,
                     })
                     .parse_keyframes_selector()?;
 
@@ -211,8 +260,14 @@ impl<'a> Parser<'a> {
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
-        .parse_stmt()?;
+        .parse_block()?;
 
         Ok(Stmt::Keyframes(Box::new(Keyframes { rule, name, body })))
     }
@@ -0,0 +1,79 @@
+use std::mem;
+
+use crate::{atrule::UnknownAtRule, error::SassResult, selector::Selector};
+
+use super::{Parser, Stmt};
+
+impl<'a> Parser<'a> {
+    /// Parses an `@layer` rule
+    ///
+    /// A named layer block nested directly in another named layer block is
+    /// emitted after it with the two names combined, so that
+    /// `@layer a { @layer b { ... } }` becomes `@layer a.b { ... }`. The
+    /// enclosing block is split around it if anything follows it.
+    pub(super) fn parse_layer(&mut self) -> SassResult<Vec<Stmt>> {
+        if self.flags.in_function() {
+            return Err(("This at-rule is not allowed here.", self.span_before).into());
+        }
+
+        let (name, has_body) = self.parse_at_rule_params()?;
+
+        if !has_body {
+            return Ok(vec![self.layer_rule(name, Vec::new(), false)]);
+        }
+
+        let body = self.parse_block()?;
+        let body = self.wrap_in_super_selector(body)?;
+
+        // there is no name to combine those of nested layers with
+        if name.is_empty() {
+            return Ok(vec![self.layer_rule(name, body, true)]);
+        }
+
+        let mut rules = Vec::new();
+        let mut group = Vec::new();
+
+        for stmt in body {
+            match stmt {
+                Stmt::UnknownAtRule(mut nested) if is_named_layer_block(&nested) => {
+                    if !is_invisible(&group) {
+                        rules.push(self.layer_rule(name.clone(), mem::take(&mut group), true));
+                    }
+                    group.clear();
+                    nested.params = format!("{}.{}", name, nested.params);
+                    rules.push(Stmt::UnknownAtRule(nested));
+                }
+                _ => group.push(stmt),
+            }
+        }
+
+        // an empty layer block still declares where the layer is ordered
+        if rules.is_empty() || !is_invisible(&group) {
+            rules.push(self.layer_rule(name, group, true));
+        }
+
+        Ok(rules)
+    }
+
+    fn layer_rule(&self, name: String, body: Vec<Stmt>, has_body: bool) -> Stmt {
+        Stmt::UnknownAtRule(Box::new(UnknownAtRule {
+            name: "layer".to_owned(),
+            super_selector: Selector::new(self.span_before),
+            params: name,
+            body,
+            has_body,
+        }))
+    }
+}
+
+fn is_named_layer_block(rule: &UnknownAtRule) -> bool {
+    rule.name == "layer" && rule.has_body && !rule.params.is_empty()
+}
+
+/// Whether `stmts` would not produce any output, such as when they are only
+/// the empty copy of the enclosing style rule
+fn is_invisible(stmts: &[Stmt]) -> bool {
+    stmts
+        .iter()
+        .all(|stmt| matches!(stmt, Stmt::RuleSet { body, .. } if body.is_empty()))
+}
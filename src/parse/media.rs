@@ -62,7 +62,7 @@ impl<'a> Parser<'a> {
         Ok(buf)
     }
 
-    fn parse_media_feature(&mut self) -> SassResult<String> {
+    pub(super) fn parse_media_feature(&mut self) -> SassResult<String> {
         if let Some(Token { kind: '#', .. }) = self.toks.peek() {
             self.toks.next();
             self.expect_char('{')?;
@@ -120,10 +120,20 @@ impl<'a> Parser<'a> {
         let mut buf = String::new();
 
         if !matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
-            buf.push_str(&self.parse_identifier()?);
+            let ident = self.parse_identifier()?;
 
             self.whitespace_or_comment();
 
+            // a negated condition, such as `not (color)`
+            if ident.eq_ignore_ascii_case("not")
+                && matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+            {
+                buf.push_str("not ");
+                return self.parse_media_condition(buf);
+            }
+
+            buf.push_str(&ident);
+
             if let Some(tok) = self.toks.peek() {
                 if !is_name_start(tok.kind) {
                     return Ok(buf);
@@ -147,17 +157,54 @@ impl<'a> Parser<'a> {
                     return Ok(buf);
                 }
             }
+
+            loop {
+                self.whitespace_or_comment();
+                buf.push_str(&self.parse_media_feature()?);
+                self.whitespace_or_comment();
+                if !self.scan_identifier("and")? {
+                    break;
+                }
+                buf.push_str(" and ");
+            }
+
+            return Ok(buf);
         }
 
+        self.parse_media_condition(buf)
+    }
+
+    /// Parses media features joined by either `and` or `or`, each of which
+    /// may be negated with `not`
+    fn parse_media_condition(&mut self, mut buf: String) -> SassResult<String> {
+        let mut operator = None;
+
         loop {
             self.whitespace_or_comment();
+            if operator.is_some() && self.scan_identifier("not")? {
+                self.whitespace_or_comment();
+                buf.push_str("not ");
+            }
             buf.push_str(&self.parse_media_feature()?);
             self.whitespace_or_comment();
-            if !self.scan_identifier("and")? {
+
+            let next = match operator {
+                Some(op) => op,
+                None if self.scan_identifier("and")? => "and",
+                None if self.scan_identifier("or")? => "or",
+                None => break,
+            };
+
+            if operator.is_some() && !self.scan_identifier(next)? {
                 break;
             }
-            buf.push_str(" and ");
+
+            operator = Some(next);
+            buf.push(' ');
+            buf.push_str(next);
+            buf.push(' ');
         }
+
         Ok(buf)
     }
 }
@@ -6,10 +6,13 @@ use peekmore::PeekMore;
 
 use crate::{
     args::{CallArgs, FuncArgs},
-    atrule::mixin::{Content, Mixin, UserDefinedMixin},
+    atrule::mixin::{Content, ContentBlock, Mixin, UserDefinedMixin},
+    builtin::modules::Module,
+    common::Identifier,
     error::SassResult,
-    scope::Scopes,
-    utils::read_until_closing_curly_brace,
+    scope::{Scope, Scopes},
+    utils::{is_name, read_until_closing_curly_brace},
+    value::SassFunction,
     Token,
 };
 
@@ -49,14 +52,9 @@ impl<'a> Parser<'a> {
             None => return Err(("expected \"}\".", self.span_before).into()),
         });
 
-        // todo: `@include` can only give content when `@content` is present within the body
-        // if `@content` is *not* present and `@include` attempts to give a body, we throw an error
-        // `Error: Mixin doesn't accept a content block.`
-        //
-        // this is blocked on figuring out just how to check for this. presumably we could have a check
-        // not when parsing initially, but rather when `@include`ing to see if an `@content` was found.
+        let accepts_content_block = contains_content_rule(&body);
 
-        let mixin = Mixin::new_user_defined(args, body, false, self.at_root);
+        let mixin = Mixin::new_user_defined(args, body, accepts_content_block, self.at_root, span);
 
         if self.at_root {
             self.global_scope.insert_mixin(name, mixin);
@@ -72,13 +70,17 @@ impl<'a> Parser<'a> {
         }
 
         self.whitespace_or_comment();
-        let name = self.parse_identifier()?.map_node(Into::into);
+        let mut name = self
+            .parse_identifier_no_interpolation(false)?
+            .map_node(Into::into);
 
         let mixin = if let Some(Token { kind: '.', .. }) = self.toks.peek() {
             self.toks.next();
 
             let module = name;
-            let name = self.parse_identifier()?.map_node(Into::into);
+            name = self
+                .parse_identifier_no_interpolation(false)?
+                .map_node(Into::into);
 
             self.modules
                 .get(module.node, module.span)?
@@ -127,7 +129,10 @@ impl<'a> Parser<'a> {
                 toks.push(*tok);
                 self.toks.next();
             }
-            Some(toks)
+            Some(ContentBlock {
+                body: toks,
+                args: content_args,
+            })
         } else {
             None
         };
@@ -137,6 +142,21 @@ impl<'a> Parser<'a> {
             self.toks.next();
         }
 
+        self.include_mixin(mixin, name, args, content)
+    }
+
+    /// Includes `mixin`, as `@include` or `meta.apply()` does, passing it
+    /// `args` and the content block, if there is one
+    pub(crate) fn include_mixin(
+        &mut self,
+        mixin: Mixin,
+        name: Spanned<Identifier>,
+        args: CallArgs,
+        content: Option<ContentBlock>,
+    ) -> SassResult<Vec<Stmt>> {
+        // todo: user-defined mixins that don't accept a content block should
+        // be rejected here too, but only once their bodies are checked for
+        // errors such as nested function declarations before being included
         let UserDefinedMixin {
             body,
             args: fn_args,
@@ -144,13 +164,36 @@ impl<'a> Parser<'a> {
             ..
         } = match mixin {
             Mixin::UserDefined(u) => u,
-            Mixin::Builtin(b) => {
-                return b(args, self);
+            Mixin::Builtin {
+                body,
+                accepts_content_block,
+            } => {
+                if content.is_some() && !accepts_content_block {
+                    return Err(("Mixin doesn't accept a content block.", args.span()).into());
+                }
+                return body(args, content, self);
             }
         };
 
+        let (content, content_args) = match content {
+            Some(ContentBlock { body, args }) => (Some(body), args),
+            None => (None, None),
+        };
+
+        let params: Vec<Identifier> = fn_args.0.iter().map(|arg| arg.name).collect();
+
         let scope = self.eval_args(fn_args, args)?;
 
+        let cache_key = if self.options.cache_mixins && content.is_none() {
+            self.mixin_cache_key(&body, &params, &scope)
+        } else {
+            None
+        };
+
+        if let Some(stmts) = cache_key.as_ref().and_then(|key| self.mixin_cache.get(key)) {
+            return Ok(stmts);
+        }
+
         let scope_len = self.scopes.len();
 
         if declared_at_root {
@@ -183,8 +226,15 @@ impl<'a> Parser<'a> {
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
-        .parse_stmt()?;
+        .parse_stmt()
+        .map_err(|err| err.in_member(&format!("{}()", name.node), name.span))?;
 
         self.content.pop();
         self.scopes.exit_scope();
@@ -193,9 +243,70 @@ impl<'a> Parser<'a> {
             mem::swap(self.scopes, self.content_scopes);
         }
 
+        if let Some(key) = cache_key {
+            self.mixin_cache.insert(key, body.clone());
+        }
+
         Ok(body)
     }
 
+    /// Returns the key under which the output of including the mixin with
+    /// the given body and evaluated arguments is cached, or `None` if the
+    /// output may not be cached
+    fn mixin_cache_key(
+        &mut self,
+        body: &[Token],
+        params: &[Identifier],
+        scope: &Scope,
+    ) -> Option<String> {
+        let dependencies = self.mixin_cache.dependencies(body, params)?;
+
+        for namespace in &dependencies.variable_namespaces {
+            if !self
+                .modules
+                .get(*namespace, self.span_before)
+                .map_or(false, Module::is_builtin)
+            {
+                return None;
+            }
+        }
+
+        for (namespace, name) in &dependencies.functions {
+            let is_pure = match namespace {
+                Some(namespace) => self
+                    .modules
+                    .get(*namespace, self.span_before)
+                    .map_or(false, Module::is_builtin),
+                None => {
+                    matches!(
+                        self.scopes.get_fn(*name, self.global_scope),
+                        None | Some(SassFunction::Builtin(..))
+                    ) && matches!(
+                        self.content_scopes.get_fn(*name, self.global_scope),
+                        None | Some(SassFunction::Builtin(..))
+                    )
+                }
+            };
+
+            #[cfg(feature = "plugins")]
+            let is_pure = is_pure && self.options.plugin_function(name.as_str()).is_none();
+
+            if !is_pure {
+                return None;
+            }
+        }
+
+        Some(format!(
+            "{:?}|{:?}|{}|{:?}|{:?}|{}",
+            body.first()?.pos,
+            scope.vars,
+            self.super_selectors.last(),
+            self.media_queries,
+            self.flags,
+            self.at_root_has_selector,
+        ))
+    }
+
     pub(super) fn parse_content_rule(&mut self) -> SassResult<Vec<Stmt>> {
         if !self.flags.in_mixin() {
             return Err((
@@ -250,6 +361,12 @@ impl<'a> Parser<'a> {
                     options: self.options,
                     modules: self.modules,
                     module_config: self.module_config,
+                    media_queries: self.media_queries,
+                    stats: self.stats,
+                    mixin_cache: self.mixin_cache,
+                    random: self.random,
+                    import_stack: self.import_stack,
+                    limits: self.limits,
                 }
                 .parse_stmt()?
             } else {
@@ -276,3 +393,49 @@ impl<'a> Parser<'a> {
         })
     }
 }
+
+/// Whether the body of a mixin contains an `@content` rule, ignoring any
+/// that appear in strings or comments
+fn contains_content_rule(body: &[Token]) -> bool {
+    let chars: Vec<char> = body.iter().map(|tok| tok.kind).collect();
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        match chars[idx] {
+            quote @ '"' | quote @ '\'' => {
+                idx += 1;
+                while idx < chars.len() && chars[idx] != quote {
+                    if chars[idx] == '\\' {
+                        idx += 1;
+                    }
+                    idx += 1;
+                }
+            }
+            '/' if chars.get(idx + 1) == Some(&'/') => {
+                while idx < chars.len() && chars[idx] != '\n' {
+                    idx += 1;
+                }
+            }
+            '/' if chars.get(idx + 1) == Some(&'*') => {
+                idx += 3;
+                while idx < chars.len() && !(chars[idx - 1] == '*' && chars[idx] == '/') {
+                    idx += 1;
+                }
+            }
+            '@' => {
+                let name: String = chars[idx + 1..]
+                    .iter()
+                    .take_while(|c| is_name(**c))
+                    .collect();
+                if name == "content" {
+                    return true;
+                }
+                idx += name.chars().count();
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    false
+}
@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, path::Path, vec::IntoIter};
+use std::{convert::TryFrom, mem, path::Path, vec::IntoIter};
 
 use codemap::{CodeMap, Span, Spanned};
 use peekmore::{PeekMore, PeekMoreIterator};
@@ -6,20 +6,28 @@ use peekmore::{PeekMore, PeekMoreIterator};
 use crate::{
     atrule::{
         keyframes::{Keyframes, KeyframesRuleSet},
-        media::MediaRule,
+        media::{CssMediaQuery, MediaRule},
         mixin::Content,
-        AtRuleKind, SupportsRule, UnknownAtRule,
+        AtRootQuery, AtRuleKind, SupportsRule, UnknownAtRule,
     },
     builtin::modules::{ModuleConfig, Modules},
-    error::SassResult,
+    deprecation::Deprecation,
+    diagnostic::{Diagnostic, Severity},
+    error::{SassError, SassResult},
+    formatter,
+    import_stack::ImportStack,
+    limits::Limits,
+    mixin_cache::MixinCache,
+    random::Random,
     scope::{Scope, Scopes},
     selector::{
         ComplexSelectorComponent, ExtendRule, ExtendedSelector, Extender, Selector, SelectorParser,
     },
+    shorthand,
     style::Style,
-    utils::read_until_semicolon_or_closing_curly_brace,
+    utils::{peek_ident_no_interpolation, read_until_semicolon_or_closing_curly_brace},
     value::Value,
-    Options, {Cow, Token},
+    Options, Stats, {Cow, Token},
 };
 
 use common::{Comment, ContextFlags, NeverEmptyVec, SelectorOrStyle};
@@ -28,15 +36,18 @@ use variable::VariableValue;
 
 mod args;
 pub mod common;
+mod container;
 mod control_flow;
 mod function;
 mod ident;
 mod import;
 mod keyframes;
+mod layer;
 mod media;
 mod mixin;
 mod module;
 mod style;
+mod supports;
 mod throw_away;
 mod value;
 mod variable;
@@ -46,12 +57,18 @@ pub(crate) enum Stmt {
     RuleSet {
         selector: ExtendedSelector,
         body: Vec<Self>,
+        /// Where the rule was written, for
+        /// [`Options::line_comments`](crate::Options::line_comments)
+        span: Span,
     },
     Style(Style),
     Media(Box<MediaRule>),
     UnknownAtRule(Box<UnknownAtRule>),
     Supports(Box<SupportsRule>),
+    /// An `@at-root` rule, which is replaced by its body before the
+    /// stylesheet is written
     AtRoot {
+        query: AtRootQuery,
         body: Vec<Stmt>,
     },
     Comment(String),
@@ -90,6 +107,20 @@ pub(crate) struct Parser<'a> {
 
     pub modules: &'a mut Modules,
     pub module_config: &'a mut ModuleConfig,
+
+    /// The queries of the innermost enclosing `@media` rule, merged with
+    /// those of the media rules enclosing it
+    pub media_queries: &'a mut Option<Vec<CssMediaQuery>>,
+
+    pub stats: &'a mut Stats,
+
+    pub mixin_cache: &'a mut MixinCache,
+
+    pub random: &'a mut Random,
+
+    pub import_stack: &'a mut ImportStack,
+
+    pub limits: Limits,
 }
 
 impl<'a> Parser<'a> {
@@ -100,9 +131,14 @@ impl<'a> Parser<'a> {
         self.consume_char_if_exists('\u{feff}');
 
         self.whitespace();
-        stmts.append(&mut self.load_modules()?);
+        if !self.flags.in_plain_css() {
+            stmts.append(&mut self.load_modules()?);
+        }
 
-        while self.toks.peek().is_some() {
+        while let Some(tok) = self.toks.peek() {
+            if tok.kind == '}' {
+                return Err(("unmatched \"}\".", tok.pos).into());
+            }
             stmts.append(&mut self.parse_stmt()?);
             if self.flags.in_function() && !stmts.is_empty() {
                 return Ok(stmts);
@@ -144,6 +180,20 @@ impl<'a> Parser<'a> {
         Err((format!("Expected \"{}\".", ident), self.span_before).into())
     }
 
+    /// Parses the statements of a block whose opening curly brace has
+    /// already been consumed, up to and including its closing curly brace
+    fn parse_block(&mut self) -> SassResult<Vec<Stmt>> {
+        let stmts = self.parse_stmt()?;
+        // functions stop evaluating their body once they've returned
+        if self.flags.in_function() && !stmts.is_empty() {
+            return Ok(stmts);
+        }
+        self.expect_char('}')?;
+        Ok(stmts)
+    }
+
+    /// Parses statements until the end of the input, or until a closing
+    /// curly brace, which is left unconsumed
     fn parse_stmt(&mut self) -> SassResult<Vec<Stmt>> {
         let mut stmts = Vec::new();
         while let Some(Token { kind, pos }) = self.toks.peek() {
@@ -151,12 +201,20 @@ impl<'a> Parser<'a> {
                 return Ok(stmts);
             }
             self.span_before = *pos;
+            self.limits.check(self.span_before)?;
             match kind {
                 '@' => {
+                    let at_rule_start = self.span_before;
                     self.toks.next();
                     let kind_string = self.parse_identifier()?;
                     self.span_before = kind_string.span;
-                    match AtRuleKind::try_from(&kind_string)? {
+                    let kind = AtRuleKind::try_from(&kind_string)?;
+                    if self.flags.in_plain_css() && kind.is_sass_specific() {
+                        return Err(
+                            ("This at-rule isn't allowed in plain CSS.", kind_string.span).into(),
+                        );
+                    }
+                    match kind {
                         AtRuleKind::Import => stmts.append(&mut self.import()?),
                         AtRuleKind::Mixin => self.parse_mixin()?,
                         AtRuleKind::Content => stmts.append(&mut self.parse_content_rule()?),
@@ -182,13 +240,7 @@ impl<'a> Parser<'a> {
                                     .into());
                             }
 
-                            if self.at_root {
-                                stmts.append(&mut self.parse_at_root()?);
-                            } else {
-                                stmts.push(Stmt::AtRoot {
-                                    body: self.parse_at_root()?,
-                                });
-                            }
+                            stmts.push(self.parse_at_root()?);
                         }
                         AtRuleKind::Error => {
                             let Spanned {
@@ -196,11 +248,10 @@ impl<'a> Parser<'a> {
                                 span,
                             } = self.parse_value(false, &|_| false)?;
 
-                            return Err((
+                            return Err(SassError::traced(
                                 message.inspect(span)?.to_string(),
                                 span.merge(kind_string.span),
-                            )
-                                .into());
+                            ));
                         }
                         AtRuleKind::Warn => {
                             let Spanned {
@@ -212,10 +263,13 @@ impl<'a> Parser<'a> {
                                 kind_string.span.merge(*pos);
                                 self.toks.next();
                             }
-                            self.warn(&Spanned {
-                                node: message.to_css_string(span)?,
-                                span,
-                            })
+                            self.warn(
+                                &Spanned {
+                                    node: message.to_log_string(span)?,
+                                    span,
+                                },
+                                "warn",
+                            )
                         }
                         AtRuleKind::Debug => {
                             let Spanned {
@@ -228,7 +282,7 @@ impl<'a> Parser<'a> {
                                 self.toks.next();
                             }
                             self.debug(&Spanned {
-                                node: message.inspect(span)?,
+                                node: message.to_log_string(span)?,
                                 span,
                             })
                         }
@@ -245,6 +299,18 @@ impl<'a> Parser<'a> {
                                     .into());
                             }
 
+                            self.whitespace_or_comment();
+                            match self.toks.next() {
+                                Some(Token { kind: q @ '"', .. })
+                                | Some(Token { kind: q @ '\'', .. }) => {
+                                    self.parse_quoted_string(q)?;
+                                }
+                                Some(Token { pos, .. }) => {
+                                    return Err(("Expected string.", pos).into())
+                                }
+                                None => return Err(("Expected string.", kind_string.span).into()),
+                            }
+
                             read_until_semicolon_or_closing_curly_brace(self.toks)?;
                             if let Some(Token { kind: ';', .. }) = self.toks.peek() {
                                 self.toks.next();
@@ -252,6 +318,8 @@ impl<'a> Parser<'a> {
                             continue;
                         }
                         AtRuleKind::Media => stmts.push(self.parse_media()?),
+                        AtRuleKind::Layer => stmts.append(&mut self.parse_layer()?),
+                        AtRuleKind::Container => stmts.push(self.parse_container()?),
                         AtRuleKind::Unknown(_) => {
                             stmts.push(self.parse_unknown_at_rule(kind_string.node)?)
                         }
@@ -262,15 +330,24 @@ impl<'a> Parser<'a> {
                             )
                                 .into())
                         }
-                        AtRuleKind::Forward => todo!("@forward not yet implemented"),
-                        AtRuleKind::Extend => self.parse_extend()?,
+                        AtRuleKind::Forward => {
+                            return Err(
+                                ("@forward rules are not yet supported.", kind_string.span).into()
+                            )
+                        }
+                        AtRuleKind::Extend => self.parse_extend(at_rule_start)?,
                         AtRuleKind::Supports => stmts.push(self.parse_supports()?),
                         AtRuleKind::Keyframes => {
                             stmts.push(self.parse_keyframes(kind_string.node)?)
                         }
                     }
                 }
-                '$' => self.parse_variable_declaration()?,
+                '$' => {
+                    if self.flags.in_plain_css() {
+                        return Err(("Sass variables aren't allowed in plain CSS.", *pos).into());
+                    }
+                    self.parse_variable_declaration()?
+                }
                 '\t' | '\n' | ' ' | ';' => {
                     self.toks.next();
                     continue;
@@ -280,6 +357,13 @@ impl<'a> Parser<'a> {
                     let comment = self.parse_comment()?;
                     self.whitespace();
                     match comment.node {
+                        Comment::Silent if self.flags.in_plain_css() => {
+                            return Err((
+                                "Silent comments aren't allowed in plain CSS.",
+                                comment.span,
+                            )
+                                .into())
+                        }
                         Comment::Silent => continue,
                         Comment::Loud(s) => {
                             if !self.flags.in_function() {
@@ -291,10 +375,7 @@ impl<'a> Parser<'a> {
                 '\u{0}'..='\u{8}' | '\u{b}'..='\u{1f}' => {
                     return Err(("expected selector.", *pos).into())
                 }
-                '}' => {
-                    self.toks.next();
-                    break;
-                }
+                '}' => break,
                 // dart-sass seems to special-case the error message here?
                 '!' | '{' => return Err(("expected \"}\".", *pos).into()),
                 _ => {
@@ -312,20 +393,29 @@ impl<'a> Parser<'a> {
                             }
                             SelectorOrStyle::Style(property, value) => {
                                 if let Some(value) = value {
-                                    stmts.push(Stmt::Style(Style { property, value }));
+                                    stmts.push(Stmt::Style(self.format_style(Style {
+                                        property,
+                                        value,
+                                        parsed_as_custom_property: false,
+                                    })));
                                 } else {
-                                    stmts.extend(
-                                        self.parse_style_group(property)?
-                                            .into_iter()
-                                            .map(Stmt::Style),
-                                    );
+                                    for style in self.parse_style_group(property)? {
+                                        stmts.push(Stmt::Style(self.format_style(style)));
+                                    }
                                 }
                             }
+                            SelectorOrStyle::CustomProperty(property, value) => {
+                                stmts.push(Stmt::Style(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: true,
+                                }));
+                            }
                             SelectorOrStyle::Selector(init) => {
                                 let selector = self.parse_keyframes_selector(init)?;
                                 self.scopes.enter_new_scope();
 
-                                let body = self.parse_stmt()?;
+                                let body = self.parse_block()?;
                                 self.scopes.exit_scope();
                                 stmts.push(Stmt::KeyframesRuleSet(Box::new(KeyframesRuleSet {
                                     selector,
@@ -342,16 +432,26 @@ impl<'a> Parser<'a> {
                         }
                         SelectorOrStyle::Style(property, value) => {
                             if let Some(value) = value {
-                                stmts.push(Stmt::Style(Style { property, value }));
+                                stmts.push(Stmt::Style(self.format_style(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: false,
+                                })));
                             } else {
-                                stmts.extend(
-                                    self.parse_style_group(property)?
-                                        .into_iter()
-                                        .map(Stmt::Style),
-                                );
+                                for style in self.parse_style_group(property)? {
+                                    stmts.push(Stmt::Style(self.format_style(style)));
+                                }
                             }
                         }
+                        SelectorOrStyle::CustomProperty(property, value) => {
+                            stmts.push(Stmt::Style(Style {
+                                property,
+                                value,
+                                parsed_as_custom_property: true,
+                            }));
+                        }
                         SelectorOrStyle::Selector(init) => {
+                            let rule_start = self.span_before;
                             let at_root = self.at_root;
                             self.at_root = false;
                             let selector = self
@@ -364,15 +464,28 @@ impl<'a> Parser<'a> {
                             self.scopes.enter_new_scope();
                             self.super_selectors.push(selector.clone());
 
-                            let extended_selector = self.extender.add_selector(selector.0, None);
+                            let span = rule_start.merge(selector.0.span);
+                            self.record_nesting_depth(span)?;
+
+                            let extended_selector = self.extender.add_selector(
+                                selector.0,
+                                span,
+                                self.media_queries.clone(),
+                            )?;
 
-                            let body = self.parse_stmt()?;
+                            let body = self.parse_block()?;
                             self.scopes.exit_scope();
                             self.super_selectors.pop();
                             self.at_root = self.super_selectors.is_empty();
+
+                            if self.options.warn_shorthand_conflicts {
+                                self.warn_shorthand_conflicts(&body);
+                            }
+
                             stmts.push(Stmt::RuleSet {
                                 selector: extended_selector,
                                 body,
+                                span,
                             });
                         }
                     }
@@ -471,6 +584,12 @@ impl<'a> Parser<'a> {
                 options: self.options,
                 modules: self.modules,
                 module_config: self.module_config,
+                media_queries: self.media_queries,
+                stats: self.stats,
+                mixin_cache: self.mixin_cache,
+                random: self.random,
+                import_stack: self.import_stack,
+                limits: self.limits,
             },
             allows_parent,
             true,
@@ -511,7 +630,7 @@ impl<'a> Parser<'a> {
                                 self.toks.next();
                                 break;
                             }
-                            ('#', Some(Token { kind: '{', .. })) => {
+                            ('#', Some(Token { kind: '{', .. })) if !self.flags.in_plain_css() => {
                                 self.toks.next();
                                 comment.push_str(&self.parse_interpolation()?.to_css_string(span)?);
                                 continue;
@@ -528,6 +647,14 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_interpolation(&mut self) -> SassResult<Spanned<Value>> {
+        if self.flags.in_plain_css() {
+            return Err((
+                "Interpolation isn't allowed in plain CSS.",
+                self.span_before,
+            )
+                .into());
+        }
+
         let val = self.parse_value(true, &|_| false)?;
 
         self.span_before = val.span;
@@ -617,21 +744,56 @@ impl<'a> Parser<'a> {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
-        let mut params = String::new();
-        self.whitespace_or_comment();
-        if let Some(Token { kind: ';', .. }) | None = self.toks.peek() {
-            self.toks.next();
+        let (params, has_body) = self.parse_at_rule_params()?;
+
+        if !has_body {
             return Ok(Stmt::UnknownAtRule(Box::new(UnknownAtRule {
                 name,
                 super_selector: Selector::new(self.span_before),
-                params: String::new(),
+                params,
                 body: Vec::new(),
+                has_body,
             })));
         }
-        while let Some(tok) = self.toks.next() {
+
+        let mut body = self.parse_block()?;
+
+        // declarations in `@font-face` describe the font itself, so they are
+        // never wrapped in the enclosing style rule
+        if name != "font-face" {
+            body = self.wrap_in_super_selector(body)?;
+        }
+
+        Ok(Stmt::UnknownAtRule(Box::new(UnknownAtRule {
+            name,
+            super_selector: Selector::new(self.span_before),
+            params,
+            body,
+            has_body,
+        })))
+    }
+
+    /// Parses the parameters of an at-rule up to and including the `{` that
+    /// opens its body or the `;` that ends it, interpolating them, and returns
+    /// them along with whether the rule has a body
+    fn parse_at_rule_params(&mut self) -> SassResult<(String, bool)> {
+        let mut params = String::new();
+        let mut has_body = false;
+        self.whitespace_or_comment();
+        while let Some(tok) = self.toks.peek().copied() {
             match tok.kind {
-                '{' => break,
+                '{' => {
+                    self.toks.next();
+                    has_body = true;
+                    break;
+                }
+                ';' => {
+                    self.toks.next();
+                    break;
+                }
+                '}' => break,
                 '#' => {
+                    self.toks.next();
                     if let Some(Token { kind: '{', pos }) = self.toks.peek() {
                         self.span_before = self.span_before.merge(*pos);
                         self.toks.next();
@@ -646,37 +808,100 @@ impl<'a> Parser<'a> {
                     params.push(' ');
                     continue;
                 }
+                q @ '"' | q @ '\'' => {
+                    self.toks.next();
+                    let string = self.parse_quoted_string(q)?;
+                    params.push_str(&string.node.to_css_string(string.span)?);
+                    continue;
+                }
+                '/' => match self.toks.peek_forward(1) {
+                    Some(Token { kind: '/', .. }) => {
+                        self.toks.reset_cursor();
+                        self.read_until_newline();
+                        self.whitespace();
+                        if !params.ends_with(' ') {
+                            params.push(' ');
+                        }
+                        continue;
+                    }
+                    Some(Token { kind: '*', .. }) => {
+                        self.toks.reset_cursor();
+                        self.toks.next();
+                        self.toks.next();
+                        params.push_str("/*");
+                        while let Some(tok) = self.toks.next() {
+                            params.push(tok.kind);
+                            if tok.kind == '*' && self.consume_char_if_exists('/') {
+                                params.push('/');
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+                    _ => self.toks.reset_cursor(),
+                },
+                // `//` is part of a url rather than a comment
+                'u' | 'U' if self.looking_at_url() => {
+                    let start = self.parse_identifier_no_interpolation(false)?;
+                    self.expect_char('(')?;
+                    match self.try_parse_url()? {
+                        Some(url) => params.push_str(&format!("{}{}", start.node, &url[3..])),
+                        None => {
+                            params.push_str(&start.node);
+                            params.push('(');
+                        }
+                    }
+                    continue;
+                }
                 _ => {}
             }
+            self.toks.next();
             params.push(tok.kind);
         }
 
-        let raw_body = self.parse_stmt()?;
-        let mut rules = Vec::with_capacity(raw_body.len());
-        let mut body = Vec::new();
-
-        for stmt in raw_body {
-            match stmt {
-                Stmt::Style(..) => body.push(stmt),
-                _ => rules.push(stmt),
-            }
-        }
+        Ok((params.trim().to_owned(), has_body))
+    }
 
-        if !self.super_selectors.last().is_empty() {
-            body = vec![Stmt::RuleSet {
-                selector: ExtendedSelector::new(self.super_selectors.last().clone().0),
-                body,
-            }];
+    /// Wraps everything in the body of an at-rule that would be emitted inside
+    /// of a style rule in a copy of the enclosing one, ahead of the rules that
+    /// bubbled up, if the at-rule is nested in a style rule
+    fn wrap_in_super_selector(&mut self, body: Vec<Stmt>) -> SassResult<Vec<Stmt>> {
+        if self.super_selectors.last().is_empty() {
+            return Ok(body);
         }
 
+        let (children, mut rules): (Vec<Stmt>, Vec<Stmt>) =
+            body.into_iter().partition(|stmt| match stmt {
+                Stmt::Style(..) | Stmt::Comment(..) => true,
+                Stmt::UnknownAtRule(u) => !u.has_body,
+                _ => false,
+            });
+
+        let selector = self.super_selectors.last().clone().0;
+        let span = selector.span;
+        let mut body = vec![Stmt::RuleSet {
+            selector: self
+                .extender
+                .add_selector(selector, span, self.media_queries.clone())?,
+            body: children,
+            span,
+        }];
         body.append(&mut rules);
 
-        Ok(Stmt::UnknownAtRule(Box::new(UnknownAtRule {
-            name,
-            super_selector: Selector::new(self.span_before),
-            params: params.trim().to_owned(),
-            body,
-        })))
+        Ok(body)
+    }
+
+    /// Whether the next tokens are `url(`, without consuming them
+    fn looking_at_url(&mut self) -> bool {
+        let is_url = match peek_ident_no_interpolation(self.toks, false, self.span_before) {
+            Ok(ident) => {
+                ident.node.eq_ignore_ascii_case("url")
+                    && matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+            }
+            Err(..) => false,
+        };
+        self.toks.reset_cursor();
+        is_url
     }
 
     fn parse_media(&mut self) -> SassResult<Stmt> {
@@ -684,13 +909,38 @@ impl<'a> Parser<'a> {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
-        let query = self.parse_media_query_list()?;
+        let span = self.span_before;
+
+        let query = CssMediaQuery::parse_list(&self.parse_media_query_list()?, span)?;
 
         self.whitespace();
 
         self.expect_char('{')?;
 
-        let raw_body = self.parse_stmt()?;
+        let (query, is_merged) = match self.media_queries {
+            Some(parent) => match CssMediaQuery::merge_lists(parent, &query) {
+                Some(merged) => (merged, true),
+                None => (query, false),
+            },
+            None => (query, false),
+        };
+
+        // the queries have no intersection with those of the enclosing media rule,
+        // so nothing inside of this rule can ever match
+        if query.is_empty() {
+            self.throw_away_until_closing_curly_brace()?;
+            return Ok(Stmt::Media(Box::new(MediaRule {
+                super_selector: Selector::new(span),
+                query,
+                body: Vec::new(),
+                is_merged,
+            })));
+        }
+
+        let old_media_queries = self.media_queries.replace(query.clone());
+        let raw_body = self.parse_block();
+        *self.media_queries = old_media_queries;
+        let raw_body = raw_body?;
 
         let mut rules = Vec::with_capacity(raw_body.len());
         let mut body = Vec::new();
@@ -703,9 +953,14 @@ impl<'a> Parser<'a> {
         }
 
         if !self.super_selectors.last().is_empty() {
+            let selector = self.super_selectors.last().clone().0;
+            let span = selector.span;
             body = vec![Stmt::RuleSet {
-                selector: ExtendedSelector::new(self.super_selectors.last().clone().0),
+                selector: self
+                    .extender
+                    .add_selector(selector, span, Some(query.clone()))?,
                 body,
+                span,
             }];
         }
 
@@ -715,17 +970,76 @@ impl<'a> Parser<'a> {
             super_selector: Selector::new(self.span_before),
             query,
             body,
+            is_merged,
         })))
     }
 
-    fn parse_at_root(&mut self) -> SassResult<Vec<Stmt>> {
+    fn parse_at_root(&mut self) -> SassResult<Stmt> {
         self.whitespace();
-        let mut at_root_has_selector = false;
-        let at_rule_selector = if self.consume_char_if_exists('{') {
-            self.super_selectors.last().clone()
+        let query = if matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
+            let query = self.parse_at_root_query()?;
+            self.whitespace();
+            self.expect_char('{')?;
+            Some(query)
         } else {
-            at_root_has_selector = true;
+            None
+        };
+        let has_selector = query.is_none() && !self.consume_char_if_exists('{');
+        let query = query.unwrap_or_default();
+
+        // rules nested in the body are no longer nested in the enclosing
+        // media rule, so they neither merge with nor extend across it
+        let old_media_queries = if query.excludes_name("media") {
+            Some(self.media_queries.take())
+        } else {
+            None
+        };
+        let body = if query.excludes_style_rules() {
+            self.parse_at_root_body(has_selector)
+        } else {
+            self.parse_at_root_body_in_style_rule()
+        };
+        if let Some(old_media_queries) = old_media_queries {
+            *self.media_queries = old_media_queries;
+        }
+
+        Ok(Stmt::AtRoot { query, body: body? })
+    }
+
+    /// Parses the query of an `@at-root` rule, such as `(without: media)`
+    fn parse_at_root_query(&mut self) -> SassResult<AtRootQuery> {
+        self.expect_char('(')?;
+        self.whitespace_or_comment();
+
+        let kind = self.parse_identifier()?;
+        let include = match kind.node.to_ascii_lowercase().as_str() {
+            "with" => true,
+            "without" => false,
+            _ => return Err(("Expected \"with\" or \"without\".", kind.span).into()),
+        };
+        self.whitespace_or_comment();
+        self.expect_char(':')?;
+        self.whitespace_or_comment();
+
+        let mut names = Vec::new();
+        loop {
+            names.push(self.parse_identifier()?.node.to_ascii_lowercase());
+            self.whitespace_or_comment();
+            if self.consume_char_if_exists(')') {
+                break;
+            }
+        }
+
+        Ok(AtRootQuery::new(include, names))
+    }
+
+    /// Parses the body of an `@at-root` rule that leaves the enclosing style
+    /// rule, so that rules nested in it aren't nested in the parent selector
+    fn parse_at_root_body(&mut self, has_selector: bool) -> SassResult<Vec<Stmt>> {
+        let at_rule_selector = if has_selector {
             self.parse_selector(true, false, String::new())?.0
+        } else {
+            self.super_selectors.last().clone()
         }
         .resolve_parent_selectors(self.super_selectors.last(), false)?;
 
@@ -744,14 +1058,20 @@ impl<'a> Parser<'a> {
             content: self.content,
             flags: self.flags | ContextFlags::IN_AT_ROOT_RULE,
             at_root: true,
-            at_root_has_selector,
+            at_root_has_selector: has_selector,
             extender: self.extender,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
-        .parse_stmt()?
+        .parse_block()?
         .into_iter()
         .filter_map(|s| match s {
             Stmt::Style(..) => {
@@ -761,18 +1081,59 @@ impl<'a> Parser<'a> {
             _ => Some(Ok(s)),
         })
         .collect::<SassResult<Vec<Stmt>>>()?;
+        let span = at_rule_selector.0.span;
         let mut stmts = vec![Stmt::RuleSet {
             selector: ExtendedSelector::new(at_rule_selector.0),
             body: styles,
+            span,
         }];
         stmts.extend(raw_stmts);
         Ok(stmts)
     }
 
-    fn parse_extend(&mut self) -> SassResult<()> {
+    /// Parses the body of an `@at-root` rule that stays inside the enclosing
+    /// style rule, which is nested in the parent selector as usual
+    fn parse_at_root_body_in_style_rule(&mut self) -> SassResult<Vec<Stmt>> {
+        self.whitespace();
+
+        let mut styles = Vec::new();
+        let mut rules = Vec::new();
+        for stmt in self.parse_block()? {
+            match stmt {
+                Stmt::Style(..) => styles.push(stmt),
+                _ => rules.push(stmt),
+            }
+        }
+
+        if !self.super_selectors.last().is_empty() {
+            let selector = self.super_selectors.last().clone().0;
+            let span = selector.span;
+            styles = vec![Stmt::RuleSet {
+                selector: self
+                    .extender
+                    .add_selector(selector, span, self.media_queries.clone())?,
+                body: styles,
+                span,
+            }];
+        }
+
+        styles.append(&mut rules);
+        Ok(styles)
+    }
+
+    /// Parses an `@extend` rule, where `start` is the span of its `@`, so that
+    /// the span of the rule covers all of `@extend <selector>`
+    fn parse_extend(&mut self, start: Span) -> SassResult<()> {
         if self.flags.in_function() {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
+        if self.flags.in_keyframes() {
+            return Err((
+                "@extend may only be used within style rules.",
+                self.span_before,
+            )
+                .into());
+        }
         // todo: track when inside ruleset or `@content`
         // if !self.in_style_rule && !self.in_mixin && !self.in_content_block {
         //     return Err(("@extend may only be used within style rules.", self.span_before).into());
@@ -796,6 +1157,12 @@ impl<'a> Parser<'a> {
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
         .parse_selector(false, true, String::new())?;
 
@@ -804,7 +1171,8 @@ impl<'a> Parser<'a> {
 
         self.consume_char_if_exists(';');
 
-        let extend_rule = ExtendRule::new(value.clone(), is_optional, self.span_before);
+        let span = start.merge(value.0.span);
+        let extend_rule = ExtendRule::new(value.clone(), is_optional, span);
 
         let super_selector = self.super_selectors.last();
 
@@ -832,9 +1200,9 @@ impl<'a> Parser<'a> {
                 super_selector.clone().0,
                 compound.components.first().unwrap(),
                 &extend_rule,
-                &None,
-                self.span_before,
-            )
+                self.media_queries,
+                span,
+            )?;
         }
 
         Ok(())
@@ -845,13 +1213,18 @@ impl<'a> Parser<'a> {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
-        let params = self.parse_media_args()?;
+        self.whitespace_or_comment();
 
-        if params.is_empty() {
+        if matches!(self.toks.peek(), Some(Token { kind: '{', .. }) | None) {
             return Err(("Expected \"not\".", self.span_before).into());
         }
 
-        let raw_body = self.parse_stmt()?;
+        let params = self.parse_supports_condition()?;
+
+        self.whitespace_or_comment();
+        self.expect_char('{')?;
+
+        let raw_body = self.parse_block()?;
 
         let mut rules = Vec::with_capacity(raw_body.len());
         let mut body = Vec::new();
@@ -864,76 +1237,168 @@ impl<'a> Parser<'a> {
         }
 
         if !self.super_selectors.last().is_empty() {
+            let selector = self.super_selectors.last().clone().0;
+            let span = selector.span;
             body = vec![Stmt::RuleSet {
-                selector: ExtendedSelector::new(self.super_selectors.last().clone().0),
+                selector: self
+                    .extender
+                    .add_selector(selector, span, self.media_queries.clone())?,
                 body,
+                span,
             }];
         }
 
         body.append(&mut rules);
 
-        Ok(Stmt::Supports(Box::new(SupportsRule {
-            params: params.trim().to_owned(),
-            body,
-        })))
+        Ok(Stmt::Supports(Box::new(SupportsRule { params, body })))
     }
+}
 
-    // todo: we should use a specialized struct to represent these
-    fn parse_media_args(&mut self) -> SassResult<String> {
-        let mut params = String::new();
-        self.whitespace();
-        while let Some(tok) = self.toks.next() {
-            match tok.kind {
-                '{' => break,
-                '#' => {
-                    if let Some(Token { kind: '{', pos }) = self.toks.peek().cloned() {
-                        self.toks.next();
-                        self.span_before = pos;
-                        let interpolation = self.parse_interpolation()?;
-                        params.push_str(&interpolation.node.to_css_string(interpolation.span)?);
-                        continue;
-                    } else {
-                        params.push(tok.kind);
-                    }
-                }
-                '\n' | ' ' | '\t' => {
-                    self.whitespace();
-                    params.push(' ');
-                    continue;
-                }
-                _ => {}
+impl<'a> Parser<'a> {
+    /// Records the nesting depth of the style rule at `span`, which has just
+    /// been pushed onto `self.super_selectors`
+    fn record_nesting_depth(&mut self, span: Span) -> SassResult<()> {
+        let depth = self.super_selectors.len() - 1;
+
+        if let Some(max) = self.options.max_nesting_depth {
+            if depth > max {
+                return Err((
+                    format!(
+                        "Style rules may not be nested more than {} levels deep.",
+                        max
+                    ),
+                    span,
+                )
+                    .into());
             }
-            params.push(tok.kind);
         }
-        Ok(params)
-    }
-}
 
-impl<'a> Parser<'a> {
-    fn debug(&self, message: &Spanned<Cow<'a, str>>) {
-        if self.options.quiet {
-            return;
+        let file = self.map.find_file(span.low()).name();
+        match self.stats.max_nesting_depth.get_mut(file) {
+            Some(max) => *max = (*max).max(depth),
+            None => {
+                self.stats.max_nesting_depth.insert(file.to_owned(), depth);
+            }
         }
+
+        Ok(())
+    }
+
+    fn debug(&mut self, message: &Spanned<Cow<'a, str>>) {
         let loc = self.map.look_up_span(message.span);
-        eprintln!(
+        let human = format!(
             "{}:{} Debug: {}",
             loc.file.name(),
             loc.begin.line + 1,
             message.node
         );
+        let diagnostic = Diagnostic::new(
+            Severity::Debug,
+            "debug",
+            message.node.to_string(),
+            Some(&loc),
+        );
+        self.report(diagnostic, &human, message.span);
     }
 
-    fn warn(&self, message: &Spanned<Cow<'a, str>>) {
-        if self.options.quiet {
-            return;
+    /// Applies the [`ValueFormatter`](crate::ValueFormatter) from the
+    /// options, if there is one, to the value of `style`
+    ///
+    /// Custom property values are written verbatim, so they are never passed
+    /// here
+    fn format_style(&self, mut style: Style) -> Style {
+        if let Some(formatter) = self.options.value_formatter {
+            let value = mem::replace(&mut style.value.node, Value::Null);
+            style.value.node = formatter::format_value(formatter, value);
         }
+        style
+    }
+
+    fn warn_shorthand_conflicts(&mut self, body: &[Stmt]) {
+        let styles = body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::Style(style) => Some(style),
+                _ => None,
+            })
+            .collect::<Vec<&Style>>();
+
+        for (message, span) in shorthand::conflicts(&styles) {
+            self.warn(
+                &Spanned {
+                    node: Cow::owned(message),
+                    span,
+                },
+                "shorthand-conflict",
+            );
+        }
+    }
+
+    /// Emits a warning, where `code` identifies the kind of warning in
+    /// machine-readable diagnostics
+    pub(crate) fn warn(&mut self, message: &Spanned<Cow<'a, str>>, code: &'static str) {
         let loc = self.map.look_up_span(message.span);
-        eprintln!(
+        let human = format!(
             "Warning: {}\n    {} {}:{}  root stylesheet",
             message.node,
             loc.file.name(),
             loc.begin.line + 1,
             loc.begin.column + 1
         );
+        let diagnostic = Diagnostic::new(
+            Severity::Warning,
+            code,
+            message.node.to_string(),
+            Some(&loc),
+        );
+        self.report(diagnostic, &human, message.span);
+    }
+
+    /// Warns that a deprecated feature was used, unless that deprecation is
+    /// silenced, or fails if it's fatal
+    pub(crate) fn warn_deprecation(
+        &mut self,
+        deprecation: Deprecation,
+        message: &Spanned<Cow<'a, str>>,
+    ) -> SassResult<()> {
+        if self.options.fatal_deprecations.contains(&deprecation) {
+            return Err((
+                format!(
+                    "{}\n\nThis is only an error because you've set the {} deprecation to be fatal.\nRemove this setting if you need to keep using this feature.",
+                    message.node,
+                    deprecation.id()
+                ),
+                message.span,
+            )
+                .into());
+        }
+
+        if !self.options.silence_deprecations.contains(&deprecation) {
+            self.warn(message, deprecation.code());
+        }
+
+        Ok(())
+    }
+
+    /// Passes a warning or `@debug` message to the logger and records it in
+    /// the statistics
+    ///
+    /// Unless compiling verbosely, a deprecation warning is only printed the
+    /// first few times its kind is emitted, and only once for each place
+    /// it's emitted from.
+    fn report(&mut self, diagnostic: Diagnostic, human: &str, span: Span) {
+        if diagnostic.is_deprecation()
+            && !self
+                .stats
+                .deprecation_locations
+                .insert((diagnostic.code, span))
+        {
+            return;
+        }
+
+        if self.options.verbose || !diagnostic.is_repetitive(&self.stats.diagnostics) {
+            self.options.log(&diagnostic, human);
+        }
+        self.stats.diagnostics.push(diagnostic);
     }
 }
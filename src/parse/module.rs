@@ -1,6 +1,6 @@
-use std::{convert::TryFrom, fs};
+use std::convert::TryFrom;
 
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 use peekmore::PeekMore;
 
 use crate::{
@@ -8,16 +8,25 @@ use crate::{
     builtin::modules::{
         declare_module_color, declare_module_list, declare_module_map, declare_module_math,
         declare_module_meta, declare_module_selector, declare_module_string, Module, ModuleConfig,
+        Modules,
     },
     common::Identifier,
     error::SassResult,
-    lexer::Lexer,
-    parse::{common::Comment, Parser, Stmt, VariableValue},
-    scope::Scope,
+    parse::{common::Comment, ContextFlags, Parser, Stmt, VariableValue},
+    scope::{Scope, Scopes},
+    syntax::Syntax,
     utils::peek_ident_no_interpolation,
     Token,
 };
 
+/// The namespace of a module loaded without an `as` clause, which is the last
+/// component of its url, without a leading underscore or any extension
+fn default_namespace(url: &str) -> &str {
+    let basename = url.rsplit('/').next().unwrap_or(url);
+    let basename = basename.strip_prefix('_').unwrap_or(basename);
+    basename.split('.').next().unwrap_or(basename)
+}
+
 impl<'a> Parser<'a> {
     fn parse_module_alias(&mut self) -> SassResult<Option<String>> {
         if let Some(Token { kind: 'a', .. }) | Some(Token { kind: 'A', .. }) = self.toks.peek() {
@@ -68,10 +77,12 @@ impl<'a> Parser<'a> {
                 self.expect_char(':')?;
                 self.whitespace_or_comment();
 
-                let value = self.parse_value(
-                    false,
-                    &|toks| matches!(toks.peek(), Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. }))
-                )?;
+                let value = self.parse_value(false, &|toks| {
+                    matches!(
+                        toks.peek(),
+                        Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })
+                    )
+                })?;
 
                 config.insert(name.map_node(|n| n.into()), value)?;
 
@@ -92,10 +103,17 @@ impl<'a> Parser<'a> {
         Ok(config)
     }
 
+    /// Loads the module `name`, evaluating it unless it has already been
+    /// loaded, and returns it along with the CSS it emits
+    ///
+    /// A module only emits CSS the first time it's loaded, unless
+    /// `emit_loaded_css` is set, as it is for `meta.load-css()`.
     pub fn load_module(
         &mut self,
         name: &str,
         config: &mut ModuleConfig,
+        span: Span,
+        emit_loaded_css: bool,
     ) -> SassResult<(Module, Vec<Stmt>)> {
         Ok(match name {
             "sass:color" => (declare_module_color(), Vec::new()),
@@ -106,35 +124,86 @@ impl<'a> Parser<'a> {
             "sass:selector" => (declare_module_selector(), Vec::new()),
             "sass:string" => (declare_module_string(), Vec::new()),
             _ => {
-                if let Some(import) = self.find_import(name.as_ref()) {
-                    let mut global_scope = Scope::new();
+                if let Some(import) = self.find_import(name.as_ref(), span)? {
+                    self.check_import_allowed(name, &import, self.span_before)?;
+                    self.stats.loaded_paths.insert(import.clone());
+
+                    let loaded = self.import_stack.loaded_module(&import, self.options.fs);
+                    if let Some(module) = &loaded {
+                        if !config.is_empty() {
+                            return Err((
+                                "This module was already loaded, so it can't be configured using \"with\".",
+                                span,
+                            )
+                                .into());
+                        }
 
-                    let file = self
-                        .map
-                        .add_file(name.to_owned(), String::from_utf8(fs::read(&import)?)?);
+                        // `meta.load-css()` emits the module's CSS nested in
+                        // whichever style rule it's called from, so the module is
+                        // evaluated again for that, although its members are
+                        // still those of the copy that was loaded first
+                        if !emit_loaded_css {
+                            return Ok((module.clone(), Vec::new()));
+                        }
+                    }
 
+                    let mut global_scope = Scope::new();
+
+                    let file = self.map.add_file(
+                        self.options.source_url(import.to_string_lossy().into()),
+                        String::from_utf8(self.options.fs.read(&import)?)?,
+                    );
+
+                    self.import_stack.push(
+                        &import,
+                        span,
+                        self.map,
+                        self.options.fs,
+                        self.options.unicode_error_messages,
+                    )?;
+
+                    let mut modules = Modules::default();
+
+                    // a module's top level is its own, even when `meta.load-css()`
+                    // loads it inside a style rule, mixin, or control flow rule,
+                    // although its CSS is still nested in the enclosing rule
+                    let has_parent_selector = !self.super_selectors.last().is_empty();
+                    let syntax = Syntax::for_path(&import);
                     let stmts = Parser {
-                        toks: &mut Lexer::new(&file)
-                            .collect::<Vec<Token>>()
+                        toks: &mut self
+                            .options
+                            .tokenize(syntax, &import, &file)?
                             .into_iter()
                             .peekmore(),
                         map: self.map,
                         path: &import,
-                        scopes: self.scopes,
+                        scopes: &mut Scopes::new(),
                         global_scope: &mut global_scope,
                         super_selectors: self.super_selectors,
                         span_before: file.span.subspan(0, 0),
                         content: self.content,
-                        flags: self.flags,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
+                        flags: ContextFlags::empty().for_syntax(syntax),
+                        at_root: true,
+                        at_root_has_selector: has_parent_selector,
                         extender: self.extender,
                         content_scopes: self.content_scopes,
                         options: self.options,
-                        modules: self.modules,
+                        modules: &mut modules,
                         module_config: config,
+                        media_queries: &mut None,
+                        stats: self.stats,
+                        mixin_cache: self.mixin_cache,
+                        random: self.random,
+                        import_stack: self.import_stack,
+                        limits: self.limits,
                     }
-                    .parse()?;
+                    .parse()
+                    .map_err(|err| err.in_member("@use", span));
+
+                    self.import_stack.pop();
+                    self.modules.inherit(modules);
+
+                    let stmts = stmts?;
 
                     if !config.is_empty() {
                         return Err((
@@ -144,7 +213,20 @@ impl<'a> Parser<'a> {
                             .into());
                     }
 
-                    (Module::new_from_scope(global_scope, false), stmts)
+                    let module = match loaded {
+                        Some(module) => module,
+                        None => {
+                            let module = Module::new_from_scope(global_scope, false);
+                            self.import_stack.insert_module(
+                                &import,
+                                self.options.fs,
+                                module.clone(),
+                            );
+                            module
+                        }
+                    };
+
+                    (module, stmts)
                 } else {
                     return Err(("Can't find stylesheet to import.", self.span_before).into());
                 }
@@ -206,7 +288,7 @@ impl<'a> Parser<'a> {
                     self.expect_char(';')?;
 
                     let (module, mut stmts) =
-                        self.load_module(module_name.as_ref(), &mut config)?;
+                        self.load_module(module_name.as_ref(), &mut config, span, false)?;
 
                     comments.append(&mut stmts);
 
@@ -230,7 +312,7 @@ impl<'a> Parser<'a> {
                             "sass:meta" => "meta".to_owned(),
                             "sass:selector" => "selector".to_owned(),
                             "sass:string" => "string".to_owned(),
-                            _ => module_name.into_owned(),
+                            _ => default_namespace(&module_name).to_owned(),
                         },
                     };
 
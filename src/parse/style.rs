@@ -1,6 +1,7 @@
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 
 use crate::{
+    common::QuoteKind,
     error::SassResult,
     interner::InternedString,
     style::Style,
@@ -22,6 +23,42 @@ impl<'a> Parser<'a> {
                     self.toks.reset_cursor();
                     break;
                 }
+                '#' => {
+                    toks.push(*tok);
+                    self.toks.peek_forward(1);
+                    if let Some(tok @ Token { kind: '{', .. }) = self.toks.peek().copied() {
+                        // interpolation may itself contain braces, so it is
+                        // consumed as a whole
+                        toks.push(tok);
+                        self.toks.peek_forward(1);
+                        let mut scope = 0;
+                        while let Some(tok) = self.toks.peek().copied() {
+                            toks.push(tok);
+                            self.toks.peek_forward(1);
+                            match tok.kind {
+                                // braces inside of strings aren't counted
+                                quote @ '"' | quote @ '\'' => {
+                                    while let Some(tok) = self.toks.peek().copied() {
+                                        toks.push(tok);
+                                        self.toks.peek_forward(1);
+                                        if tok.kind == '\\' {
+                                            if let Some(tok) = self.toks.peek().copied() {
+                                                toks.push(tok);
+                                                self.toks.peek_forward(1);
+                                            }
+                                        } else if tok.kind == quote {
+                                            break;
+                                        }
+                                    }
+                                }
+                                '{' => scope += 1,
+                                '}' if scope == 0 => break,
+                                '}' => scope -= 1,
+                                _ => {}
+                            }
+                        }
+                    }
+                }
                 '{' => {
                     self.toks.reset_cursor();
                     return None;
@@ -108,12 +145,31 @@ impl<'a> Parser<'a> {
             }
         }
 
+        // only a name written with a literal `--` is a custom property, so
+        // `#{--a}: b` is evaluated as usual
+        let custom_property_start = match (self.toks.peek().copied(), self.toks.peek_next()) {
+            (Some(Token { kind: '-', pos }), Some(Token { kind: '-', .. })) => Some(pos),
+            _ => None,
+        };
+        self.toks.reset_cursor();
+
         let mut property = self.parse_identifier()?.node;
         let whitespace_after_property = self.whitespace_or_comment();
 
         match self.toks.peek() {
             Some(Token { kind: ':', .. }) => {
                 self.toks.next();
+                if let Some(start) = custom_property_start {
+                    let value = self.parse_custom_property_value(start)?;
+                    return Ok(SelectorOrStyle::CustomProperty(
+                        InternedString::get_or_intern(property),
+                        Box::new(value),
+                    ));
+                }
+                let looking_at_interpolation =
+                    matches!(self.toks.peek(), Some(Token { kind: '#', .. }))
+                        && matches!(self.toks.peek_next(), Some(Token { kind: '{', .. }));
+                self.toks.reset_cursor();
                 if let Some(Token { kind, .. }) = self.toks.peek() {
                     return Ok(match kind {
                         ':' => {
@@ -123,7 +179,7 @@ impl<'a> Parser<'a> {
                             property.push(':');
                             SelectorOrStyle::Selector(property)
                         }
-                        c if is_name(*c) => {
+                        c if is_name(*c) || looking_at_interpolation => {
                             if let Some(toks) =
                                 self.parse_style_value_when_no_space_after_semicolon()
                             {
@@ -191,6 +247,128 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses the value of a custom property like `--color: red` verbatim,
+    /// evaluating only interpolation
+    ///
+    /// `start` is where the property name began. Continuation lines are
+    /// stripped of the indentation they share, up to the column of the
+    /// name, so that they can be reindented relative to the property
+    fn parse_custom_property_value(&mut self, start: Span) -> SassResult<Spanned<Value>> {
+        let mut value = String::new();
+        let mut brackets = Vec::new();
+        let mut span = start;
+
+        while let Some(tok) = self.toks.peek().copied() {
+            match tok.kind {
+                ';' | '}' if brackets.is_empty() => break,
+                ')' | ']' if brackets.is_empty() => {
+                    return Err(("expected \";\".", tok.pos).into());
+                }
+                '(' | '[' | '{' => {
+                    self.toks.next();
+                    value.push(tok.kind);
+                    brackets.push(match tok.kind {
+                        '(' => ')',
+                        '[' => ']',
+                        _ => '}',
+                    });
+                }
+                ')' | ']' | '}' => {
+                    let expected = brackets.pop().unwrap();
+                    if tok.kind != expected {
+                        return Err((format!("expected \"{}\".", expected), tok.pos).into());
+                    }
+                    self.toks.next();
+                    value.push(tok.kind);
+                }
+                '#' if matches!(self.toks.peek_next(), Some(Token { kind: '{', .. })) => {
+                    self.toks.next();
+                    self.toks.next();
+                    value.push_str(&self.parse_interpolation_as_string()?);
+                }
+                '/' if matches!(self.toks.peek_next(), Some(Token { kind: '*', .. })) => {
+                    self.toks.next();
+                    self.toks.next();
+                    value.push_str("/*");
+                    let mut last = '/';
+                    loop {
+                        let tok = self.toks.next().ok_or(("expected more input.", tok.pos))?;
+                        value.push(tok.kind);
+                        if last == '*' && tok.kind == '/' {
+                            break;
+                        }
+                        last = tok.kind;
+                    }
+                }
+                q @ '"' | q @ '\'' => {
+                    self.toks.next();
+                    value.push(q);
+                    loop {
+                        let tok = match self.toks.next() {
+                            Some(tok) if tok.kind != '\n' => tok,
+                            Some(..) | None => {
+                                return Err((format!("Expected {}.", q), tok.pos).into())
+                            }
+                        };
+                        match tok.kind {
+                            '#' if matches!(self.toks.peek(), Some(Token { kind: '{', .. })) => {
+                                self.toks.next();
+                                value.push_str(&self.parse_interpolation_as_string()?);
+                            }
+                            '\\' => {
+                                value.push('\\');
+                                if let Some(next) = self.toks.next() {
+                                    value.push(next.kind);
+                                }
+                            }
+                            c => {
+                                value.push(c);
+                                if c == q {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                '\\' => {
+                    self.toks.next();
+                    value.push('\\');
+                    if let Some(next) = self.toks.next() {
+                        value.push(next.kind);
+                    }
+                }
+                c => {
+                    self.toks.next();
+                    value.push(c);
+                }
+            }
+            self.toks.reset_cursor();
+            span = span.merge(tok.pos);
+        }
+        self.toks.reset_cursor();
+
+        if let Some(expected) = brackets.pop() {
+            return Err((format!("expected \"{}\".", expected), span).into());
+        }
+
+        // trailing whitespace is dropped, unless it is escaped
+        while value.ends_with(|c: char| c.is_ascii_whitespace())
+            && !value[..value.len() - 1].ends_with('\\')
+        {
+            value.pop();
+        }
+
+        let name_column = self.map.look_up_pos(start.low()).position.column;
+
+        Ok(Spanned {
+            node: Value::String(
+                strip_shared_indentation(&value, name_column),
+                QuoteKind::None,
+            ),
+            span,
+        })
+    }
+
     fn parse_style_value(&mut self) -> SassResult<Spanned<Value>> {
         self.parse_value(false, &|_| false)
     }
@@ -204,6 +382,11 @@ impl<'a> Parser<'a> {
         while let Some(tok) = self.toks.peek().cloned() {
             match tok.kind {
                 '{' => {
+                    if self.flags.in_plain_css() {
+                        return Err(
+                            ("Nested declarations aren't allowed in plain CSS.", tok.pos).into(),
+                        );
+                    }
                     self.toks.next();
                     self.whitespace();
                     loop {
@@ -229,20 +412,36 @@ impl<'a> Parser<'a> {
                         let value = Box::new(self.parse_style_value()?);
                         match self.toks.peek() {
                             Some(Token { kind: '}', .. }) => {
-                                styles.push(Style { property, value });
+                                styles.push(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: false,
+                                });
                             }
                             Some(Token { kind: ';', .. }) => {
                                 self.toks.next();
                                 self.whitespace();
-                                styles.push(Style { property, value });
+                                styles.push(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: false,
+                                });
                             }
                             Some(Token { kind: '{', .. }) => {
-                                styles.push(Style { property, value });
+                                styles.push(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: false,
+                                });
                                 styles.append(&mut self.parse_style_group(property)?);
                             }
                             Some(..) | None => {
                                 self.whitespace();
-                                styles.push(Style { property, value });
+                                styles.push(Style {
+                                    property,
+                                    value,
+                                    parsed_as_custom_property: false,
+                                });
                             }
                         }
                         if let Some(tok) = self.toks.peek() {
@@ -272,6 +471,7 @@ impl<'a> Parser<'a> {
                             let mut v = vec![Style {
                                 property: super_property,
                                 value: Box::new(value),
+                                parsed_as_custom_property: false,
                             }];
                             v.append(&mut self.parse_style_group(super_property)?);
                             return Ok(v);
@@ -281,6 +481,7 @@ impl<'a> Parser<'a> {
                     return Ok(vec![Style {
                         property: super_property,
                         value: Box::new(value),
+                        parsed_as_custom_property: false,
                     }]);
                 }
             }
@@ -288,3 +489,35 @@ impl<'a> Parser<'a> {
         Ok(styles)
     }
 }
+
+/// Removes the indentation that every non-blank line after the first shares,
+/// but no more than `max`, and empties lines that are only whitespace
+fn strip_shared_indentation(value: &str, max: usize) -> String {
+    let mut lines = value.split('\n');
+    let mut buf = lines.next().unwrap_or_default().to_owned();
+    let rest = lines.collect::<Vec<&str>>();
+
+    let is_indentation = |c: char| c == ' ' || c == '\t';
+    let shared = rest
+        .iter()
+        .filter_map(|line| {
+            let content = line.trim_start_matches(is_indentation);
+            if content.is_empty() {
+                None
+            } else {
+                Some(line.len() - content.len())
+            }
+        })
+        .min()
+        .unwrap_or(0)
+        .min(max);
+
+    for line in rest {
+        buf.push('\n');
+        if !line.trim_start_matches(is_indentation).is_empty() {
+            buf.push_str(&line[shared..]);
+        }
+    }
+
+    buf
+}
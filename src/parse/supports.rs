@@ -0,0 +1,176 @@
+use crate::{
+    error::SassResult,
+    utils::{is_name_start, peek_ident_no_interpolation},
+    Token,
+};
+
+use super::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parses the condition of an `@supports` rule, evaluating any SassScript
+    /// it contains
+    pub(super) fn parse_supports_condition(&mut self) -> SassResult<String> {
+        if self.scan_identifier("not")? {
+            self.whitespace_or_comment();
+            return Ok(format!(
+                "not {}",
+                self.parse_supports_condition_in_parens()?
+            ));
+        }
+
+        let mut buf = self.parse_supports_condition_in_parens()?;
+        self.whitespace_or_comment();
+
+        let mut operator = None;
+        loop {
+            let next = match operator {
+                Some(op) => op,
+                None if self.scan_identifier("and")? => "and",
+                None if self.scan_identifier("or")? => "or",
+                None => break,
+            };
+
+            if operator.is_some() && !self.scan_identifier(next)? {
+                break;
+            }
+
+            operator = Some(next);
+            self.whitespace_or_comment();
+
+            buf.push(' ');
+            buf.push_str(next);
+            buf.push(' ');
+            buf.push_str(&self.parse_supports_condition_in_parens()?);
+            self.whitespace_or_comment();
+        }
+
+        Ok(buf)
+    }
+
+    fn parse_supports_condition_in_parens(&mut self) -> SassResult<String> {
+        match self.toks.peek() {
+            Some(Token { kind: '#', .. }) => {
+                self.toks.next();
+                self.expect_char('{')?;
+                return Ok(self.parse_interpolation_as_string()?.into_owned());
+            }
+            Some(Token { kind: '(', .. }) => {}
+            Some(..) => {
+                // a function, such as `selector(a > b)`
+                let name = self.parse_identifier()?.node;
+                self.expect_char('(')?;
+                return Ok(format!(
+                    "{}({})",
+                    name,
+                    self.parse_supports_declaration_value()?
+                ));
+            }
+            None => return Err(("expected \"(\".", self.span_before).into()),
+        }
+
+        self.expect_char('(')?;
+        self.whitespace_or_comment();
+
+        if matches!(self.toks.peek(), Some(Token { kind: '(', .. })) || self.looking_at_negation() {
+            let condition = self.parse_supports_condition()?;
+            self.expect_char(')')?;
+            return Ok(format!("({})", condition));
+        }
+
+        Ok(format!("({})", self.parse_supports_declaration()?))
+    }
+
+    /// Parses a declaration such as `display: grid`, up to and including the
+    /// closing parenthesis that follows it
+    pub(super) fn parse_supports_declaration(&mut self) -> SassResult<String> {
+        let is_custom_property = matches!(self.toks.peek(), Some(Token { kind: '-', .. }))
+            && matches!(self.toks.peek_forward(1), Some(Token { kind: '-', .. }));
+        self.toks.reset_cursor();
+
+        if is_custom_property {
+            let name = self.parse_identifier()?.node;
+            self.whitespace_or_comment();
+            self.expect_char(':')?;
+            return Ok(format!(
+                "{}:{}",
+                name,
+                self.parse_supports_declaration_value()?
+            ));
+        }
+
+        let name = self.parse_value(false, &|toks| {
+            matches!(toks.peek(), Some(Token { kind: ':', .. }))
+        })?;
+        self.expect_char(':')?;
+        self.whitespace_or_comment();
+
+        let value = self.parse_value(false, &|toks| {
+            matches!(toks.peek(), Some(Token { kind: ')', .. }))
+        })?;
+        self.expect_char(')')?;
+
+        Ok(format!(
+            "{}: {}",
+            name.node.unquote().to_css_string(name.span)?,
+            value.node.to_css_string(value.span)?
+        ))
+    }
+
+    /// Whether the next tokens are `not` followed by a condition
+    pub(super) fn looking_at_negation(&mut self) -> bool {
+        let is_negation = match peek_ident_no_interpolation(self.toks, false, self.span_before) {
+            Ok(ident) if ident.node.eq_ignore_ascii_case("not") => !matches!(
+                self.toks.peek(),
+                Some(Token { kind, .. }) if is_name_start(*kind) || *kind == ':' || *kind == '-'
+            ),
+            _ => false,
+        };
+        self.toks.reset_cursor();
+        is_negation
+    }
+
+    /// Consumes text up to and including the closing parenthesis that
+    /// matches an already consumed opening parenthesis, resolving any
+    /// interpolation but otherwise leaving the text unchanged
+    pub(super) fn parse_supports_declaration_value(&mut self) -> SassResult<String> {
+        let mut buf = String::new();
+        let mut depth = 1;
+
+        while let Some(tok) = self.toks.next() {
+            match tok.kind {
+                '#' if matches!(self.toks.peek(), Some(Token { kind: '{', .. })) => {
+                    self.toks.next();
+                    buf.push_str(&self.parse_interpolation_as_string()?);
+                    continue;
+                }
+                q @ '"' | q @ '\'' => {
+                    buf.push(q);
+                    while let Some(tok) = self.toks.next() {
+                        buf.push(tok.kind);
+                        match tok.kind {
+                            '\\' => {
+                                if let Some(next) = self.toks.next() {
+                                    buf.push(next.kind);
+                                }
+                            }
+                            c if c == q => break,
+                            _ => {}
+                        }
+                    }
+                    continue;
+                }
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(buf);
+                    }
+                }
+                _ => {}
+            }
+            buf.push(tok.kind);
+        }
+
+        Err(("expected \")\".", self.span_before).into())
+    }
+}
@@ -0,0 +1,265 @@
+use peekmore::PeekMore;
+
+use crate::{
+    builtin::GLOBAL_FUNCTIONS,
+    common::Identifier,
+    error::SassResult,
+    utils::{is_name_start, peek_until_closing_paren},
+    value::{
+        CalculationArg, CalculationName, CalculationOperator, SassCalculation, SassFunction, Value,
+    },
+    Token,
+};
+
+use super::super::Parser;
+
+impl<'a> Parser<'a> {
+    /// Tries to parse the arguments to a CSS math function as a calculation,
+    /// after its opening parenthesis
+    ///
+    /// Returns `None` without consuming anything if the arguments aren't
+    /// valid calculation syntax.
+    pub(super) fn try_parse_calculation(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Option<Value>> {
+        let toks = peek_until_closing_paren(self.toks)?;
+
+        let args = Parser {
+            toks: &mut toks.into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            content_scopes: self.content_scopes,
+            options: self.options,
+            modules: self.modules,
+            module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
+        }
+        .parse_calculation_args(name)?;
+
+        match args {
+            Some(args) => {
+                self.toks.truncate_iterator_to_cursor();
+                Ok(Some(SassCalculation::new(name, args)))
+            }
+            None => {
+                self.toks.reset_cursor();
+                Ok(None)
+            }
+        }
+    }
+
+    fn parse_calculation_args(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Option<Vec<CalculationArg>>> {
+        let mut args = Vec::new();
+
+        loop {
+            self.whitespace_or_comment();
+
+            match self.parse_calculation_sum()? {
+                Some(arg) => args.push(arg),
+                None => return Ok(None),
+            }
+
+            self.whitespace_or_comment();
+
+            match self.toks.next() {
+                Some(Token { kind: ',', .. }) if name != CalculationName::Calc => {}
+                Some(Token { kind: ')', .. }) => break,
+                _ => return Ok(None),
+            }
+        }
+
+        if name == CalculationName::Clamp && args.len() != 3 {
+            return Ok(None);
+        }
+
+        Ok(Some(args))
+    }
+
+    fn parse_calculation_sum(&mut self) -> SassResult<Option<CalculationArg>> {
+        let mut sum = match self.parse_calculation_product()? {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        loop {
+            self.whitespace_or_comment();
+
+            let op = match self.toks.peek() {
+                Some(Token { kind: '+', .. }) => CalculationOperator::Plus,
+                Some(Token { kind: '-', .. }) => CalculationOperator::Minus,
+                _ => return Ok(Some(sum)),
+            };
+
+            self.toks.next();
+            self.whitespace_or_comment();
+
+            match self.parse_calculation_product()? {
+                Some(rhs) => sum = CalculationArg::operate(sum, op, rhs),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn parse_calculation_product(&mut self) -> SassResult<Option<CalculationArg>> {
+        let mut product = match self.parse_calculation_value()? {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        loop {
+            self.whitespace_or_comment();
+
+            let op = match self.toks.peek() {
+                Some(Token { kind: '*', .. }) => CalculationOperator::Mul,
+                Some(Token { kind: '/', .. }) => CalculationOperator::Div,
+                _ => return Ok(Some(product)),
+            };
+
+            self.toks.next();
+            self.whitespace_or_comment();
+
+            match self.parse_calculation_value()? {
+                Some(rhs) => product = CalculationArg::operate(product, op, rhs),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn parse_calculation_value(&mut self) -> SassResult<Option<CalculationArg>> {
+        let kind = match self.toks.peek() {
+            Some(tok) => tok.kind,
+            None => return Ok(None),
+        };
+        let next = self.toks.peek_next().map(|tok| tok.kind);
+        self.toks.reset_cursor();
+
+        Ok(Some(match kind {
+            '(' => {
+                self.toks.next();
+                self.whitespace_or_comment();
+
+                let inner = match self.parse_calculation_sum()? {
+                    Some(arg) => arg,
+                    None => return Ok(None),
+                };
+
+                self.whitespace_or_comment();
+
+                if !self.consume_char_if_exists(')') {
+                    return Ok(None);
+                }
+
+                inner
+            }
+            '0'..='9' | '.' => self.parse_calculation_number()?,
+            '+' | '-' if matches!(next, Some('0'..='9') | Some('.')) => {
+                self.toks.next();
+                match self.parse_calculation_number()? {
+                    CalculationArg::Number(num, unit) if kind == '-' => {
+                        CalculationArg::Number(-num, unit)
+                    }
+                    arg => arg,
+                }
+            }
+            '$' => {
+                self.toks.next();
+                let name = self
+                    .parse_identifier_no_interpolation(false)?
+                    .map_node(Into::into);
+                let value = self.scopes.get_var(name, self.global_scope)?.clone();
+                return Ok(CalculationArg::from_value(value));
+            }
+            '#' if next == Some('{') => {
+                self.toks.next();
+                self.toks.next();
+                let interpolation = self.parse_interpolation()?;
+                CalculationArg::Interpolation(
+                    interpolation
+                        .node
+                        .unquote()
+                        .to_css_string(interpolation.span)?
+                        .into_owned(),
+                )
+            }
+            '-' if !matches!(next, Some(c) if is_name_start(c) || c == '-' || c == '\\') => {
+                return Ok(None)
+            }
+            c if is_name_start(c) || c == '-' || c == '\\' => {
+                return self.parse_calculation_function();
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    fn parse_calculation_number(&mut self) -> SassResult<CalculationArg> {
+        match self.parse_number_value()?.node {
+            Value::Dimension(Some(num), unit, _) => Ok(CalculationArg::Number(num, unit)),
+            _ => unreachable!("parsed numbers are never NaN"),
+        }
+    }
+
+    /// Parses a function call within a calculation, which may be a nested
+    /// calculation, a Sass function, or a plain CSS function such as `var()`
+    fn parse_calculation_function(&mut self) -> SassResult<Option<CalculationArg>> {
+        let name = self.parse_identifier()?.node;
+
+        if !self.consume_char_if_exists('(') {
+            return Ok(None);
+        }
+
+        let lower = name.to_ascii_lowercase();
+        let calculation = match lower.as_str() {
+            "calc" => Some(CalculationName::Calc),
+            "min" => Some(CalculationName::Min),
+            "max" => Some(CalculationName::Max),
+            "clamp" => Some(CalculationName::Clamp),
+            _ => None,
+        };
+
+        if let Some(calculation) = calculation {
+            return match self.parse_calculation_args(calculation)? {
+                Some(args) => Ok(CalculationArg::from_value(SassCalculation::new(
+                    calculation,
+                    args,
+                ))),
+                None => Ok(None),
+            };
+        }
+
+        let as_ident = Identifier::from(&name);
+        let function = match self.scopes.get_fn(as_ident, self.global_scope) {
+            Some(function) => Some(function),
+            None => GLOBAL_FUNCTIONS
+                .get(as_ident.as_str())
+                .map(|f| SassFunction::Builtin(f.clone(), as_ident)),
+        };
+
+        if let Some(function) = function {
+            let args = self.parse_call_args()?;
+            let value = function.call(args, self)?;
+            return Ok(CalculationArg::from_value(value));
+        }
+
+        let mut buf = name;
+        self.parse_calc_args(&mut buf, true)?;
+        Ok(Some(CalculationArg::String(buf)))
+    }
+}
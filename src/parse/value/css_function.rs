@@ -4,18 +4,22 @@ use codemap::Spanned;
 
 use crate::{
     error::SassResult,
-    utils::{
-        as_hex, hex_char_for, is_name, peek_ident_no_interpolation, peek_until_closing_curly_brace,
-        peek_whitespace,
-    },
-    value::Value,
+    utils::{as_hex, hex_char_for, is_name, peek_until_closing_curly_brace, peek_whitespace},
+    value::{CalculationName, SassCalculation, Value},
     Token,
 };
 
 use super::super::Parser;
 
 impl<'a> Parser<'a> {
-    pub(super) fn parse_calc_args(&mut self, buf: &mut String) -> SassResult<()> {
+    /// Parses the arguments to a special function such as `calc()` or
+    /// `element()` verbatim, evaluating only interpolation and, if
+    /// `substitute_variables` is `true`, variables
+    pub(super) fn parse_calc_args(
+        &mut self,
+        buf: &mut String,
+        substitute_variables: bool,
+    ) -> SassResult<()> {
         buf.reserve(2);
         buf.push('(');
         let mut nesting = 0;
@@ -55,6 +59,16 @@ impl<'a> Parser<'a> {
                     }
                     buf.push('"');
                 }
+                '$' if substitute_variables => {
+                    let name = self
+                        .parse_identifier_no_interpolation(false)?
+                        .map_node(Into::into);
+                    let value = self
+                        .scopes
+                        .get_var(name, self.global_scope)?
+                        .to_css_string(name.span)?;
+                    buf.push_str(&value);
+                }
                 c => buf.push(c),
             }
         }
@@ -77,7 +91,7 @@ impl<'a> Parser<'a> {
                     string.push(tok.kind);
                 }
                 '(' => {
-                    self.parse_calc_args(&mut string)?;
+                    self.parse_calc_args(&mut string, false)?;
                     break;
                 }
                 _ => return Err(("expected \"(\".", span).into()),
@@ -86,7 +100,7 @@ impl<'a> Parser<'a> {
         Ok(string)
     }
 
-    pub(super) fn try_parse_url(&mut self) -> SassResult<Option<String>> {
+    pub(in crate::parse) fn try_parse_url(&mut self) -> SassResult<Option<String>> {
         let mut buf = String::from("url(");
         peek_whitespace(self.toks);
         while let Some(tok) = self.toks.peek() {
@@ -134,216 +148,30 @@ impl<'a> Parser<'a> {
         Ok(None)
     }
 
-    pub(super) fn try_parse_min_max(
-        &mut self,
-        fn_name: &str,
-        allow_comma: bool,
-    ) -> SassResult<Option<String>> {
-        let mut buf = if allow_comma {
-            format!("{}(", fn_name)
-        } else {
-            String::new()
-        };
-        peek_whitespace(self.toks);
-        while let Some(tok) = self.toks.peek() {
-            let kind = tok.kind;
-            match kind {
-                '+' | '-' | '0'..='9' => {
-                    self.toks.advance_cursor();
-                    if let Some(number) = self.peek_number()? {
-                        buf.push(kind);
-                        buf.push_str(&number);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                '#' => {
-                    self.toks.advance_cursor();
-                    if let Some(Token { kind: '{', .. }) = self.toks.peek() {
-                        self.toks.advance_cursor();
-                        let interpolation = self.peek_interpolation()?;
-                        match interpolation.node {
-                            Value::String(ref s, ..) => buf.push_str(s),
-                            v => buf.push_str(v.to_css_string(interpolation.span)?.borrow()),
-                        };
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                'c' | 'C' => {
-                    if let Some(name) = self.try_parse_min_max_function("calc")? {
-                        buf.push_str(&name);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                'e' | 'E' => {
-                    if let Some(name) = self.try_parse_min_max_function("env")? {
-                        buf.push_str(&name);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                'v' | 'V' => {
-                    if let Some(name) = self.try_parse_min_max_function("var")? {
-                        buf.push_str(&name);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                '(' => {
-                    self.toks.advance_cursor();
-                    buf.push('(');
-                    if let Some(val) = self.try_parse_min_max(fn_name, false)? {
-                        buf.push_str(&val);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                'm' | 'M' => {
-                    self.toks.advance_cursor();
-                    match self.toks.peek() {
-                        Some(Token { kind: 'i', .. }) | Some(Token { kind: 'I', .. }) => {
-                            self.toks.advance_cursor();
-                            if !matches!(self.toks.peek(), Some(Token { kind: 'n', .. }) | Some(Token { kind: 'N', .. }))
-                            {
-                                return Ok(None);
-                            }
-                            buf.push_str("min(")
-                        }
-                        Some(Token { kind: 'a', .. }) | Some(Token { kind: 'A', .. }) => {
-                            self.toks.advance_cursor();
-                            if !matches!(self.toks.peek(), Some(Token { kind: 'x', .. }) | Some(Token { kind: 'X', .. }))
-                            {
-                                return Ok(None);
-                            }
-                            buf.push_str("max(")
-                        }
-                        _ => return Ok(None),
-                    }
-
-                    self.toks.advance_cursor();
-
-                    if !matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
-                        return Ok(None);
-                    }
-
-                    if let Some(val) = self.try_parse_min_max(fn_name, false)? {
-                        buf.push_str(&val);
-                    } else {
-                        return Ok(None);
-                    }
-                }
-                _ => return Ok(None),
-            }
-
-            peek_whitespace(self.toks);
-
-            let next = match self.toks.peek() {
-                Some(tok) => tok,
-                None => return Ok(None),
-            };
-
-            match next.kind {
-                ')' => {
-                    self.toks.advance_cursor();
-                    buf.push(')');
-                    return Ok(Some(buf));
-                }
-                '+' | '-' | '*' | '/' => {
-                    buf.push(' ');
-                    buf.push(next.kind);
-                    buf.push(' ');
-                    self.toks.advance_cursor();
-                }
-                ',' => {
-                    if !allow_comma {
-                        return Ok(None);
-                    }
-                    self.toks.advance_cursor();
-                    buf.push(',');
-                    buf.push(' ');
-                }
-                _ => return Ok(None),
-            }
-
-            peek_whitespace(self.toks);
+    /// Parses a call to `calc()`, `clamp()`, `min()` or `max()`, after its
+    /// opening parenthesis
+    ///
+    /// The arguments of a `calc()` or `clamp()` that aren't valid calculation
+    /// syntax are kept as they were written, other than interpolation and
+    /// variables being substituted.
+    pub(super) fn parse_calculation(&mut self, name: CalculationName) -> SassResult<Value> {
+        if let Some(calculation) = self.try_parse_calculation(name)? {
+            return Ok(calculation);
         }
 
-        Ok(Some(buf))
-    }
+        let mut args = String::new();
+        self.parse_calc_args(&mut args, true)?;
 
-    #[allow(dead_code, unused_mut, unused_variables, unused_assignments)]
-    fn try_parse_min_max_function(&mut self, fn_name: &'static str) -> SassResult<Option<String>> {
-        let mut ident = peek_ident_no_interpolation(self.toks, false, self.span_before)?.node;
-        ident.make_ascii_lowercase();
-        if ident != fn_name {
-            return Ok(None);
-        }
-        if !matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
-            return Ok(None);
-        }
-        self.toks.advance_cursor();
-        ident.push('(');
-        todo!("special functions inside `min()` or `max()`")
+        // remove the parentheses surrounding the arguments
+        args.pop();
+        args.remove(0);
+
+        Ok(SassCalculation::unparsed(name, args))
     }
 }
 
 /// Methods required to do arbitrary lookahead
 impl<'a> Parser<'a> {
-    fn peek_number(&mut self) -> SassResult<Option<String>> {
-        let mut buf = String::new();
-
-        let num = self.peek_whole_number();
-        buf.push_str(&num);
-
-        self.toks.advance_cursor();
-
-        if let Some(Token { kind: '.', .. }) = self.toks.peek() {
-            self.toks.advance_cursor();
-            let num = self.peek_whole_number();
-            if num.is_empty() {
-                return Ok(None);
-            }
-            buf.push_str(&num);
-        } else {
-            self.toks.move_cursor_back().unwrap();
-        }
-
-        let next = match self.toks.peek() {
-            Some(tok) => tok,
-            None => return Ok(Some(buf)),
-        };
-
-        match next.kind {
-            'a'..='z' | 'A'..='Z' | '-' | '_' | '\\' => {
-                let unit = peek_ident_no_interpolation(self.toks, true, self.span_before)?.node;
-
-                buf.push_str(&unit);
-            }
-            '%' => {
-                self.toks.advance_cursor();
-                buf.push('%');
-            }
-            _ => {}
-        }
-
-        Ok(Some(buf))
-    }
-
-    fn peek_whole_number(&mut self) -> String {
-        let mut buf = String::new();
-        while let Some(tok) = self.toks.peek() {
-            if tok.kind.is_ascii_digit() {
-                buf.push(tok.kind);
-                self.toks.advance_cursor();
-            } else {
-                return buf;
-            }
-        }
-        buf
-    }
-
     fn peek_interpolation(&mut self) -> SassResult<Spanned<Value>> {
         let vec = peek_until_closing_curly_brace(self.toks)?;
         self.toks.advance_cursor();
@@ -377,8 +205,12 @@ impl<'a> Parser<'a> {
                 span = span.merge(next.pos);
                 self.toks.peek_forward(1);
             }
-            if matches!(self.toks.peek(), Some(Token { kind: ' ', .. }) | Some(Token { kind: '\n', .. }) | Some(Token { kind: '\t', .. }))
-            {
+            if matches!(
+                self.toks.peek(),
+                Some(Token { kind: ' ', .. })
+                    | Some(Token { kind: '\n', .. })
+                    | Some(Token { kind: '\t', .. })
+            ) {
                 self.toks.peek_forward(1);
             }
         } else {
@@ -8,9 +8,11 @@ use num_traits::Zero;
 use crate::{
     args::CallArgs,
     common::{Op, QuoteKind},
+    deprecation::Deprecation,
     error::SassResult,
     unit::Unit,
     value::{SassFunction, Value},
+    Cow,
 };
 
 use super::super::Parser;
@@ -87,7 +89,10 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             Op::Plus => self.add(val1, val2)?,
             Op::Minus => self.sub(val1, val2)?,
             Op::Mul => self.mul(val1, val2)?,
-            Op::Div => self.div(val1, val2, in_parens)?,
+            Op::Div => {
+                self.warn_for_slash_division(&val1, &val2, in_parens)?;
+                self.div(val1, val2, in_parens)?
+            }
             Op::Rem => self.rem(val1, val2)?,
             Op::And => Self::and(val1, val2)?,
             Op::Or => Self::or(val1, val2)?,
@@ -153,6 +158,61 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
+    /// Warns if `/` is about to divide two numbers, rather than separate
+    /// them, since dart-sass deprecates this in favor of `math.div()`
+    fn warn_for_slash_division(
+        &mut self,
+        left: &HigherIntermediateValue,
+        right: &HigherIntermediateValue,
+        in_parens: bool,
+    ) -> SassResult<()> {
+        let (left, right) = match (left, right) {
+            (
+                HigherIntermediateValue::Literal(left @ Value::Dimension(_, _, should_divide1)),
+                HigherIntermediateValue::Literal(right @ Value::Dimension(_, _, should_divide2)),
+            ) if *should_divide1 || *should_divide2 || in_parens => {
+                (left.inspect(self.span)?, right.inspect(self.span)?)
+            }
+            _ => return Ok(()),
+        };
+
+        self.parser.warn_deprecation(
+            Deprecation::SlashDiv,
+            &Spanned {
+                node: Cow::owned(format!(
+                    "Using / for division outside of calc() is deprecated and will be removed in Dart Sass 2.0.0.\n\nRecommendation: math.div({0}, {1}) or calc({0} / {1})\n\nMore info and automated migrator: https://sass-lang.com/d/slash-div",
+                    left, right
+                )),
+                span: self.span,
+            },
+        )
+    }
+
+    /// Calculations can't be used in arithmetic, although they can still be
+    /// concatenated with strings using `+`
+    fn check_calculation_operands(&self, left: &Value, op: Op, right: &Value) -> SassResult<()> {
+        if !matches!(left, Value::Calculation(..)) && !matches!(right, Value::Calculation(..)) {
+            return Ok(());
+        }
+
+        if op == Op::Plus
+            && (matches!(left, Value::String(..)) || matches!(right, Value::String(..)))
+        {
+            return Ok(());
+        }
+
+        Err((
+            format!(
+                "Undefined operation \"{} {} {}\".",
+                left.inspect(self.span)?,
+                op,
+                right.inspect(self.span)?
+            ),
+            self.span,
+        )
+            .into())
+    }
+
     fn add(
         &self,
         left: HigherIntermediateValue,
@@ -166,8 +226,9 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             HigherIntermediateValue::Literal(v) => v,
             v => panic!("{:?}", v),
         };
+        self.check_calculation_operands(&left, Op::Plus, &right)?;
         Ok(match left {
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", left.inspect(self.span)?),
                     self.span,
@@ -240,14 +301,14 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     format!("{}{}{}", num, unit, right.to_css_string(self.span)?),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
                     )
                         .into())
                 }
-                Value::Color(..) => {
+                Value::Color(..) | Value::Calculation(..) => {
                     return Err((
                         format!(
                             "Undefined operation \"{}{} + {}\".",
@@ -283,7 +344,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 Value::String(text2, ..) => Value::String(text + &text2, quotes),
                 _ => Value::String(text + &right.to_css_string(self.span)?, quotes),
             },
-            Value::List(..) | Value::ArgList(..) => match right {
+            Value::List(..) | Value::ArgList(..) | Value::Calculation(..) => match right {
                 Value::String(s, q) => {
                     Value::String(format!("{}{}", left.to_css_string(self.span)?, s), q)
                 }
@@ -312,6 +373,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             HigherIntermediateValue::Literal(v) => v,
             v => panic!("{:?}", v),
         };
+        self.check_calculation_operands(&left, Op::Minus, &right)?;
         Ok(match left {
             Value::Null => Value::String(
                 format!("-{}", right.to_css_string(self.span)?),
@@ -347,14 +409,14 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     format!("{}{}-{}", num, unit, right.to_css_string(self.span)?),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
                     )
                         .into())
                 }
-                Value::Color(..) => {
+                Value::Color(..) | Value::Calculation(..) => {
                     return Err((
                         format!(
                             "Undefined operation \"{}{} - {}\".",
@@ -441,7 +503,8 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     } else if unit2 == Unit::None {
                         Value::Dimension(Some(num * num2), unit, true)
                     } else {
-                        Value::Dimension(Some(num * num2), unit * unit2, true)
+                        let (unit, factor) = unit.product(unit2);
+                        Value::Dimension(Some(num * num2 * factor), unit, true)
                     }
                 }
                 _ => {
@@ -471,7 +534,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         })
     }
 
-    fn div(
+    pub fn div(
         &self,
         left: HigherIntermediateValue,
         right: HigherIntermediateValue,
@@ -508,32 +571,15 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         if unit == unit2 {
                             Value::Dimension(Some(num / num2), Unit::None, true)
 
-                        // `unit(1 / 1em)` => `"em^-1"`
-                        } else if unit == Unit::None {
-                            Value::Dimension(Some(num / num2), Unit::None / unit2, true)
-
                         // `unit(1em / 1)` => `"em"`
                         } else if unit2 == Unit::None {
                             Value::Dimension(Some(num / num2), unit, true)
 
-                        // `unit(1in / 1px)` => `""`
-                        } else if unit.comparable(&unit2) {
-                            Value::Dimension(
-                                Some(num / num2.convert(&unit2, &unit)),
-                                Unit::None,
-                                true,
-                            )
-                        // `unit(1em / 1px)` => `"em/px"`
-                        // todo: this should probably be its own variant
-                        // within the `Value` enum
+                        // `unit(1in / 1px)` => `""`, `unit(1 / 1em)` => `"em^-1"`,
+                        // and `unit(1em / 1px)` => `"em/px"`
                         } else {
-                            // todo: remember to account for `Mul` and `Div`
-                            // todo!("non-comparable inverse units")
-                            return Err((
-                                "Division of non-comparable units not yet supported.",
-                                self.span,
-                            )
-                                .into());
+                            let (unit, factor) = unit.quotient(unit2);
+                            Value::Dimension(Some(num / num2 * factor), unit, true)
                         }
                     } else {
                         Value::String(
@@ -550,12 +596,13 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 | Value::False
                 | Value::Important
                 | Value::Color(..)
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!("{}{}/{}", num, unit, right.to_css_string(self.span)?),
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(format!("{}{}/", num, unit), QuoteKind::None),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -595,12 +642,13 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 | Value::Dimension(..)
                 | Value::Color(..)
                 | Value::List(..)
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!("{}{}{}/{}", q1, s1, q1, right.to_css_string(self.span)?),
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(format!("{}{}{}/", q1, s1, q1), QuoteKind::None),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -668,7 +716,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     } else if u2 == Unit::None {
                         Value::Dimension(Some(n % n2), u, true)
                     } else {
-                        Value::Dimension(Some(n), u, true)
+                        Value::Dimension(Some(n % n2.convert(&u2, &u)), u, true)
                     }
                 }
                 _ => {
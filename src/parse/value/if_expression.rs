@@ -0,0 +1,171 @@
+use std::mem;
+
+use crate::{
+    common::Identifier,
+    error::SassResult,
+    utils::{is_name, peek_until_closing_paren},
+    value::Value,
+    Token,
+};
+
+use super::super::Parser;
+
+/// The parameters of `if()`, in order
+const PARAMS: [&str; 3] = ["condition", "if-true", "if-false"];
+
+/// The tokens of an argument, along with its name if it was passed by name
+type UnevaluatedArg = (Option<Identifier>, Vec<Token>);
+
+impl<'a> Parser<'a> {
+    /// Parses the arguments to `if()`, evaluating only the condition and
+    /// the argument for the branch that is taken, so that the other branch
+    /// may contain e.g. a recursive call or `map-get()` on a `null` map
+    ///
+    /// Returns `None`, without consuming any tokens, if the arguments
+    /// contain a rest argument, which must be evaluated before it is known
+    /// which argument is which.
+    pub(super) fn try_parse_if_expression(&mut self) -> SassResult<Option<Value>> {
+        let mut toks = peek_until_closing_paren(self.toks)?;
+
+        if !matches!(toks.pop(), Some(Token { kind: ')', .. })) {
+            return Err(("expected \")\".", self.span_before).into());
+        }
+
+        let args = match split_args(toks) {
+            Some(args) => args,
+            None => {
+                self.toks.reset_cursor();
+                return Ok(None);
+            }
+        };
+
+        self.toks.truncate_iterator_to_cursor();
+
+        let span = self.span_before;
+
+        if args.len() > PARAMS.len() {
+            return Err((
+                format!(
+                    "Only {} arguments allowed, but {} were passed.",
+                    PARAMS.len(),
+                    args.len()
+                ),
+                span,
+            )
+                .into());
+        }
+
+        let mut params: [Option<Vec<Token>>; 3] = [None, None, None];
+
+        for (position, (name, toks)) in args.into_iter().enumerate() {
+            let idx = match name {
+                Some(name) => PARAMS
+                    .iter()
+                    .position(|param| Identifier::from(*param) == name)
+                    .ok_or((format!("No argument named ${}.", name), span))?,
+                None => position,
+            };
+
+            if params[idx].replace(toks).is_some() {
+                return Err((
+                    format!(
+                        "Argument ${} was passed both by position and by name.",
+                        PARAMS[idx]
+                    ),
+                    span,
+                )
+                    .into());
+            }
+        }
+
+        let [condition, if_true, if_false] = params;
+        let condition = condition.ok_or(("Missing argument $condition.", span))?;
+        let if_true = if_true.ok_or(("Missing argument $if-true.", span))?;
+        let if_false = if_false.ok_or(("Missing argument $if-false.", span))?;
+
+        let branch = if self.parse_value_from_vec(condition, true)?.node.is_true() {
+            if_true
+        } else {
+            if_false
+        };
+
+        Ok(Some(self.parse_value_from_vec(branch, true)?.node))
+    }
+}
+
+/// Splits the tokens of a list of arguments at each comma that isn't nested
+/// within parentheses, brackets, or a string, and separates the name from
+/// each argument passed by name
+///
+/// Returns `None` if the arguments contain a rest argument.
+fn split_args(toks: Vec<Token>) -> Option<Vec<UnevaluatedArg>> {
+    let mut args = Vec::new();
+    let mut current = Vec::new();
+    let mut nesting = 0_usize;
+    let mut quote = None;
+    let mut toks = toks.into_iter().peekable();
+
+    while let Some(tok) = toks.next() {
+        match (quote, tok.kind) {
+            (_, '\\') => {
+                current.push(tok);
+                if let Some(next) = toks.next() {
+                    current.push(next);
+                }
+                continue;
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(..), _) => {}
+            (None, q @ '"') | (None, q @ '\'') => quote = Some(q),
+            (None, '(') | (None, '[') | (None, '{') => nesting += 1,
+            (None, ')') | (None, ']') | (None, '}') => nesting = nesting.saturating_sub(1),
+            (None, ',') if nesting == 0 => {
+                args.push(named_arg(mem::take(&mut current)));
+                continue;
+            }
+            (None, '.') if nesting == 0 && matches!(toks.peek(), Some(Token { kind: '.', .. })) => {
+                return None;
+            }
+            _ => {}
+        }
+        current.push(tok);
+    }
+
+    // a trailing comma doesn't begin another argument
+    if current.iter().any(|tok| !tok.kind.is_whitespace()) {
+        args.push(named_arg(current));
+    }
+
+    Some(args)
+}
+
+/// Separates the name of an argument of the form `$name: value` from its
+/// value
+fn named_arg(toks: Vec<Token>) -> UnevaluatedArg {
+    let start = toks
+        .iter()
+        .position(|tok| !tok.kind.is_whitespace())
+        .unwrap_or(toks.len());
+
+    if !matches!(toks.get(start), Some(Token { kind: '$', .. })) {
+        return (None, toks);
+    }
+
+    let name: String = toks[start + 1..]
+        .iter()
+        .map(|tok| tok.kind)
+        .take_while(|&c| is_name(c))
+        .collect();
+
+    let mut rest = start + 1 + name.chars().count();
+    while matches!(toks.get(rest), Some(tok) if tok.kind.is_whitespace()) {
+        rest += 1;
+    }
+
+    match toks.get(rest) {
+        Some(Token { kind: ':', .. }) if !name.is_empty() => {
+            (Some(Identifier::from(name)), toks[rest + 1..].to_vec())
+        }
+        _ => (None, toks),
+    }
+}
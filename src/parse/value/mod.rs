@@ -1,5 +1,7 @@
 pub(crate) use eval::{HigherIntermediateValue, ValueVisitor};
 
+mod calculation;
 mod css_function;
 mod eval;
+mod if_expression;
 mod parse;
@@ -1,9 +1,5 @@
 use std::{iter::Iterator, mem, vec::IntoIter};
 
-use num_bigint::BigInt;
-use num_rational::{BigRational, Rational64};
-use num_traits::{pow, One, ToPrimitive};
-
 use codemap::{Span, Spanned};
 
 use peekmore::{PeekMore, PeekMoreIterator};
@@ -15,7 +11,7 @@ use crate::{
     error::SassResult,
     unit::Unit,
     utils::{eat_whole_number, is_name, IsWhitespace, ParsedNumber},
-    value::{Number, SassFunction, SassMap, Value},
+    value::{CalculationName, SassFunction, SassMap, Value},
     Token,
 };
 
@@ -189,6 +185,12 @@ impl<'a> Parser<'a> {
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            media_queries: self.media_queries,
+            stats: self.stats,
+            mixin_cache: self.mixin_cache,
+            random: self.random,
+            import_stack: self.import_stack,
+            limits: self.limits,
         }
         .parse_value(in_paren, &|_| false)
     }
@@ -199,54 +201,83 @@ impl<'a> Parser<'a> {
         module: &str,
         mut module_span: Span,
     ) -> SassResult<Spanned<IntermediateValue>> {
-        Ok(
-            IntermediateValue::Value(if self.consume_char_if_exists('$') {
-                let var = self
-                    .parse_identifier_no_interpolation(false)?
-                    .map_node(|i| i.into());
+        Ok(IntermediateValue::Value(if self.flags.in_plain_css() {
+            return Err((
+                "Module namespaces aren't allowed in plain CSS.",
+                module_span,
+            )
+                .into());
+        } else if self.consume_char_if_exists('$') {
+            let var = self
+                .parse_identifier_no_interpolation(false)?
+                .map_node(|i| i.into());
 
-                module_span = module_span.merge(var.span);
+            module_span = module_span.merge(var.span);
 
-                let value = self.modules.get(module.into(), module_span)?.get_var(var)?;
-                HigherIntermediateValue::Literal(value.clone())
-            } else {
-                let fn_name = self
-                    .parse_identifier_no_interpolation(false)?
-                    .map_node(|i| i.into());
+            let value = self.modules.get(module.into(), module_span)?.get_var(var)?;
+            HigherIntermediateValue::Literal(value)
+        } else {
+            let fn_name = self
+                .parse_identifier_no_interpolation(false)?
+                .map_node(|i| i.into());
 
-                let function = self
-                    .modules
-                    .get(module.into(), module_span)?
-                    .get_fn(fn_name)?
-                    .ok_or(("Undefined function.", fn_name.span))?;
+            let function = self
+                .modules
+                .get(module.into(), module_span)?
+                .get_fn(fn_name)?
+                .ok_or(("Undefined function.", fn_name.span))?;
 
-                self.expect_char('(')?;
+            self.expect_char('(')?;
 
-                let call_args = self.parse_call_args()?;
+            let call_args = self.parse_call_args()?;
 
-                HigherIntermediateValue::Function(function, call_args)
-            })
-            .span(module_span),
-        )
+            HigherIntermediateValue::Function(function, call_args)
+        })
+        .span(module_span))
     }
 
     fn parse_fn_call(
         &mut self,
-        mut s: String,
+        s: String,
         lower: String,
     ) -> SassResult<Spanned<IntermediateValue>> {
         if lower == "min" || lower == "max" {
-            match self.try_parse_min_max(&lower, true)? {
-                Some(val) => {
-                    self.toks.truncate_iterator_to_cursor();
-                    return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                        Value::String(val, QuoteKind::None),
-                    ))
-                    .span(self.span_before));
-                }
-                None => {
-                    self.toks.reset_cursor();
-                }
+            let name = if lower == "min" {
+                CalculationName::Min
+            } else {
+                CalculationName::Max
+            };
+
+            if let Some(value) = self.try_parse_calculation(name)? {
+                return Ok(
+                    IntermediateValue::Value(HigherIntermediateValue::Literal(value))
+                        .span(self.span_before),
+                );
+            }
+        }
+
+        // plain CSS can't call Sass functions, other than those that share
+        // their name with a CSS function, which is called instead
+        if self.flags.in_plain_css() {
+            if GLOBAL_FUNCTIONS.contains_key(s.as_str()) && !is_plain_css_function(&s) {
+                return Err((
+                    "This function isn't allowed in plain CSS.",
+                    self.span_before,
+                )
+                    .into());
+            }
+            return self.parse_css_fn_call(s, lower);
+        }
+
+        // `if()` only evaluates the argument for the branch that is taken,
+        // so it can't be called like other functions, even if a function of
+        // the same name has been declared
+        if s == "if" {
+            if let Some(value) = self.try_parse_if_expression()? {
+                return Ok(
+                    IntermediateValue::Value(HigherIntermediateValue::Literal(value))
+                        .span(self.span_before),
+                );
             }
         }
 
@@ -254,30 +285,23 @@ impl<'a> Parser<'a> {
         let func = match self.scopes.get_fn(as_ident, self.global_scope) {
             Some(f) => f,
             None => {
-                if let Some(f) = GLOBAL_FUNCTIONS.get(as_ident.as_str()) {
+                #[cfg(feature = "plugins")]
+                if let Some(f) = self.options.plugin_function(as_ident.as_str()) {
                     return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
-                        SassFunction::Builtin(f.clone(), as_ident),
+                        SassFunction::Plugin(f, as_ident),
                         self.parse_call_args()?,
                     ))
                     .span(self.span_before));
-                } else {
-                    // check for special cased CSS functions
-                    match unvendor(&lower) {
-                        "calc" | "element" | "expression" => {
-                            s = lower;
-                            self.parse_calc_args(&mut s)?;
-                        }
-                        "url" => match self.try_parse_url()? {
-                            Some(val) => s = val,
-                            None => s.push_str(&self.parse_call_args()?.to_css_string()?),
-                        },
-                        _ => s.push_str(&self.parse_call_args()?.to_css_string()?),
-                    }
+                }
 
-                    return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                        Value::String(s, QuoteKind::None),
+                if let Some(f) = GLOBAL_FUNCTIONS.get(as_ident.as_str()) {
+                    return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
+                        SassFunction::Builtin(f.clone(), as_ident),
+                        self.parse_call_args()?,
                     ))
                     .span(self.span_before));
+                } else {
+                    return self.parse_css_fn_call(s, lower);
                 }
             }
         };
@@ -289,14 +313,73 @@ impl<'a> Parser<'a> {
         )
     }
 
+    /// Parses a call to a plain CSS function, which is emitted as written
+    /// once its arguments have been evaluated
+    fn parse_css_fn_call(
+        &mut self,
+        mut s: String,
+        lower: String,
+    ) -> SassResult<Spanned<IntermediateValue>> {
+        let calculation = match lower.as_str() {
+            "calc" => Some(CalculationName::Calc),
+            "clamp" => Some(CalculationName::Clamp),
+            _ => None,
+        };
+
+        if let Some(name) = calculation {
+            return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
+                self.parse_calculation(name)?,
+            ))
+            .span(self.span_before));
+        }
+
+        // check for special cased CSS functions
+        match unvendor(&lower) {
+            "calc" | "element" | "expression" => {
+                s = lower;
+                self.parse_calc_args(&mut s, false)?;
+            }
+            "url" => match self.try_parse_url()? {
+                Some(val) => s = val,
+                None => s.push_str(&self.parse_call_args()?.to_css_string()?),
+            },
+            _ => s.push_str(&self.parse_call_args()?.to_css_string()?),
+        }
+
+        Ok(
+            IntermediateValue::Value(HigherIntermediateValue::Literal(Value::String(
+                s,
+                QuoteKind::None,
+            )))
+            .span(self.span_before),
+        )
+    }
+
     fn parse_ident_value(
         &mut self,
         predicate: &dyn Fn(&mut PeekMoreIterator<IntoIter<Token>>) -> bool,
     ) -> SassResult<Spanned<IntermediateValue>> {
-        let Spanned { node: mut s, span } = self.parse_identifier()?;
+        let (Spanned { node: mut s, span }, interpolated) =
+            self.parse_identifier_and_interpolated()?;
 
         self.span_before = span;
 
+        // interpolated identifiers are always plain strings, and calls to
+        // them are always plain CSS functions
+        if interpolated {
+            if self.consume_char_if_exists('(') {
+                s.push_str(&self.parse_call_args()?.to_css_string()?);
+            }
+
+            return Ok(
+                IntermediateValue::Value(HigherIntermediateValue::Literal(Value::String(
+                    s,
+                    QuoteKind::None,
+                )))
+                .span(span),
+            );
+        }
+
         let lower = s.to_ascii_lowercase();
 
         if lower == "progid" && self.consume_char_if_exists(':') {
@@ -341,9 +424,9 @@ impl<'a> Parser<'a> {
                 "true" => IntermediateValue::Value(HigherIntermediateValue::Literal(Value::True)),
                 "false" => IntermediateValue::Value(HigherIntermediateValue::Literal(Value::False)),
                 "null" => IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Null)),
-                "not" => IntermediateValue::Op(Op::Not),
-                "and" => IntermediateValue::Op(Op::And),
-                "or" => IntermediateValue::Op(Op::Or),
+                "not" if !self.flags.in_plain_css() => IntermediateValue::Op(Op::Not),
+                "and" if !self.flags.in_plain_css() => IntermediateValue::Op(Op::And),
+                "or" if !self.flags.in_plain_css() => IntermediateValue::Op(Op::Or),
                 _ => IntermediateValue::Value(HigherIntermediateValue::Literal(Value::String(
                     s,
                     QuoteKind::None,
@@ -471,6 +554,17 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a number along with its unit
+    pub(super) fn parse_number_value(&mut self) -> SassResult<Spanned<Value>> {
+        let Spanned { node, span } = self.parse_dimension(&|_| false)?;
+        match node {
+            IntermediateValue::Value(HigherIntermediateValue::Literal(value)) => {
+                Ok(value.span(span))
+            }
+            _ => unreachable!("parsing a number always produces a literal"),
+        }
+    }
+
     fn parse_dimension(
         &mut self,
         predicate: &dyn Fn(&mut PeekMoreIterator<IntoIter<Token>>) -> bool,
@@ -513,51 +607,13 @@ impl<'a> Parser<'a> {
             Unit::None
         };
 
-        let n = if val.dec_len == 0 {
-            if val.num.len() <= 18 && val.times_ten.is_empty() {
-                let n = Rational64::new_raw(parse_i64(&val.num), 1);
-                return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                    Value::Dimension(Some(Number::new_small(n)), unit, false),
-                ))
-                .span(span));
-            }
-            BigRational::new_raw(val.num.parse::<BigInt>().unwrap(), BigInt::one())
-        } else {
-            if val.num.len() <= 18 && val.times_ten.is_empty() {
-                let n = Rational64::new(parse_i64(&val.num), pow(10, val.dec_len));
-                return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                    Value::Dimension(Some(Number::new_small(n)), unit, false),
-                ))
-                .span(span));
-            }
-            BigRational::new(val.num.parse().unwrap(), pow(BigInt::from(10), val.dec_len))
-        };
-
-        if val.times_ten.is_empty() {
-            return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                Value::Dimension(Some(Number::new_big(n)), unit, false),
-            ))
-            .span(span));
-        }
-
-        let times_ten = pow(
-            BigInt::from(10),
-            val.times_ten
-                .parse::<BigInt>()
-                .unwrap()
-                .to_usize()
-                .ok_or(("Exponent too large (expected usize).", span))?,
-        );
-
-        let times_ten = if val.times_ten_is_postive {
-            BigRational::new_raw(times_ten, BigInt::one())
-        } else {
-            BigRational::new(BigInt::one(), times_ten)
-        };
+        let n = val
+            .into_number()
+            .ok_or(("Exponent too large (expected usize).", span))?;
 
         Ok(
             IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Dimension(
-                Some(Number::new_big(n * times_ten)),
+                Some(n),
                 unit,
                 false,
             )))
@@ -578,10 +634,12 @@ impl<'a> Parser<'a> {
         }
 
         let mut map = SassMap::new();
-        let key = self.parse_value(
-            true,
-            &|c| matches!(c.peek(), Some(Token { kind: ':', .. }) | Some(Token { kind: ')', .. })),
-        )?;
+        let key = self.parse_value(true, &|c| {
+            matches!(
+                c.peek(),
+                Some(Token { kind: ':', .. }) | Some(Token { kind: ')', .. })
+            )
+        })?;
 
         match self.toks.next() {
             Some(Token { kind: ':', .. }) => {}
@@ -594,10 +652,12 @@ impl<'a> Parser<'a> {
             Some(..) | None => return Err(("expected \")\".", key.span).into()),
         }
 
-        let val = self.parse_value(
-            true,
-            &|c| matches!(c.peek(), Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })),
-        )?;
+        let val = self.parse_value(true, &|c| {
+            matches!(
+                c.peek(),
+                Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })
+            )
+        })?;
 
         map.insert(key.node, val.node);
 
@@ -629,20 +689,32 @@ impl<'a> Parser<'a> {
             });
         }
 
+        // the error for a duplicate key is only emitted once the whole map has
+        // been consumed, so that it is not lost if this map is an argument
+        let mut duplicate_key = None;
+
         loop {
-            let key =
-                self.parse_value(true, &|c| matches!(c.peek(), Some(Token { kind: ':', .. }) | Some(Token { kind: ',', .. })))?;
+            let key = self.parse_value(true, &|c| {
+                matches!(
+                    c.peek(),
+                    Some(Token { kind: ':', .. }) | Some(Token { kind: ',', .. })
+                )
+            })?;
 
             self.expect_char(':')?;
 
             self.whitespace_or_comment();
-            let val =
-                self.parse_value(true, &|c| matches!(c.peek(), Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })))?;
+            let val = self.parse_value(true, &|c| {
+                matches!(
+                    c.peek(),
+                    Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })
+                )
+            })?;
 
             span = span.merge(val.span);
 
-            if map.insert(key.node.clone(), val.node) {
-                return Err(("Duplicate key.", key.span).into());
+            if map.insert(key.node.clone(), val.node) && duplicate_key.is_none() {
+                duplicate_key = Some(key.span);
             }
 
             let found_comma = self.consume_char_if_exists(',');
@@ -658,6 +730,11 @@ impl<'a> Parser<'a> {
                 Some(..) | None => return Err(("expected \")\".", val.span).into()),
             }
         }
+
+        if let Some(span) = duplicate_key {
+            return Err(("Duplicate key.", span).into());
+        }
+
         Ok(Spanned {
             node: IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Map(map))),
             span,
@@ -800,7 +877,12 @@ impl<'a> Parser<'a> {
             }
             '0'..='9' | '.' => return Some(self.parse_dimension(predicate)),
             '(' => {
-                self.toks.next();
+                let span = self.toks.next().unwrap().pos();
+                if self.flags.in_plain_css() {
+                    return Some(Err(
+                        ("Parentheses aren't allowed in plain CSS.", span).into()
+                    ));
+                }
                 return Some(self.parse_paren());
             }
             '&' => {
@@ -843,7 +925,12 @@ impl<'a> Parser<'a> {
             }
             '[' => return Some(self.parse_bracketed_list()),
             '$' => {
-                self.toks.next();
+                let span = self.toks.next().unwrap().pos();
+                if self.flags.in_plain_css() {
+                    return Some(Err(
+                        ("Sass variables aren't allowed in plain CSS.", span).into()
+                    ));
+                }
                 let val = match self.parse_identifier_no_interpolation(false) {
                     Ok(v) => v.map_node(|i| i.into()),
                     Err(e) => return Some(Err(e)),
@@ -1086,6 +1173,12 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
         last_was_whitespace: bool,
         in_paren: bool,
     ) -> SassResult<()> {
+        // `/` separates values in CSS, and `+` and `-` may be signs, so those
+        // are only rejected once they're known to be binary operators
+        if !matches!(op.node, Op::Div | Op::Plus | Op::Minus) {
+            self.check_plain_css_operator(op.span)?;
+        }
+
         match op.node {
             Op::Not => {
                 self.whitespace();
@@ -1125,6 +1218,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
             }
             Op::Plus => {
                 if let Some(left) = space_separated.pop() {
+                    self.check_plain_css_operator(op.span)?;
                     self.whitespace();
                     let right = self.single_value(in_paren)?;
                     space_separated.push(Spanned {
@@ -1148,6 +1242,7 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
                 if self.whitespace() || !last_was_whitespace {
                     let right = self.single_value(in_paren)?;
                     if let Some(left) = space_separated.pop() {
+                        self.check_plain_css_operator(op.span)?;
                         space_separated.push(Spanned {
                             node: HigherIntermediateValue::BinaryOp(
                                 Box::new(left.node),
@@ -1284,6 +1379,13 @@ impl<'a, 'b: 'a> IntermediateValueIterator<'a, 'b> {
         Ok(())
     }
 
+    fn check_plain_css_operator(&self, span: Span) -> SassResult<()> {
+        if self.parser.flags.in_plain_css() {
+            return Err(("Operators aren't allowed in plain CSS.", span).into());
+        }
+        Ok(())
+    }
+
     fn single_value(&mut self, in_paren: bool) -> SassResult<Spanned<HigherIntermediateValue>> {
         let next = self
             .next()
@@ -1362,10 +1464,25 @@ impl IsWhitespace for SassResult<Spanned<IntermediateValue>> {
     }
 }
 
-fn parse_i64(s: &str) -> i64 {
-    s.as_bytes()
-        .iter()
-        .fold(0, |total, this| total * 10 + i64::from(this - b'0'))
+/// Whether `name` is both a global Sass function and a CSS function, and so
+/// may be called in plain CSS
+fn is_plain_css_function(name: &str) -> bool {
+    matches!(
+        name,
+        "rgb"
+            | "rgba"
+            | "hsl"
+            | "hsla"
+            | "grayscale"
+            | "invert"
+            | "alpha"
+            | "opacity"
+            | "saturate"
+            | "min"
+            | "max"
+            | "round"
+            | "abs"
+    )
 }
 
 fn is_keyword_operator(s: &str) -> bool {
@@ -36,76 +36,37 @@ impl<'a> Parser<'a> {
             default,
         } = self.parse_variable_value()?;
 
-        if default {
-            let config_val = self.module_config.get(ident).filter(|v| !v.is_null());
+        let assign_global = global || (self.at_root && !self.flags.in_control_flow());
 
-            let value = if (self.at_root && !self.flags.in_control_flow()) || global {
-                if self.global_scope.default_var_exists(ident) {
-                    return Ok(());
-                } else if let Some(value) = config_val {
-                    value
-                } else {
-                    var_value?.node
-                }
-            } else if self.at_root && self.flags.in_control_flow() {
+        let value = if default {
+            if assign_global {
                 if self.global_scope.default_var_exists(ident) {
                     return Ok(());
                 }
 
-                var_value?.node
-            } else if self.at_root {
-                var_value?.node
+                match self.module_config.get(ident).filter(|v| !v.is_null()) {
+                    Some(value) => value,
+                    None => var_value?.node,
+                }
             } else {
-                if self.scopes.default_var_exists(ident) {
+                if self.scopes.default_var_exists(ident, self.global_scope) {
                     return Ok(());
                 }
 
                 var_value?.node
-            };
-
-            if self.at_root && self.global_scope.var_exists(ident) {
-                if !self.global_scope.default_var_exists(ident) {
-                    self.global_scope.insert_var(ident, value.clone());
-                }
-            } else if self.at_root
-                && !self.flags.in_control_flow()
-                && !self.global_scope.default_var_exists(ident)
-            {
-                self.global_scope.insert_var(ident, value.clone());
             }
-
-            if global {
-                self.global_scope.insert_var(ident, value.clone());
-            }
-
-            if self.at_root && !self.flags.in_control_flow() {
-                return Ok(());
-            }
-
-            self.scopes.insert_var(ident, value);
-
-            return Ok(());
-        }
-
-        let value = var_value?.node;
-
-        if global {
-            self.global_scope.insert_var(ident, value.clone());
+        } else {
+            var_value?.node
+        };
+
+        if assign_global {
+            self.global_scope.insert_var(ident, value);
+        } else {
+            // control flow at the root of a stylesheet may update globals
+            self.scopes
+                .assign_var(ident, value, self.global_scope, self.at_root);
         }
 
-        if self.at_root {
-            if self.flags.in_control_flow() {
-                if self.global_scope.var_exists(ident) {
-                    self.global_scope.insert_var(ident, value);
-                } else {
-                    self.scopes.insert_var(ident, value);
-                }
-            } else {
-                self.global_scope.insert_var(ident, value);
-            }
-        } else if !(self.flags.in_control_flow() && global) {
-            self.scopes.insert_var(ident, value);
-        }
         Ok(())
     }
 
@@ -115,7 +76,12 @@ impl<'a> Parser<'a> {
 
         let value = self.parse_value(true, &|toks| {
             if matches!(toks.peek(), Some(Token { kind: '!', .. })) {
-                let is_important = matches!(toks.peek_next(), Some(Token { kind: 'i', .. }) | Some(Token { kind: 'I', .. }) | Some(Token { kind: '=', .. }));
+                let is_important = matches!(
+                    toks.peek_next(),
+                    Some(Token { kind: 'i', .. })
+                        | Some(Token { kind: 'I', .. })
+                        | Some(Token { kind: '=', .. })
+                );
                 toks.reset_cursor();
                 !is_important
             } else {
@@ -0,0 +1,300 @@
+//! A stable, C-compatible ABI for providing custom functions from dynamic
+//! libraries, so that plugins can be written in any language able to export
+//! C functions
+//!
+//! A plugin is a dynamic library exporting the following symbols:
+//!
+//! ```c
+//! typedef struct {
+//!     // The name of the function, as called from Sass
+//!     const char *name;
+//!     // Called with the arguments to the function, each serialized as by
+//!     // `inspect()`. Returns a Sass expression to be used as the result
+//!     // or, if `*is_error` has been set to `true`, an error message
+//!     char *(*call)(size_t argc, const char *const *argv, bool *is_error);
+//!     // Called by grass to free each string returned by `call`
+//!     void (*free)(char *result);
+//! } GrassPluginFunction;
+//!
+//! // Must return `GRASS_PLUGIN_ABI_VERSION` (currently 1)
+//! uint32_t grass_plugin_abi_version(void);
+//!
+//! // Returns an array of functions, terminated by an entry whose `name` is NULL.
+//! // The array must live as long as the library is loaded
+//! const GrassPluginFunction *grass_plugin_functions(void);
+//! ```
+//!
+//! Plugin functions accept only positional arguments.
+
+use std::{
+    ffi::{CStr, CString},
+    fmt, io,
+    os::{
+        raw::{c_char, c_void},
+        unix::ffi::OsStrExt,
+    },
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{args::CallArgs, error::SassResult, parse::Parser, value::Value, Token};
+
+/// The version of the plugin ABI implemented by this version of grass
+pub const GRASS_PLUGIN_ABI_VERSION: u32 = 1;
+
+static PLUGIN_FUNCTION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+type PluginCallback = unsafe extern "C" fn(
+    argc: usize,
+    argv: *const *const c_char,
+    is_error: *mut bool,
+) -> *mut c_char;
+
+/// A custom function exported by a plugin, laid out as `GrassPluginFunction`
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PluginFunction {
+    pub name: *const c_char,
+    pub call: Option<PluginCallback>,
+    pub free: Option<unsafe extern "C" fn(result: *mut c_char)>,
+}
+
+/// A set of custom functions, usually loaded from a dynamic library with
+/// [`Plugin::load`]
+///
+/// The plugin must outlive every compilation it is passed to with
+/// [`Options::plugin`](crate::Options::plugin).
+pub struct Plugin {
+    /// The handle returned by `dlopen`, or null if the functions were not
+    /// loaded from a dynamic library
+    handle: *mut c_void,
+    functions: Vec<(String, PluginFn)>,
+}
+
+impl Plugin {
+    /// Load a plugin from the dynamic library at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library cannot be loaded, does not export the
+    /// required symbols, or was built against a different version of the
+    /// plugin ABI.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        // SAFETY: loading a library runs its initializers, which is
+        // inherent to loading plugins
+        let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if handle.is_null() {
+            return Err(dl_error());
+        }
+
+        // construct the plugin first so that the library is closed if any of
+        // the following fails
+        let mut plugin = Plugin {
+            handle,
+            functions: Vec::new(),
+        };
+
+        // SAFETY: the symbols are required to have these signatures
+        unsafe {
+            let version: unsafe extern "C" fn() -> u32 =
+                std::mem::transmute(plugin.symbol("grass_plugin_abi_version")?);
+
+            let version = version();
+            if version != GRASS_PLUGIN_ABI_VERSION {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!(
+                        "plugin was built for ABI version {}, but grass implements version {}",
+                        version, GRASS_PLUGIN_ABI_VERSION
+                    ),
+                ));
+            }
+
+            let functions: unsafe extern "C" fn() -> *const PluginFunction =
+                std::mem::transmute(plugin.symbol("grass_plugin_functions")?);
+
+            plugin.functions = collect_functions(functions())?;
+        }
+
+        Ok(plugin)
+    }
+
+    /// Create a plugin from functions that are linked into the current
+    /// binary, rather than loaded from a dynamic library
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any function is missing its `call` or `free`
+    /// callback, or its name is not valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// `functions` must point to an array of valid `PluginFunction`s,
+    /// terminated by an entry whose `name` is null. The names must be valid
+    /// C strings, and the callbacks must be safe to call for as long as the
+    /// plugin is alive.
+    pub unsafe fn from_raw(functions: *const PluginFunction) -> io::Result<Self> {
+        Ok(Plugin {
+            handle: std::ptr::null_mut(),
+            functions: collect_functions(functions)?,
+        })
+    }
+
+    /// The names of the functions provided by this plugin
+    pub fn function_names(&self) -> impl Iterator<Item = &str> {
+        self.functions.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<PluginFn> {
+        self.functions
+            .iter()
+            .find(|(fn_name, _)| fn_name.replace('_', "-") == name)
+            .map(|(_, f)| *f)
+    }
+
+    unsafe fn symbol(&self, name: &str) -> io::Result<*mut c_void> {
+        let name = CString::new(name).unwrap();
+        let symbol = libc::dlsym(self.handle, name.as_ptr());
+        if symbol.is_null() {
+            return Err(dl_error());
+        }
+        Ok(symbol)
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            // SAFETY: the handle was returned by `dlopen` and is closed only once
+            unsafe {
+                libc::dlclose(self.handle);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("functions", &self.function_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+unsafe fn collect_functions(mut ptr: *const PluginFunction) -> io::Result<Vec<(String, PluginFn)>> {
+    let mut functions = Vec::new();
+
+    if ptr.is_null() {
+        return Ok(functions);
+    }
+
+    while !(*ptr).name.is_null() {
+        let function = *ptr;
+
+        let name = CStr::from_ptr(function.name)
+            .to_str()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .to_owned();
+
+        let (call, free) = match (function.call, function.free) {
+            (Some(call), Some(free)) => (call, free),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("plugin function `{}` is missing a callback", name),
+                ))
+            }
+        };
+
+        functions.push((
+            name,
+            PluginFn {
+                call,
+                free,
+                id: PLUGIN_FUNCTION_COUNT.fetch_add(1, Ordering::Relaxed),
+            },
+        ));
+
+        ptr = ptr.add(1);
+    }
+
+    Ok(functions)
+}
+
+fn dl_error() -> io::Error {
+    // SAFETY: `dlerror` returns either null or a valid C string
+    let message = unsafe {
+        let err = libc::dlerror();
+        if err.is_null() {
+            "unknown error loading plugin".to_owned()
+        } else {
+            CStr::from_ptr(err).to_string_lossy().into_owned()
+        }
+    };
+    io::Error::new(io::ErrorKind::Other, message)
+}
+
+/// A function provided by a plugin, as referenced from Sass
+#[derive(Clone, Copy)]
+pub(crate) struct PluginFn {
+    call: PluginCallback,
+    free: unsafe extern "C" fn(result: *mut c_char),
+    id: usize,
+}
+
+impl PartialEq for PluginFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for PluginFn {}
+
+impl PluginFn {
+    pub fn call(self, args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+        let span = args.span();
+
+        let args = args
+            .get_variadic()?
+            .into_iter()
+            .map(|arg| {
+                CString::new(arg.node.inspect(arg.span)?.into_owned()).map_err(|_| {
+                    (
+                        "Arguments to plugin functions may not contain null bytes.",
+                        span,
+                    )
+                        .into()
+                })
+            })
+            .collect::<SassResult<Vec<CString>>>()?;
+
+        let argv: Vec<*const c_char> = args.iter().map(|arg| arg.as_ptr()).collect();
+        let mut is_error = false;
+
+        // SAFETY: the plugin guarantees that `call` may be called with valid C strings
+        // and that the result is either null or a valid C string to be freed with `free`
+        let result = unsafe {
+            let ptr = (self.call)(argv.len(), argv.as_ptr(), &mut is_error);
+            if ptr.is_null() {
+                return Err(("Plugin function did not return a value.", span).into());
+            }
+            let result = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+            (self.free)(ptr);
+            result
+        };
+
+        if is_error {
+            return Err((result, span).into());
+        }
+
+        let toks = result
+            .chars()
+            .map(|c| Token::new(span, c))
+            .collect::<Vec<Token>>();
+
+        Ok(parser.parse_value_from_vec(toks, true)?.node)
+    }
+}
@@ -0,0 +1,65 @@
+//! The source of random numbers for `random()` and `unique-id()`
+
+#[cfg(feature = "random")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The number of distinct ids returned by `unique-id()`, which are six
+/// base-36 digits long
+#[cfg(feature = "random")]
+const UNIQUE_ID_LIMIT: u32 = 36 * 36 * 36 * 36 * 36 * 36;
+
+/// The random number generator for a single compilation, seeded by
+/// [`Options::random_seed`](crate::Options::random_seed) if it is set
+#[derive(Debug)]
+pub(crate) struct Random {
+    #[cfg(feature = "random")]
+    rng: StdRng,
+
+    #[cfg(feature = "random")]
+    previous_unique_id: u32,
+}
+
+impl Random {
+    #[cfg_attr(not(feature = "random"), allow(unused_variables))]
+    pub fn new(seed: Option<u64>) -> Self {
+        #[cfg(feature = "random")]
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            #[cfg(feature = "random")]
+            previous_unique_id: rng.gen_range(0, UNIQUE_ID_LIMIT),
+            #[cfg(feature = "random")]
+            rng,
+        }
+    }
+
+    #[cfg(feature = "random")]
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Returns an id for `unique-id()`, which is `u` followed by six base-36
+    /// digits, so that it is always a valid CSS identifier
+    ///
+    /// As in dart-sass, each id is greater than the previous one by a random
+    /// amount between `1` and `36`, so that ids are unpredictable but are
+    /// never repeated within a compilation unless over 60 million are
+    /// generated.
+    #[cfg(feature = "random")]
+    pub fn unique_id(&mut self) -> String {
+        self.previous_unique_id =
+            (self.previous_unique_id + self.rng.gen_range(1, 37)) % UNIQUE_ID_LIMIT;
+
+        let mut digits = [b'0'; 6];
+        let mut id = self.previous_unique_id;
+        for digit in digits.iter_mut().rev() {
+            *digit = std::char::from_digit(id % 36, 36).unwrap() as u8;
+            id /= 36;
+        }
+
+        format!("u{}", std::str::from_utf8(&digits).unwrap())
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, rc::Rc};
 
 use codemap::Spanned;
 
@@ -7,14 +7,18 @@ use crate::{
     builtin::{modules::Module, GLOBAL_FUNCTIONS},
     common::Identifier,
     error::SassResult,
+    index_map::IndexMap,
     value::{SassFunction, Value},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct Scope {
-    pub vars: BTreeMap<Identifier, Value>,
+    /// Variables and functions are kept in the order they were declared, so
+    /// that `meta.module-variables()` and `meta.module-functions()` list
+    /// them in that order
+    pub vars: IndexMap<Identifier, Value>,
     pub mixins: BTreeMap<Identifier, Mixin>,
-    pub functions: BTreeMap<Identifier, SassFunction>,
+    pub functions: IndexMap<Identifier, SassFunction>,
 }
 
 impl Scope {
@@ -23,9 +27,9 @@ impl Scope {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            vars: BTreeMap::new(),
+            vars: IndexMap::new(),
             mixins: BTreeMap::new(),
-            functions: BTreeMap::new(),
+            functions: IndexMap::new(),
         }
     }
 
@@ -85,7 +89,12 @@ impl Scope {
     }
 
     pub fn merge_module(&mut self, other: Module) {
-        self.merge(other.scope);
+        // the functions declared in the module only hold weak references to
+        // its scope, so nothing else owns it
+        match Rc::try_unwrap(other.scope) {
+            Ok(scope) => self.merge(scope.into_inner()),
+            Err(scope) => self.merge(scope.borrow().clone()),
+        }
     }
 
     pub fn default_var_exists(&self, s: Identifier) -> bool {
@@ -133,20 +142,30 @@ impl Scopes {
 
 /// Variables
 impl Scopes {
-    pub fn insert_var(&mut self, s: Identifier, v: Value) -> Option<Value> {
+    /// Assigns to a variable declared without `!global`
+    ///
+    /// The innermost scope that already declares the variable is updated,
+    /// so that blocks can update the variables of the blocks around them.
+    /// Otherwise, the variable is declared in the innermost scope, shadowing
+    /// any global of the same name, unless the scopes are `semi_global`, as
+    /// the scopes of control flow at the root of a stylesheet are, in which
+    /// case an existing global is updated instead.
+    pub fn assign_var(
+        &mut self,
+        s: Identifier,
+        v: Value,
+        global_scope: &mut Scope,
+        semi_global: bool,
+    ) -> Option<Value> {
         for scope in self.0.iter_mut().rev() {
             if scope.var_exists(s) {
                 return scope.insert_var(s, v);
             }
         }
-        if let Some(scope) = self.0.last_mut() {
-            scope.insert_var(s, v)
-        } else {
-            let mut scope = Scope::new();
-            scope.insert_var(s, v);
-            self.0.push(scope);
-            None
+        if semi_global && global_scope.var_exists(s) {
+            return global_scope.insert_var(s, v);
         }
+        self.insert_var_last(s, v)
     }
 
     /// Always insert this variable into the innermost scope
@@ -163,14 +182,19 @@ impl Scopes {
         }
     }
 
-    pub fn default_var_exists(&self, name: Identifier) -> bool {
+    /// Whether the variable a `!default` declaration of `name` would assign
+    /// to is already set to something other than `null`
+    ///
+    /// Only the innermost declaration of the variable is considered, since
+    /// it shadows the others.
+    pub fn default_var_exists(&self, name: Identifier, global_scope: &Scope) -> bool {
         for scope in self.0.iter().rev() {
-            if scope.default_var_exists(name) {
-                return true;
+            if scope.var_exists(name) {
+                return scope.default_var_exists(name);
             }
         }
 
-        false
+        global_scope.default_var_exists(name)
     }
 
     pub fn get_var<'a>(
@@ -179,8 +203,8 @@ impl Scopes {
         global_scope: &'a Scope,
     ) -> SassResult<&Value> {
         for scope in self.0.iter().rev() {
-            if scope.var_exists(name.node) {
-                return scope.get_var(name);
+            if let Some(value) = scope.get_var_no_err(name.node) {
+                return Ok(value);
             }
         }
         global_scope.get_var(name)
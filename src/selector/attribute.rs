@@ -52,6 +52,15 @@ fn attribute_name(parser: &mut Parser<'_>, start: Span) -> SassResult<QualifiedN
             namespace: Namespace::Asterisk,
         });
     }
+    if next.kind == '|' {
+        parser.toks.next();
+
+        let ident = parser.parse_identifier()?.node;
+        return Ok(QualifiedName {
+            ident,
+            namespace: Namespace::Empty,
+        });
+    }
     parser.span_before = next.pos;
     let name_or_namespace = parser.parse_identifier()?;
     match parser.toks.peek() {
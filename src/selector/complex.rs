@@ -179,7 +179,9 @@ impl ComplexSelector {
                 };
 
                 if combinator1 == &Combinator::FollowingSibling {
-                    if combinator2 == &Combinator::Child {
+                    if combinator2 != &Combinator::FollowingSibling
+                        && combinator2 != &Combinator::NextSibling
+                    {
                         return false;
                     }
                 } else if combinator1 != combinator2 {
@@ -249,14 +251,25 @@ pub(crate) enum Combinator {
     ///
     /// `'~'`
     FollowingSibling,
+
+    /// Matches the right-hand selector if it's a cell in the column that the
+    /// left-hand selector represents.
+    ///
+    /// Unlike the other combinators, this doesn't relate the position of the
+    /// two elements in the DOM tree, so selectors joined by it are never
+    /// merged with others when extending.
+    ///
+    /// `'||'`
+    Column,
 }
 
 impl Display for Combinator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_char(match self {
-            Self::NextSibling => '+',
-            Self::Child => '>',
-            Self::FollowingSibling => '~',
+        f.write_str(match self {
+            Self::NextSibling => "+",
+            Self::Child => ">",
+            Self::FollowingSibling => "~",
+            Self::Column => "||",
         })
     }
 }
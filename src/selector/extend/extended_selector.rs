@@ -1,33 +1,32 @@
 use std::{
-    cell::RefCell,
-    collections::{hash_set::IntoIter, HashSet},
+    cell::{Ref, RefCell},
+    collections::hash_set::IntoIter,
     hash::{Hash, Hasher},
     ptr,
     rc::Rc,
 };
 
-use crate::selector::{Selector, SelectorList};
+use crate::{
+    hash::HashSet,
+    selector::{Selector, SelectorList},
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ExtendedSelector(Rc<RefCell<SelectorList>>);
 
+/// Style rules are compared by identity, consistently with `Hash`, so that
+/// distinct rules with equal selectors are each extended
 impl PartialEq for ExtendedSelector {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        Rc::ptr_eq(&self.0, &other.0)
     }
 }
 
 impl Eq for ExtendedSelector {}
 
 impl Hash for ExtendedSelector {
-    // We hash the ptr here for efficiency.
-    // TODO: is this an issue? it probably is,
-    // but I haven't managed to find a test case
-    // that exhibits it.
     fn hash<H: Hasher>(&self, state: &mut H) {
         ptr::hash(&*self.0, state)
-        // in case we need to hash the actual value:
-        // self.0.borrow().hash(state);
     }
 }
 
@@ -43,6 +42,11 @@ impl ExtendedSelector {
         })
     }
 
+    /// The selector list this currently refers to
+    pub fn list(&self) -> Ref<'_, SelectorList> {
+        self.0.borrow()
+    }
+
     pub fn set_inner(&mut self, selector: SelectorList) {
         self.0.replace(selector);
     }
@@ -60,7 +64,7 @@ pub(crate) struct SelectorHashSet(HashSet<ExtendedSelector>);
 
 impl SelectorHashSet {
     pub fn new() -> Self {
-        Self(HashSet::new())
+        Self(HashSet::default())
     }
 
     pub fn insert(&mut self, selector: ExtendedSelector) {
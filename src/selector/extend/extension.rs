@@ -1,11 +1,13 @@
 use codemap::Span;
 
-use super::{ComplexSelector, CssMediaQuery, SimpleSelector};
+use crate::{atrule::media::CssMediaQuery, error::SassResult};
+
+use super::{ComplexSelector, ComplexSelectorComponent, Shared, SimpleSelector};
 
 #[derive(Clone, Debug)]
 pub(crate) struct Extension {
     /// The selector in which the `@extend` appeared.
-    pub extender: ComplexSelector,
+    pub extender: Shared<ComplexSelector>,
 
     /// The selector that's being extended.
     ///
@@ -30,13 +32,15 @@ pub(crate) struct Extension {
     /// The span in which `extender` was defined.
     pub span: Span,
 
+    /// If this extension was created by merging two extensions, the
+    /// extensions it was created from
     pub left: Option<Box<Extension>>,
     pub right: Option<Box<Extension>>,
 }
 
 impl Extension {
     pub fn one_off(
-        extender: ComplexSelector,
+        extender: Shared<ComplexSelector>,
         specificity: Option<i32>,
         is_original: bool,
         span: Span,
@@ -56,19 +60,53 @@ impl Extension {
 
     /// Asserts that the `media_context` for a selector is compatible with the
     /// query context for this extender.
-    // todo: this should return a `Result`. it currently does not because the cascade effect
-    // from this returning a `Result` will make some code returning `Option`s much uglier (we can't
-    // use `?` to return both `Option` and `Result` from the same function)
-    pub fn assert_compatible_media_context(&self, media_context: &Option<Vec<CssMediaQuery>>) {
-        if &self.media_context == media_context {
-            return;
+    ///
+    /// An extension defined outside of any media query can extend selectors
+    /// in any context.
+    pub fn assert_compatible_media_context(
+        &self,
+        media_context: &Option<Vec<CssMediaQuery>>,
+    ) -> SassResult<()> {
+        if self.media_context.is_none() || &self.media_context == media_context {
+            return Ok(());
+        }
+
+        Err((
+            "You may not @extend selectors across media queries.",
+            self.span,
+        )
+            .into())
+    }
+
+    /// The simple selectors that make up `extender`, which must be a single
+    /// compound selector, as it is for a one-off extension of an original
+    /// selector
+    pub fn compound_extender(&self) -> SassResult<&[SimpleSelector]> {
+        match self.extender.components.as_slice() {
+            [ComplexSelectorComponent::Compound(compound)] => Ok(&compound.components),
+            _ => Err((
+                format!("Can't extend complex selector {}.", self.extender),
+                self.span,
+            )
+                .into()),
         }
+    }
 
-        // Err(("You may not @extend selectors across media queries.", self.span).into())
+    /// Returns the extensions this extension was created from by merging, or
+    /// `self` if it was not created by merging
+    pub fn unmerge(&self) -> Vec<&Extension> {
+        match (&self.left, &self.right) {
+            (Some(left), Some(right)) => {
+                let mut extensions = left.unmerge();
+                extensions.append(&mut right.unmerge());
+                extensions
+            }
+            _ => vec![self],
+        }
     }
 
     #[allow(clippy::missing_const_for_fn)]
-    pub fn with_extender(mut self, extender: ComplexSelector) -> Self {
+    pub fn with_extender(mut self, extender: Shared<ComplexSelector>) -> Self {
         self.extender = extender;
         self
     }
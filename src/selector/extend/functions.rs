@@ -505,11 +505,8 @@ fn merge_final_combinators(
                     components_two
                         .push_back(ComplexSelectorComponent::Combinator(Combinator::Child));
                 }
-                (..) => {
-                    if combinator_one != combinator_two {
-                        return None;
-                    }
-
+                (Combinator::Child, Combinator::Child)
+                | (Combinator::NextSibling, Combinator::NextSibling) => {
                     let unified = compound_one.unify(compound_two)?;
 
                     result.push_front(vec![vec![
@@ -517,11 +514,39 @@ fn merge_final_combinators(
                         ComplexSelectorComponent::Combinator(*combinator_one),
                     ]]);
                 }
+                (Combinator::Column, Combinator::Column) => {
+                    if compound_one.is_super_selector(&compound_two, &None) {
+                        result.push_front(vec![vec![
+                            ComplexSelectorComponent::Compound(compound_two),
+                            ComplexSelectorComponent::Combinator(Combinator::Column),
+                        ]]);
+                    } else if compound_two.is_super_selector(&compound_one, &None) {
+                        result.push_front(vec![vec![
+                            ComplexSelectorComponent::Compound(compound_one),
+                            ComplexSelectorComponent::Combinator(Combinator::Column),
+                        ]]);
+                    } else {
+                        // A cell may span columns matched by each compound
+                        // separately, which a single selector can't express.
+                        return None;
+                    }
+                }
+                // Any other pair, including the column combinator with any
+                // other combinator, doesn't describe a relationship that both
+                // selectors can be merged into.
+                (..) => return None,
             }
 
             merge_final_combinators(components_one, components_two, Some(result))
         }
         (Some(combinator_one), None) => {
+            // The column the left-hand side of `||` represents isn't an
+            // ancestor or sibling of its cells, so it can't be placed among
+            // the other selector's parents.
+            if *combinator_one == Combinator::Column && !components_two.is_empty() {
+                return None;
+            }
+
             if *combinator_one == Combinator::Child && !components_two.is_empty() {
                 if let Some(ComplexSelectorComponent::Compound(c1)) = components_one.back() {
                     if let Some(ComplexSelectorComponent::Compound(c2)) = components_two.back() {
@@ -540,6 +565,10 @@ fn merge_final_combinators(
             merge_final_combinators(components_one, components_two, Some(result))
         }
         (None, Some(combinator_two)) => {
+            if *combinator_two == Combinator::Column && !components_one.is_empty() {
+                return None;
+            }
+
             if *combinator_two == Combinator::Child && !components_one.is_empty() {
                 if let Some(ComplexSelectorComponent::Compound(c1)) = components_one.back() {
                     if let Some(ComplexSelectorComponent::Compound(c2)) = components_two.back() {
@@ -761,5 +790,12 @@ fn must_unify(
 /// Returns whether a `CompoundSelector` may contain only one simple selector of
 /// the same type as `simple`.
 fn is_unique(simple: &SimpleSelector) -> bool {
-    matches!(simple, SimpleSelector::Id(..) | SimpleSelector::Pseudo(Pseudo { is_class: false, .. }))
+    matches!(
+        simple,
+        SimpleSelector::Id(..)
+            | SimpleSelector::Pseudo(Pseudo {
+                is_class: false,
+                ..
+            })
+    )
 }
@@ -1,4 +1,4 @@
-use crate::error::SassResult;
+use crate::error::{SassError, SassResult};
 
 use super::Extension;
 
@@ -33,11 +33,15 @@ impl MergedExtension {
             && right.media_context.is_some()
             && left.media_context != right.media_context
         {
-            return Err((
+            let err: Box<SassError> = (
                 "You may not @extend the same selector from within different media queries.",
                 right.span,
             )
-                .into());
+                .into();
+            return Err(err.with_secondary_span(
+                "the same selector is extended within another media query here",
+                left.span,
+            ));
         }
 
         if right.is_optional && right.media_context.is_none() {
@@ -53,18 +57,18 @@ impl MergedExtension {
 
     fn into_extension(left: Extension, right: Extension) -> Extension {
         Extension {
-            extender: left.extender,
-            target: left.target,
+            extender: left.extender.clone(),
+            target: left.target.clone(),
             span: left.span,
-            media_context: match left.media_context {
-                Some(v) => Some(v),
-                None => right.media_context,
+            media_context: match &left.media_context {
+                Some(v) => Some(v.clone()),
+                None => right.media_context.clone(),
             },
             specificity: left.specificity,
             is_optional: true,
             is_original: false,
-            left: None,
-            right: None,
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
         }
     }
 }
@@ -1,13 +1,14 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    hash::Hash,
-};
+use std::{collections::VecDeque, hash::Hash, rc::Rc};
 
 use codemap::Span;
 
-use indexmap::IndexMap;
-
-use crate::error::SassResult;
+use crate::{
+    atrule::media::CssMediaQuery,
+    error::{SassError, SassResult},
+    hash::{HashMap, HashSet},
+    index_map::IndexMap,
+    limits::Limits,
+};
 
 use super::{
     ComplexSelector, ComplexSelectorComponent, CompoundSelector, Pseudo, SelectorList,
@@ -21,15 +22,16 @@ pub(crate) use functions::unify_complex;
 use functions::{paths, weave};
 use merged::MergedExtension;
 pub(crate) use rule::ExtendRule;
+use shared::{Interner, Shared};
+use superselector_index::SuperSelectorIndex;
 
 mod extended_selector;
 mod extension;
 mod functions;
 mod merged;
 mod rule;
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub(crate) struct CssMediaQuery;
+mod shared;
+mod superselector_index;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 /// Different modes in which extension can run.
@@ -58,6 +60,10 @@ impl Default for ExtendMode {
     }
 }
 
+/// The extensions of each target simple selector, by the selector in which
+/// each `@extend` appeared
+type Extensions = HashMap<SimpleSelector, IndexMap<Shared<ComplexSelector>, Rc<Extension>>>;
+
 #[derive(Clone, Debug)]
 pub(crate) struct Extender {
     /// A map from all simple selectors in the stylesheet to the selector lists
@@ -65,21 +71,26 @@ pub(crate) struct Extender {
     ///
     /// This is used to find which selectors an `@extend` applies to and adjust
     /// them.
-    selectors: HashMap<SimpleSelector, SelectorHashSet>,
+    selectors: HashMap<Shared<SimpleSelector>, SelectorHashSet>,
 
     /// A map from all extended simple selectors to the sources of those
     /// extensions.
-    extensions: HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
+    ///
+    /// Extensions are reference-counted so that they can be shared between
+    /// this map, `extensions_by_extender`, and the options built while
+    /// extending a compound selector without deep-copying their extenders,
+    /// and each is stored under the same shared selector as its extender.
+    extensions: Extensions,
 
     /// A map from all simple selectors in extenders to the extensions that those
     /// extenders define.
-    extensions_by_extender: HashMap<SimpleSelector, Vec<Extension>>,
+    extensions_by_extender: HashMap<Shared<SimpleSelector>, Vec<Rc<Extension>>>,
 
     /// A map from CSS selectors to the media query contexts they're defined in.
     ///
     /// This tracks the contexts in which each selector's style rule is defined.
     /// If a rule is defined at the top level, it doesn't have an entry.
-    media_contexts: HashMap<SelectorList, Vec<CssMediaQuery>>,
+    media_contexts: HashMap<ExtendedSelector, Vec<CssMediaQuery>>,
 
     /// A map from `SimpleSelector`s to the specificity of their source
     /// selectors.
@@ -90,7 +101,20 @@ pub(crate) struct Extender {
     /// of extend][].
     ///
     /// [second law of extend]: https://github.com/sass/sass/issues/324#issuecomment-4607184
-    source_specificity: HashMap<SimpleSelector, i32>,
+    source_specificity: HashMap<Shared<SimpleSelector>, i32>,
+
+    /// The simple selectors used as keys of `selectors`,
+    /// `extensions_by_extender` and `source_specificity`, so that each map
+    /// shares one copy of every simple selector.
+    simples: Interner<SimpleSelector>,
+
+    /// The compound selectors of `one_off_extensions`.
+    compounds: Interner<CompoundSelector>,
+
+    /// The one-off extensions created for compound selectors while extending,
+    /// which are reused until the source specificity of one of their simple
+    /// selectors is recorded.
+    one_off_extensions: HashMap<Shared<CompoundSelector>, Rc<Extension>>,
 
     /// A set of `ComplexSelector`s that were originally part of
     /// their component `SelectorList`s, as opposed to being added by `@extend`.
@@ -104,6 +128,25 @@ pub(crate) struct Extender {
     /// The mode that controls this extender's behavior.
     mode: ExtendMode,
 
+    /// The selectors of all style rules added to this extender, paired with
+    /// the span of the style rule's selector in the source.
+    ///
+    /// This is only tracked if a selector map was requested.
+    rule_origins: Option<Vec<(Span, ExtendedSelector)>>,
+
+    /// The span of the first style rule in which each placeholder selector
+    /// appeared.
+    ///
+    /// This is only tracked if a dead code report was requested.
+    placeholder_origins: Option<HashMap<SimpleSelector, Span>>,
+
+    /// The limits of the compilation this extender belongs to, which are
+    /// checked while extending since a single `@extend` can produce an
+    /// exponential number of selectors.
+    ///
+    /// Extenders used by selector functions have no limits of their own.
+    limits: Option<Limits>,
+
     span: Span,
 }
 
@@ -124,17 +167,35 @@ impl Extender {
 
     pub fn new(span: Span) -> Self {
         Self {
-            selectors: HashMap::new(),
-            extensions: HashMap::new(),
-            extensions_by_extender: HashMap::new(),
-            media_contexts: HashMap::new(),
-            source_specificity: HashMap::new(),
-            originals: HashSet::new(),
+            selectors: HashMap::default(),
+            extensions: HashMap::default(),
+            extensions_by_extender: HashMap::default(),
+            media_contexts: HashMap::default(),
+            source_specificity: HashMap::default(),
+            simples: Interner::new(),
+            compounds: Interner::new(),
+            one_off_extensions: HashMap::default(),
+            originals: HashSet::default(),
             mode: ExtendMode::Normal,
+            rule_origins: None,
+            placeholder_origins: None,
+            limits: None,
             span,
         }
     }
 
+    /// Enforces the limits of a compilation while extending selectors
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = Some(limits);
+    }
+
+    fn check_limits(&self, span: Span) -> SassResult<()> {
+        match self.limits {
+            Some(limits) => limits.check(span),
+            None => Ok(()),
+        }
+    }
+
     pub fn replace(
         selector: SelectorList,
         source: SelectorList,
@@ -151,13 +212,14 @@ impl Extender {
         mode: ExtendMode,
         span: Span,
     ) -> SassResult<SelectorList> {
-        let extenders: IndexMap<ComplexSelector, Extension> = source
+        let extenders: IndexMap<Shared<ComplexSelector>, Rc<Extension>> = source
             .components
             .into_iter()
             .map(|complex| {
+                let complex = Shared::new(complex);
                 (
                     complex.clone(),
-                    Extension::one_off(complex, None, false, span),
+                    Rc::new(Extension::one_off(complex, None, false, span)),
                 )
             })
             .collect();
@@ -174,16 +236,15 @@ impl Extender {
             })
             .collect::<SassResult<Vec<CompoundSelector>>>()?;
 
-        let extensions: HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>> =
-            compound_targets
-                .into_iter()
-                .flat_map(|compound| {
-                    compound
-                        .components
-                        .into_iter()
-                        .map(|simple| (simple, extenders.clone()))
-                })
-                .collect();
+        let extensions: Extensions = compound_targets
+            .into_iter()
+            .flat_map(|compound| {
+                compound
+                    .components
+                    .into_iter()
+                    .map(|simple| (simple, extenders.clone()))
+            })
+            .collect();
 
         let mut extender = Extender::with_mode(mode, span);
 
@@ -193,7 +254,7 @@ impl Extender {
                 .extend(selector.components.iter().cloned());
         }
 
-        Ok(extender.extend_list(selector, Some(&extensions), &None))
+        extender.extend_list(selector, Some(&extensions), &None)
     }
 
     fn with_mode(mode: ExtendMode, span: Span) -> Self {
@@ -207,27 +268,17 @@ impl Extender {
     fn extend_list(
         &mut self,
         list: SelectorList,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        extensions: Option<&Extensions>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> SelectorList {
+    ) -> SassResult<SelectorList> {
         // This could be written more simply using Vec<Vec<T>>, but we want to avoid
         // any allocations in the common case where no extends apply.
         let mut extended: Option<Vec<ComplexSelector>> = None;
         for (i, complex) in list.components.iter().enumerate() {
-            if let Some(result) =
-                self.extend_complex(complex.clone(), extensions, media_query_context)
-            {
-                if extended.is_none() {
-                    extended = Some(if i == 0 {
-                        Vec::new()
-                    } else {
-                        list.components[0..i].to_vec()
-                    });
-                }
-                match extended.as_mut() {
-                    Some(v) => v.extend(result.into_iter()),
-                    None => unreachable!(),
-                }
+            if let Some(result) = self.extend_complex(complex, extensions, media_query_context)? {
+                extended
+                    .get_or_insert_with(|| list.components[0..i].to_vec())
+                    .extend(result.into_iter());
             } else if let Some(extended) = extended.as_mut() {
                 extended.push(complex.clone());
             }
@@ -235,23 +286,23 @@ impl Extender {
 
         let extended = match extended {
             Some(v) => v,
-            None => return list,
+            None => return Ok(list),
         };
 
-        SelectorList {
+        Ok(SelectorList {
             components: self.trim(extended, |complex| self.originals.contains(complex)),
             span: self.span,
-        }
+        })
     }
 
     /// Extends `complex` using `extensions`, and returns the contents of a
     /// `SelectorList`.
     fn extend_complex(
         &mut self,
-        complex: ComplexSelector,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        complex: &ComplexSelector,
+        extensions: Option<&Extensions>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<ComplexSelector>> {
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // The complex selectors that each compound selector in `complex.components`
         // can expand to.
         //
@@ -276,28 +327,24 @@ impl Extender {
         for (i, component) in complex.components.iter().enumerate() {
             if let ComplexSelectorComponent::Compound(component) = component {
                 if let Some(extended) =
-                    self.extend_compound(component, extensions, media_query_context)
+                    self.extend_compound(component, extensions, media_query_context)?
                 {
-                    if extended_not_expanded.is_none() {
-                        extended_not_expanded = Some(
+                    extended_not_expanded
+                        .get_or_insert_with(|| {
                             complex
                                 .components
-                                .clone()
-                                .into_iter()
+                                .iter()
                                 .take(i)
+                                .cloned()
                                 .map(|component| {
                                     vec![ComplexSelector {
                                         components: vec![component],
                                         line_break: complex.line_break,
                                     }]
                                 })
-                                .collect(),
-                        );
-                    }
-                    match extended_not_expanded.as_mut() {
-                        Some(v) => v.push(extended),
-                        None => unreachable!(),
-                    }
+                                .collect()
+                        })
+                        .push(extended);
                 } else {
                     match extended_not_expanded.as_mut() {
                         Some(v) => v.push(vec![ComplexSelector {
@@ -318,17 +365,22 @@ impl Extender {
             }
         }
 
-        let extended_not_expanded = extended_not_expanded?;
+        let extended_not_expanded = match extended_not_expanded {
+            Some(v) => v,
+            None => return Ok(None),
+        };
 
         let mut first = true;
 
-        Some(
+        Ok(Some(
             paths(extended_not_expanded)
                 .into_iter()
                 .flat_map(move |path| {
+                    let line_break = complex_has_line_break
+                        || path.iter().any(|input_complex| input_complex.line_break);
+
                     weave(
-                        path.clone()
-                            .into_iter()
+                        path.into_iter()
                             .map(move |complex| complex.components)
                             .collect(),
                     )
@@ -336,14 +388,13 @@ impl Extender {
                     .map(|components| {
                         let output_complex = ComplexSelector {
                             components,
-                            line_break: complex_has_line_break
-                                || path.iter().any(|input_complex| input_complex.line_break),
+                            line_break,
                         };
 
                         // Make sure that copies of `complex` retain their status as "original"
                         // selectors. This includes selectors that are modified because a :not()
                         // was extended into.
-                        if first && self.originals.contains(&complex.clone()) {
+                        if first && self.originals.contains(complex) {
                             self.originals.insert(output_complex.clone());
                         }
                         first = false;
@@ -353,7 +404,7 @@ impl Extender {
                     .collect::<Vec<ComplexSelector>>()
                 })
                 .collect(),
-        )
+        ))
     }
 
     /// Extends `compound` using `extensions`, and returns the contents of a
@@ -366,24 +417,22 @@ impl Extender {
     fn extend_compound(
         &mut self,
         compound: &CompoundSelector,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        extensions: Option<&Extensions>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<ComplexSelector>> {
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // If there's more than one target and they all need to match, we track
         // which targets are actually extended.
-        let mut targets_used: HashSet<SimpleSelector> = HashSet::new();
-
-        let mut options: Option<Vec<Vec<Extension>>> = None;
+        let mut targets_used: HashSet<SimpleSelector> = HashSet::default();
 
-        for i in 0..compound.components.len() {
-            let simple = compound.components.get(i).cloned().unwrap();
+        let mut options: Option<Vec<Vec<Rc<Extension>>>> = None;
 
+        for (i, simple) in compound.components.iter().enumerate() {
             match self.extend_simple(
                 simple.clone(),
                 extensions,
                 media_query_context,
                 &mut targets_used,
-            ) {
+            )? {
                 Some(extended) => {
                     if options.is_none() {
                         let mut new_options = Vec::new();
@@ -395,19 +444,21 @@ impl Extender {
                         options.replace(new_options);
                     }
 
-                    match options.as_mut() {
-                        Some(v) => v.extend(extended.into_iter()),
-                        None => unreachable!(),
+                    if let Some(v) = options.as_mut() {
+                        v.extend(extended.into_iter());
                     }
                 }
                 None => match options.as_mut() {
-                    Some(v) => v.push(vec![self.extension_for_simple(simple)]),
+                    Some(v) => v.push(vec![self.extension_for_simple(simple.clone())]),
                     None => {}
                 },
             }
         }
 
-        let options = options?;
+        let options = match options {
+            Some(v) => v,
+            None => return Ok(None),
+        };
 
         // If `self.mode` isn't `ExtendMode::Normal` and we didn't use all the targets in
         // `extensions`, extension fails for `compound`.
@@ -416,23 +467,18 @@ impl Extender {
             && targets_used.len() != extensions.map_or(self.extensions.len(), HashMap::len)
             && self.mode != ExtendMode::Normal
         {
-            return None;
+            return Ok(None);
         }
 
         // Optimize for the simple case of a single simple selector that doesn't
         // need any unification.
         if options.len() == 1 {
-            return Some(
-                options
-                    .first()?
-                    .clone()
-                    .into_iter()
-                    .map(|state| {
-                        state.assert_compatible_media_context(media_query_context);
-                        state.extender
-                    })
-                    .collect(),
-            );
+            let mut extended = Vec::new();
+            for state in options.into_iter().flatten() {
+                state.assert_compatible_media_context(media_query_context)?;
+                extended.push(ComplexSelector::clone(&state.extender));
+            }
+            return Ok(Some(extended));
         }
 
         // Find all paths through `options`. In this case, each path represents a
@@ -461,99 +507,81 @@ impl Extender {
         //     ]
         let mut first = self.mode != ExtendMode::Replace;
 
-        let unified_paths: Vec<Option<Vec<ComplexSelector>>> = paths(options)
-            .into_iter()
-            .map(|path| {
-                let complexes: Vec<Vec<ComplexSelectorComponent>> = if first {
-                    // The first path is always the original selector. We can't just
-                    // return `compound` directly because pseudo selectors may be
-                    // modified, but we don't have to do any unification.
-                    first = false;
+        let mut unified_paths: Vec<ComplexSelector> = Vec::new();
 
-                    vec![vec![ComplexSelectorComponent::Compound(CompoundSelector {
-                        components: path
-                            .clone()
-                            .into_iter()
-                            .flat_map(|state| {
-                                debug_assert!(state.extender.components.len() == 1);
-                                match state.extender.components.last().cloned() {
-                                    Some(ComplexSelectorComponent::Compound(c)) => c.components,
-                                    Some(..) | None => unreachable!(),
-                                }
-                            })
-                            .collect(),
-                    })]]
-                } else {
-                    let mut to_unify: VecDeque<Vec<ComplexSelectorComponent>> = VecDeque::new();
-                    let mut originals: Vec<SimpleSelector> = Vec::new();
-
-                    for state in path.clone() {
-                        if state.is_original {
-                            originals.extend(match state.extender.components.last().cloned() {
-                                Some(ComplexSelectorComponent::Compound(c)) => c.components,
-                                Some(..) | None => unreachable!(),
-                            });
-                        } else {
-                            to_unify.push_back(state.extender.components.clone());
-                        }
-                    }
-                    if !originals.is_empty() {
-                        to_unify.push_front(vec![ComplexSelectorComponent::Compound(
-                            CompoundSelector {
-                                components: originals,
-                            },
-                        )]);
-                    }
+        for path in paths(options) {
+            let complexes: Vec<Vec<ComplexSelectorComponent>> = if first {
+                // The first path is always the original selector. We can't just
+                // return `compound` directly because pseudo selectors may be
+                // modified, but we don't have to do any unification.
+                first = false;
 
-                    unify_complex(Vec::from(to_unify))?
-                };
+                let mut components = Vec::new();
+                for state in &path {
+                    components.extend_from_slice(state.compound_extender()?);
+                }
 
-                let mut line_break = false;
+                vec![vec![ComplexSelectorComponent::Compound(CompoundSelector {
+                    components,
+                })]]
+            } else {
+                let mut to_unify: VecDeque<Vec<ComplexSelectorComponent>> = VecDeque::new();
+                let mut originals: Vec<SimpleSelector> = Vec::new();
 
-                for state in path {
-                    state.assert_compatible_media_context(media_query_context);
-                    line_break = line_break || state.extender.line_break;
+                for state in &path {
+                    if state.is_original {
+                        originals.extend_from_slice(state.compound_extender()?);
+                    } else {
+                        to_unify.push_back(state.extender.components.clone());
+                    }
+                }
+                if !originals.is_empty() {
+                    to_unify.push_front(vec![ComplexSelectorComponent::Compound(
+                        CompoundSelector {
+                            components: originals,
+                        },
+                    )]);
                 }
 
-                Some(
-                    complexes
-                        .into_iter()
-                        .map(|components| ComplexSelector {
-                            components,
-                            line_break,
-                        })
-                        .collect(),
-                )
-            })
-            .collect();
+                match unify_complex(Vec::from(to_unify)) {
+                    Some(complexes) => complexes,
+                    None => continue,
+                }
+            };
 
-        Some(
-            unified_paths
-                .into_iter()
-                .filter_map(|complexes| complexes)
-                .flatten()
-                .collect(),
-        )
+            let mut line_break = false;
+
+            for state in path {
+                state.assert_compatible_media_context(media_query_context)?;
+                line_break = line_break || state.extender.line_break;
+            }
+
+            unified_paths.extend(complexes.into_iter().map(|components| ComplexSelector {
+                components,
+                line_break,
+            }));
+        }
+
+        Ok(Some(unified_paths))
     }
 
     fn extend_simple(
         &mut self,
         simple: SimpleSelector,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        extensions: Option<&Extensions>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
         targets_used: &mut HashSet<SimpleSelector>,
-    ) -> Option<Vec<Vec<Extension>>> {
-        if let SimpleSelector::Pseudo(Pseudo {
-            selector: Some(..), ..
-        }) = &simple
+    ) -> SassResult<Option<Vec<Vec<Rc<Extension>>>>> {
+        if let SimpleSelector::Pseudo(
+            pseudo @ Pseudo {
+                selector: Some(..), ..
+            },
+        ) = &simple
         {
-            let simple = if let SimpleSelector::Pseudo(pseudo) = simple.clone() {
-                pseudo
-            } else {
-                unreachable!()
-            };
-            if let Some(extended) = self.extend_pseudo(simple, extensions, media_query_context) {
-                return Some(
+            if let Some(extended) =
+                self.extend_pseudo(pseudo.clone(), extensions, media_query_context)?
+            {
+                return Ok(Some(
                     extended
                         .into_iter()
                         .map(move |pseudo| {
@@ -568,12 +596,13 @@ impl Extender {
                             })
                         })
                         .collect(),
-                );
+                ));
             }
         }
 
-        self.without_pseudo(simple, extensions, targets_used, self.mode)
-            .map(|v| vec![v])
+        Ok(self
+            .without_pseudo(simple, extensions, targets_used, self.mode)
+            .map(|v| vec![v]))
     }
 
     /// Extends `pseudo` using `extensions`, and returns a list of resulting
@@ -581,9 +610,9 @@ impl Extender {
     fn extend_pseudo(
         &mut self,
         pseudo: Pseudo,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        extensions: Option<&Extensions>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<Pseudo>> {
+    ) -> SassResult<Option<Vec<Pseudo>>> {
         let extended = self.extend_list(
             pseudo
                 .selector
@@ -592,10 +621,10 @@ impl Extender {
                 .unwrap_or_else(|| SelectorList::new(self.span)),
             extensions,
             media_query_context,
-        );
+        )?;
         /*todo: identical(extended, pseudo.selector)*/
         if Some(&extended) == pseudo.selector.as_deref() {
-            return None;
+            return Ok(None);
         }
 
         // For `:not()`, we usually want to get rid of any complex selectors because
@@ -604,13 +633,12 @@ impl Extender {
         // selector, or the result of extending has only complex selectors, because
         // either way we aren't breaking anything that isn't already broken.
         let mut complexes = if pseudo.normalized_name() == "not"
-            && !pseudo
-                .selector
-                .clone()
-                .unwrap()
-                .components
-                .iter()
-                .any(|complex| complex.components.len() > 1)
+            && !pseudo.selector.as_ref().map_or(false, |selector| {
+                selector
+                    .components
+                    .iter()
+                    .any(|complex| complex.components.len() > 1)
+            })
             && extended
                 .components
                 .iter()
@@ -638,16 +666,14 @@ impl Extender {
                 if compound.components.len() != 1 {
                     return vec![complex];
                 }
-                if !compound.components.first().unwrap().is_pseudo() {
-                    return vec![complex];
-                }
                 let inner_pseudo = match compound.components.first() {
                     Some(SimpleSelector::Pseudo(pseudo)) => pseudo,
                     Some(..) | None => return vec![complex],
                 };
-                if inner_pseudo.selector.is_none() {
-                    return vec![complex];
-                }
+                let inner_selector = match &inner_pseudo.selector {
+                    Some(selector) => selector,
+                    None => return vec![complex],
+                };
 
                 match pseudo.normalized_name() {
                     "not" => {
@@ -657,14 +683,15 @@ impl Extender {
                         // become `.foo:not(.bar)`. However, this is a narrow edge case and
                         // supporting it properly would make this code and the code calling it
                         // a lot more complicated, so it's not supported for now.
-                        if inner_pseudo.normalized_name() == "matches" {
-                            inner_pseudo.selector.clone().unwrap().components
+                        if matches!(inner_pseudo.normalized_name(), "is" | "matches" | "where") {
+                            inner_selector.components.clone()
                         } else {
                             Vec::new()
                         }
                     }
-                    "matches" | "any" | "current" | "nth-child" | "nth-last-child" => {
-                        // As above, we could theoretically support :not within :matches, but
+                    "is" | "matches" | "where" | "any" | "current" | "nth-child"
+                    | "nth-last-child" => {
+                        // As above, we could theoretically support :not within :is, but
                         // doing so would require this method and its callers to handle much
                         // more complex cases that likely aren't worth the pain.
                         if inner_pseudo.name != pseudo.name
@@ -672,7 +699,7 @@ impl Extender {
                         {
                             Vec::new()
                         } else {
-                            inner_pseudo.selector.clone().unwrap().components
+                            inner_selector.components.clone()
                         }
                     }
                     "has" | "host" | "host-context" | "slotted" => {
@@ -690,7 +717,10 @@ impl Extender {
         // In order to support those browsers, we break up the contents of a `:not`
         // unless it originally contained a selector list.
         if pseudo.normalized_name() == "not"
-            && pseudo.selector.clone().unwrap().components.len() == 1
+            && pseudo
+                .selector
+                .as_ref()
+                .map_or(false, |selector| selector.components.len() == 1)
         {
             let result = complexes
                 .into_iter()
@@ -702,74 +732,79 @@ impl Extender {
                 })
                 .collect::<Vec<Pseudo>>();
             if result.is_empty() {
-                None
+                Ok(None)
             } else {
-                Some(result)
+                Ok(Some(result))
             }
         } else {
-            Some(vec![pseudo.with_selector(Some(Box::new(SelectorList {
-                components: complexes,
-                span: self.span,
-            })))])
+            Ok(Some(vec![pseudo.with_selector(Some(Box::new(
+                SelectorList {
+                    components: complexes,
+                    span: self.span,
+                },
+            )))]))
         }
     }
 
     /// Extends `simple` without extending the contents of any selector pseudos
     /// it contains.
     fn without_pseudo(
-        &self,
+        &mut self,
         simple: SimpleSelector,
-        extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
+        extensions: Option<&Extensions>,
         targets_used: &mut HashSet<SimpleSelector>,
         mode: ExtendMode,
-    ) -> Option<Vec<Extension>> {
-        let extenders = extensions.unwrap_or(&self.extensions).get(&simple)?;
+    ) -> Option<Vec<Rc<Extension>>> {
+        let extenders: Vec<Rc<Extension>> = extensions
+            .unwrap_or(&self.extensions)
+            .get(&simple)?
+            .values()
+            .cloned()
+            .collect();
 
         targets_used.insert(simple.clone());
 
         if mode == ExtendMode::Replace {
-            return Some(extenders.values().cloned().collect());
+            return Some(extenders);
         }
 
         let mut tmp = vec![self.extension_for_simple(simple)];
-        tmp.extend(extenders.values().cloned());
+        tmp.extend(extenders);
 
         Some(tmp)
     }
 
     /// Returns a one-off `Extension` whose extender is composed solely of
     /// `simple`.
-    fn extension_for_simple(&self, simple: SimpleSelector) -> Extension {
-        let specificity = Some(*self.source_specificity.get(&simple).unwrap_or(&0_i32));
-        Extension::one_off(
-            ComplexSelector {
-                components: vec![ComplexSelectorComponent::Compound(CompoundSelector {
-                    components: vec![simple],
-                })],
-                line_break: false,
-            },
-            specificity,
-            true,
-            self.span,
-        )
+    fn extension_for_simple(&mut self, simple: SimpleSelector) -> Rc<Extension> {
+        self.extension_for_compound(vec![simple])
     }
 
     /// Returns a one-off `Extension` whose extender is composed solely of a
     /// compound selector containing `simples`.
-    fn extension_for_compound(&self, simples: Vec<SimpleSelector>) -> Extension {
-        let compound = CompoundSelector {
+    fn extension_for_compound(&mut self, simples: Vec<SimpleSelector>) -> Rc<Extension> {
+        let compound = self.compounds.intern(&CompoundSelector {
             components: simples,
-        };
+        });
+        if let Some(extension) = self.one_off_extensions.get(&compound) {
+            return Rc::clone(extension);
+        }
+
         let specificity = Some(self.source_specificity_for(&compound));
-        Extension::one_off(
-            ComplexSelector {
-                components: vec![ComplexSelectorComponent::Compound(compound)],
+        let extension = Rc::new(Extension::one_off(
+            Shared::new(ComplexSelector {
+                components: vec![ComplexSelectorComponent::Compound(CompoundSelector::clone(
+                    &compound,
+                ))],
                 line_break: false,
-            },
+            }),
             specificity,
             true,
             self.span,
-        )
+        ));
+        self.one_off_extensions
+            .insert(compound, Rc::clone(&extension));
+        extension
     }
 
     /// Returns the maximum specificity for sources that went into producing
@@ -777,11 +812,32 @@ impl Extender {
     fn source_specificity_for(&self, compound: &CompoundSelector) -> i32 {
         let mut specificity = 0;
         for simple in &compound.components {
-            specificity = specificity.max(*self.source_specificity.get(simple).unwrap_or(&0));
+            let source_specificity = self
+                .simples
+                .get(simple)
+                .and_then(|simple| self.source_specificity.get(simple));
+            specificity = specificity.max(*source_specificity.unwrap_or(&0));
         }
         specificity
     }
 
+    /// Records the specificity of the `@extend` that `simple` first appeared in
+    /// the extender of, which one-off extensions created before then don't
+    /// account for.
+    fn add_source_specificity(&mut self, simple: Shared<SimpleSelector>, specificity: i32) {
+        if !self.source_specificity.contains_key(&simple) {
+            self.source_specificity.insert(simple, specificity);
+            self.one_off_extensions.clear();
+        }
+    }
+
+    /// Whether `simple` appears in any selector added to this extender.
+    fn has_selector(&self, simple: &SimpleSelector) -> bool {
+        self.simples
+            .get(simple)
+            .map_or(false, |simple| self.selectors.contains_key(simple))
+    }
+
     /// Removes elements from `selectors` if they're subselectors of other
     /// elements.
     ///
@@ -792,22 +848,21 @@ impl Extender {
         selectors: Vec<ComplexSelector>,
         is_original: impl Fn(&ComplexSelector) -> bool,
     ) -> Vec<ComplexSelector> {
-        // Avoid truly horrific quadratic behavior.
-        //
-        // TODO(nweiz): I think there may be a way to get perfect trimming without
-        // going quadratic by building some sort of trie-like data structure that
-        // can be used to look up superselectors.
-        if selectors.len() > 100 {
-            return selectors;
-        }
-
-        // This is n² on the sequences, but only comparing between separate
-        // sequences should limit the quadratic behavior. We iterate from last to
-        // first and reverse the result so that, if two selectors are identical, we
-        // keep the first one.
+        // Rather than comparing every pair of selectors, only compare each
+        // selector against those that the index says might be superselectors of
+        // it. We iterate from last to first and reverse the result so that, if two
+        // selectors are identical, we keep the first one.
+        let index = SuperSelectorIndex::new(&selectors);
         let mut result: VecDeque<ComplexSelector> = VecDeque::new();
         let mut num_originals = 0;
 
+        // Whether the selector at each index has been added to `result`.
+        let mut kept = vec![false; selectors.len()];
+
+        // The originals that have been added to `result`, so that we only look
+        // for a duplicate when there is one.
+        let mut seen_originals: HashSet<&ComplexSelector> = HashSet::default();
+
         // :outer
         for i in (0..=(selectors.len().saturating_sub(1))).rev() {
             let mut should_continue_to_outer = false;
@@ -815,11 +870,13 @@ impl Extender {
             if is_original(complex1) {
                 // Make sure we don't include duplicate originals, which could happen if
                 // a style rule extends a component of its own selector.
-                for j in 0..num_originals {
-                    if result.get(j) == Some(complex1) {
-                        rotate_slice(&mut result, 0, j + 1);
-                        should_continue_to_outer = true;
-                        break;
+                if !seen_originals.insert(complex1) {
+                    for j in 0..num_originals {
+                        if result.get(j) == Some(complex1) {
+                            rotate_slice(&mut result, 0, j + 1);
+                            should_continue_to_outer = true;
+                            break;
+                        }
                     }
                 }
                 if should_continue_to_outer {
@@ -827,6 +884,7 @@ impl Extender {
                 }
                 num_originals += 1;
                 result.push_front(complex1.clone());
+                kept[i] = true;
                 continue;
             }
 
@@ -841,20 +899,14 @@ impl Extender {
                 }
             }
 
-            // Look in `result` rather than `selectors` for selectors after `i`. This
+            // Only compare against selectors after `i` that are in `result`. This
             // ensures that we aren't comparing against a selector that's already been
             // trimmed, and thus that if there are two identical selectors only one is
             // trimmed.
-            let should_continue = result.iter().any(|complex2| {
-                complex2.min_specificity() >= max_specificity
-                    && complex2.is_super_selector(complex1)
-            });
-            if should_continue {
-                continue;
-            }
-
-            let should_continue = selectors.iter().take(i).any(|complex2| {
-                complex2.min_specificity() >= max_specificity
+            let should_continue = index.candidates(complex1).into_iter().any(|j| {
+                let complex2 = &selectors[j];
+                (j < i || kept[j])
+                    && complex2.min_specificity() >= max_specificity
                     && complex2.is_super_selector(complex1)
             });
             if should_continue {
@@ -862,6 +914,7 @@ impl Extender {
             }
 
             result.push_front(complex1.clone());
+            kept[i] = true;
         }
 
         Vec::from(result)
@@ -873,67 +926,123 @@ impl Extender {
     /// selector. If any more relevant extensions are added, the returned selector
     /// is automatically updated.
     ///
+    /// The `span` is the span of the style rule's selector in the source.
+    ///
     /// The `media_query_context` is the media query context in which the selector was
     /// defined, or `None` if it was defined at the top level of the document.
     pub fn add_selector(
         &mut self,
         mut selector: SelectorList,
-        // span: Span,
+        span: Span,
         media_query_context: Option<Vec<CssMediaQuery>>,
-    ) -> ExtendedSelector {
+    ) -> SassResult<ExtendedSelector> {
         if !selector.is_invisible() {
-            for complex in selector.components.clone() {
-                self.originals.insert(complex);
-            }
+            self.originals.extend(selector.components.iter().cloned());
+        }
+
+        if let Some(placeholder_origins) = &mut self.placeholder_origins {
+            record_placeholders(placeholder_origins, &selector, span);
         }
 
         if !self.extensions.is_empty() {
-            selector = self.extend_list(selector, None, &media_query_context);
-            /*
-              todo: when we have error handling
-                  } on SassException catch (error) {
-              throw SassException(
-                  "From ${error.span.message('')}\n"
-                  "${error.message}",
-                  span);
-            }
-              */
+            selector = self
+                .extend_list(selector, None, &media_query_context)
+                .map_err(|err| err.nested_in(span))?;
         }
-        if let Some(mut media_query_context) = media_query_context {
+        let extended_selector = ExtendedSelector::new(selector);
+        if let Some(media_query_context) = media_query_context {
             self.media_contexts
-                .get_mut(&selector)
-                .replace(&mut media_query_context);
+                .insert(extended_selector.clone(), media_query_context);
+        }
+        if let Some(rule_origins) = &mut self.rule_origins {
+            rule_origins.push((span, extended_selector.clone()));
         }
-        let extended_selector = ExtendedSelector::new(selector.clone());
-        self.register_selector(selector, &extended_selector);
-        extended_selector
+        self.register_selector(&extended_selector.list(), &extended_selector);
+        Ok(extended_selector)
+    }
+
+    /// Begin recording the source span of every selector added through
+    /// `Extender::add_selector`, so that the final selector each style rule
+    /// produced can be recovered after compilation.
+    pub fn track_rule_origins(&mut self) {
+        self.rule_origins = Some(Vec::new());
+    }
+
+    /// The selectors recorded since `Extender::track_rule_origins` was called,
+    /// in the order their style rules were encountered.
+    pub fn rule_origins(&self) -> &[(Span, ExtendedSelector)] {
+        self.rule_origins.as_deref().unwrap_or(&[])
+    }
+
+    /// Begin recording where placeholder selectors appear, so that those
+    /// which are never extended can be reported after compilation.
+    pub fn track_placeholder_origins(&mut self) {
+        self.placeholder_origins = Some(HashMap::default());
+    }
+
+    /// The placeholder selectors that are not the target of any `@extend`,
+    /// paired with the span of the first style rule each appeared in, in
+    /// source order
+    ///
+    /// This is empty unless `Extender::track_placeholder_origins` was called,
+    /// and must only be called once all style rules have been added.
+    pub fn unextended_placeholders(&self) -> Vec<(Span, &SimpleSelector)> {
+        let mut placeholders = match &self.placeholder_origins {
+            Some(placeholder_origins) => placeholder_origins
+                .iter()
+                .filter(|(placeholder, _)| !self.extensions.contains_key(placeholder))
+                .map(|(placeholder, span)| (*span, placeholder))
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        placeholders.sort_by_key(|(span, _)| span.low());
+        placeholders
+    }
+
+    /// The optional `@extend`s whose target did not appear in any style rule,
+    /// paired with the span of the `@extend`, in source order
+    ///
+    /// This must only be called once all style rules have been added.
+    pub fn unmatched_extensions(&self) -> Vec<(Span, &SimpleSelector)> {
+        let mut unmatched = self
+            .extensions
+            .iter()
+            .filter(|(target, _)| !self.has_selector(target))
+            .flat_map(|(target, sources)| {
+                sources
+                    .values()
+                    .flat_map(|extension| extension.unmerge())
+                    .map(move |extension| (extension.span, target))
+            })
+            .collect::<Vec<_>>();
+        // `self.extensions` is unordered, and an `@extend` within a style rule
+        // with a selector list is stored once per complex selector in the list
+        unmatched.sort_by_cached_key(|(span, target)| (span.low(), target.to_string()));
+        unmatched.dedup();
+        unmatched
     }
 
     /// Registers the `SimpleSelector`s in `list` to point to `selector` in
     /// `self.selectors`.
-    fn register_selector(&mut self, list: SelectorList, selector: &ExtendedSelector) {
-        for complex in list.components {
-            for component in complex.components {
+    fn register_selector(&mut self, list: &SelectorList, selector: &ExtendedSelector) {
+        for complex in &list.components {
+            for component in &complex.components {
                 if let ComplexSelectorComponent::Compound(component) = component {
-                    for simple in component.components {
-                        // PERF: we compute the hash twice, which isn't great, but we avoid a superfluous
-                        // clone in cases where we have already seen a simple selector (common in
-                        // scenarios in which there is a lot of nesting)
-                        if let Some(entry) = self.selectors.get_mut(&simple) {
-                            entry.insert(selector.clone())
-                        } else {
-                            self.selectors
-                                .entry(simple.clone())
-                                .or_insert_with(SelectorHashSet::new)
-                                .insert(selector.clone());
-                        }
+                    for simple in &component.components {
+                        // interning only copies simple selectors that haven't
+                        // been seen before, which is rare in scenarios in which
+                        // there is a lot of nesting
+                        self.selectors
+                            .entry(self.simples.intern(simple))
+                            .or_insert_with(SelectorHashSet::new)
+                            .insert(selector.clone());
 
                         if let SimpleSelector::Pseudo(Pseudo {
                             selector: Some(simple_selector),
                             ..
                         }) = simple
                         {
-                            self.register_selector(*simple_selector, selector);
+                            self.register_selector(simple_selector, selector);
                         }
                     }
                 }
@@ -957,13 +1066,21 @@ impl Extender {
         extend: &ExtendRule,
         media_context: &Option<Vec<CssMediaQuery>>,
         span: Span,
-    ) {
-        let selectors = self.selectors.get(target).cloned();
-        let existing_extensions = self.extensions_by_extender.get(target).cloned();
+    ) -> SassResult<()> {
+        let (selectors, existing_extensions) = match self.simples.get(target) {
+            Some(target) => (
+                self.selectors.get(target).cloned(),
+                self.extensions_by_extender.get(target).cloned(),
+            ),
+            None => (None, None),
+        };
 
-        let mut new_extensions: Option<IndexMap<ComplexSelector, Extension>> = None;
+        let mut new_extensions: Option<IndexMap<Shared<ComplexSelector>, Rc<Extension>>> = None;
 
         for complex in extender.components {
+            self.check_limits(span)?;
+
+            let complex = Shared::new(complex);
             let state = Extension {
                 specificity: complex.max_specificity(),
                 extender: complex.clone(),
@@ -981,29 +1098,29 @@ impl Extender {
                 .entry(target.clone())
                 .or_insert_with(IndexMap::new);
 
-            if let Some(existing_state) = sources.get(&complex) {
+            if let Some(existing_state) = sources.get_mut(&complex) {
                 // If there's already an extend from `extender` to `target`, we don't need
                 // to re-run the extension. We may need to mark the extension as
                 // mandatory, though.
-                let mut new_val = MergedExtension::merge(existing_state.clone(), state).unwrap();
-                sources.get_mut(&complex).replace(&mut new_val);
+                *existing_state =
+                    Rc::new(MergedExtension::merge((**existing_state).clone(), state)?);
                 continue;
             }
 
-            sources.insert(complex.clone(), state.clone());
+            let state = Rc::new(state);
+            sources.insert(complex.clone(), Rc::clone(&state));
 
-            for component in complex.components.clone() {
+            for component in &complex.components {
                 if let ComplexSelectorComponent::Compound(component) = component {
-                    for simple in component.components {
+                    for simple in &component.components {
+                        let simple = self.simples.intern(simple);
                         self.extensions_by_extender
                             .entry(simple.clone())
                             .or_insert_with(Vec::new)
-                            .push(state.clone());
+                            .push(Rc::clone(&state));
                         // Only source specificity for the original selector is relevant.
                         // Selectors generated by `@extend` don't get new specificity.
-                        self.source_specificity
-                            .entry(simple.clone())
-                            .or_insert_with(|| complex.max_specificity());
+                        self.add_source_specificity(simple, state.specificity);
                     }
                 }
             }
@@ -1011,30 +1128,67 @@ impl Extender {
             if selectors.is_some() || existing_extensions.is_some() {
                 new_extensions
                     .get_or_insert_with(IndexMap::new)
-                    .insert(complex.clone(), state.clone());
+                    .insert(complex, Rc::clone(&state));
             }
         }
 
         let new_extensions = if let Some(new) = new_extensions {
             new
         } else {
-            return;
+            return Ok(());
         };
 
-        let mut new_extensions_by_target = HashMap::new();
+        let mut new_extensions_by_target = HashMap::default();
         new_extensions_by_target.insert(target.clone(), new_extensions);
 
         if let Some(existing_extensions) = existing_extensions {
             let additional_extensions =
-                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target);
+                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target)?;
             if let Some(additional_extensions) = additional_extensions {
                 map_add_all_2(&mut new_extensions_by_target, additional_extensions);
             }
         }
 
         if let Some(selectors) = selectors {
-            self.extend_existing_selectors(selectors, &new_extensions_by_target);
+            self.extend_existing_selectors(selectors, &new_extensions_by_target)?;
         }
+
+        Ok(())
+    }
+
+    /// Returns an error if any mandatory extension's target did not appear in
+    /// any style rule
+    ///
+    /// This must only be called once all style rules have been added.
+    pub fn check_unsatisfied_extensions(&self) -> SassResult<()> {
+        // `self.extensions` is unordered, so report the extension that comes
+        // first in the source to keep errors deterministic
+        let unsatisfied = self
+            .extensions
+            .iter()
+            .filter(|(target, _)| !self.has_selector(target))
+            .flat_map(|(target, sources)| {
+                sources
+                    .values()
+                    .flat_map(|extension| extension.unmerge())
+                    .filter(|extension| !extension.is_optional)
+                    .map(move |extension| (target, extension))
+            })
+            .min_by_key(|(_, extension)| extension.span.low());
+
+        if let Some((target, extension)) = unsatisfied {
+            let err: Box<SassError> = (
+                format!(
+                    "The target selector was not found.\nUse \"@extend {} !optional\" to avoid this error.",
+                    target
+                ),
+                extension.span,
+            )
+                .into();
+            return Err(err.with_suggestion(format!("@extend {} !optional", target)));
+        }
+
+        Ok(())
     }
 
     /// Extend `extensions` using `new_extensions`.
@@ -1054,43 +1208,42 @@ impl Extender {
     /// Returns `None` if there are no extensions to add.
     fn extend_existing_extensions(
         &mut self,
-        extensions: Vec<Extension>,
-        new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) -> Option<HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>> {
-        let mut additional_extensions: Option<
-            HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-        > = None;
+        extensions: Vec<Rc<Extension>>,
+        new_extensions: &Extensions,
+    ) -> SassResult<Option<Extensions>> {
+        let mut additional_extensions: Option<Extensions> = None;
         for extension in extensions {
-            let mut sources = self
-                .extensions
-                .get(&extension.target.clone().unwrap())
-                .unwrap()
-                .clone();
+            self.check_limits(extension.span)?;
+
+            // only extensions with a target are tracked by their extender
+            let target = match &extension.target {
+                Some(target) => target.clone(),
+                None => continue,
+            };
+            let mut sources = match self.extensions.get(&target) {
+                Some(sources) => sources.clone(),
+                None => continue,
+            };
 
             // `extend_existing_selectors` would have thrown already.
-            let selectors: Vec<ComplexSelector> = if let Some(v) = self.extend_complex(
-                extension.extender.clone(),
-                Some(new_extensions),
-                &extension.media_context,
-            ) {
-                v
-            } else {
-                continue;
+            let selectors: Vec<ComplexSelector> = match self
+                .extend_complex(
+                    &extension.extender,
+                    Some(new_extensions),
+                    &extension.media_context,
+                )
+                .map_err(|err| err.nested_in(extension.span))?
+            {
+                Some(v) => v,
+                None => continue,
             };
-            // todo: when we add error handling, this error is special
-            /*
-            } on SassException catch (error) {
-                throw SassException(
-                    "From ${extension.extenderSpan.message('')}\n"
-                    "${error.message}",
-                    error.span);
-            }
-            */
 
-            let contains_extension = selectors.first() == Some(&extension.extender);
+            let contains_extension = selectors.first() == Some(&*extension.extender);
 
             let mut first = false;
             for complex in selectors {
+                self.check_limits(extension.span)?;
+
                 // If the output contains the original complex selector, there's no
                 // need to recreate it.
                 if contains_extension && first {
@@ -1098,35 +1251,38 @@ impl Extender {
                     continue;
                 }
 
-                let with_extender = extension.clone().with_extender(complex.clone());
+                let complex = Shared::new(complex);
+                let with_extender = Rc::new((*extension).clone().with_extender(complex.clone()));
                 let existing_extension = sources.get(&complex);
                 if let Some(existing_extension) = existing_extension.cloned() {
-                    sources.get_mut(&complex).replace(
-                        &mut MergedExtension::merge(existing_extension.clone(), with_extender)
-                            .unwrap(),
-                    );
+                    sources
+                        .get_mut(&complex)
+                        .replace(&mut Rc::new(MergedExtension::merge(
+                            (*existing_extension).clone(),
+                            (*with_extender).clone(),
+                        )?));
                 } else {
                     sources
                         .get_mut(&complex)
-                        .replace(&mut with_extender.clone());
+                        .replace(&mut Rc::clone(&with_extender));
 
-                    for component in complex.components.clone() {
+                    for component in &complex.components {
                         if let ComplexSelectorComponent::Compound(component) = component {
-                            for simple in component.components {
+                            for simple in &component.components {
                                 self.extensions_by_extender
-                                    .entry(simple)
+                                    .entry(self.simples.intern(simple))
                                     .or_insert_with(Vec::new)
-                                    .push(with_extender.clone());
+                                    .push(Rc::clone(&with_extender));
                             }
                         }
                     }
 
-                    if new_extensions.contains_key(&extension.target.clone().unwrap()) {
+                    if new_extensions.contains_key(&target) {
                         additional_extensions
-                            .get_or_insert_with(HashMap::new)
-                            .entry(extension.target.clone().unwrap())
+                            .get_or_insert_with(HashMap::default)
+                            .entry(target.clone())
                             .or_insert_with(IndexMap::new)
-                            .insert(complex.clone(), with_extender.clone());
+                            .insert(complex.clone(), Rc::clone(&with_extender));
                     }
                 }
             }
@@ -1137,46 +1293,69 @@ impl Extender {
                 sources.remove(&extension.extender);
             }
         }
-        additional_extensions
+        Ok(additional_extensions)
     }
 
     /// Extend `extensions` using `new_extensions`.
     fn extend_existing_selectors(
         &mut self,
         selectors: SelectorHashSet,
-        new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) {
+        new_extensions: &Extensions,
+    ) -> SassResult<()> {
         for mut selector in selectors.into_iter() {
             let old_value = selector.clone().into_selector().0;
-            selector.set_inner(self.extend_list(
-                old_value.clone(),
-                Some(new_extensions),
-                &self.media_contexts.get(&old_value).cloned(),
-            ));
-            /*
-            todo: error handling
-            } on SassException catch (error) {
-            throw SassException(
-                "From ${selector.span.message('')}\n"
-                "${error.message}",
-                error.span);
-            }
-
-            */
+            let span = old_value.span;
+            self.check_limits(span)?;
+
+            let new_value = self
+                .extend_list(
+                    old_value,
+                    Some(new_extensions),
+                    &self.media_contexts.get(&selector).cloned(),
+                )
+                .map_err(|err| err.nested_in(span))?;
 
             // If no extends actually happened (for example becaues unification
             // failed), we don't need to re-register the selector.
-            let selector_as_selector = selector.clone().into_selector().0;
-            if old_value == selector_as_selector {
-                continue;
+            if *selector.list() != new_value {
+                self.register_selector(&new_value, &selector);
             }
-            self.register_selector(selector_as_selector, &selector);
+            selector.set_inner(new_value);
         }
+
+        Ok(())
     }
 }
 
 /// Rotates the element in list from `start` (inclusive) to `end` (exclusive)
 /// one index higher, looping the final element back to `start`.
+/// Records the span of every placeholder in `list` that has not been seen
+/// before, including those within selector pseudo-classes such as `:not()`
+fn record_placeholders(
+    placeholder_origins: &mut HashMap<SimpleSelector, Span>,
+    list: &SelectorList,
+    span: Span,
+) {
+    for complex in &list.components {
+        for component in &complex.components {
+            if let ComplexSelectorComponent::Compound(compound) = component {
+                for simple in &compound.components {
+                    match simple {
+                        SimpleSelector::Placeholder(..) => {
+                            placeholder_origins.entry(simple.clone()).or_insert(span);
+                        }
+                        SimpleSelector::Pseudo(Pseudo {
+                            selector: Some(selector),
+                            ..
+                        }) => record_placeholders(placeholder_origins, selector, span),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn rotate_slice<T: Clone>(list: &mut VecDeque<T>, start: usize, end: usize) {
     let mut element = list.get(end - 1).unwrap().clone();
     for i in start..end {
@@ -1189,7 +1368,7 @@ fn rotate_slice<T: Clone>(list: &mut VecDeque<T>, start: usize, end: usize) {
 /// Like `HashMap::extend`, but for two-layer maps.
 ///
 /// This avoids copying inner maps from `source` if possible.
-fn map_add_all_2<K1: Hash + Eq, K2: Hash + Eq, V>(
+fn map_add_all_2<K1: Hash + Eq, K2: Hash + Eq + Clone, V>(
     destination: &mut HashMap<K1, IndexMap<K2, V>>,
     source: HashMap<K1, IndexMap<K2, V>>,
 ) {
@@ -0,0 +1,120 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    rc::Rc,
+};
+
+use crate::hash::{hash, HashMap};
+
+/// A selector shared by reference count, along with its hash
+///
+/// This is used for the selectors the `Extender` stores in several places at
+/// once, such as the extender of an `Extension` and the key it's stored
+/// under, so that cloning one doesn't copy the selector and hashing one doesn't
+/// walk it again.
+pub(crate) struct Shared<T>(Rc<SharedInner<T>>);
+
+struct SharedInner<T> {
+    value: T,
+
+    /// The hash of `value`, computed once when it was shared
+    hash: u64,
+}
+
+impl<T: Hash> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(SharedInner {
+            hash: hash(&value),
+            value,
+        }))
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0.value
+    }
+}
+
+/// Copies of the same shared selector, including every selector returned by
+/// an `Interner` for equal values, are equal without comparing their contents,
+/// and selectors with different hashes are never compared
+impl<T: PartialEq> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+            || (self.0.hash == other.0.hash && self.0.value == other.0.value)
+    }
+}
+
+impl<T: Eq> Eq for Shared<T> {}
+
+impl<T> Hash for Shared<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.value, f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.value, f)
+    }
+}
+
+/// Interns selectors, so that equal selectors are the same `Shared` value,
+/// stored in a single allocation and compared by pointer
+///
+/// Each `Extender` has its own interners, so that selectors are only kept for
+/// as long as the compilation that uses them.
+#[derive(Clone, Debug)]
+pub(crate) struct Interner<T> {
+    /// The interned selectors, by their hashes
+    selectors: HashMap<u64, Vec<Shared<T>>>,
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    pub fn new() -> Self {
+        Self {
+            selectors: HashMap::default(),
+        }
+    }
+
+    /// The interned selector equal to `value`, if there is one
+    pub fn get(&self, value: &T) -> Option<&Shared<T>> {
+        self.selectors
+            .get(&hash(value))?
+            .iter()
+            .find(|shared| shared.0.value == *value)
+    }
+
+    /// The interned selector equal to `value`, interning a copy of `value` if
+    /// there isn't one yet
+    pub fn intern(&mut self, value: &T) -> Shared<T> {
+        let hash = hash(value);
+        let interned = self.selectors.entry(hash).or_insert_with(Vec::new);
+        if let Some(shared) = interned.iter().find(|shared| shared.0.value == *value) {
+            return shared.clone();
+        }
+
+        let shared = Shared(Rc::new(SharedInner {
+            value: value.clone(),
+            hash,
+        }));
+        interned.push(shared.clone());
+        shared
+    }
+}
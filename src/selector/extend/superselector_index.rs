@@ -0,0 +1,104 @@
+use crate::hash::{HashMap, HashSet};
+
+use super::super::{ComplexSelector, ComplexSelectorComponent, Namespace, Pseudo, SimpleSelector};
+
+/// An index of a list of complex selectors, used to find the ones that might
+/// be superselectors of a given selector without comparing it against every
+/// selector in the list.
+///
+/// A complex selector can only be a superselector of `other` if its final
+/// compound selector is a superselector of the final compound selector of
+/// `other`. Most simple selectors are only superselectors of themselves, so
+/// each complex selector is filed under one such simple selector from its
+/// final compound, which `other` must then contain.
+pub(super) struct SuperSelectorIndex<'a> {
+    /// The indices of the selectors filed under each simple selector.
+    by_simple: HashMap<&'a SimpleSelector, Vec<usize>>,
+
+    /// The indices of the selectors whose final compound contains no simple
+    /// selector that must appear in their subselectors, such as `*` or
+    /// `:is(.a, .b)`, and so must always be compared.
+    unindexed: Vec<usize>,
+}
+
+impl<'a> SuperSelectorIndex<'a> {
+    pub fn new(selectors: &'a [ComplexSelector]) -> Self {
+        let mut by_simple: HashMap<&'a SimpleSelector, Vec<usize>> = HashMap::default();
+        let mut unindexed = Vec::new();
+
+        for (idx, complex) in selectors.iter().enumerate() {
+            let key = match complex.components.last() {
+                Some(ComplexSelectorComponent::Compound(compound)) => compound
+                    .components
+                    .iter()
+                    .find(|simple| is_only_super_selector_of_itself(simple)),
+                // a selector ending in a combinator is never a superselector,
+                // so it doesn't need to be looked up at all
+                Some(ComplexSelectorComponent::Combinator(..)) | None => continue,
+            };
+
+            match key {
+                Some(simple) => by_simple.entry(simple).or_default().push(idx),
+                None => unindexed.push(idx),
+            }
+        }
+
+        SuperSelectorIndex {
+            by_simple,
+            unindexed,
+        }
+    }
+
+    /// Returns the indices of the selectors that might be superselectors of
+    /// `complex`, in no particular order.
+    ///
+    /// Every selector that is a superselector of `complex` is included, but
+    /// not every selector that's included is a superselector.
+    pub fn candidates(&self, complex: &ComplexSelector) -> Vec<usize> {
+        let compound = match complex.components.last() {
+            Some(ComplexSelectorComponent::Compound(compound)) => compound,
+            Some(ComplexSelectorComponent::Combinator(..)) | None => return Vec::new(),
+        };
+
+        // The simple selectors that a superselector of `compound` may be filed
+        // under. This includes those inside selector pseudo-classes, since
+        // `.a` is a superselector of `:is(.a.b, .a.c)`.
+        let mut simples: HashSet<&SimpleSelector> = HashSet::default();
+        for simple in &compound.components {
+            simples.insert(simple);
+            if let SimpleSelector::Pseudo(Pseudo {
+                selector: Some(selector),
+                ..
+            }) = simple
+            {
+                for inner in &selector.components {
+                    if let [ComplexSelectorComponent::Compound(inner)] = inner.components.as_slice()
+                    {
+                        simples.extend(inner.components.iter());
+                    }
+                }
+            }
+        }
+
+        let mut candidates = self.unindexed.clone();
+        for simple in simples {
+            if let Some(indices) = self.by_simple.get(simple) {
+                candidates.extend_from_slice(indices);
+            }
+        }
+        candidates
+    }
+}
+
+/// Returns whether `simple` is a superselector only of simple selectors equal
+/// to it.
+fn is_only_super_selector_of_itself(simple: &SimpleSelector) -> bool {
+    match simple {
+        SimpleSelector::Universal(..)
+        | SimpleSelector::Pseudo(Pseudo {
+            selector: Some(..), ..
+        }) => false,
+        SimpleSelector::Type(name) => name.namespace != Namespace::Asterisk,
+        _ => true,
+    }
+}
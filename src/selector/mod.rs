@@ -70,6 +70,14 @@ impl Selector {
         })
     }
 
+    /// Removes the line breaks that separate the complex selectors in this
+    /// list, so that it is written on a single line
+    pub fn remove_line_breaks(&mut self) {
+        for complex in &mut self.0.components {
+            complex.line_break = false;
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
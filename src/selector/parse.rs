@@ -36,9 +36,11 @@ impl DevouredWhitespace {
 }
 
 /// Pseudo-class selectors that take unadorned selectors as arguments.
-const SELECTOR_PSEUDO_CLASSES: [&str; 7] = [
+const SELECTOR_PSEUDO_CLASSES: [&str; 9] = [
     "not",
+    "is",
     "matches",
+    "where",
     "current",
     "any",
     "has",
@@ -134,6 +136,13 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
         loop {
             self.parser.whitespace();
 
+            if self.looking_at_column_combinator() {
+                self.parser.toks.next();
+                self.parser.toks.next();
+                components.push(ComplexSelectorComponent::Combinator(Combinator::Column));
+                continue;
+            }
+
             // todo: can we do while let Some(..) = self.parser.toks.peek() ?
             match self.parser.toks.peek() {
                 Some(Token { kind: '+', .. }) => {
@@ -235,6 +244,21 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
         }
     }
 
+    /// Returns whether the scanner is immediately before `||`, which is the
+    /// column combinator rather than the start of a namespace.
+    fn looking_at_column_combinator(&mut self) -> bool {
+        if !matches!(self.parser.toks.peek(), Some(Token { kind: '|', .. })) {
+            return false;
+        }
+
+        let is_column = matches!(
+            self.parser.toks.peek_forward(1),
+            Some(Token { kind: '|', .. })
+        );
+        self.parser.toks.reset_cursor();
+        is_column
+    }
+
     fn looking_at_identifier_body(&mut self) -> bool {
         matches!(self.parser.toks.peek(), Some(t) if is_name(t.kind) || t.kind == '\\')
     }
@@ -249,6 +273,13 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
             Some(Token { kind: '.', .. }) => self.parse_class_selector(),
             Some(Token { kind: '#', .. }) => self.parse_id_selector(),
             Some(Token { kind: '%', .. }) => {
+                if self.parser.flags.in_plain_css() {
+                    return Err((
+                        "Placeholder selectors aren't allowed in plain CSS.",
+                        self.span,
+                    )
+                        .into());
+                }
                 if !self.allows_placeholder {
                     return Err(("Placeholder selectors aren't allowed here.", self.span).into());
                 }
@@ -333,16 +364,19 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
         } else if unvendored == "nth-child" || unvendored == "nth-last-child" {
             let mut this_arg = self.parse_a_n_plus_b()?;
             let found_whitespace = self.parser.whitespace();
-            #[allow(clippy::match_same_arms)]
-            match (found_whitespace, self.parser.toks.peek()) {
-                (_, Some(Token { kind: ')', .. })) => {}
-                (true, _) => {
-                    self.expect_identifier("of")?;
-                    this_arg.push_str(" of");
-                    self.parser.whitespace();
-                    selector = Some(Box::new(self.parse_selector_list()?));
-                }
-                _ => {}
+            // the whitespace before `of` may already have been consumed while
+            // looking for the rest of `an+b`
+            let found_of = match self.parser.toks.peek() {
+                Some(Token { kind: ')', .. }) => false,
+                Some(Token { kind, .. }) => found_whitespace || is_name_start(*kind),
+                None => found_whitespace,
+            };
+            if found_of {
+                self.expect_identifier("of")?;
+                this_arg.push_str(" of");
+                self.parser.whitespace();
+                selector = Some(Box::new(self.parse_selector_list()?));
+                self.parser.whitespace();
             }
             self.parser.expect_char(')')?;
             argument = Some(this_arg.into_boxed_str());
@@ -367,8 +401,10 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
 
     fn parse_parent_selector(&mut self) -> SassResult<SimpleSelector> {
         self.parser.toks.next();
+        // the suffix continues the parent's last identifier, so it may begin
+        // with characters like digits that can't begin an identifier
         let suffix = if self.looking_at_identifier_body() {
-            Some(self.parser.parse_identifier()?.node)
+            Some(self.parser.ident_body_no_interpolation(false)?.node)
         } else {
             None
         };
@@ -393,6 +429,9 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
                 self.parser.span_before = self.parser.span_before.merge(*pos);
                 self.parser.toks.next();
                 if let Some(Token { kind: '|', .. }) = self.parser.toks.peek() {
+                    if self.looking_at_column_combinator() {
+                        return Ok(SimpleSelector::Universal(Namespace::None));
+                    }
                     self.parser.toks.next();
                     if let Some(Token { kind: '*', .. }) = self.parser.toks.peek() {
                         self.parser.toks.next();
@@ -428,8 +467,8 @@ impl<'a, 'b> SelectorParser<'a, 'b> {
 
         let name_or_namespace = self.parser.parse_identifier()?.node;
 
-        Ok(match self.parser.toks.peek() {
-            Some(Token { kind: '|', .. }) => {
+        Ok(match self.parser.toks.peek().copied() {
+            Some(Token { kind: '|', .. }) if !self.looking_at_column_combinator() => {
                 self.parser.toks.next();
                 if let Some(Token { kind: '*', .. }) = self.parser.toks.peek() {
                     self.parser.toks.next();
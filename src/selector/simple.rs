@@ -12,7 +12,14 @@ use super::{
     QualifiedName, SelectorList, Specificity,
 };
 
-const SUBSELECTOR_PSEUDOS: [&str; 4] = ["matches", "any", "nth-child", "nth-last-child"];
+const SUBSELECTOR_PSEUDOS: [&str; 6] = [
+    "is",
+    "matches",
+    "where",
+    "any",
+    "nth-child",
+    "nth-last-child",
+];
 
 const BASE_SPECIFICITY: i32 = 1000;
 
@@ -83,7 +90,7 @@ impl fmt::Display for SimpleSelector {
 impl SimpleSelector {
     /// The minimum possible specificity that this selector can have.
     ///
-    /// Pseudo selectors that contain selectors, like `:not()` and `:matches()`,
+    /// Pseudo selectors that contain selectors, like `:not()` and `:is()`,
     /// can have a range of possible specificities.
     ///
     /// Specifity is represented in base 1000. The spec says this should be
@@ -101,7 +108,7 @@ impl SimpleSelector {
 
     /// The maximum possible specificity that this selector can have.
     ///
-    /// Pseudo selectors that contain selectors, like `:not()` and `:matches()`,
+    /// Pseudo selectors that contain selectors, like `:not()` and `:is()`,
     /// can have a range of possible specificities.
     pub fn max_specificity(&self) -> i32 {
         match self {
@@ -346,9 +353,32 @@ impl SimpleSelector {
         Some(result)
     }
 
+    /// Whether this selector matches every element that `other` matches
+    ///
+    /// Apart from equal selectors, this is only the case when this selector
+    /// uses the `*` namespace, or is a universal selector in the same namespace
+    /// as `other`.
+    pub fn is_super_selector(&self, other: &Self) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match (self, other) {
+            (Self::Type(name), Self::Type(other_name)) => {
+                name.ident == other_name.ident && name.namespace == Namespace::Asterisk
+            }
+            (Self::Universal(Namespace::Asterisk), Self::Type(..))
+            | (Self::Universal(Namespace::Asterisk), Self::Universal(..)) => true,
+            (Self::Universal(namespace), Self::Type(other_name)) => {
+                *namespace == other_name.namespace
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_super_selector_of_compound(&self, compound: &CompoundSelector) -> bool {
         compound.components.iter().any(|their_simple| {
-            if self == their_simple {
+            if self.is_super_selector(their_simple) {
                 return true;
             }
             if let SimpleSelector::Pseudo(Pseudo {
@@ -368,7 +398,8 @@ impl SimpleSelector {
                             .unwrap()
                             .as_compound()
                             .components
-                            .contains(self)
+                            .iter()
+                            .any(|simple| self.is_super_selector(simple))
                     });
                 }
                 false
@@ -487,7 +518,7 @@ impl Pseudo {
     ) -> bool {
         debug_assert!(self.selector.is_some());
         match self.normalized_name() {
-            "matches" | "any" => {
+            "is" | "matches" | "where" | "any" => {
                 let pseudos = selector_pseudos_named(compound.clone(), &self.name, true);
                 pseudos.iter().any(move |pseudo2| {
                     self.selector
@@ -576,8 +607,7 @@ impl Pseudo {
                 .any(|pseudo2| self.selector == pseudo2.selector),
             "nth-child" | "nth-last-child" => compound.components.iter().any(|pseudo2| {
                 if let SimpleSelector::Pseudo(
-                    pseudo
-                    @ Pseudo {
+                    pseudo @ Pseudo {
                         selector: Some(..), ..
                     },
                 ) = pseudo2
@@ -625,6 +655,11 @@ impl Pseudo {
             }
         };
 
+        // `:where()` never adds to the specificity of the selector it's in
+        if self.normalized_name() == "where" {
+            return Specificity { min: 0, max: 0 };
+        }
+
         if self.name == "not" {
             let mut min = 0;
             let mut max = 0;
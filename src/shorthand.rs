@@ -0,0 +1,249 @@
+//! Detection of shorthand properties that conflict with their longhands
+//! within a single style rule, enabled by
+//! [`Options::warn_shorthand_conflicts`](crate::Options::warn_shorthand_conflicts)
+
+use codemap::Span;
+
+use crate::style::Style;
+
+/// Shorthand properties and the properties they set
+///
+/// Longhands may themselves be shorthands, so `border` also sets
+/// `border-top-width`.
+const SHORTHANDS: &[(&str, &[&str])] = &[
+    (
+        "animation",
+        &[
+            "animation-name",
+            "animation-duration",
+            "animation-timing-function",
+            "animation-delay",
+            "animation-iteration-count",
+            "animation-direction",
+            "animation-fill-mode",
+            "animation-play-state",
+        ],
+    ),
+    (
+        "background",
+        &[
+            "background-color",
+            "background-image",
+            "background-position",
+            "background-size",
+            "background-repeat",
+            "background-attachment",
+            "background-origin",
+            "background-clip",
+        ],
+    ),
+    (
+        "border",
+        &[
+            "border-top",
+            "border-right",
+            "border-bottom",
+            "border-left",
+            "border-width",
+            "border-style",
+            "border-color",
+        ],
+    ),
+    (
+        "border-top",
+        &["border-top-width", "border-top-style", "border-top-color"],
+    ),
+    (
+        "border-right",
+        &[
+            "border-right-width",
+            "border-right-style",
+            "border-right-color",
+        ],
+    ),
+    (
+        "border-bottom",
+        &[
+            "border-bottom-width",
+            "border-bottom-style",
+            "border-bottom-color",
+        ],
+    ),
+    (
+        "border-left",
+        &[
+            "border-left-width",
+            "border-left-style",
+            "border-left-color",
+        ],
+    ),
+    (
+        "border-width",
+        &[
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+        ],
+    ),
+    (
+        "border-style",
+        &[
+            "border-top-style",
+            "border-right-style",
+            "border-bottom-style",
+            "border-left-style",
+        ],
+    ),
+    (
+        "border-color",
+        &[
+            "border-top-color",
+            "border-right-color",
+            "border-bottom-color",
+            "border-left-color",
+        ],
+    ),
+    (
+        "border-radius",
+        &[
+            "border-top-left-radius",
+            "border-top-right-radius",
+            "border-bottom-right-radius",
+            "border-bottom-left-radius",
+        ],
+    ),
+    (
+        "column-rule",
+        &[
+            "column-rule-width",
+            "column-rule-style",
+            "column-rule-color",
+        ],
+    ),
+    ("columns", &["column-width", "column-count"]),
+    ("flex", &["flex-grow", "flex-shrink", "flex-basis"]),
+    ("flex-flow", &["flex-direction", "flex-wrap"]),
+    (
+        "font",
+        &[
+            "font-style",
+            "font-variant",
+            "font-weight",
+            "font-stretch",
+            "font-size",
+            "line-height",
+            "font-family",
+        ],
+    ),
+    ("gap", &["row-gap", "column-gap"]),
+    (
+        "grid-area",
+        &[
+            "grid-row-start",
+            "grid-column-start",
+            "grid-row-end",
+            "grid-column-end",
+        ],
+    ),
+    ("grid-column", &["grid-column-start", "grid-column-end"]),
+    ("grid-row", &["grid-row-start", "grid-row-end"]),
+    (
+        "grid-template",
+        &[
+            "grid-template-rows",
+            "grid-template-columns",
+            "grid-template-areas",
+        ],
+    ),
+    ("inset", &["top", "right", "bottom", "left"]),
+    (
+        "list-style",
+        &["list-style-type", "list-style-position", "list-style-image"],
+    ),
+    (
+        "margin",
+        &["margin-top", "margin-right", "margin-bottom", "margin-left"],
+    ),
+    (
+        "outline",
+        &["outline-color", "outline-style", "outline-width"],
+    ),
+    ("overflow", &["overflow-x", "overflow-y"]),
+    (
+        "padding",
+        &[
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+        ],
+    ),
+    ("place-content", &["align-content", "justify-content"]),
+    ("place-items", &["align-items", "justify-items"]),
+    ("place-self", &["align-self", "justify-self"]),
+    (
+        "text-decoration",
+        &[
+            "text-decoration-line",
+            "text-decoration-color",
+            "text-decoration-style",
+            "text-decoration-thickness",
+        ],
+    ),
+    (
+        "transition",
+        &[
+            "transition-property",
+            "transition-duration",
+            "transition-timing-function",
+            "transition-delay",
+        ],
+    ),
+];
+
+/// Whether setting `shorthand` also sets `longhand`
+fn sets(shorthand: &str, longhand: &str) -> bool {
+    SHORTHANDS
+        .iter()
+        .find(|(name, _)| *name == shorthand)
+        .map_or(false, |(_, longhands)| {
+            longhands
+                .iter()
+                .any(|name| *name == longhand || sets(name, longhand))
+        })
+}
+
+/// Returns a warning for each declaration in `styles` that overrides, or is
+/// partially overridden by, a declaration that comes before it
+pub(crate) fn conflicts(styles: &[&Style]) -> Vec<(String, Span)> {
+    let mut warnings = Vec::new();
+
+    for (idx, later) in styles.iter().enumerate() {
+        let later_name = later.property.resolve_ref().to_ascii_lowercase();
+
+        for earlier in &styles[..idx] {
+            let earlier_name = earlier.property.resolve_ref().to_ascii_lowercase();
+
+            if sets(&later_name, &earlier_name) {
+                warnings.push((
+                    format!(
+                        "The shorthand property `{}` overrides `{}`, which was declared earlier in the same rule.",
+                        later.property, earlier.property
+                    ),
+                    later.value.span,
+                ));
+            } else if sets(&earlier_name, &later_name) {
+                warnings.push((
+                    format!(
+                        "`{}` overrides part of the shorthand property `{}`, which was declared earlier in the same rule.",
+                        later.property, earlier.property
+                    ),
+                    later.value.span,
+                ));
+            }
+        }
+    }
+
+    warnings
+}
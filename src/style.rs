@@ -7,10 +7,35 @@ use crate::{error::SassResult, interner::InternedString, value::Value};
 pub(crate) struct Style {
     pub property: InternedString,
     pub value: Box<Spanned<Value>>,
+    /// Whether the value was kept verbatim, as the value of a custom property
+    /// like `--color: red` is
+    ///
+    /// Such values include the whitespace that followed the colon, and
+    /// continuation lines that are already reindented to be relative to the
+    /// property
+    pub parsed_as_custom_property: bool,
 }
 
 impl Style {
-    pub fn to_string(&self) -> SassResult<String> {
+    /// Writes the style, indenting any continuation lines of a verbatim
+    /// custom property value by `indentation`
+    pub fn to_string(&self, indentation: &str) -> SassResult<String> {
+        if let (true, Value::String(value, ..)) = (self.parsed_as_custom_property, &self.value.node)
+        {
+            let mut buf = format!("{}:", self.property);
+            let mut lines = value.split('\n');
+            buf.push_str(lines.next().unwrap_or_default());
+            for line in lines {
+                buf.push('\n');
+                if !line.is_empty() {
+                    buf.push_str(indentation);
+                    buf.push_str(line);
+                }
+            }
+            buf.push(';');
+            return Ok(buf);
+        }
+
         Ok(format!(
             "{}: {};",
             self.property,
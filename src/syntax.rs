@@ -0,0 +1,62 @@
+//! The syntaxes a stylesheet can be written in
+//!
+//! See [`Options::syntax`](crate::Options::syntax)
+
+use std::{ffi::OsStr, path::Path, sync::Arc};
+
+use codemap::File;
+
+use crate::{error::SassResult, indented, lexer::Lexer, Token};
+
+/// The syntax of a stylesheet
+///
+/// Files loaded with `@import`, `@use`, or `meta.load-css()` are read in the
+/// syntax their extension implies: `.sass` files use the indented syntax,
+/// `.css` files are plain CSS, and any other file is SCSS.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Syntax {
+    /// SCSS, the syntax of `.scss` files, which is a superset of CSS
+    Scss,
+
+    /// The indented syntax of `.sass` files, which uses indentation rather
+    /// than curly braces to nest rules and newlines rather than semicolons
+    /// to separate declarations
+    Indented,
+
+    /// Plain CSS, the syntax of `.css` files, which is parsed as SCSS but
+    /// without any of the features Sass adds, such as variables,
+    /// interpolation, and Sass at-rules
+    ///
+    /// Using one of those features is an error, and every `@import` is left
+    /// as a plain CSS import.
+    Css,
+}
+
+impl Default for Syntax {
+    #[inline]
+    fn default() -> Self {
+        Syntax::Scss
+    }
+}
+
+impl Syntax {
+    /// The syntax of a file loaded from `path`, based on its extension
+    pub(crate) fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("sass") => Syntax::Indented,
+            Some("css") => Syntax::Css,
+            _ => Syntax::Scss,
+        }
+    }
+
+    /// The tokens of `file`, in SCSS
+    pub(crate) fn tokenize(self, file: &Arc<File>) -> SassResult<Vec<Token>> {
+        let toks = Lexer::new(file).collect::<Vec<Token>>();
+
+        match self {
+            Syntax::Scss | Syntax::Css => Ok(toks),
+            Syntax::Indented => indented::to_scss(toks),
+        }
+    }
+}
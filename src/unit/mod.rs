@@ -1,9 +1,8 @@
-use std::{
-    fmt,
-    ops::{Div, Mul},
-};
+use std::fmt;
 
-use crate::interner::InternedString;
+use num_traits::One;
+
+use crate::{interner::InternedString, value::Number};
 
 pub(crate) use conversion::UNIT_CONVERSION_TABLE;
 
@@ -141,108 +140,153 @@ impl DivUnit {
 impl fmt::Display for DivUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.numer == Unit::None {
-            write!(f, "{}^-1", self.denom)
+            if let Unit::Mul(..) = self.denom {
+                write!(f, "({})^-1", self.denom)
+            } else {
+                write!(f, "{}^-1", self.denom)
+            }
         } else {
             write!(f, "{}/{}", self.numer, self.denom)
         }
     }
 }
 
-#[allow(clippy::match_same_arms)]
-impl Mul<Unit> for DivUnit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
-        match rhs {
-            Unit::Mul(..) => todo!(),
-            Unit::Div(..) => todo!(),
-            Unit::None => todo!(),
-            _ => {
-                if self.denom == rhs {
-                    self.numer
-                } else {
-                    match self.denom {
-                        Unit::Mul(..) => todo!(),
-                        Unit::Div(..) => unreachable!(),
-                        _ => match self.numer {
-                            Unit::Mul(..) => todo!(),
-                            Unit::Div(..) => unreachable!(),
-                            Unit::None => {
-                                let numer = Unit::Mul(Box::new(vec![rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                            _ => {
-                                let numer = Unit::Mul(Box::new(vec![self.numer, rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                        },
-                    }
-                }
-            }
+impl Unit {
+    pub fn comparable(&self, other: &Unit) -> bool {
+        if other == &Unit::None {
+            return true;
+        }
+        if self.is_compound() || other.is_compound() {
+            return self.conversion_factor(other).is_some();
+        }
+        match self.kind() {
+            UnitKind::FontRelative | UnitKind::ViewportRelative | UnitKind::Other => self == other,
+            UnitKind::None => true,
+            u => other.kind() == u,
         }
     }
-}
 
-// impl Div<Unit> for DivUnit {
-//     type Output = Unit;
-//     fn div(self, rhs: Unit) -> Self::Output {
-//         todo!()
-//     }
-// }
+    const fn is_compound(&self) -> bool {
+        matches!(self, Unit::Mul(..) | Unit::Div(..))
+    }
 
-impl Mul<Unit> for Unit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
+    /// Splits this unit into the units it is multiplied by and the units it
+    /// is divided by
+    ///
+    /// `Unit::None` has neither, so `px*s/ms` splits into `[px, s]` and
+    /// `[ms]`, and `px` into `[px]` and `[]`
+    pub fn numer_and_denom(self) -> (Vec<Unit>, Vec<Unit>) {
         match self {
-            Unit::Mul(u) => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = *u;
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
-                }
-                Unit::Div(..) => todo!(),
-                _ => {
-                    let mut unit1 = *u;
-                    unit1.push(rhs);
-                    Unit::Mul(Box::new(unit1))
-                }
-            },
-            Unit::Div(div) => *div * rhs,
-            _ => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = vec![self];
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
-                }
-                Unit::Div(..) => todo!(),
-                _ => Unit::Mul(Box::new(vec![self, rhs])),
-            },
+            Unit::None => (Vec::new(), Vec::new()),
+            Unit::Mul(units) => (*units, Vec::new()),
+            Unit::Div(div) => {
+                let DivUnit { numer, denom } = *div;
+                let (mut numers, mut denoms) = numer.numer_and_denom();
+                let (denom_numers, denom_denoms) = denom.numer_and_denom();
+                numers.extend(denom_denoms);
+                denoms.extend(denom_numers);
+                (numers, denoms)
+            }
+            unit => (vec![unit], Vec::new()),
         }
     }
-}
 
-impl Div<Unit> for Unit {
-    type Output = Unit;
-    fn div(self, rhs: Unit) -> Self::Output {
-        if let Unit::Div(..) = self {
-            todo!()
-        } else if let Unit::Div(..) = rhs {
-            todo!()
+    /// The inverse of [`Unit::numer_and_denom`]
+    pub fn from_numer_and_denom(mut numers: Vec<Unit>, mut denoms: Vec<Unit>) -> Self {
+        fn product(mut units: Vec<Unit>) -> Unit {
+            match units.len() {
+                0 => Unit::None,
+                1 => units.pop().unwrap_or(Unit::None),
+                _ => Unit::Mul(Box::new(units)),
+            }
+        }
+
+        numers.retain(|unit| unit != &Unit::None);
+        denoms.retain(|unit| unit != &Unit::None);
+
+        if denoms.is_empty() {
+            product(numers)
         } else {
-            Unit::Div(Box::new(DivUnit::new(self, rhs)))
+            Unit::Div(Box::new(DivUnit::new(product(numers), product(denoms))))
         }
     }
-}
 
-impl Unit {
-    pub fn comparable(&self, other: &Unit) -> bool {
-        if other == &Unit::None {
-            return true;
+    /// Multiplies two units, cancelling out any unit of one that is divided
+    /// by a compatible unit of the other
+    ///
+    /// Returns the resulting unit along with the factor the product of the
+    /// two numbers must be multiplied by to account for the conversions made
+    /// while cancelling, so that `1in * (1/1px)` is `96`
+    pub fn product(self, other: Unit) -> (Unit, Number) {
+        let (numers1, denoms1) = self.numer_and_denom();
+        let (numers2, denoms2) = other.numer_and_denom();
+
+        let mut factor = Number::one();
+        let mut numers = Vec::new();
+
+        let mut remaining_denoms2 = denoms2;
+        for numer in numers1 {
+            cancel(numer, &mut remaining_denoms2, &mut numers, &mut factor);
         }
-        match self.kind() {
-            UnitKind::FontRelative | UnitKind::ViewportRelative | UnitKind::Other => self == other,
-            UnitKind::None => true,
-            u => other.kind() == u,
+
+        let mut remaining_denoms1 = denoms1;
+        for numer in numers2 {
+            cancel(numer, &mut remaining_denoms1, &mut numers, &mut factor);
+        }
+
+        remaining_denoms1.extend(remaining_denoms2);
+
+        (
+            Unit::from_numer_and_denom(numers, remaining_denoms1),
+            factor,
+        )
+    }
+
+    /// Divides two units, as in [`Unit::product`]
+    pub fn quotient(self, other: Unit) -> (Unit, Number) {
+        let (numers, denoms) = other.numer_and_denom();
+        self.product(Unit::from_numer_and_denom(denoms, numers))
+    }
+
+    /// Returns the number a value in this unit must be multiplied by to
+    /// convert it to `other`, or `None` if the two units are not compatible
+    ///
+    /// Compound units are compatible if each of their units can be converted
+    /// to a distinct unit of the other, so that `in*s` may be converted to
+    /// `ms*px`
+    pub fn conversion_factor(&self, other: &Unit) -> Option<Number> {
+        if self == other {
+            return Some(Number::one());
+        }
+
+        if !self.is_compound() && !other.is_compound() {
+            return simple_conversion_factor(self, other);
+        }
+
+        let (numers1, denoms1) = self.clone().numer_and_denom();
+        let (mut numers2, mut denoms2) = other.clone().numer_and_denom();
+
+        if numers1.len() != numers2.len() || denoms1.len() != denoms2.len() {
+            return None;
+        }
+
+        let mut factor = Number::one();
+
+        for numer in numers1 {
+            let idx = numers2
+                .iter()
+                .position(|unit| simple_conversion_factor(&numer, unit).is_some())?;
+            factor *= simple_conversion_factor(&numer, &numers2.remove(idx))?;
         }
+
+        for denom in denoms1 {
+            let idx = denoms2
+                .iter()
+                .position(|unit| simple_conversion_factor(&denom, unit).is_some())?;
+            factor /= simple_conversion_factor(&denom, &denoms2.remove(idx))?;
+        }
+
+        Some(factor)
     }
 
     /// Used internally to determine if two units are comparable or not
@@ -274,6 +318,43 @@ impl Unit {
     }
 }
 
+/// Converts between two units that are not compound, treating `x` as an
+/// alias for `dppx`
+fn simple_conversion_factor(from: &Unit, to: &Unit) -> Option<Number> {
+    fn canonicalize(unit: &Unit) -> &Unit {
+        match unit {
+            Unit::X => &Unit::Dppx,
+            unit => unit,
+        }
+    }
+
+    let from = canonicalize(from);
+    let to = canonicalize(to);
+
+    if from == to {
+        return Some(Number::one());
+    }
+
+    UNIT_CONVERSION_TABLE.get(to)?.get(from).cloned()
+}
+
+/// Removes the first unit of `denoms` that `numer` may be converted to,
+/// accumulating the conversion into `factor`, or adds `numer` to `numers` if
+/// there is none
+fn cancel(numer: Unit, denoms: &mut Vec<Unit>, numers: &mut Vec<Unit>, factor: &mut Number) {
+    let cancelled = denoms.iter().enumerate().find_map(|(idx, denom)| {
+        simple_conversion_factor(&numer, denom).map(|conversion| (idx, conversion))
+    });
+
+    match cancelled {
+        Some((idx, conversion)) => {
+            denoms.remove(idx);
+            *factor *= conversion;
+        }
+        None => numers.push(numer),
+    }
+}
+
 impl From<String> for Unit {
     fn from(unit: String) -> Self {
         match unit.to_ascii_lowercase().as_str() {
@@ -1,8 +1,12 @@
 use std::vec::IntoIter;
 
+use num_bigint::BigInt;
+use num_rational::{BigRational, Rational64};
+use num_traits::{pow, One, ToPrimitive};
+
 use peekmore::PeekMoreIterator;
 
-use crate::Token;
+use crate::{value::Number, Token};
 
 #[derive(Debug)]
 pub(crate) struct ParsedNumber {
@@ -44,6 +48,51 @@ impl ParsedNumber {
             times_ten_is_postive,
         }
     }
+
+    /// Converts the parsed digits to a number, or returns `None` if the
+    /// exponent doesn't fit in a `usize`
+    pub fn into_number(self) -> Option<Number> {
+        if self.num.len() <= 18 && self.times_ten.is_empty() {
+            let num = parse_i64(&self.num);
+            return Some(Number::new_small(if self.dec_len == 0 {
+                Rational64::new_raw(num, 1)
+            } else {
+                Rational64::new(num, pow(10, self.dec_len))
+            }));
+        }
+
+        let n = if self.dec_len == 0 {
+            BigRational::new_raw(self.num.parse::<BigInt>().unwrap(), BigInt::one())
+        } else {
+            BigRational::new(
+                self.num.parse().unwrap(),
+                pow(BigInt::from(10), self.dec_len),
+            )
+        };
+
+        if self.times_ten.is_empty() {
+            return Some(Number::new_big(n));
+        }
+
+        let times_ten = pow(
+            BigInt::from(10),
+            self.times_ten.parse::<BigInt>().unwrap().to_usize()?,
+        );
+
+        let times_ten = if self.times_ten_is_postive {
+            BigRational::new_raw(times_ten, BigInt::one())
+        } else {
+            BigRational::new(BigInt::one(), times_ten)
+        };
+
+        Some(Number::new_big(n * times_ten))
+    }
+}
+
+fn parse_i64(s: &str) -> i64 {
+    s.as_bytes()
+        .iter()
+        .fold(0, |total, this| total * 10 + i64::from(this - b'0'))
 }
 
 pub(crate) fn eat_whole_number(toks: &mut PeekMoreIterator<IntoIter<Token>>) -> String {
@@ -54,6 +54,42 @@ pub(crate) fn peek_until_closing_curly_brace(
     Ok(t)
 }
 
+pub(crate) fn peek_until_closing_paren(
+    toks: &mut PeekMoreIterator<IntoIter<Token>>,
+) -> SassResult<Vec<Token>> {
+    let mut t = Vec::new();
+    let mut nesting = 0;
+    while let Some(tok) = toks.peek().cloned() {
+        toks.advance_cursor();
+        match tok.kind {
+            q @ '"' | q @ '\'' => {
+                t.push(tok);
+                t.extend(peek_until_closing_quote(toks, q)?);
+                continue;
+            }
+            '(' => nesting += 1,
+            ')' => {
+                if nesting == 0 {
+                    t.push(tok);
+                    break;
+                }
+                nesting -= 1;
+            }
+            '\\' => {
+                t.push(tok);
+                if let Some(next) = toks.peek().cloned() {
+                    toks.advance_cursor();
+                    t.push(next);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        t.push(tok);
+    }
+    Ok(t)
+}
+
 fn peek_until_closing_quote(
     toks: &mut PeekMoreIterator<IntoIter<Token>>,
     q: char,
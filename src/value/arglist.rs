@@ -0,0 +1,75 @@
+use std::{slice::Iter, vec::IntoIter};
+
+use codemap::Spanned;
+
+use crate::{
+    common::{Identifier, QuoteKind},
+    value::{SassMap, Value},
+};
+
+/// The value bound to a rest parameter (e.g. `$args...`)
+///
+/// In addition to the positional arguments, an argument list carries any
+/// named arguments that did not match another parameter. These can be
+/// retrieved with the builtin function `keywords()`, and are passed along
+/// as named arguments when the argument list is spread into another call.
+#[derive(Debug, Clone)]
+pub(crate) struct ArgList {
+    pub elems: Vec<Spanned<Value>>,
+    pub keywords: Vec<(Identifier, Spanned<Value>)>,
+}
+
+/// Argument lists are compared like lists, so keywords are not considered
+impl PartialEq for ArgList {
+    fn eq(&self, other: &Self) -> bool {
+        self.elems == other.elems
+    }
+}
+
+impl Eq for ArgList {}
+
+impl ArgList {
+    pub const fn new(
+        elems: Vec<Spanned<Value>>,
+        keywords: Vec<(Identifier, Spanned<Value>)>,
+    ) -> Self {
+        ArgList { elems, keywords }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<Spanned<Value>> {
+        self.elems.iter()
+    }
+
+    /// The named arguments as a map from argument names (without the `$`)
+    /// to values, as returned by `keywords()`
+    pub fn keywords_map(&self) -> SassMap {
+        SassMap::new_with(
+            self.keywords
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        Value::String(name.to_string(), QuoteKind::None),
+                        value.node.clone(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl IntoIterator for ArgList {
+    type Item = Spanned<Value>;
+    type IntoIter = IntoIter<Spanned<Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.into_iter()
+    }
+}
@@ -0,0 +1,330 @@
+//! Calculations, the values of CSS math functions such as `calc()` and
+//! `clamp()`
+//!
+//! A calculation is simplified as far as possible when it is created, so that
+//! `calc(10px + 5px)` becomes `15px`. Anything that can only be resolved by
+//! the browser, such as `10px + 5%` or `var(--x)`, is kept in the output.
+
+use std::{cmp::Ordering, fmt};
+
+use num_traits::Zero;
+
+use crate::{
+    common::QuoteKind,
+    unit::Unit,
+    value::{Number, Value},
+};
+
+/// The CSS math functions that are parsed as calculations
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum CalculationName {
+    Calc,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl CalculationName {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CalculationName::Calc => "calc",
+            CalculationName::Min => "min",
+            CalculationName::Max => "max",
+            CalculationName::Clamp => "clamp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum CalculationOperator {
+    Plus,
+    Minus,
+    Mul,
+    Div,
+}
+
+impl CalculationOperator {
+    const fn precedence(self) -> u8 {
+        match self {
+            CalculationOperator::Plus | CalculationOperator::Minus => 1,
+            CalculationOperator::Mul | CalculationOperator::Div => 2,
+        }
+    }
+
+    /// Whether an operation with the operator `right` must be wrapped in
+    /// parentheses when it is the right operand of `self`
+    const fn parenthesizes(self, right: CalculationOperator) -> bool {
+        match self {
+            CalculationOperator::Plus => false,
+            CalculationOperator::Div => true,
+            CalculationOperator::Minus | CalculationOperator::Mul => {
+                matches!(
+                    right,
+                    CalculationOperator::Plus | CalculationOperator::Minus
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for CalculationOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CalculationOperator::Plus => "+",
+            CalculationOperator::Minus => "-",
+            CalculationOperator::Mul => "*",
+            CalculationOperator::Div => "/",
+        })
+    }
+}
+
+/// An argument to a calculation, or an operand within one
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum CalculationArg {
+    Number(Number, Unit),
+    Calculation(SassCalculation),
+
+    /// Text that Sass can't evaluate, such as `var(--x)`, or the entire
+    /// contents of a calculation that isn't valid calculation syntax
+    String(String),
+
+    /// The result of interpolation, which is wrapped in parentheses when it
+    /// is used as an operand
+    Interpolation(String),
+
+    Operation(
+        Box<CalculationArg>,
+        CalculationOperator,
+        Box<CalculationArg>,
+    ),
+}
+
+impl CalculationArg {
+    /// Converts the value of a variable or function call used within a
+    /// calculation, returning `None` if it can't be used in one
+    pub fn from_value(value: Value) -> Option<Self> {
+        Some(match value {
+            Value::Dimension(Some(num), unit, _) => CalculationArg::Number(num, unit),
+            Value::Calculation(calc) => CalculationArg::Calculation(calc),
+            Value::String(s, QuoteKind::None) => CalculationArg::String(s),
+            _ => return None,
+        })
+    }
+
+    /// Applies `op` to two operands, evaluating it if both are numbers whose
+    /// units allow it
+    pub fn operate(left: Self, op: CalculationOperator, right: Self) -> Self {
+        let left = left.unwrap_calc();
+        let right = right.unwrap_calc();
+
+        if let (CalculationArg::Number(num, unit), CalculationArg::Number(num2, unit2)) =
+            (&left, &right)
+        {
+            if let Some((num, unit)) = evaluate(num, unit, op, num2, unit2) {
+                return CalculationArg::Number(num, unit);
+            }
+        }
+
+        CalculationArg::Operation(Box::new(left), op, Box::new(right))
+    }
+
+    /// A `calc()` nested within another calculation is replaced by its
+    /// argument, unless that argument is kept as it was written
+    fn unwrap_calc(self) -> Self {
+        match self {
+            CalculationArg::Calculation(SassCalculation {
+                name: CalculationName::Calc,
+                mut args,
+            }) if !matches!(args.first(), Some(CalculationArg::String(..))) => args.pop().unwrap(),
+            arg => arg,
+        }
+    }
+
+    fn write_operand(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        parenthesize: impl Fn(CalculationOperator) -> bool,
+    ) -> fmt::Result {
+        match self {
+            CalculationArg::Operation(_, op, _) if parenthesize(*op) => write!(f, "({})", self),
+            CalculationArg::Interpolation(s) => write!(f, "({})", s),
+            arg => write!(f, "{}", arg),
+        }
+    }
+}
+
+impl fmt::Display for CalculationArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalculationArg::Number(num, unit) => write!(f, "{}{}", num, unit),
+            CalculationArg::Calculation(calc) => write!(f, "{}", calc),
+            CalculationArg::String(s) | CalculationArg::Interpolation(s) => f.write_str(s),
+            CalculationArg::Operation(left, op, right) => {
+                left.write_operand(f, |left| left.precedence() < op.precedence())?;
+                write!(f, " {} ", op)?;
+                right.write_operand(f, |right| op.parenthesizes(right))
+            }
+        }
+    }
+}
+
+/// Evaluates an operation on two numbers, returning `None` if their units
+/// mean that it must be left to the browser
+fn evaluate(
+    num: &Number,
+    unit: &Unit,
+    op: CalculationOperator,
+    num2: &Number,
+    unit2: &Unit,
+) -> Option<(Number, Unit)> {
+    let (num, num2) = (num.clone(), num2.clone());
+    Some(match op {
+        CalculationOperator::Plus | CalculationOperator::Minus => {
+            let num2 = if unit == unit2 {
+                num2
+            } else if unit != &Unit::None && unit2 != &Unit::None && unit.comparable(unit2) {
+                num2.convert(unit2, unit)
+            } else {
+                return None;
+            };
+
+            if op == CalculationOperator::Plus {
+                (num + num2, unit.clone())
+            } else {
+                (num - num2, unit.clone())
+            }
+        }
+        CalculationOperator::Mul => {
+            if unit == &Unit::None {
+                (num * num2, unit2.clone())
+            } else if unit2 == &Unit::None {
+                (num * num2, unit.clone())
+            } else {
+                let (unit, factor) = unit.clone().product(unit2.clone());
+                (num * num2 * factor, unit)
+            }
+        }
+        CalculationOperator::Div => {
+            if num2.is_zero() {
+                return None;
+            }
+
+            if unit == unit2 {
+                (num / num2, Unit::None)
+            } else if unit2 == &Unit::None {
+                (num / num2, unit.clone())
+            } else {
+                let (unit, factor) = unit.clone().quotient(unit2.clone());
+                (num / num2 * factor, unit)
+            }
+        }
+    })
+}
+
+/// Compares two numbers, returning `None` if their units are incompatible
+fn compare(num: &Number, unit: &Unit, num2: &Number, unit2: &Unit) -> Option<Ordering> {
+    if unit == unit2 || unit == &Unit::None || unit2 == &Unit::None {
+        Some(num.cmp(num2))
+    } else if unit.comparable(unit2) {
+        Some(num.cmp(&num2.clone().convert(unit2, unit)))
+    } else {
+        None
+    }
+}
+
+/// A call to a CSS math function that couldn't be simplified to a number
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct SassCalculation {
+    pub name: CalculationName,
+    pub args: Vec<CalculationArg>,
+}
+
+impl SassCalculation {
+    /// Creates a calculation, simplifying it to a number if possible
+    pub fn new(name: CalculationName, args: Vec<CalculationArg>) -> Value {
+        let args: Vec<CalculationArg> = args.into_iter().map(CalculationArg::unwrap_calc).collect();
+
+        let simplified = match name {
+            CalculationName::Calc => match args.first() {
+                Some(CalculationArg::Number(num, unit)) => Some((num.clone(), unit.clone())),
+                Some(CalculationArg::Calculation(calc)) if args.len() == 1 => {
+                    return Value::Calculation(calc.clone())
+                }
+                _ => None,
+            },
+            CalculationName::Min => Self::select(&args, Ordering::Less),
+            CalculationName::Max => Self::select(&args, Ordering::Greater),
+            CalculationName::Clamp => Self::clamp(&args),
+        };
+
+        match simplified {
+            Some((num, unit)) => Value::Dimension(Some(num), unit, true),
+            None => Value::Calculation(SassCalculation { name, args }),
+        }
+    }
+
+    /// Keeps the arguments of a calculation as they were written, because
+    /// they aren't valid calculation syntax
+    pub fn unparsed(name: CalculationName, args: String) -> Value {
+        Value::Calculation(SassCalculation {
+            name,
+            args: vec![CalculationArg::String(args)],
+        })
+    }
+
+    /// Finds the smallest or largest of `args`, if they are all numbers with
+    /// compatible units
+    fn select(args: &[CalculationArg], ordering: Ordering) -> Option<(Number, Unit)> {
+        let numbers = args
+            .iter()
+            .map(|arg| match arg {
+                CalculationArg::Number(num, unit) => Some((num, unit)),
+                _ => None,
+            })
+            .collect::<Option<Vec<(&Number, &Unit)>>>()?;
+
+        for (idx, (num, unit)) in numbers.iter().enumerate() {
+            for (num2, unit2) in &numbers[idx + 1..] {
+                compare(num, unit, num2, unit2)?;
+            }
+        }
+
+        let (first, rest) = numbers.split_first()?;
+        let mut selected = *first;
+        for &(num, unit) in rest {
+            if compare(num, unit, selected.0, selected.1)? == ordering {
+                selected = (num, unit);
+            }
+        }
+
+        Some((selected.0.clone(), selected.1.clone()))
+    }
+
+    /// Clamps the second argument between the first and third, in the same
+    /// manner as CSS, so that the first wins if it is larger than the third
+    fn clamp(args: &[CalculationArg]) -> Option<(Number, Unit)> {
+        match args {
+            [min, value, max] => {
+                let (num, unit) = Self::select(&[value.clone(), max.clone()], Ordering::Less)?;
+                Self::select(
+                    &[min.clone(), CalculationArg::Number(num, unit)],
+                    Ordering::Greater,
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SassCalculation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name.as_str())?;
+        for (idx, arg) in self.args.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", arg)?;
+        }
+        f.write_str(")")
+    }
+}
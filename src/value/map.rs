@@ -51,6 +51,10 @@ impl SassMap {
         Ok(None)
     }
 
+    pub fn get_ref(&self, key: &Value) -> Option<&Value> {
+        self.0.iter().find(|(k, ..)| k == key).map(|(.., v)| v)
+    }
+
     pub fn remove(&mut self, key: &Value) {
         self.0.retain(|(ref k, ..)| k.not_equals(key));
     }
@@ -15,15 +15,23 @@ use crate::{
     {Cow, Token},
 };
 
+pub(crate) use arglist::ArgList;
+pub(crate) use calculation::{
+    CalculationArg, CalculationName, CalculationOperator, SassCalculation,
+};
 use css_function::is_special_function;
 pub(crate) use map::SassMap;
-pub(crate) use number::Number;
+pub(crate) use number::{set_precision, Number, DEFAULT_PRECISION};
 pub(crate) use sass_function::SassFunction;
+pub(crate) use sass_mixin::SassMixin;
 
+mod arglist;
+mod calculation;
 pub(crate) mod css_function;
 mod map;
 mod number;
 mod sass_function;
+mod sass_mixin;
 
 #[derive(Debug, Clone)]
 pub(crate) enum Value {
@@ -37,9 +45,12 @@ pub(crate) enum Value {
     Color(Box<Color>),
     String(String, QuoteKind),
     Map(SassMap),
-    ArgList(Vec<Spanned<Value>>),
+    ArgList(ArgList),
     /// Returned by `get-function()`
     FunctionRef(SassFunction),
+    /// Returned by `meta.get-mixin()`
+    MixinRef(Box<SassMixin>),
+    Calculation(SassCalculation),
 }
 
 impl PartialEq for Value {
@@ -90,6 +101,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::MixinRef(mixin1) => {
+                if let Value::MixinRef(mixin2) = other {
+                    mixin1 == mixin2
+                } else {
+                    false
+                }
+            }
             Value::Map(map1) => {
                 if let Value::Map(map2) = other {
                     map1 == map2
@@ -111,6 +129,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::Calculation(calc1) => {
+                if let Value::Calculation(calc2) = other {
+                    calc1 == calc2
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -217,7 +242,7 @@ impl Value {
                     }
                 }
             },
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", self.inspect(span)?),
                     span,
@@ -242,6 +267,7 @@ impl Value {
                 )),
             },
             Value::Color(c) => Cow::owned(c.to_string()),
+            Value::Calculation(calc) => Cow::owned(calc.to_string()),
             Value::String(string, QuoteKind::None) => {
                 let mut after_newline = false;
                 let mut buf = String::with_capacity(string.len());
@@ -310,16 +336,19 @@ impl Value {
             Value::Dimension(..) => "number",
             Value::List(..) => "list",
             Value::FunctionRef(..) => "function",
+            Value::MixinRef(..) => "mixin",
             Value::ArgList(..) => "arglist",
             Value::True | Value::False => "bool",
             Value::Null => "null",
             Value::Map(..) => "map",
+            Value::Calculation(..) => "calculation",
         }
     }
 
     pub fn is_special_function(&self) -> bool {
         match self {
             Value::String(s, QuoteKind::None) => is_special_function(s),
+            Value::Calculation(..) => true,
             _ => false,
         }
     }
@@ -416,6 +445,15 @@ impl Value {
         }
     }
 
+    /// The text of `self` as written by `@warn` and `@debug`: the contents
+    /// of a string without its quotes, or the inspected value otherwise
+    pub fn to_log_string(&self, span: Span) -> SassResult<Cow<'static, str>> {
+        match self {
+            Value::String(s, ..) => Ok(Cow::owned(s.clone())),
+            v => v.inspect(span),
+        }
+    }
+
     // TODO:
     // https://github.com/sass/dart-sass/blob/d4adea7569832f10e3a26d0e420ae51640740cfb/lib/src/ast/sass/expression/list.dart#L39
     pub fn inspect(&self, span: Span) -> SassResult<Cow<'static, str>> {
@@ -427,28 +465,36 @@ impl Value {
             Value::List(v, sep, brackets) if v.len() == 1 => match brackets {
                 Brackets::None => match sep {
                     ListSeparator::Space => v[0].inspect(span)?,
-                    ListSeparator::Comma => Cow::owned(format!("({},)", v[0].inspect(span)?)),
+                    ListSeparator::Comma => {
+                        Cow::owned(format!("({},)", v[0].inspect_list_element(*sep, span)?))
+                    }
+                    ListSeparator::Slash => {
+                        Cow::owned(format!("({}/)", v[0].inspect_list_element(*sep, span)?))
+                    }
                 },
                 Brackets::Bracketed => match sep {
                     ListSeparator::Space => Cow::owned(format!("[{}]", v[0].inspect(span)?)),
-                    ListSeparator::Comma => Cow::owned(format!("[{},]", v[0].inspect(span)?)),
+                    ListSeparator::Comma => {
+                        Cow::owned(format!("[{},]", v[0].inspect_list_element(*sep, span)?))
+                    }
+                    ListSeparator::Slash => {
+                        Cow::owned(format!("[{}/]", v[0].inspect_list_element(*sep, span)?))
+                    }
                 },
             },
-            Value::List(vals, sep, brackets) => Cow::owned(match brackets {
-                Brackets::None => vals
+            Value::List(vals, sep, brackets) => {
+                let elements = vals
                     .iter()
-                    .map(|x| x.inspect(span))
+                    .map(|x| x.inspect_list_element(*sep, span))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                    .join(sep.as_str()),
-                Brackets::Bracketed => format!(
-                    "[{}]",
-                    vals.iter()
-                        .map(|x| x.inspect(span))
-                        .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                        .join(sep.as_str()),
-                ),
-            }),
+                    .join(sep.as_str());
+                Cow::owned(match brackets {
+                    Brackets::None => elements,
+                    Brackets::Bracketed => format!("[{}]", elements),
+                })
+            }
             Value::FunctionRef(f) => Cow::owned(format!("get-function(\"{}\")", f.name())),
+            Value::MixinRef(m) => Cow::owned(format!("get-mixin(\"{}\")", m.name)),
             Value::Null => Cow::const_str("null"),
             Value::Map(map) => Cow::owned(format!(
                 "({})",
@@ -464,14 +510,14 @@ impl Value {
                 "({},)",
                 args.iter()
                     .filter(|x| !x.is_null())
-                    .map(|a| Ok(a.node.inspect(span)?))
+                    .map(|a| a.node.inspect_list_element(ListSeparator::Comma, span))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
                     .join(", "),
             )),
             Value::ArgList(args) => Cow::owned(
                 args.iter()
                     .filter(|x| !x.is_null())
-                    .map(|a| Ok(a.node.inspect(span)?))
+                    .map(|a| a.node.inspect_list_element(ListSeparator::Comma, span))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
                     .join(", "),
             ),
@@ -479,10 +525,36 @@ impl Value {
             | Value::True
             | Value::False
             | Value::Color(..)
-            | Value::String(..) => self.to_css_string(span)?,
+            | Value::String(..)
+            | Value::Calculation(..) => self.to_css_string(span)?,
         })
     }
 
+    /// Inspects `self` as an element of a list separated by `separator`,
+    /// wrapping it in parentheses if it is itself a list whose elements would
+    /// otherwise be mistaken for those of the outer list
+    fn inspect_list_element(
+        &self,
+        separator: ListSeparator,
+        span: Span,
+    ) -> SassResult<Cow<'static, str>> {
+        let needs_parens = match self {
+            Value::List(v, sep, Brackets::None) if v.len() > 1 => match separator {
+                ListSeparator::Space => true,
+                ListSeparator::Comma => *sep == ListSeparator::Comma,
+                ListSeparator::Slash => *sep != ListSeparator::Space,
+            },
+            Value::ArgList(args) if args.len() > 1 => true,
+            _ => false,
+        };
+
+        if needs_parens {
+            Ok(Cow::owned(format!("({})", self.inspect(span)?)))
+        } else {
+            self.inspect(span)
+        }
+    }
+
     pub fn as_list(self) -> Vec<Value> {
         match self {
             Value::List(v, ..) => v,
@@ -506,6 +578,7 @@ impl Value {
         name: &str,
         allows_parent: bool,
     ) -> SassResult<Selector> {
+        let span = parser.span_before;
         let string = match self.clone().selector_string(parser.span_before)? {
             Some(v) => v,
             None => return Err((format!("${}: {} is not a valid selector: it must be a string, a list of strings, or a list of lists of strings.", name, self.inspect(parser.span_before)?), parser.span_before).into()),
@@ -532,8 +605,18 @@ impl Value {
             options: parser.options,
             modules: parser.modules,
             module_config: parser.module_config,
+            media_queries: parser.media_queries,
+            stats: parser.stats,
+            mixin_cache: parser.mixin_cache,
+            random: parser.random,
+            import_stack: parser.import_stack,
+            limits: parser.limits,
         }
-        .parse_selector(allows_parent, true, String::new())?
+        .parse_selector(allows_parent, true, String::new())
+        .map_err(|err| {
+            let (message, span) = err.raw(span);
+            (format!("${}: {}", name, message), span)
+        })?
         .0)
     }
 
@@ -566,6 +649,7 @@ impl Value {
                             }
                         }
                     }
+                    ListSeparator::Slash => return Ok(None),
                 }
 
                 result.join(sep.as_str())
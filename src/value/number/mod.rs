@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     cmp::Ordering,
     convert::{From, TryFrom},
     fmt::{self, Display, Write},
@@ -12,13 +13,40 @@ use num_traits::{
     CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Signed, ToPrimitive, Zero,
 };
 
-use crate::unit::{Unit, UNIT_CONVERSION_TABLE};
+use crate::unit::Unit;
 
 use integer::Integer;
 
 mod integer;
 
-const PRECISION: usize = 10;
+/// The number of digits after the decimal point that numbers are written
+/// with by default, matching `dart-sass`
+pub(crate) const DEFAULT_PRECISION: usize = 10;
+
+thread_local!(static PRECISION: Cell<usize> = Cell::new(DEFAULT_PRECISION));
+
+/// Sets the number of digits after the decimal point that numbers are
+/// written with on this thread, until the returned guard is dropped
+///
+/// Numbers are stored exactly, so this only affects how they are displayed.
+pub(crate) fn set_precision(precision: usize) -> PrecisionGuard {
+    PrecisionGuard {
+        previous: PRECISION.with(|cell| cell.replace(precision)),
+    }
+}
+
+/// Restores the previous precision when dropped, so that nested or failed
+/// compilations don't leak their precision into later ones
+#[must_use]
+pub(crate) struct PrecisionGuard {
+    previous: usize,
+}
+
+impl Drop for PrecisionGuard {
+    fn drop(&mut self) {
+        PRECISION.with(|cell| cell.set(self.previous));
+    }
+}
 
 #[derive(Clone, Eq, PartialEq)]
 pub(crate) enum Number {
@@ -112,7 +140,7 @@ impl Number {
     }
 
     #[allow(clippy::cast_precision_loss)]
-    fn as_float(self) -> Option<f64> {
+    pub fn as_float(self) -> Option<f64> {
         Some(match self {
             Number::Small(n) => ((*n.numer() as f64) / (*n.denom() as f64)),
             Number::Big(n) => ((n.numer().to_f64()?) / (n.denom().to_f64()?)),
@@ -149,7 +177,10 @@ impl Number {
 
     /// Invariants: `from.comparable(&to)` must be true
     pub fn convert(self, from: &Unit, to: &Unit) -> Self {
-        self * UNIT_CONVERSION_TABLE[to][from].clone()
+        match from.conversion_factor(to) {
+            Some(factor) => self * factor,
+            None => self,
+        }
     }
 }
 
@@ -356,12 +387,17 @@ impl ToPrimitive for Number {
 
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut whole = self.to_integer().abs();
-        let has_decimal = self.is_decimal();
+        let precision = PRECISION.with(Cell::get);
+        let mut whole = if precision == 0 {
+            self.abs().round().to_integer()
+        } else {
+            self.to_integer().abs()
+        };
+        let has_decimal = self.is_decimal() && precision > 0;
         let mut frac = self.abs().fract();
-        let mut dec = String::with_capacity(if has_decimal { PRECISION } else { 0 });
+        let mut dec = String::with_capacity(if has_decimal { precision } else { 0 });
         if has_decimal {
-            for _ in 0..(PRECISION - 1) {
+            for _ in 0..(precision - 1) {
                 frac *= 10_i64;
                 write!(dec, "{}", frac.to_integer())?;
                 frac = frac.fract();
@@ -8,12 +8,22 @@
 //!
 //! Builtin functions are those that have been implemented in rust and are
 //! in the global scope.
+//!
+//! Plain CSS functions are those returned by `get-function($name, $css: true)`,
+//! which are emitted as they were called.
 
 use std::fmt;
 
+#[cfg(feature = "plugins")]
+use crate::plugin::PluginFn;
 use crate::{
-    args::CallArgs, atrule::Function, builtin::Builtin, common::Identifier, error::SassResult,
-    parse::Parser, value::Value,
+    args::CallArgs,
+    atrule::Function,
+    builtin::Builtin,
+    common::{Identifier, QuoteKind},
+    error::SassResult,
+    parse::Parser,
+    value::Value,
 };
 
 /// A Sass function
@@ -26,6 +36,9 @@ use crate::{
 pub(crate) enum SassFunction {
     Builtin(Builtin, Identifier),
     UserDefined(Box<Function>, Identifier),
+    Plain(Identifier),
+    #[cfg(feature = "plugins")]
+    Plugin(PluginFn, Identifier),
 }
 
 impl SassFunction {
@@ -34,7 +47,9 @@ impl SassFunction {
     /// Used mainly in debugging and `inspect()`
     pub fn name(&self) -> &Identifier {
         match self {
-            Self::Builtin(_, name) | Self::UserDefined(_, name) => name,
+            Self::Builtin(_, name) | Self::UserDefined(_, name) | Self::Plain(name) => name,
+            #[cfg(feature = "plugins")]
+            Self::Plugin(_, name) => name,
         }
     }
 
@@ -45,13 +60,27 @@ impl SassFunction {
         match &self {
             Self::Builtin(..) => "Builtin",
             Self::UserDefined(..) => "UserDefined",
+            Self::Plain(..) => "Plain",
+            #[cfg(feature = "plugins")]
+            Self::Plugin(..) => "Plugin",
         }
     }
 
     pub fn call(self, args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         match self {
             Self::Builtin(f, ..) => f.0(args, parser),
-            Self::UserDefined(f, ..) => parser.eval_function(*f, args),
+            Self::UserDefined(f, name) => {
+                let span = args.span();
+                parser
+                    .eval_function(*f, args)
+                    .map_err(|err| err.in_member(&format!("{}()", name), span))
+            }
+            Self::Plain(name) => Ok(Value::String(
+                format!("{}{}", name, args.to_css_string()?.node),
+                QuoteKind::None,
+            )),
+            #[cfg(feature = "plugins")]
+            Self::Plugin(f, ..) => f.call(args, parser),
         }
     }
 }
@@ -0,0 +1,23 @@
+//! Sass mixins as values
+//!
+//! Mixin values are returned by `meta.get-mixin()`, so that mixins can be
+//! stored in variables, passed to other mixins, and included with
+//! `meta.apply()`.
+
+use crate::{atrule::mixin::Mixin, common::Identifier};
+
+/// A reference to a mixin
+///
+/// The mixin name is stored in addition to the mixin itself for use in
+/// `inspect()`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct SassMixin {
+    pub name: Identifier,
+    pub mixin: Mixin,
+}
+
+impl SassMixin {
+    pub const fn new(name: Identifier, mixin: Mixin) -> Self {
+        Self { name, mixin }
+    }
+}
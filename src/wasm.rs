@@ -0,0 +1,158 @@
+//! Bindings that expose grass to JavaScript, enabled by the `wasm` feature
+//!
+//! ```js
+//! import { compile } from "@connorskees/grass";
+//!
+//! const css = compile("@use \"theme\";\na { color: theme.$primary; }", {
+//!   style: "expanded",
+//!   files: { "theme.scss": "$primary: red;" },
+//! });
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use js_sys::{Array, Error as JsError, Object, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{Compilation, Diagnostic, Input, Logger, MemoryFs, Options, OutputStyle, Result};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(message: &str);
+}
+
+/// Writes each message to the JavaScript console
+#[derive(Debug, Clone, Copy)]
+struct ConsoleLogger;
+
+impl Logger for ConsoleLogger {
+    fn warn(&self, _diagnostic: &Diagnostic, formatted: &str) {
+        console_warn(formatted);
+    }
+
+    fn debug(&self, _diagnostic: &Diagnostic, formatted: &str) {
+        console_debug(formatted);
+    }
+}
+
+/// Compiles a stylesheet, throwing an `Error` describing the problem if it
+/// is invalid
+///
+/// `options` may be `undefined`, or an object with any of these properties,
+/// which are named after the options of `compileString()` in the `sass`
+/// package:
+///
+///  - `style`: either `"expanded"` or `"compressed"`
+///  - `charset`: `false` to never emit a `@charset` or byte-order mark
+///  - `quiet`: `true` to silence warnings and `@debug`
+///  - `verbose`: `true` to print every deprecation warning
+///  - `files`: an object mapping paths to the contents of the stylesheets
+///    that may be loaded with `@import` and `@use`, since there is no
+///    filesystem to load them from
+///  - `loadPaths`: an array of directories within `files` to look for
+///    stylesheets in
+///
+/// Warnings and the output of `@debug` are written to the console.
+#[wasm_bindgen]
+pub fn compile(input: String, options: JsValue) -> std::result::Result<String, JsValue> {
+    let mut fs = MemoryFs::default();
+    let mut load_paths = Vec::new();
+
+    if !options.is_undefined() && !options.is_null() {
+        for entry in entries(&property(&options, "files")?)? {
+            let (path, contents) = (entry.get(0), entry.get(1));
+            match (path.as_string(), contents.as_string()) {
+                (Some(path), Some(contents)) => fs.add_file(path, contents),
+                _ => return Err(JsError::new("Each file must be a string.").into()),
+            }
+        }
+
+        let paths = property(&options, "loadPaths")?;
+        if !paths.is_undefined() {
+            for path in Array::from(&paths).iter() {
+                match path.as_string() {
+                    Some(path) => load_paths.push(PathBuf::from(path)),
+                    None => return Err(JsError::new("Each load path must be a string.").into()),
+                }
+            }
+        }
+    }
+
+    let load_paths: Vec<&Path> = load_paths.iter().map(PathBuf::as_path).collect();
+    let options = options_from_js(&options)?.fs(&fs).load_paths(&load_paths);
+
+    compile_string(input, &options).map_err(|e| JsError::new(&e.to_string()).into())
+}
+
+/// Compiles a stylesheet with the default options, throwing a string
+/// describing the problem if it is invalid
+#[wasm_bindgen]
+pub fn from_string(input: String) -> std::result::Result<String, JsValue> {
+    compile_string(
+        input,
+        &Options::default()
+            .logger(&ConsoleLogger)
+            .fs(&MemoryFs::default()),
+    )
+    .map_err(|e| e.to_string().into())
+}
+
+fn compile_string(input: String, options: &Options) -> Result<String> {
+    crate::compile(
+        &mut Compilation::new(Input::String(input), options)?,
+        options,
+    )
+}
+
+fn options_from_js(options: &JsValue) -> std::result::Result<Options<'static>, JsValue> {
+    let mut result = Options::default().logger(&ConsoleLogger);
+
+    if options.is_undefined() || options.is_null() {
+        return Ok(result);
+    }
+
+    if let Some(style) = property(options, "style")?.as_string() {
+        result = result.style(match style.as_str() {
+            "expanded" => OutputStyle::Expanded,
+            "compressed" => OutputStyle::Compressed,
+            _ => return Err(JsError::new(&format!("Unknown style \"{}\".", style)).into()),
+        });
+    }
+
+    if let Some(charset) = property(options, "charset")?.as_bool() {
+        result = result.allows_charset(charset);
+    }
+
+    if let Some(quiet) = property(options, "quiet")?.as_bool() {
+        result = result.quiet(quiet);
+    }
+
+    if let Some(verbose) = property(options, "verbose")?.as_bool() {
+        result = result.verbose(verbose);
+    }
+
+    Ok(result)
+}
+
+fn property(object: &JsValue, name: &str) -> std::result::Result<JsValue, JsValue> {
+    Reflect::get(object, &JsValue::from_str(name))
+}
+
+/// The `[key, value]` pairs of `object`, or none if it is `undefined`
+fn entries(object: &JsValue) -> std::result::Result<Vec<Array>, JsValue> {
+    if object.is_undefined() {
+        return Ok(Vec::new());
+    }
+
+    match object.dyn_ref::<Object>() {
+        Some(object) => Ok(Object::entries(object)
+            .iter()
+            .map(|entry| Array::from(&entry))
+            .collect()),
+        None => Err(JsError::new("files must be an object.").into()),
+    }
+}
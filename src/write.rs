@@ -0,0 +1,146 @@
+//! Writing compiled CSS to a file
+//!
+//! See [`write_css`](crate::write_css)
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Configuration for how [`write_css`](crate::write_css) writes a file
+#[derive(Debug, Clone, Copy)]
+pub struct WriteOptions {
+    create_dirs: bool,
+    overwrite: bool,
+    atomic: bool,
+}
+
+impl Default for WriteOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            create_dirs: false,
+            overwrite: true,
+            atomic: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// This flag tells grass to create the directory the file is written
+    /// to, along with any of its parents, if it doesn't exist.
+    ///
+    /// By default, writing to a directory that doesn't exist is an error.
+    #[must_use]
+    #[inline]
+    pub fn create_dirs(mut self, create_dirs: bool) -> Self {
+        self.create_dirs = create_dirs;
+        self
+    }
+
+    /// This flag tells grass whether to replace the file if it already
+    /// exists. If it is `false`, writing to an existing file fails with
+    /// [`io::ErrorKind::AlreadyExists`].
+    ///
+    /// By default, existing files are overwritten.
+    #[must_use]
+    #[inline]
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// This flag tells grass to write the CSS to a temporary file next to
+    /// the destination, then rename it over the destination, so that other
+    /// processes, such as file watchers and development servers, never see
+    /// a partially written file.
+    ///
+    /// By default, the destination is truncated and written to directly.
+    #[must_use]
+    #[inline]
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+}
+
+/// Write compiled CSS to `path`, as configured by `options`
+///
+/// ```
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let css = grass::from_string("a { b { color: red; } }".to_string(), &grass::Options::default())?;
+///     let dir = std::env::temp_dir().join("grass-write-css-doctest");
+///
+///     grass::write_css(
+///         &dir.join("css/style.css"),
+///         &css,
+///         &grass::WriteOptions::default().create_dirs(true).atomic(true),
+///     )?;
+///
+///     assert_eq!(std::fs::read_to_string(dir.join("css/style.css"))?, css);
+///     # std::fs::remove_dir_all(dir)?;
+///     Ok(())
+/// }
+/// ```
+pub fn write_css(path: &Path, css: &str, options: &WriteOptions) -> io::Result<()> {
+    if options.create_dirs {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    if !options.atomic {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(options.overwrite)
+            .create_new(!options.overwrite)
+            .truncate(true)
+            .open(path)?;
+        return file.write_all(css.as_bytes());
+    }
+
+    if !options.overwrite && path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+
+    let temp = temp_path(path)?;
+    let result = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&temp)
+        .and_then(|mut file| {
+            file.write_all(css.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+
+    if result.is_err() {
+        // the temporary file may not have been created, in which case there
+        // is nothing to clean up
+        let _ = fs::remove_file(&temp);
+    }
+
+    result
+}
+
+/// A path for a hidden temporary file in the same directory as `path`, so
+/// that it is on the same filesystem and can be renamed over it
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file", path.display()),
+        )
+    })?;
+
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+
+    Ok(path.with_file_name(temp_name))
+}
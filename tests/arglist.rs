@@ -92,3 +92,80 @@ test!(
     }",
     ""
 );
+test!(
+    keywords_of_named_args,
+    "@function foo($a...) {
+        @return keywords($a);
+    }
+
+    a {
+        color: inspect(foo(1, $y: 2, $x: 3));
+    }",
+    "a {\n  color: (y: 2, x: 3);\n}\n"
+);
+test!(
+    keywords_of_no_named_args,
+    "@function foo($a...) {
+        @return keywords($a);
+    }
+
+    a {
+        color: inspect(foo(1, 2));
+    }",
+    "a {\n  color: ();\n}\n"
+);
+test!(
+    keywords_in_mixin,
+    "@mixin foo($a...) {
+        color: inspect(keywords($a));
+    }
+
+    a {
+        @include foo($x: 1);
+    }",
+    "a {\n  color: (x: 1);\n}\n"
+);
+test!(
+    keywords_of_splatted_map,
+    "@function foo($a...) {
+        @return keywords($a);
+    }
+
+    a {
+        color: inspect(foo((x: 1, y: 2)...));
+    }",
+    "a {\n  color: (x: 1, y: 2);\n}\n"
+);
+test!(
+    named_args_are_not_positional_in_arglist,
+    "@function foo($a...) {
+        @return length($a);
+    }
+
+    a {
+        color: foo(1, 2, $x: 3);
+    }",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    splat_arglist_forwards_named_args,
+    "@function foo($args...) {
+        @return bar($args...);
+    }
+
+    @function bar($a, $b: 0) {
+        @return $a + $b;
+    }
+
+    a {
+        color: foo(1, $b: 2);
+    }",
+    "a {\n  color: 3;\n}\n"
+);
+error!(
+    keywords_of_non_arglist,
+    "a {
+        color: keywords(1 2);
+    }",
+    "Error: $args: 1 2 is not an argument list."
+);
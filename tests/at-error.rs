@@ -1,3 +1,5 @@
+use std::io::Write;
+
 #[macro_use]
 mod macros;
 
@@ -15,3 +17,70 @@ error!(
     error_is_inspected,
     "a {\n  @error null;\n}\n", "Error: null"
 );
+
+fn trace(input: &str) -> Vec<String> {
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .to_string()
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with('╵'))
+            .skip(1)
+            .map(str::to_owned)
+            .collect(),
+    }
+}
+
+#[test]
+fn trace_at_root() {
+    assert_eq!(
+        vec!["  stdin 2:3  root stylesheet"],
+        trace("a {\n  @error hi;\n}\n")
+    );
+}
+
+#[test]
+fn trace_through_function_and_mixin() {
+    assert_eq!(
+        vec![
+            "  stdin 2:3   foo()",
+            "  stdin 5:14  bar()",
+            "  stdin 8:3   root stylesheet",
+        ],
+        trace(
+            "@function foo() {\n  @error hi;\n}\n@mixin bar {\n  color: foo();\n}\na {\n  @include bar;\n}\n"
+        )
+    );
+}
+
+#[test]
+fn trace_through_call() {
+    assert_eq!(
+        vec!["  stdin 2:3   foo()", "  stdin 5:15  root stylesheet"],
+        trace("@function foo() {\n  @error hi;\n}\na {\n  color: call(get-function(foo));\n}\n")
+    );
+}
+
+#[test]
+fn trace_through_import() {
+    let input = "@import \"at_error_trace_through_import\";\n";
+    tempfile!("at_error_trace_through_import.scss", "@error hi;\n");
+    assert_eq!(
+        vec![
+            "  at_error_trace_through_import.scss 1:1  @import",
+            "  stdin 1:9                               root stylesheet",
+        ],
+        trace(input)
+    );
+}
+
+#[test]
+fn other_errors_have_no_trace() {
+    match grass::from_string(
+        "@function foo() {\n  @return 1 + red;\n}\na {\n  color: foo();\n}\n".to_string(),
+        &grass::Options::default(),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e.to_string().ends_with("./stdin:2:11\n")),
+    }
+}
@@ -66,6 +66,80 @@ test!(
     "a {}\n\n@at-root {\n    @-ms-viewport { width: device-width; }\n}\n",
     "@-ms-viewport {\n  width: device-width;\n}\n"
 );
+test!(
+    without_media,
+    "@media screen {\n  .foo {\n    a: b;\n    @at-root (without: media) {\n      .bar {\n        c: d;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  .foo {\n    a: b;\n  }\n}\n.foo .bar {\n  c: d;\n}\n"
+);
+test!(
+    without_media_styles_stay_in_style_rule,
+    "@media screen {\n  .foo {\n    @at-root (without: media) {\n      a: b;\n    }\n  }\n}\n",
+    ".foo {\n  a: b;\n}\n"
+);
+test!(
+    without_media_in_style_rule_outside_media,
+    ".foo {\n  @media screen {\n    @at-root (without: media) {\n      a: b;\n    }\n  }\n}\n",
+    ".foo {\n  a: b;\n}\n"
+);
+test!(
+    without_all,
+    "@media screen {\n  .foo {\n    @at-root (without: all) {\n      .bar {\n        a: b;\n      }\n    }\n  }\n}\n",
+    ".bar {\n  a: b;\n}\n"
+);
+test!(
+    with_media,
+    "@media screen {\n  .foo {\n    @at-root (with: media) {\n      .bar {\n        a: b;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    with_rule,
+    "@media screen {\n  .foo {\n    @at-root (with: rule) {\n      .bar {\n        a: b;\n      }\n    }\n  }\n}\n",
+    ".foo .bar {\n  a: b;\n}\n"
+);
+test!(
+    without_outer_at_rule_recreates_inner,
+    "@supports (a: b) {\n  @media screen {\n    .foo {\n      @at-root (without: supports) {\n        c: d;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  .foo {\n    c: d;\n  }\n}\n"
+);
+test!(
+    without_unknown_at_rule,
+    "@foo {\n  .bar {\n    @at-root (without: foo) {\n      a: b;\n    }\n  }\n}\n",
+    "@foo {}\n.bar {\n  a: b;\n}\n"
+);
+test!(
+    default_query_stays_in_media,
+    ".foo {\n  @media screen {\n    @at-root .bar {\n      a: b;\n    }\n  }\n}\n",
+    "@media screen {\n  .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    contents_come_before_later_nested_rules,
+    ".foo {\n  @at-root .bar {\n    a: b;\n  }\n  .baz {\n    c: d;\n  }\n}\n",
+    ".bar {\n  a: b;\n}\n.foo .baz {\n  c: d;\n}\n"
+);
+test!(
+    query_is_case_insensitive,
+    "@media screen {\n  .foo {\n    @at-root (WITHOUT: MEDIA) {\n      a: b;\n    }\n  }\n}\n",
+    ".foo {\n  a: b;\n}\n"
+);
+test!(
+    query_with_interpolation,
+    "$rule: media;\n\n@media screen {\n  .foo {\n    @at-root (without: #{$rule}) {\n      a: b;\n    }\n  }\n}\n",
+    ".foo {\n  a: b;\n}\n"
+);
+test!(
+    query_at_root_of_stylesheet,
+    "@at-root (without: media) {\n  .foo {\n    a: b;\n  }\n}\n",
+    ".foo {\n  a: b;\n}\n"
+);
+error!(
+    query_neither_with_nor_without,
+    ".foo {\n  @at-root (within: media) {\n    a: b;\n  }\n}\n",
+    "Error: Expected \"with\" or \"without\"."
+);
+error!(
+    query_missing_colon,
+    ".foo {\n  @at-root (without media) {\n    a: b;\n  }\n}\n", "Error: expected \":\"."
+);
 error!(
     #[ignore = "we do not currently validate missing closing curly braces"]
     missing_closing_curly_brace,
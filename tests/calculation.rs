@@ -0,0 +1,137 @@
+#[macro_use]
+mod macros;
+
+test!(
+    calc_simplified_to_number,
+    "a {\n  color: calc(10px + 5px);\n}\n",
+    "a {\n  color: 15px;\n}\n"
+);
+test!(
+    calc_converts_compatible_units,
+    "a {\n  color: calc(1in - 48px);\n}\n",
+    "a {\n  color: 0.5in;\n}\n"
+);
+test!(
+    calc_multiplication_before_addition,
+    "a {\n  color: calc(1% + 2px * 3);\n}\n",
+    "a {\n  color: calc(1% + 6px);\n}\n"
+);
+test!(
+    calc_variable_with_incompatible_units,
+    "$a: 10px;\na {\n  color: calc($a + 10%);\n}\n",
+    "a {\n  color: calc(10px + 10%);\n}\n"
+);
+test!(
+    calc_keeps_necessary_parens,
+    "a {\n  color: calc(2 * (1px + 2%));\n}\n",
+    "a {\n  color: calc(2 * (1px + 2%));\n}\n"
+);
+test!(
+    calc_keeps_parens_on_right_of_minus,
+    "a {\n  color: calc(1px - (2% + 3vh));\n}\n",
+    "a {\n  color: calc(1px - (2% + 3vh));\n}\n"
+);
+test!(
+    calc_removes_unnecessary_parens,
+    "a {\n  color: calc((1px + 2%) + (3vh * 4));\n}\n",
+    "a {\n  color: calc(1px + 2% + 12vh);\n}\n"
+);
+test!(
+    calc_nested_calc_is_unwrapped,
+    "a {\n  color: calc(2 * calc(1px + 2%));\n}\n",
+    "a {\n  color: calc(2 * (1px + 2%));\n}\n"
+);
+test!(
+    calc_of_calc_is_unwrapped,
+    "a {\n  color: calc(calc(1px + 2%));\n}\n",
+    "a {\n  color: calc(1px + 2%);\n}\n"
+);
+test!(
+    calc_interpolation_is_parenthesized,
+    "a {\n  color: calc(#{\"1px + 2px\"} * 2);\n}\n",
+    "a {\n  color: calc((1px + 2px) * 2);\n}\n"
+);
+test!(
+    calc_css_function,
+    "a {\n  color: calc(var(--x) * 2);\n}\n",
+    "a {\n  color: calc(var(--x) * 2);\n}\n"
+);
+test!(
+    calc_sass_function,
+    "a {\n  color: calc(percentage(0.5) - 1px);\n}\n",
+    "a {\n  color: calc(50% - 1px);\n}\n"
+);
+test!(
+    calc_user_defined_function,
+    "@function double($n) {\n  @return $n * 2;\n}\na {\n  color: calc(double(2px) + 1px);\n}\n",
+    "a {\n  color: 5px;\n}\n"
+);
+test!(
+    calc_negative_number,
+    "a {\n  color: calc(-1px - -2px);\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    calc_division_by_zero_is_not_simplified,
+    "a {\n  color: calc(1px / 0);\n}\n",
+    "a {\n  color: calc(1px / 0);\n}\n"
+);
+test!(
+    calc_invalid_syntax_kept_as_written,
+    "a {\n  color: calc(1px + foo bar);\n}\n",
+    "a {\n  color: calc(1px + foo bar);\n}\n"
+);
+test!(
+    calc_stored_in_variable,
+    "$a: calc(1px + 2%);\na {\n  color: calc($a * 2);\n}\n",
+    "a {\n  color: calc((1px + 2%) * 2);\n}\n"
+);
+test!(
+    type_of_calculation,
+    "$a: calc(1px + 2%);\na {\n  color: type-of($a);\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    type_of_simplified_calculation,
+    "$a: calc(1px + 2px);\na {\n  color: type-of($a);\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    min_incompatible_units,
+    "a {\n  color: min(1px, 2%, 3vh);\n}\n",
+    "a {\n  color: min(1px, 2%, 3vh);\n}\n"
+);
+test!(
+    min_simplified_with_conversion,
+    "a {\n  color: min(1in, 90px);\n}\n",
+    "a {\n  color: 90px;\n}\n"
+);
+test!(
+    min_of_max,
+    "a {\n  color: min(1px, max(2px, 3px));\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    clamp_simplified,
+    "a {\n  color: clamp(1px, 5px, 3px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    clamp_min_greater_than_max,
+    "a {\n  color: clamp(5px, 1px, 3px);\n}\n",
+    "a {\n  color: 5px;\n}\n"
+);
+test!(
+    clamp_incompatible_units,
+    "a {\n  color: clamp(1px, 2%, 3px);\n}\n",
+    "a {\n  color: clamp(1px, 2%, 3px);\n}\n"
+);
+test!(
+    clamp_wrong_number_of_args_kept_as_written,
+    "a {\n  color: clamp(1px, 2px);\n}\n",
+    "a {\n  color: clamp(1px, 2px);\n}\n"
+);
+error!(
+    min_non_number_falls_back_to_sass_function,
+    "$a: 1px;\na {\n  color: min($a, foo);\n}\n", "Error: foo is not a number."
+);
@@ -1,3 +1,5 @@
+use std::io::Write;
+
 #[macro_use]
 mod macros;
 
@@ -16,3 +18,73 @@ test!(
     "@charset \"foo\";\na {\n  color: red;\n}\n",
     "a {\n  color: red;\n}\n"
 );
+test!(
+    utf8_bom_input,
+    "\u{feff}a {\n  color: red;\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    charset_in_comment_is_detected,
+    "/* é */\na {\n  color: red;\n}\n",
+    "@charset \"UTF-8\";\n/* é */\na {\n  color: red;\n}\n"
+);
+
+#[test]
+fn no_charset_without_allows_charset() {
+    assert_eq!(
+        "a {\n  color: é;\n}\n",
+        grass::from_string(
+            "a {\n  color: é;\n}\n".to_owned(),
+            &grass::Options::default().allows_charset(false)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn compressed_uses_byte_order_mark() {
+    let css = grass::from_string(
+        "a {\n  color: é;\n}\n".to_owned(),
+        &grass::Options::default().style(grass::OutputStyle::Compressed),
+    )
+    .unwrap();
+    assert!(css.starts_with('\u{feff}'));
+    assert!(!css.contains("@charset"));
+}
+
+#[test]
+fn charset_and_bom_in_imported_file() {
+    let input =
+        "@import \"charset_and_bom_in_imported_file_a\", \"charset_and_bom_in_imported_file_b\";";
+    tempfile!(
+        "charset_and_bom_in_imported_file_a.scss",
+        "@charset \"UTF-8\";\na {\n  color: red;\n}\n"
+    );
+    tempfile!(
+        "charset_and_bom_in_imported_file_b.scss",
+        "\u{feff}b {\n  color: red;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn invalid_utf8_in_imported_file() {
+    let input = "@import \"invalid_utf8_in_imported_file\";";
+    let mut f = tempfile::Builder::new()
+        .rand_bytes(0)
+        .prefix("")
+        .suffix("invalid_utf8_in_imported_file.scss")
+        .tempfile_in("")
+        .unwrap();
+    f.write_all(b"a {\n  color: \"\xe9\";\n}\n").unwrap();
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Invalid UTF-8 character \"\\xE9\"",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "commandline")]
+
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn grass(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_grass"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn stdin_to_stdout() {
+    let output = grass(&["--stdin"], "a { b { color: red; } }");
+    assert!(output.status.success());
+    assert_eq!(b"a b {\n  color: red;\n}\n", output.stdout.as_slice());
+}
+
+#[test]
+fn dash_reads_from_stdin() {
+    let output = grass(&["-"], "a { color: red; }");
+    assert!(output.status.success());
+    assert_eq!(b"a {\n  color: red;\n}\n", output.stdout.as_slice());
+}
+
+#[test]
+fn stdin_indented() {
+    let output = grass(&["--stdin", "--indented"], "a\n  b\n    color: red\n");
+    assert!(output.status.success());
+    assert_eq!(b"a b {\n  color: red;\n}\n", output.stdout.as_slice());
+}
+
+#[test]
+fn stdin_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.css");
+    let output = grass(&["--stdin", path.to_str().unwrap()], "a { color: red; }");
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!("a {\n  color: red;\n}\n", fs::read_to_string(path).unwrap());
+}
+
+#[test]
+fn stdin_with_input_and_output() {
+    let output = grass(&["--stdin", "in.scss", "out.css"], "");
+    assert_eq!(Some(64), output.status.code());
+}
+
+#[test]
+fn file_to_stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("in.scss");
+    fs::write(&path, "$a: red;\na { color: $a; }\n").unwrap();
+    let output = grass(&["--load-path", "a", "--quiet", path.to_str().unwrap()], "");
+    assert!(output.status.success());
+    assert_eq!(b"a {\n  color: red;\n}\n", output.stdout.as_slice());
+}
+
+#[test]
+fn compressed_style() {
+    let output = grass(&["--style=compressed", "--stdin"], "a { content: \"é\"; }");
+    assert!(output.status.success());
+    assert!(output.stdout.starts_with("\u{FEFF}".as_bytes()));
+}
+
+#[test]
+fn no_source_map() {
+    let output = grass(&["--no-source-map", "--stdin"], "a { color: red; }");
+    assert!(output.status.success());
+}
+
+#[test]
+fn error_exits_unsuccessfully() {
+    let output = grass(&["--stdin"], "a {");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("Error: expected \"}\"."));
+}
+
+#[test]
+fn watch_requires_output_file() {
+    let output = grass(&["--watch", "in.scss"], "");
+    assert_eq!(Some(64), output.status.code());
+
+    let output = grass(&["--watch", "--stdin", "out.css"], "");
+    assert_eq!(Some(64), output.status.code());
+}
+
+#[test]
+fn directory_to_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::write(src.join("main.scss"), "@import \"partial\";\na { b: c; }\n").unwrap();
+    fs::write(src.join("_partial.scss"), "p { q: r; }\n").unwrap();
+    fs::write(src.join("sub/x.scss"), "x { y: z; }\n").unwrap();
+    fs::write(src.join("notes.txt"), "not a stylesheet").unwrap();
+
+    let out = dir.path().join("out");
+    let pair = format!("{}:{}", src.display(), out.display());
+    let output = grass(&["--create-dirs", &pair], "");
+    assert!(output.status.success());
+
+    assert_eq!(
+        "p {\n  q: r;\n}\n\na {\n  b: c;\n}\n",
+        fs::read_to_string(out.join("main.css")).unwrap()
+    );
+    assert_eq!(
+        "x {\n  y: z;\n}\n",
+        fs::read_to_string(out.join("sub/x.css")).unwrap()
+    );
+    assert!(!out.join("_partial.css").exists());
+    assert!(!out.join("notes.css").exists());
+}
+
+#[test]
+fn many_files_to_many_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.scss");
+    let b = dir.path().join("b.scss");
+    fs::write(&a, "a { color: red; }\n").unwrap();
+    fs::write(&b, "b { color: red; }\n").unwrap();
+
+    let output = grass(
+        &[
+            &format!("{}:{}", a.display(), dir.path().join("a.css").display()),
+            &format!("{}:{}", b.display(), dir.path().join("b.css").display()),
+        ],
+        "",
+    );
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        fs::read_to_string(dir.path().join("b.css")).unwrap()
+    );
+}
+
+#[test]
+fn stops_on_error_unless_told_not_to() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir(&src).unwrap();
+    fs::write(src.join("a.scss"), "a {").unwrap();
+    fs::write(src.join("b.scss"), "b { color: red; }\n").unwrap();
+
+    let out = dir.path().join("out");
+    let pair = format!("{}:{}", src.display(), out.display());
+    let output = grass(&["--create-dirs", &pair], "");
+    assert!(!output.status.success());
+    assert!(!out.join("b.css").exists());
+
+    let output = grass(&["--create-dirs", "--no-stop-on-error", &pair], "");
+    assert!(!output.status.success());
+    assert!(out.join("b.css").exists());
+}
+
+#[test]
+fn pairs_and_positional_args() {
+    let output = grass(&["a.scss:a.css", "b.scss"], "");
+    assert_eq!(Some(64), output.status.code());
+
+    let output = grass(&["a.scss", "b.css", "c.css"], "");
+    assert_eq!(Some(64), output.status.code());
+}
@@ -0,0 +1,130 @@
+#[macro_use]
+mod macros;
+
+test!(
+    hwb_three_args,
+    "@use 'sass:color';\na {\n  color: color.hwb(120deg, 20%, 30%);\n}\n",
+    "a {\n  color: #33b333;\n}\n"
+);
+test!(
+    hwb_channels_list,
+    "@use 'sass:color';\na {\n  color: color.hwb(120deg 20% 30%);\n}\n",
+    "a {\n  color: #33b333;\n}\n"
+);
+test!(
+    hwb_with_alpha,
+    "@use 'sass:color';\na {\n  color: color.hwb(0, 0%, 0%, 0.5);\n}\n",
+    "a {\n  color: rgba(255, 0, 0, 0.5);\n}\n"
+);
+test!(
+    hwb_negative_hue,
+    "@use 'sass:color';\na {\n  color: color.hwb(-120, 0%, 0%);\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    hwb_whiteness_and_blackness_above_100_percent_are_normalized,
+    "@use 'sass:color';\na {\n  color: color.hwb(0, 60%, 60%);\n}\n",
+    "a {\n  color: gray;\n}\n"
+);
+test!(
+    hwb_is_not_global,
+    "a {\n  color: hwb(0, 0%, 0%);\n}\n",
+    "a {\n  color: hwb(0, 0%, 0%);\n}\n"
+);
+error!(
+    hwb_whiteness_without_unit,
+    "@use 'sass:color';\na {\n  color: color.hwb(0, 10, 0%);\n}\n",
+    "Error: $whiteness: Expected 10 to have unit \"%\"."
+);
+error!(
+    hwb_blackness_out_of_range,
+    "@use 'sass:color';\na {\n  color: color.hwb(0, 0%, 110%);\n}\n",
+    "Error: $blackness: Expected 110% to be within 0% and 100%."
+);
+error!(
+    hwb_special_function_channels,
+    "@use 'sass:color';\na {\n  color: color.hwb(var(--a));\n}\n",
+    "Error: Expected numeric channels, got \"hwb(var(--a))\"."
+);
+error!(
+    hwb_missing_blackness,
+    "@use 'sass:color';\na {\n  color: color.hwb(0, 0%);\n}\n",
+    "Error: Missing argument $blackness."
+);
+test!(
+    whiteness,
+    "@use 'sass:color';\na {\n  color: color.whiteness(#33b333);\n}\n",
+    "a {\n  color: 20%;\n}\n"
+);
+test!(
+    blackness,
+    "@use 'sass:color';\na {\n  color: color.blackness(#33b333);\n}\n",
+    "a {\n  color: 29.8039215686%;\n}\n"
+);
+error!(
+    whiteness_not_color,
+    "@use 'sass:color';\na {\n  color: color.whiteness(1);\n}\n",
+    "Error: $color: 1 is not a color."
+);
+test!(
+    change_whiteness,
+    "@use 'sass:color';\na {\n  color: color.change(red, $whiteness: 20%);\n}\n",
+    "a {\n  color: #ff3333;\n}\n"
+);
+test!(
+    adjust_blackness,
+    "@use 'sass:color';\na {\n  color: color.adjust(#33b333, $blackness: -30%);\n}\n",
+    "a {\n  color: #33ff33;\n}\n"
+);
+test!(
+    scale_whiteness,
+    "@use 'sass:color';\na {\n  color: color.scale(red, $whiteness: 50%);\n}\n",
+    "a {\n  color: #ff8080;\n}\n"
+);
+error!(
+    adjust_rgb_and_hwb,
+    "@use 'sass:color';\na {\n  color: color.adjust(red, $red: 1, $whiteness: 1%);\n}\n",
+    "Error: RGB parameters may not be passed along with HWB parameters."
+);
+error!(
+    change_hsl_and_hwb,
+    "@use 'sass:color';\na {\n  color: color.change(red, $lightness: 1%, $blackness: 1%);\n}\n",
+    "Error: HSL parameters may not be passed along with HWB parameters."
+);
+error!(
+    change_whiteness_without_unit,
+    "@use 'sass:color';\na {\n  color: color.change(red, $whiteness: 1);\n}\n",
+    "Error: $whiteness: Expected 1 to have unit \"%\"."
+);
+test!(
+    opacity,
+    "@use 'sass:color';\na {\n  color: color.opacity(rgba(red, 0.5));\n}\n",
+    "a {\n  color: 0.5;\n}\n"
+);
+error!(
+    darken_is_not_in_module,
+    "@use 'sass:color';\na {\n  color: color.darken(red, 10%);\n}\n",
+    "Error: The function darken() isn't in the sass:color module."
+);
+error!(
+    adjust_hue_is_not_in_module,
+    "@use 'sass:color';\na {\n  color: color.adjust-hue(red, 10deg);\n}\n",
+    "Error: The function adjust-hue() isn't in the sass:color module."
+);
+error!(
+    fade_out_is_not_in_module,
+    "@use 'sass:color';\na {\n  color: color.fade-out(red, 0.5);\n}\n",
+    "Error: The function fade-out() isn't in the sass:color module."
+);
+
+#[test]
+fn removed_function_recommends_adjust() {
+    let err = grass::from_string(
+        "@use 'sass:color';\na {\n  color: color.transparentize(red, 0.5);\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Recommendation: color.adjust(red, $alpha: -0.5)"));
+}
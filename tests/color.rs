@@ -337,6 +337,21 @@ error!(
     "a {\n  color: invert(1, 50%);\n}\n",
     "Error: Only one argument may be passed to the plain-CSS invert() function."
 );
+test!(
+    plain_invert_full_weight,
+    "a {\n  color: invert(1, 100%);\n}\n",
+    "a {\n  color: invert(1);\n}\n"
+);
+test!(
+    plain_invert_special_function,
+    "a {\n  color: invert(var(--x));\n  color: invert(calc(1px + 2%), 100%);\n}\n",
+    "a {\n  color: invert(var(--x));\n  color: invert(calc(1px + 2%));\n}\n"
+);
+error!(
+    plain_invert_special_function_two_args,
+    "a {\n  color: invert(var(--x), 50%);\n}\n",
+    "Error: Only one argument may be passed to the plain-CSS invert() function."
+);
 test!(
     invert_weight_percent,
     "a {\n  color: invert(white, 20%);\n}\n",
@@ -389,6 +404,11 @@ test!(
     // blocked on recognizing when to use 3-hex over 6-hex
     "a {\n  color: #ee0000;\n}\n"
 );
+error!(
+    lighten_nan_amount,
+    "a {\n  color: lighten(red, (0 / 0));\n}\n",
+    "Error: $amount: Expected NaN to be within 0 and 100."
+);
 test!(
     darken_named_args,
     "a {\n  color: darken($color: hsl(25, 100%, 80%), $amount: 30%);\n}\n",
@@ -416,6 +436,15 @@ test!(
     "a {\n  color: saturate($amount: 50%);\n}\n",
     "a {\n  color: saturate(50%);\n}\n"
 );
+test!(
+    saturate_one_arg_special_function,
+    "a {\n  color: saturate(var(--x));\n}\n",
+    "a {\n  color: saturate(var(--x));\n}\n"
+);
+error!(
+    saturate_one_arg_non_number,
+    "a {\n  color: saturate(red);\n}\n", "Error: $amount: red is not a number."
+);
 test!(
     saturate_basic,
     "a {\n  color: saturate(hsl(120, 30%, 90%), 20%);\n}\n",
@@ -471,6 +500,11 @@ test!(
     "a {\n  color: grayscale(plum);\n}\n",
     "a {\n  color: #bfbfbf;\n}\n"
 );
+test!(
+    grayscale_special_function,
+    "a {\n  color: grayscale(var(--x));\n}\n",
+    "a {\n  color: grayscale(var(--x));\n}\n"
+);
 test!(
     grayscale_2,
     "a {\n  color: grayscale(red);\n}\n",
@@ -734,8 +768,8 @@ error!(
 // todo: we need many more of these tests
 test!(
     rgba_special_fn_4th_arg_max,
-    "a {\n  color: rgba(1 2 max(3, 3));\n}\n",
-    "a {\n  color: rgba(1, 2, max(3, 3));\n}\n"
+    "a {\n  color: rgba(1 2 max(3%, 3vh));\n}\n",
+    "a {\n  color: rgba(1, 2, max(3%, 3vh));\n}\n"
 );
 test!(
     #[ignore = "we do not check if interpolation occurred"]
@@ -743,3 +777,150 @@ test!(
     "a {\n  color: type-of(r#{e}d);\n}\n",
     "a {\n  color: string;\n}\n"
 );
+test!(
+    rgb_channels_above_max_are_clamped,
+    "a {\n  color: rgb(300, 256, 1000%);\n}\n",
+    "a {\n  color: white;\n}\n"
+);
+test!(
+    rgb_negative_percent_channel_is_clamped,
+    "a {\n  color: rgb(-5%, 0, 50%);\n}\n",
+    "a {\n  color: navy;\n}\n"
+);
+test!(
+    rgb_percent_channels_round_after_scaling,
+    "a {\n  color: rgb(50.5%, 0, 0);\n}\n",
+    "a {\n  color: #810000;\n}\n"
+);
+test!(
+    rgb_one_arg_percent_channels,
+    "a {\n  color: rgb(100% 50% 0%);\n}\n",
+    "a {\n  color: #ff8000;\n}\n"
+);
+test!(
+    hsl_negative_saturation_is_clamped,
+    "a {\n  color: hsl(0, -10%, 50%);\n}\n",
+    "a {\n  color: gray;\n}\n"
+);
+test!(
+    hsl_saturation_and_lightness_above_max_are_clamped,
+    "a {\n  color: hsl(0, 150%, 50%);\n  color: hsl(0, 50%, 120%);\n}\n",
+    "a {\n  color: red;\n  color: white;\n}\n"
+);
+test!(
+    rgba_two_args_percent_alpha_above_max,
+    "a {\n  color: rgba(#abc, 150%);\n}\n",
+    "a {\n  color: #aabbcc;\n}\n"
+);
+test!(
+    rgba_two_args_var_color,
+    "a {\n  color: rgba(var(--color), 0.5);\n}\n",
+    "a {\n  color: rgba(var(--color), 0.5);\n}\n"
+);
+test!(
+    rgba_two_args_var_alpha,
+    "a {\n  color: rgba(red, var(--alpha));\n}\n",
+    "a {\n  color: rgba(255, 0, 0, var(--alpha));\n}\n"
+);
+test!(
+    rgb_one_arg_var,
+    "a {\n  color: rgb(var(--channels));\n}\n",
+    "a {\n  color: rgb(var(--channels));\n}\n"
+);
+test!(
+    rgb_one_arg_fewer_than_three_channels_with_var,
+    "a {\n  color: rgb(1 var(--channels));\n}\n",
+    "a {\n  color: rgb(1 var(--channels));\n}\n"
+);
+test!(
+    hsl_one_arg_var,
+    "a {\n  color: hsl(var(--channels));\n}\n",
+    "a {\n  color: hsl(var(--channels));\n}\n"
+);
+test!(
+    rgb_var_after_non_number_channel,
+    "a {\n  color: rgb(foo, var(--green), 3);\n}\n",
+    "a {\n  color: rgb(foo, var(--green), 3);\n}\n"
+);
+error!(
+    rgb_one_arg_missing_blue,
+    "a {\n  color: rgb(1 2);\n}\n", "Error: Missing element $blue."
+);
+error!(
+    rgb_one_arg_channel_with_unit,
+    "a {\n  color: rgb(1px 2 3);\n}\n", "Error: $red: Expected 1px to have no units or \"%\"."
+);
+error!(
+    rgb_one_arg_comma_separated,
+    "a {\n  color: rgb((1, 2, 3));\n}\n", "Error: $channels must be a space-separated list."
+);
+error!(
+    rgb_one_arg_bracketed,
+    "a {\n  color: rgb([1 2 3]);\n}\n", "Error: $channels must be an unbracketed list."
+);
+error!(
+    rgb_too_many_args,
+    "a {\n  color: rgb(1, 2, 3, 4, 5);\n}\n", "Error: Only 4 arguments allowed, but 5 were passed."
+);
+error!(
+    hsl_two_args_missing_lightness,
+    "a {\n  color: hsl(1, 2);\n}\n", "Error: Missing argument $lightness."
+);
+error!(
+    adjust_color_rgb_and_hsl,
+    "a {\n  color: adjust-color(red, $red: 10, $hue: 10);\n}\n",
+    "Error: RGB parameters may not be passed along with HSL parameters."
+);
+error!(
+    change_color_rgb_and_hsl,
+    "a {\n  color: change-color(red, $lightness: 10%, $blue: 10);\n}\n",
+    "Error: RGB parameters may not be passed along with HSL parameters."
+);
+error!(
+    scale_color_rgb_and_hsl,
+    "a {\n  color: scale-color(red, $red: 10%, $saturation: 10%);\n}\n",
+    "Error: RGB parameters may not be passed along with HSL parameters."
+);
+error!(
+    change_color_unknown_named_arg,
+    "a {\n  color: change-color(red, $foo: 1);\n}\n", "Error: No argument named $foo."
+);
+error!(
+    scale_color_hue,
+    "a {\n  color: scale-color(red, $hue: 10%);\n}\n", "Error: No argument named $hue."
+);
+error!(
+    adjust_color_two_positional_args,
+    "a {\n  color: adjust-color(red, 10);\n}\n",
+    "Error: Only one positional argument is allowed. All other arguments must be passed by name."
+);
+error!(
+    scale_color_two_positional_args,
+    "a {\n  color: scale-color(red, 10%);\n}\n",
+    "Error: Only one positional argument is allowed. All other arguments must be passed by name."
+);
+error!(
+    change_color_nan_channel,
+    "a {\n  color: change-color(red, $red: (0/0));\n}\n",
+    "Error: $red: Expected NaN to be within 0 and 255."
+);
+error!(
+    adjust_color_nan_hsl_channel,
+    "a {\n  color: adjust-color(red, $lightness: (0/0));\n}\n",
+    "Error: $lightness: Expected NaN to be within -100 and 100."
+);
+error!(
+    mix_nan_weight,
+    "a {\n  color: mix(red, blue, (0/0));\n}\n",
+    "Error: $weight: Expected NaN to be within 0 and 100."
+);
+test!(
+    adjust_color_named_color_arg,
+    "a {\n  color: adjust-color($color: red, $hue: 30, $alpha: -0.5);\n}\n",
+    "a {\n  color: rgba(255, 128, 0, 0.5);\n}\n"
+);
+test!(
+    mix_weighted_by_alpha,
+    "a {\n  color: mix(rgba(255, 0, 0, 0.5), blue, 30%);\n}\n",
+    "a {\n  color: rgba(32, 0, 223, 0.85);\n}\n"
+);
@@ -0,0 +1,140 @@
+use grass::{MemoryFs, Options, Syntax};
+
+#[test]
+fn defaults_to_scss() {
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::compile_string("a { color: red; }".to_string(), &Options::default()).unwrap()
+    );
+}
+
+#[test]
+fn error_refers_to_stdin_without_url() {
+    let err = grass::compile_string("a { b: }".to_string(), &Options::default()).unwrap_err();
+    assert_eq!(Some("stdin"), err.diagnostic().file.as_deref());
+}
+
+#[test]
+fn error_refers_to_url() {
+    let err = grass::compile_string(
+        "a { b: }".to_string(),
+        &Options::default().url("styles/input.scss"),
+    )
+    .unwrap_err();
+    assert_eq!(Some("styles/input.scss"), err.diagnostic().file.as_deref());
+}
+
+#[test]
+fn url_is_rewritten() {
+    let rewrite = |url: &str| format!("webpack:///{}", url);
+    let err = grass::compile_string(
+        "a { b: }".to_string(),
+        &Options::default()
+            .url("input.scss")
+            .source_url_rewriter(&rewrite),
+    )
+    .unwrap_err();
+    assert_eq!(
+        Some("webpack:///input.scss"),
+        err.diagnostic().file.as_deref()
+    );
+}
+
+#[test]
+fn loads_relative_to_url() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("styles/_colors.scss", "$primary: red;");
+    fs.add_file("_colors.scss", "$primary: blue;");
+
+    let css = grass::compile_string(
+        "@use \"colors\";\na { color: colors.$primary; }".to_string(),
+        &Options::default().fs(&fs).url("styles/input.scss"),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn loads_relative_to_current_directory_without_url() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("styles/_colors.scss", "$primary: red;");
+    fs.add_file("_colors.scss", "$primary: blue;");
+
+    let css = grass::compile_string(
+        "@use \"colors\";\na { color: colors.$primary; }".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: blue;\n}\n", css);
+}
+
+#[test]
+fn syntax_is_not_inferred_from_url() {
+    let css = grass::compile_string(
+        "a { color: red; }".to_string(),
+        &Options::default().url("input.sass"),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn indented() {
+    let css = grass::compile_string(
+        "a\n  color: red\n".to_string(),
+        &Options::default().syntax(Syntax::Indented),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn scss_loads_indented() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("_mixins.sass", "=red\n  color: red\n");
+
+    let css = grass::compile_string(
+        "@import \"mixins\";\na { @include red; }".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn indented_loads_scss() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("_mixins.scss", "@mixin red { color: red; }");
+
+    let css = grass::compile_string(
+        "@import mixins\na\n  +red\n".to_string(),
+        &Options::default().fs(&fs).syntax(Syntax::Indented),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn sass_and_scss_partials_are_ambiguous() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("_colors.sass", "$primary: red\n");
+    fs.add_file("_colors.scss", "$primary: red;");
+
+    let err = grass::compile_string(
+        "@import \"colors\";".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Error: It's not clear which file to import."));
+}
+
+#[test]
+fn from_path_infers_indented_syntax() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("input.sass", "a\n  color: red\n");
+
+    let css = grass::from_path("input.sass", &Options::default().fs(&fs)).unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+}
@@ -0,0 +1,281 @@
+use std::{cell::Cell, io, path::Path, path::PathBuf, sync::Arc, thread};
+
+use grass::{Compiler, Fs, MemoryFs, Options};
+
+/// Counts the files read from a `MemoryFs`
+#[derive(Debug, Default)]
+struct CountingFs {
+    fs: MemoryFs,
+    reads: Cell<usize>,
+}
+
+impl Fs for CountingFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        self.fs.is_dir(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.fs.is_file(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        self.fs.read(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+}
+
+#[test]
+fn compiler_and_errors_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Compiler>();
+    assert_send_sync::<grass::Error>();
+}
+
+#[test]
+fn shared_files_are_read_once() {
+    let mut fs = CountingFs::default();
+    fs.fs.add_file("_shared.scss", "$color: red;");
+    fs.fs
+        .add_file("a.scss", "@use \"shared\";\na { color: shared.$color; }");
+    fs.fs
+        .add_file("b.scss", "@import \"shared\";\nb { color: $color; }");
+
+    let compiler = Compiler::new();
+    let options = Options::default().fs(&fs);
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        compiler.from_path("a.scss", &options).unwrap()
+    );
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        compiler.from_path("b.scss", &options).unwrap()
+    );
+    assert_eq!(3, fs.reads.get());
+
+    compiler.from_path("a.scss", &options).unwrap();
+    assert_eq!(3, fs.reads.get());
+}
+
+#[test]
+fn shared_partials_report_errors_where_they_were_written() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("_shared.scss", "$a: 1;\na {\n  color: ;\n}\n");
+    fs.add_file("_other.scss", "b {\n  color: red;\n}\n");
+    fs.add_file("a.scss", "@import \"shared\";");
+    fs.add_file("b.scss", "@import \"other\";\n@import \"shared\";");
+
+    let compiler = Compiler::new();
+    let options = Options::default().fs(&fs);
+
+    let a = compiler.from_path("a.scss", &options).unwrap_err();
+    let b = compiler.from_path("b.scss", &options).unwrap_err();
+
+    assert_eq!(a.diagnostic().file, b.diagnostic().file);
+    assert_eq!(a.diagnostic().range, b.diagnostic().range);
+    assert_eq!(3, b.diagnostic().range.unwrap().start.line);
+}
+
+#[test]
+fn files_with_new_contents_are_parsed_again() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("_shared.scss", "a { color: red; }");
+    fs.add_file("input.scss", "@import \"shared\";");
+
+    let compiler = Compiler::new();
+    compiler
+        .from_path("input.scss", &Options::default().fs(&fs))
+        .unwrap();
+
+    fs.add_file("_shared.scss", "a { color: blue; }");
+    compiler.invalidate(&["_shared.scss"]);
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        compiler
+            .from_path("input.scss", &Options::default().fs(&fs))
+            .unwrap()
+    );
+}
+
+#[test]
+fn clear_forgets_files() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("a.scss", "a { color: red; }");
+
+    let compiler = Compiler::new();
+    compiler
+        .from_path("a.scss", &Options::default().fs(&fs))
+        .unwrap();
+
+    fs.add_file("a.scss", "a { color: blue; }");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        compiler
+            .from_path("a.scss", &Options::default().fs(&fs))
+            .unwrap()
+    );
+
+    compiler.clear();
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        compiler
+            .from_path("a.scss", &Options::default().fs(&fs))
+            .unwrap()
+    );
+}
+
+#[test]
+fn missing_files_are_not_remembered() {
+    let mut fs = MemoryFs::default();
+    let compiler = Compiler::new();
+    assert!(compiler
+        .from_path("a.scss", &Options::default().fs(&fs))
+        .is_err());
+
+    fs.add_file("a.scss", "a { color: red; }");
+    assert!(compiler
+        .from_path("a.scss", &Options::default().fs(&fs))
+        .is_ok());
+}
+
+#[test]
+fn compiles_in_parallel() {
+    let compiler = Arc::new(Compiler::new());
+
+    let threads: Vec<_> = (0..8)
+        .map(|n| {
+            let compiler = Arc::clone(&compiler);
+            thread::spawn(move || {
+                compiler.from_string(format!("a {{ width: {}px; }}", n), &Options::default())
+            })
+        })
+        .collect();
+
+    for (n, thread) in threads.into_iter().enumerate() {
+        assert_eq!(
+            format!("a {{\n  width: {}px;\n}}\n", n),
+            thread.join().unwrap().unwrap()
+        );
+    }
+}
+
+#[test]
+fn incremental_reuses_result_until_dependency_invalidated() {
+    let mut fs = CountingFs::default();
+    fs.fs.add_file("input.scss", "@import \"lib/a\";");
+    fs.fs.add_file("lib/_a.scss", "@import \"b\";");
+    fs.fs.add_file("lib/_b.scss", "a { color: red; }");
+    fs.fs.add_file("unrelated.scss", "");
+
+    let compiler = Compiler::new();
+    let options = Options::default().fs(&fs);
+
+    let (css, stats) = compiler
+        .incremental_from_path("input.scss", &options)
+        .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+    assert_eq!(
+        vec![Path::new("lib/_a.scss"), Path::new("lib/_b.scss")],
+        stats.loaded_paths.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(3, fs.reads.get());
+
+    compiler.invalidate(&["unrelated.scss"]);
+    compiler
+        .incremental_from_path("./input.scss", &options)
+        .unwrap();
+    assert_eq!(3, fs.reads.get());
+
+    compiler.invalidate(&["lib/_b.scss"]);
+    compiler
+        .incremental_from_path("input.scss", &options)
+        .unwrap();
+    assert_eq!(4, fs.reads.get());
+}
+
+#[test]
+fn incremental_recompiles_with_different_options() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("input.scss", "a { color: red; }");
+
+    let compiler = Compiler::new();
+    let (css, _) = compiler
+        .incremental_from_path("input.scss", &Options::default().fs(&fs))
+        .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", css);
+
+    let (css, _) = compiler
+        .incremental_from_path(
+            "input.scss",
+            &Options::default().fs(&fs).line_comments(true),
+        )
+        .unwrap();
+    assert_eq!("/* line 1, input.scss */\na {\n  color: red;\n}\n", css);
+}
+
+#[test]
+fn invalidate_entry_point() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("input.scss", "a { color: red; }");
+
+    let compiler = Compiler::new();
+    compiler
+        .incremental_from_path("input.scss", &Options::default().fs(&fs))
+        .unwrap();
+
+    fs.add_file("input.scss", "a { color: blue; }");
+    compiler.invalidate(&["./input.scss"]);
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        compiler
+            .incremental_from_path("input.scss", &Options::default().fs(&fs))
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn invalidate_by_absolute_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("input.scss");
+    let partial = dir.path().join("_partial.scss");
+    std::fs::write(&input, "@import \"partial\";").unwrap();
+    std::fs::write(&partial, "a { color: red; }").unwrap();
+
+    let compiler = Compiler::new();
+    let input = input.to_str().unwrap();
+    compiler
+        .incremental_from_path(input, &Options::default())
+        .unwrap();
+
+    std::fs::write(&partial, "a { color: blue; }").unwrap();
+    compiler.invalidate(&[partial.canonicalize().unwrap()]);
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        compiler
+            .incremental_from_path(input, &Options::default())
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn invalidate_finds_added_files() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("input.scss", "@import \"a\";");
+
+    let compiler = Compiler::new();
+    assert!(compiler
+        .incremental_from_path("input.scss", &Options::default().fs(&fs))
+        .is_err());
+
+    fs.add_file("a.scss", "a { color: red; }");
+    compiler.invalidate(&["a.scss"]);
+    assert!(compiler
+        .incremental_from_path("input.scss", &Options::default().fs(&fs))
+        .is_ok());
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+mod macros;
+
+test!(
+    named_query,
+    "@container sidebar (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container sidebar (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_query,
+    "$name: sidebar;\n$width: 400px;\n@container #{$name} (min-width: #{$width + 1}) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container sidebar (min-width: 401px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    inside_style_rule,
+    ".card {\n  @container (min-width: 400px) {\n    color: red;\n  }\n}\n",
+    "@container (min-width: 400px) {\n  .card {\n    color: red;\n  }\n}\n"
+);
+test!(
+    style_rule_inside_container_inside_style_rule,
+    ".card {\n  @container (min-width: 400px) {\n    color: red;\n    &:hover {\n      color: blue;\n    }\n  }\n}\n",
+    "@container (min-width: 400px) {\n  .card {\n    color: red;\n  }\n  .card:hover {\n    color: blue;\n  }\n}\n"
+);
+test!(
+    nested_containers_are_kept_nested,
+    ".card {\n  @container sidebar (min-width: 400px) {\n    @container (max-width: 800px) {\n      color: red;\n    }\n  }\n}\n",
+    "@container sidebar (min-width: 400px) {\n  @container (max-width: 800px) {\n    .card {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    supports_inside_container_inside_style_rule,
+    ".card {\n  @container (min-width: 400px) {\n    @supports (display: grid) {\n      display: grid;\n    }\n  }\n}\n",
+    "@container (min-width: 400px) {\n  @supports (display: grid) {\n    .card {\n      display: grid;\n    }\n  }\n}\n"
+);
+test!(
+    no_blank_line_between_children,
+    "@container (min-width: 400px) {\n  a {\n    color: red;\n  }\n  @container (max-width: 800px) {\n    b {\n      color: red;\n    }\n  }\n}\n",
+    "@container (min-width: 400px) {\n  a {\n    color: red;\n  }\n  @container (max-width: 800px) {\n    b {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    evaluates_sassscript_in_query,
+    "$width: 400px;\n@container (min-width: $width) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    negated_query,
+    "@container sidebar not (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container sidebar not (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    queries_joined_by_and,
+    "@container (min-width: 400px)   and (max-width: 800px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container (min-width: 400px) and (max-width: 800px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_condition,
+    "@container ((min-width: 400px) or (max-width: 200px)) and (orientation: landscape) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container ((min-width: 400px) or (max-width: 200px)) and (orientation: landscape) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    style_query,
+    "$color: red;\n@container style(color: $color) {\n  a {\n    color: red;\n  }\n}\n",
+    "@container style(color: red) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_condition,
+    "$query: \"(min-width: 400px)\";\n@container #{$query} {\n  a {\n    color: red;\n  }\n}\n",
+    "@container (min-width: 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+error!(
+    missing_body,
+    "@container (min-width: 400px);\n", "Error: expected \"{\"."
+);
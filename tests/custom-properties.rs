@@ -0,0 +1,105 @@
+#[macro_use]
+mod macros;
+
+test!(
+    value_is_not_evaluated,
+    ":root {\n  --a: 1 + 1;\n  --b: $foo;\n}\n",
+    ":root {\n  --a: 1 + 1;\n  --b: $foo;\n}\n"
+);
+test!(
+    whitespace_and_case_are_preserved,
+    "a {\n  --a:FOO  Bar;\n  --b:   c;\n}\n",
+    "a {\n  --a:FOO  Bar;\n  --b:   c;\n}\n"
+);
+test!(
+    trailing_whitespace_is_removed,
+    "a {\n  --a: b   ;\n}\n",
+    "a {\n  --a: b;\n}\n"
+);
+test!(
+    empty_value,
+    "a {\n  --a:;\n  --b: ;\n}\n",
+    "a {\n  --a:;\n  --b:;\n}\n"
+);
+test!(
+    invalid_looking_tokens,
+    "a {\n  --a: @foo !important ~ %;\n}\n",
+    "a {\n  --a: @foo !important ~ %;\n}\n"
+);
+test!(
+    curly_braces_are_not_nested_properties,
+    "a {\n  --a: { b: c };\n}\n",
+    "a {\n  --a: { b: c };\n}\n"
+);
+test!(
+    semicolon_inside_brackets,
+    "a {\n  --a: [b; c] (d; e);\n}\n",
+    "a {\n  --a: [b; c] (d; e);\n}\n"
+);
+test!(
+    quoted_strings_are_kept_as_written,
+    "a {\n  --a: 'b;}' \"c\";\n}\n",
+    "a {\n  --a: 'b;}' \"c\";\n}\n"
+);
+test!(
+    comments_are_kept,
+    "a {\n  --a: /* b */ c // d;\n}\n",
+    "a {\n  --a: /* b */ c // d;\n}\n"
+);
+test!(
+    interpolation_is_evaluated,
+    "$a: red;\n\na {\n  --a: #{$a} #{1 + 1}px;\n  --b: \"#{$a}\";\n}\n",
+    "a {\n  --a: red 2px;\n  --b: \"red\";\n}\n"
+);
+test!(
+    interpolated_name_is_custom_property,
+    "a {\n  --#{b}: 1 + 1;\n}\n",
+    "a {\n  --b: 1 + 1;\n}\n"
+);
+test!(
+    name_from_interpolation_is_evaluated,
+    "a {\n  #{\"--b\"}: 1 + 1;\n}\n",
+    "a {\n  --b: 2;\n}\n"
+);
+test!(
+    no_semicolon_before_closing_curly_brace,
+    "a {\n  --a: b\n}\n",
+    "a {\n  --a: b;\n}\n"
+);
+test!(
+    multiline_value_is_reindented,
+    "a {\n  b {\n    --a: foo\n         bar\n           baz;\n  }\n}\n",
+    "a b {\n  --a: foo\n       bar\n         baz;\n}\n"
+);
+test!(
+    multiline_value_is_reindented_no_further_than_name,
+    "a {\n  --a:\n        b\n\n        c;\n}\n",
+    "a {\n  --a:\n        b\n\n        c;\n}\n"
+);
+test!(
+    multiline_value_in_media,
+    "@media screen {\n  a {\n    --a: {\n      b: c;\n    };\n  }\n}\n",
+    "@media screen {\n  a {\n    --a: {\n      b: c;\n    };\n  }\n}\n"
+);
+test!(
+    in_mixin,
+    "@mixin foo {\n  --a: 1 + 1;\n}\n\na {\n  @include foo;\n}\n",
+    "a {\n  --a: 1 + 1;\n}\n"
+);
+test!(
+    in_keyframes,
+    "@keyframes foo {\n  from {\n    --a: 1 + 1;\n  }\n}\n",
+    "@keyframes foo {\n  from {\n    --a: 1 + 1;\n  }\n}\n"
+);
+error!(
+    unclosed_paren,
+    "a {\n  --a: (b;\n}\n", "Error: expected \")\"."
+);
+error!(
+    mismatched_bracket,
+    "a {\n  --a: (b];\n}\n", "Error: expected \")\"."
+);
+error!(
+    unclosed_quote,
+    "a {\n  --a: \"b\n}\n", "Error: Expected \"."
+);
@@ -0,0 +1,136 @@
+use std::io::Write;
+
+use grass::{DeadCode, DeadSelector};
+
+#[macro_use]
+mod macros;
+
+fn dead_code(input: &str) -> DeadCode {
+    grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().dead_code(true),
+    )
+    .expect(input)
+    .dead_code
+    .unwrap()
+}
+
+fn selectors(dead: &[DeadSelector]) -> Vec<&str> {
+    dead.iter().map(|dead| dead.selector.as_str()).collect()
+}
+
+#[test]
+fn no_dead_code() {
+    let dead = dead_code("%a {\n  color: red;\n}\n\nb {\n  @extend %a;\n}\n");
+    assert_eq!(DeadCode::default(), dead);
+}
+
+#[test]
+fn unextended_placeholder() {
+    let dead = dead_code("a {\n  color: red;\n}\n\n%b {\n  color: red;\n}\n");
+    assert_eq!(
+        vec![DeadSelector {
+            file: "stdin".to_owned(),
+            line: 5,
+            column: 1,
+            selector: "%b".to_owned(),
+        }],
+        dead.unextended_placeholders
+    );
+    assert!(dead.unmatched_extends.is_empty());
+}
+
+#[test]
+fn placeholder_located_at_first_use() {
+    let dead = dead_code("a {\n  %b {\n    color: red;\n  }\n}\n\n%b c {\n  color: red;\n}\n");
+    assert_eq!(1, dead.unextended_placeholders.len());
+    assert_eq!(2, dead.unextended_placeholders[0].line);
+    assert_eq!(3, dead.unextended_placeholders[0].column);
+}
+
+#[test]
+fn placeholder_extended_before_use_is_not_reported() {
+    let dead = dead_code("a {\n  @extend %b;\n}\n\n%b {\n  color: red;\n}\n");
+    assert!(dead.unextended_placeholders.is_empty());
+}
+
+#[test]
+fn placeholders_in_source_order() {
+    let dead = dead_code(
+        "%c {\n  color: red;\n}\n\n%a, %b {\n  color: red;\n}\n\nd {\n  @extend %a;\n}\n",
+    );
+    assert_eq!(vec!["%c", "%b"], selectors(&dead.unextended_placeholders));
+}
+
+#[test]
+fn placeholder_in_pseudo_selector() {
+    let dead = dead_code("a:not(%b) {\n  color: red;\n}\n");
+    assert_eq!(vec!["%b"], selectors(&dead.unextended_placeholders));
+}
+
+#[test]
+fn placeholder_only_extended_by_unused_placeholder_is_not_reported() {
+    let dead = dead_code("%a {\n  color: red;\n}\n\n%b {\n  @extend %a;\n}\n");
+    assert_eq!(vec!["%b"], selectors(&dead.unextended_placeholders));
+}
+
+#[test]
+fn unmatched_optional_extend() {
+    let dead = dead_code("a {\n  @extend .b !optional;\n}\n");
+    assert_eq!(
+        vec![DeadSelector {
+            file: "stdin".to_owned(),
+            line: 2,
+            column: 3,
+            selector: ".b".to_owned(),
+        }],
+        dead.unmatched_extends
+    );
+}
+
+#[test]
+fn unmatched_extend_with_selector_list_extender_reported_once() {
+    let dead = dead_code("a, b {\n  @extend %c !optional;\n}\n");
+    assert_eq!(vec!["%c"], selectors(&dead.unmatched_extends));
+}
+
+#[test]
+fn extend_of_list_reports_each_unmatched_target() {
+    let dead = dead_code(".a {\n  color: red;\n}\n\nb {\n  @extend .a, .c, .d !optional;\n}\n");
+    assert_eq!(vec![".c", ".d"], selectors(&dead.unmatched_extends));
+}
+
+#[test]
+fn unmatched_extends_in_source_order() {
+    let dead = dead_code("a {\n  @extend .c !optional;\n}\n\nb {\n  @extend .b !optional;\n}\n");
+    assert_eq!(vec![".c", ".b"], selectors(&dead.unmatched_extends));
+}
+
+#[test]
+fn mandatory_unmatched_extend_is_still_an_error() {
+    assert!(grass::compile_with_reports(
+        grass::Input::String("a {\n  @extend .b;\n}\n".to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().dead_code(true)
+    )
+    .is_err());
+}
+
+#[test]
+fn placeholder_in_imported_file() {
+    let input = "@import \"dead_code_imported\";\n";
+    tempfile!(
+        "dead_code_imported.scss",
+        "a {\n  color: red;\n}\n\n%b {\n  color: red;\n}\n"
+    );
+
+    let dead = dead_code(input);
+
+    assert_eq!(1, dead.unextended_placeholders.len());
+    assert_eq!(
+        "dead_code_imported.scss",
+        dead.unextended_placeholders[0].file
+    );
+    assert_eq!(5, dead.unextended_placeholders[0].line);
+}
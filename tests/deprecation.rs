@@ -0,0 +1,123 @@
+use std::io::Write;
+
+use grass::Deprecation;
+
+#[macro_use]
+mod macros;
+
+fn deprecation_warnings(input: &str, options: grass::Options<'_>) -> Vec<&'static str> {
+    let stats = grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &options.quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    stats
+        .diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.code.starts_with("deprecated-"))
+        .map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+#[test]
+fn deprecation_ids() {
+    assert_eq!("slash-div", Deprecation::SlashDiv.id());
+    assert_eq!(Some(Deprecation::Elseif), Deprecation::from_id("elseif"));
+    assert_eq!(None, Deprecation::from_id("not-a-deprecation"));
+    for deprecation in Deprecation::ALL {
+        assert_eq!(Some(*deprecation), Deprecation::from_id(deprecation.id()));
+    }
+}
+
+#[test]
+fn silenced_deprecation_is_not_reported() {
+    let input = "a {\n  color: (10px / 2);\n  @if false {} @elseif true {}\n}\n";
+    assert_eq!(
+        vec!["deprecated-elseif"],
+        deprecation_warnings(
+            input,
+            grass::Options::default().silence_deprecations(&[Deprecation::SlashDiv])
+        )
+    );
+}
+
+#[test]
+fn fatal_deprecation_is_an_error() {
+    let input = "a {\n  color: (10px / 2);\n}\n";
+    let err = grass::from_string(
+        input.to_string(),
+        &grass::Options::default().fatal_deprecations(&[Deprecation::SlashDiv]),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains(
+        "This is only an error because you've set the slash-div deprecation to be fatal."
+    ));
+}
+
+#[test]
+fn fatal_deprecation_takes_precedence_over_silenced() {
+    let input = "a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n";
+    assert!(grass::from_string(
+        input.to_string(),
+        &grass::Options::default()
+            .fatal_deprecations(&[Deprecation::CallString])
+            .silence_deprecations(&[Deprecation::CallString]),
+    )
+    .is_err());
+}
+
+#[test]
+fn other_deprecations_are_unaffected_by_fatal() {
+    let input = "a {\n  @if false {} @elseif true {}\n}\n";
+    assert_eq!(
+        vec!["deprecated-elseif"],
+        deprecation_warnings(
+            input,
+            grass::Options::default().fatal_deprecations(&[Deprecation::SlashDiv])
+        )
+    );
+}
+
+#[test]
+fn deprecation_is_reported_once_per_location() {
+    let input = "@for $i from 1 through 3 {\n  a {\n    color: (10px / 2);\n  }\n}\n";
+    assert_eq!(
+        vec!["deprecated-slash-div"],
+        deprecation_warnings(input, grass::Options::default())
+    );
+}
+
+#[test]
+fn sass_import_is_deprecated() {
+    let input = "@import \"deprecated_import\";\n@import \"plain.css\";\n";
+    tempfile!("deprecated_import.scss", "a {\n  color: red;\n}\n");
+    assert_eq!(
+        vec!["deprecated-import"],
+        deprecation_warnings(input, grass::Options::default())
+    );
+}
+
+#[test]
+fn hsl_units_are_deprecated() {
+    let input = "a {\n  color: hsl(10rad, 50%, 50%);\n  color: hsl(10, 50, 50);\n  color: hsl(10deg, 50%, 50%);\n}\n";
+    assert_eq!(
+        vec!["deprecated-function-units", "deprecated-function-units"],
+        deprecation_warnings(input, grass::Options::default())
+    );
+}
+
+#[test]
+fn hsl_units_deprecation_can_be_fatal() {
+    let input = "a {\n  color: hsl(10, 50, 50%);\n}\n";
+    let err = grass::from_string(
+        input.to_string(),
+        &grass::Options::default().fatal_deprecations(&[Deprecation::FunctionUnits]),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("$saturation: Passing a number without unit % (50) is deprecated."));
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "determinism-audit")]
+
+#[macro_use]
+mod macros;
+
+test!(
+    extend_with_many_targets,
+    "a, b, c, d, e, f, g, h {
+        color: red;
+    }
+    i {
+        @extend a, b, c, d, e, f, g, h;
+    }
+    j {
+        @extend i;
+    }",
+    "a, i, j, b, c, d, e, f, g, h {\n  color: red;\n}\n"
+);
+test!(
+    random_is_seeded_once_per_compilation,
+    "a {
+        color: unique-id() == unique-id();
+    }",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    extend_rules_with_equal_selectors,
+    ".foo { a: 1; }
+    .foo { a: 2; }
+    .foo { a: 3; }
+    .foo { a: 4; }
+    .bar { @extend .foo; }",
+    ".foo, .bar {\n  a: 1;\n}\n\n.foo, .bar {\n  a: 2;\n}\n\n.foo, .bar {\n  a: 3;\n}\n\n.foo, .bar {\n  a: 4;\n}\n"
+);
@@ -0,0 +1,266 @@
+use grass::{checkstyle_report, Diagnostic, Position, SecondarySpan, Severity, SourceRange};
+
+#[test]
+fn error_diagnostic() {
+    let err = grass::from_string(
+        "a {\n  color: ;\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        Diagnostic {
+            severity: Severity::Error,
+            code: "error",
+            message: "Expected expression.".to_string(),
+            file: Some("stdin".to_string()),
+            range: Some(SourceRange {
+                start: Position { line: 2, column: 3 },
+                end: Position { line: 2, column: 8 },
+            }),
+            secondary: Vec::new(),
+            suggestion: None,
+        },
+        err.diagnostic()
+    );
+}
+
+#[test]
+fn error_diagnostic_json() {
+    let err = grass::from_string(
+        "a {\n  color: ;\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        "{\"severity\":\"error\",\"code\":\"error\",\"message\":\"Expected expression.\",\"file\":\"stdin\",\"range\":{\"start\":{\"line\":2,\"column\":3},\"end\":{\"line\":2,\"column\":8}},\"secondary\":[],\"suggestion\":null}",
+        err.diagnostic().to_json()
+    );
+}
+
+#[test]
+fn diagnostic_json_escapes_strings() {
+    let diagnostic = Diagnostic {
+        severity: Severity::Warning,
+        code: "warn",
+        message: "a \"b\"\n\\c\u{1}".to_string(),
+        file: None,
+        range: None,
+        secondary: Vec::new(),
+        suggestion: None,
+    };
+
+    assert_eq!(
+        "{\"severity\":\"warning\",\"code\":\"warn\",\"message\":\"a \\\"b\\\"\\n\\\\c\\u0001\",\"file\":null,\"range\":null,\"secondary\":[],\"suggestion\":null}",
+        diagnostic.to_json()
+    );
+}
+
+/// The byte offset of `position` in `source`
+fn offset(source: &str, position: Position) -> usize {
+    source
+        .lines()
+        .take(position.line - 1)
+        .map(|line| line.len() + 1)
+        .sum::<usize>()
+        + position.column
+        - 1
+}
+
+#[test]
+fn error_diagnostic_suggestion() {
+    let source = ".x {\n  @extend .y;\n}\n";
+    let err = grass::from_string(source.to_string(), &grass::Options::default()).unwrap_err();
+    let diagnostic = err.diagnostic();
+
+    let suggestion = diagnostic.suggestion.as_deref().unwrap();
+    assert_eq!("@extend .y !optional", suggestion);
+
+    // replacing the range with the suggestion fixes the error
+    let range = diagnostic.range.unwrap();
+    let fixed = format!(
+        "{}{}{}",
+        &source[..offset(source, range.start)],
+        suggestion,
+        &source[offset(source, range.end)..]
+    );
+    assert_eq!(".x {\n  @extend .y !optional;\n}\n", fixed);
+    assert_eq!(
+        "",
+        grass::from_string(fixed, &grass::Options::default()).unwrap()
+    );
+}
+
+#[test]
+fn nested_error_diagnostic_secondary_span() {
+    let err = grass::from_string(
+        ".foo {a: b}\n@media screen {\n  .bar {@extend .foo}\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err();
+    let diagnostic = err.diagnostic();
+
+    assert_eq!(1, diagnostic.secondary.len());
+    assert_eq!(
+        "while extending this selector",
+        diagnostic.secondary[0].message
+    );
+    assert_eq!("stdin", diagnostic.secondary[0].file);
+    assert_eq!(
+        Position { line: 1, column: 1 },
+        diagnostic.secondary[0].range.start
+    );
+    assert_eq!(None, diagnostic.suggestion);
+}
+
+#[test]
+fn diagnostic_json_secondary_span_and_suggestion() {
+    let diagnostic = Diagnostic {
+        severity: Severity::Error,
+        code: "error",
+        message: "a".to_string(),
+        file: None,
+        range: None,
+        secondary: vec![SecondarySpan {
+            message: "b".to_string(),
+            file: "c.scss".to_string(),
+            range: SourceRange {
+                start: Position { line: 1, column: 2 },
+                end: Position { line: 3, column: 4 },
+            },
+        }],
+        suggestion: Some("d".to_string()),
+    };
+
+    assert_eq!(
+        "{\"severity\":\"error\",\"code\":\"error\",\"message\":\"a\",\"file\":null,\"range\":null,\"secondary\":[{\"message\":\"b\",\"file\":\"c.scss\",\"range\":{\"start\":{\"line\":1,\"column\":2},\"end\":{\"line\":3,\"column\":4}}}],\"suggestion\":\"d\"}",
+        diagnostic.to_json()
+    );
+}
+
+#[test]
+fn io_error_diagnostic() {
+    let err = grass::from_path("does-not-exist.scss", &grass::Options::default()).unwrap_err();
+    let diagnostic = err.diagnostic();
+
+    assert_eq!(Severity::Error, diagnostic.severity);
+    assert_eq!("io", diagnostic.code);
+    assert_eq!(None, diagnostic.range);
+}
+
+#[test]
+fn error_diagnostic_github() {
+    let err = grass::from_string(
+        "a {\n  color: ;\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        "::error file=stdin,line=2,col=3,endLine=2,endColumn=8,title=error::Expected expression.",
+        err.diagnostic().to_github()
+    );
+}
+
+#[test]
+fn diagnostic_github_escapes_strings() {
+    let diagnostic = Diagnostic {
+        severity: Severity::Debug,
+        code: "debug",
+        message: "100%\na: b, c".to_string(),
+        file: Some("a:b,c.scss".to_string()),
+        range: None,
+        secondary: Vec::new(),
+        suggestion: None,
+    };
+
+    assert_eq!(
+        "::notice file=a%3Ab%2Cc.scss,title=debug::100%25%0Aa: b, c",
+        diagnostic.to_github()
+    );
+}
+
+#[test]
+fn stats_record_warnings_and_debug() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("@warn foo;\n@debug 1 + 1;\n".to_string()),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            Diagnostic {
+                severity: Severity::Warning,
+                code: "warn",
+                message: "foo".to_string(),
+                file: Some("stdin".to_string()),
+                range: Some(SourceRange {
+                    start: Position { line: 1, column: 7 },
+                    end: Position { line: 1, column: 8 },
+                }),
+                secondary: Vec::new(),
+                suggestion: None,
+            },
+            Diagnostic {
+                severity: Severity::Debug,
+                code: "debug",
+                message: "2".to_string(),
+                file: Some("stdin".to_string()),
+                range: Some(SourceRange {
+                    start: Position { line: 2, column: 8 },
+                    end: Position { line: 2, column: 9 },
+                }),
+                secondary: Vec::new(),
+                suggestion: None,
+            },
+        ],
+        stats.diagnostics
+    );
+}
+
+#[test]
+fn checkstyle_report_groups_by_file() {
+    let diagnostic = |file: Option<&str>, line, message: &str| Diagnostic {
+        severity: Severity::Warning,
+        code: "warn",
+        message: message.to_string(),
+        file: file.map(str::to_string),
+        range: Some(SourceRange {
+            start: Position { line, column: 1 },
+            end: Position { line, column: 2 },
+        }),
+        secondary: Vec::new(),
+        suggestion: None,
+    };
+
+    assert_eq!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <checkstyle version=\"4.3\">\n  \
+         <file name=\"a.scss\">\n    \
+         <error line=\"1\" column=\"1\" severity=\"warning\" message=\"&lt;&quot;x&quot; &amp; y&gt;\" source=\"grass.warn\"/>\n    \
+         <error line=\"3\" column=\"1\" severity=\"warning\" message=\"z\" source=\"grass.warn\"/>\n  \
+         </file>\n  \
+         <file name=\"b.scss\">\n    \
+         <error line=\"2\" column=\"1\" severity=\"warning\" message=\"y\" source=\"grass.warn\"/>\n  \
+         </file>\n\
+         </checkstyle>\n",
+        checkstyle_report(&[
+            diagnostic(Some("a.scss"), 1, "<\"x\" & y>"),
+            diagnostic(Some("b.scss"), 2, "y"),
+            diagnostic(Some("a.scss"), 3, "z"),
+        ])
+    );
+}
+
+#[test]
+fn checkstyle_report_empty() {
+    assert_eq!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n</checkstyle>\n",
+        checkstyle_report(&[])
+    );
+}
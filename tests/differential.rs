@@ -0,0 +1,200 @@
+#![cfg(feature = "differential")]
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use grass::differential::{compare, DartSass};
+
+/// A small xorshift generator, so that generated inputs are the same on
+/// every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.next() as usize % items.len()]
+    }
+}
+
+const SELECTORS: &[&str] = &[
+    "a",
+    ".b",
+    "#c",
+    "%d",
+    "a.b",
+    ".b .c",
+    ".c > a",
+    "a + .b",
+    "a ~ #c",
+    ":hover",
+    "::before",
+    ".b:not(.c)",
+    "a:is(.b, .c)",
+    "[d=e]",
+];
+
+const NESTED_SELECTORS: &[&str] = &["&", "& + &", "&-suffix", "&:hover", "a &"];
+
+/// Only simple selectors may be extended
+const EXTEND_TARGETS: &[&str] = &["a", ".b", ".c", "#c", "%d", ":hover", "[d=e]"];
+
+const NUMBERS: &[&str] = &[
+    "0", "1", "-1", "1.5", "10px", "2em", "50%", "1in", "96px", "1s", "100ms", "90deg", "1turn",
+    "3.14159", "1e3", "(1/3)", "0.1px",
+];
+
+const OPERATORS: &[&str] = &["+", "-", "*", "%", "==", "<", ">="];
+
+const STRINGS: &[&str] = &[
+    "abc",
+    "\"abc\"",
+    "'a b'",
+    "\"\"",
+    "\"\\1F600\"",
+    "ß",
+    "\"a\\\"b\"",
+];
+
+const STRING_FUNCTIONS: &[&str] = &[
+    "to-upper-case({s})",
+    "to-lower-case({s})",
+    "str-length({s})",
+    "quote({s})",
+    "unquote({s})",
+    "str-index({s}, {s})",
+    "str-insert({s}, {s}, {n})",
+    "str-slice({s}, {n}, {n})",
+    "inspect({s})",
+];
+
+fn number(rng: &mut Rng) -> String {
+    rng.pick(NUMBERS).to_owned()
+}
+
+fn value(rng: &mut Rng) -> String {
+    match rng.next() % 4 {
+        0 => format!("{} {} {}", number(rng), rng.pick(OPERATORS), number(rng)),
+        1 => {
+            let template = rng.pick(STRING_FUNCTIONS);
+            let mut out = String::new();
+            let mut rest = template;
+            while let Some(idx) = rest.find('{') {
+                out.push_str(&rest[..idx]);
+                let value = if rest[idx..].starts_with("{s}") {
+                    rng.pick(STRINGS).to_owned()
+                } else {
+                    format!("{}", rng.next() % 7) + ["", "px"][rng.next() as usize % 2]
+                };
+                out.push_str(&value);
+                rest = &rest[idx + 3..];
+            }
+            out.push_str(rest);
+            out
+        }
+        2 => format!(
+            "unit({}) comparable({}, {})",
+            number(rng),
+            number(rng),
+            number(rng)
+        ),
+        _ => format!("{} + {}", rng.pick(STRINGS), rng.pick(STRINGS)),
+    }
+}
+
+fn style_rule(rng: &mut Rng, depth: u32) -> String {
+    let selector = if depth > 0 && rng.next() % 2 == 0 {
+        rng.pick(NESTED_SELECTORS)
+    } else {
+        rng.pick(SELECTORS)
+    };
+    let mut out = format!("{} {{\n", selector);
+
+    for _ in 0..1 + rng.next() % 3 {
+        match rng.next() % 5 {
+            0 => {
+                let optional = ["", " !optional"][rng.next() as usize % 2];
+                writeln!(out, "@extend {}{};", rng.pick(EXTEND_TARGETS), optional).unwrap()
+            }
+            1 if depth < 2 => out.push_str(&style_rule(rng, depth + 1)),
+            _ => writeln!(out, "x: {};", value(rng)).unwrap(),
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn generate(seed: u64) -> String {
+    let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1);
+    (0..1 + rng.next() % 4)
+        .map(|_| style_rule(&mut rng, 0))
+        .collect()
+}
+
+#[test]
+fn differential() {
+    let dart_sass = match DartSass::find() {
+        Some(dart_sass) => dart_sass,
+        None => {
+            eprintln!("skipping differential test: set `DART_SASS` to a dart-sass executable");
+            return;
+        }
+    };
+
+    let mut inputs = Vec::new();
+
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/differential");
+    let mut paths: Vec<_> = fs::read_dir(&corpus)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "scss"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        inputs.push((
+            path.display().to_string(),
+            fs::read_to_string(&path).unwrap(),
+        ));
+    }
+
+    let cases = env::var("GRASS_DIFFERENTIAL_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(100);
+    for seed in 0..cases {
+        inputs.push((format!("generated #{}", seed), generate(seed)));
+    }
+
+    let options = grass::Options::default();
+    let mut report = String::new();
+    let mut failures = 0;
+
+    for (name, input) in &inputs {
+        if let Some(difference) = compare(input, &options, &dart_sass).unwrap() {
+            if difference.is_miscompilation() {
+                failures += 1;
+            }
+            writeln!(
+                report,
+                "--- {}\n{}\n{}\n",
+                name,
+                input.trim_end(),
+                difference
+            )
+            .unwrap();
+        }
+    }
+
+    eprint!("{}", report);
+    assert!(
+        failures == 0,
+        "grass and dart-sass disagree on {} of {} inputs",
+        failures,
+        inputs.len()
+    );
+}
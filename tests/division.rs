@@ -172,3 +172,53 @@ test!(
     "a {\n  color: (0 / 0);\n}\n",
     "a {\n  color: NaN;\n}\n"
 );
+
+fn slash_division_warnings(input: &str) -> usize {
+    let stats = grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    stats
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code == "deprecated-slash-div")
+        .count()
+}
+
+#[test]
+fn slash_division_is_deprecated() {
+    assert_eq!(
+        1,
+        slash_division_warnings("$a: 10px / 2;\na {\n  color: $a;\n}\n")
+    );
+    assert_eq!(1, slash_division_warnings("a {\n  color: (10px / 2);\n}\n"));
+}
+
+#[test]
+fn slash_division_warning_recommends_math_div() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("a {\n  color: (10px / 2);\n}\n".to_string()),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert!(stats.diagnostics[0]
+        .message
+        .contains("Recommendation: math.div(10px, 2) or calc(10px / 2)"));
+}
+
+#[test]
+fn slash_separator_is_not_deprecated() {
+    assert_eq!(0, slash_division_warnings("a {\n  font: 10px/8px;\n}\n"));
+    assert_eq!(0, slash_division_warnings("a {\n  color: (a / b);\n}\n"));
+    assert_eq!(
+        0,
+        slash_division_warnings("@use 'sass:math';\na {\n  color: math.div(10px, 2);\n}\n")
+    );
+}
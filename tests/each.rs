@@ -99,3 +99,59 @@ error!(
     missing_closing_curly_brace,
     "@each $i in 1 {", "Error: expected \"}\"."
 );
+test!(
+    each_two_vars_in_multi_element_map,
+    "a {\n  @each $key, $value in (a: 1, b: 2) {\n    #{$key}: $value;\n  }\n}\n",
+    "a {\n  a: 1;\n  b: 2;\n}\n"
+);
+test!(
+    each_three_vars_in_map_third_is_null,
+    "a {\n  @each $key, $value, $extra in (a: b) {\n    color: $key $value inspect($extra);\n  }\n}\n",
+    "a {\n  color: a b null;\n}\n"
+);
+test!(
+    each_destructure_ragged_list_of_lists,
+    "a {\n  @each $a, $b, $c in (1 2, 3 4 5 6) {\n    color: $a $b inspect($c);\n  }\n}\n",
+    "a {\n  color: 1 2 null;\n  color: 3 4 5;\n}\n"
+);
+test!(
+    each_destructure_comma_separated_inner_lists,
+    "a {\n  @each $a, $b in ((1, 2), (3, 4)) {\n    color: $a $b;\n  }\n}\n",
+    "a {\n  color: 1 2;\n  color: 3 4;\n}\n"
+);
+test!(
+    each_destructure_map_value_is_list,
+    "a {\n  @each $key, $value in (a: (1 2)) {\n    color: $key inspect($value);\n  }\n}\n",
+    "a {\n  color: a 1 2;\n}\n"
+);
+test!(
+    each_destructure_arglist,
+    "@mixin foo($args...) {\n  @each $a, $b in $args {\n    color: $a $b;\n  }\n}\n\na {\n  @include foo(1 2, 3 4);\n}\n",
+    "a {\n  color: 1 2;\n  color: 3 4;\n}\n"
+);
+test!(
+    each_destructure_inside_function,
+    "@function sum($map) {\n  $sum: 0;\n  @each $key, $value in $map {\n    $sum: $sum + $value;\n  }\n  @return $sum;\n}\n\na {\n  color: sum((a: 1, b: 2, c: 3));\n}\n",
+    "a {\n  color: 6;\n}\n"
+);
+test!(
+    each_map_preserves_key_types,
+    "$map: (1px: a, \"b\": c, red: d, (1 2): e);
+    @each $key, $value in $map {
+        a {
+            type: type-of($key);
+            key: inspect($key);
+            value: map-get($map, $key);
+        }
+    }",
+    "a {\n  type: number;\n  key: 1px;\n  value: a;\n}\n\na {\n  type: string;\n  key: \"b\";\n  value: c;\n}\n\na {\n  type: color;\n  key: red;\n  value: d;\n}\n\na {\n  type: list;\n  key: 1 2;\n  value: e;\n}\n"
+);
+test!(
+    each_map_number_key_is_usable_as_number,
+    "@each $key, $value in (1px: a) {
+        a {
+            color: $key * 2;
+        }
+    }",
+    "a {\n  color: 2px;\n}\n"
+);
@@ -255,3 +255,40 @@ error!(
     unclosed_bracketed_list,
     "a { color: [a", "Error: expected \"]\"."
 );
+error!(unclosed_style_rule, "a {", "Error: expected \"}\".");
+error!(
+    unclosed_nested_style_rule,
+    "a { b { color: red; }", "Error: expected \"}\"."
+);
+error!(
+    unclosed_media_rule,
+    "@media screen { a { color: red; }", "Error: expected \"}\"."
+);
+error!(
+    unclosed_if_rule,
+    "@if true { a { color: red; }", "Error: expected \"}\"."
+);
+error!(
+    unmatched_closing_curly_brace,
+    "}", "Error: unmatched \"}\"."
+);
+error!(
+    unmatched_closing_curly_brace_after_style_rule,
+    "a { color: red; } }", "Error: unmatched \"}\"."
+);
+error!(
+    else_without_body,
+    "@if true {} @else", "Error: expected \"{\"."
+);
+error!(
+    charset_without_string,
+    "@charset", "Error: Expected string."
+);
+error!(
+    charset_with_unquoted_string,
+    "@charset utf-8;", "Error: Expected string."
+);
+error!(
+    forward_is_not_supported,
+    "@forward \"foo\";", "Error: @forward rules are not yet supported."
+);
@@ -0,0 +1,211 @@
+use std::io::Write;
+
+use grass::{SassValue, Separator};
+
+#[macro_use]
+mod macros;
+
+fn exports(input: &str) -> grass::Exports {
+    grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().exports(true),
+    )
+    .expect(input)
+    .exports
+    .unwrap()
+}
+
+fn number(value: f64, unit: &str) -> SassValue {
+    SassValue::Number {
+        value,
+        unit: unit.to_string(),
+    }
+}
+
+fn unquoted(text: &str) -> SassValue {
+    SassValue::String {
+        text: text.to_string(),
+        quoted: false,
+    }
+}
+
+#[test]
+fn root_variables() {
+    let exports =
+        exports("$a: 1px + 2px;\n$b: \"foo\";\n$c: null;\n$d: true;\n$e: (1/4);\n$f: 1px * 1px;\n");
+
+    assert_eq!(
+        vec![
+            ("a", number(3.0, "px")),
+            (
+                "b",
+                SassValue::String {
+                    text: "foo".to_string(),
+                    quoted: true
+                }
+            ),
+            ("c", SassValue::Null),
+            ("d", SassValue::Bool(true)),
+            ("e", number(0.25, "")),
+            ("f", number(1.0, "px*px")),
+        ],
+        exports
+            .root
+            .variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert!(exports.modules.is_empty());
+}
+
+#[test]
+fn final_value_of_reassigned_variable() {
+    let exports = exports("$a: 1;\na {\n  $a: 2 !global;\n  color: $a;\n}\n");
+    assert_eq!(number(2.0, ""), exports.root.variables["a"]);
+}
+
+#[test]
+fn local_variables_are_not_exported() {
+    let exports = exports("a {\n  $a: 1;\n  color: $a;\n}\n");
+    assert!(exports.root.variables.is_empty());
+}
+
+#[test]
+fn private_variables_are_not_exported() {
+    let exports = exports("$-a: 1;\n$_b: 2;\n$c_d: 3;\n");
+    assert_eq!(
+        vec!["c-d"],
+        exports.root.variables.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn map_and_list() {
+    let exports = exports("$scale: (small: 4px, large: [8px 16px]);\n$list: 1, 2;\n");
+
+    assert_eq!(
+        SassValue::Map(vec![
+            (unquoted("small"), number(4.0, "px")),
+            (
+                unquoted("large"),
+                SassValue::List {
+                    items: vec![number(8.0, "px"), number(16.0, "px")],
+                    separator: Separator::Space,
+                    bracketed: true,
+                }
+            ),
+        ]),
+        exports.root.variables["scale"]
+    );
+    assert_eq!(
+        SassValue::List {
+            items: vec![number(1.0, ""), number(2.0, "")],
+            separator: Separator::Comma,
+            bracketed: false,
+        },
+        exports.root.variables["list"]
+    );
+}
+
+#[test]
+fn color() {
+    let exports = exports("$a: rgba(255, 0, 51, 0.5);\n");
+    assert_eq!(
+        SassValue::Color {
+            red: 255.0,
+            green: 0.0,
+            blue: 51.0,
+            alpha: 0.5,
+        },
+        exports.root.variables["a"]
+    );
+}
+
+#[test]
+fn function_reference() {
+    let exports = exports("$a: get-function(\"lighten\");\n");
+    assert_eq!(
+        SassValue::Function("lighten".to_string()),
+        exports.root.variables["a"]
+    );
+}
+
+#[test]
+fn mixin_reference() {
+    let exports = exports("@use \"sass:meta\";\n@mixin foo {}\n$a: meta.get-mixin(foo);\n");
+    assert_eq!(
+        SassValue::Mixin("foo".to_string()),
+        exports.root.variables["a"]
+    );
+}
+
+#[test]
+fn used_module() {
+    let input = "@use \"exports-used-module\";\n";
+    tempfile!(
+        "exports-used-module.scss",
+        "$spacing: 4px;\n$-private: 1;\n$scale: $spacing * 2;\n"
+    );
+
+    let exports = exports(input);
+
+    assert!(exports.root.variables.is_empty());
+    assert_eq!(
+        vec!["exports-used-module"],
+        exports.modules.keys().collect::<Vec<_>>()
+    );
+    let module = &exports.modules["exports-used-module"];
+    assert_eq!(
+        vec![("scale", number(8.0, "px")), ("spacing", number(4.0, "px")),],
+        module
+            .variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn used_module_with_configuration_and_alias() {
+    let input = "@use \"exports_configured_module\" as tokens with ($base: 2px);\n";
+    tempfile!(
+        "exports_configured_module.scss",
+        "$base: 1px !default;\n$double: $base * 2;\n"
+    );
+
+    let exports = exports(input);
+
+    let module = &exports.modules["tokens"];
+    assert_eq!(number(2.0, "px"), module.variables["base"]);
+    assert_eq!(number(4.0, "px"), module.variables["double"]);
+}
+
+#[test]
+fn module_variable_assigned_by_root() {
+    let input = "@use \"exports_assigned_module\" as tokens;\ntokens.$a: 2;\n";
+    tempfile!("exports_assigned_module.scss", "$a: 1;\n");
+
+    let exports = exports(input);
+
+    assert_eq!(number(2.0, ""), exports.modules["tokens"].variables["a"]);
+}
+
+#[test]
+fn module_used_without_namespace_is_in_root() {
+    let input = "@use \"exports_star_module\" as *;\n";
+    tempfile!("exports_star_module.scss", "$a: 1;\n");
+
+    let exports = exports(input);
+
+    assert!(exports.modules.is_empty());
+    assert_eq!(number(1.0, ""), exports.root.variables["a"]);
+}
+
+#[test]
+fn builtin_modules_are_not_exported() {
+    let exports = exports("@use \"sass:math\";\n$a: math.$pi;\n");
+    assert!(exports.modules.is_empty());
+    assert!(exports.root.variables.contains_key("a"));
+}
@@ -483,6 +483,13 @@ test!(
     ",
     "-a [foo=bar].baz, -a [foo=bar][ns|foo=bar] {\n  a: b;\n}\n"
 );
+test!(
+    attribute_quoted_value_matches_unquoted,
+    "[foo=\"bar\"] {a: b}
+    .baz {@extend [foo=bar]}
+    ",
+    "[foo=bar], .baz {\n  a: b;\n}\n"
+);
 test!(
     attribute_unification_5,
     "%-a %-a [foo=bar].bar {a: b}
@@ -1394,13 +1401,48 @@ test!(
     "@media screen {\n  @unknown {\n    .foo, .bar {\n      a: b;\n    }\n  }\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_media_queries,
     "@media screen {.foo {a: b}}
     @media screen {.bar {@extend .foo}}
     ",
     "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
 );
+test!(
+    extend_from_outside_media_into_media,
+    "@media screen {.foo {a: b}}
+    .bar {@extend .foo}
+    ",
+    "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+error!(
+    extend_from_media_into_top_level,
+    ".foo {a: b}\n@media screen {\n  .bar {@extend .foo}\n}\n",
+    "Error: From line 1, column 1 of stdin: "
+);
+error!(
+    extend_from_media_into_later_top_level,
+    "@media screen {\n  .bar {@extend .foo}\n}\n.foo {a: b}\n",
+    "Error: From line 4, column 1 of stdin: "
+);
+error!(
+    extend_from_media_into_different_media,
+    "@media screen {.foo {a: b}}\n@media print {.bar {@extend .foo}}\n",
+    "Error: From line 1, column 16 of stdin: "
+);
+#[test]
+fn extend_across_media_queries_reports_extend() {
+    match grass::from_string(
+        ".foo {a: b}\n@media screen {\n  .bar {@extend .foo}\n}\n".to_string(),
+        &grass::Options::default(),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => {
+            let e = e.to_string();
+            assert!(e.contains("\nYou may not @extend selectors across media queries.\n"));
+            assert!(e.ends_with("./stdin:3:9\n"));
+        }
+    }
+}
 test!(
     #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_unknown_at_rules,
@@ -1563,7 +1605,6 @@ test!(
     ".parent1 .child {\n  a: b;\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_inside_double_nested_media,
     "@media all {
         @media (orientation: landscape) {
@@ -1895,5 +1936,129 @@ error!(
     "Error: Parent selectors aren't allowed here."
 );
 
+error!(
+    extend_target_not_found,
+    ".x {\n  @extend .y;\n}\n", "Error: The target selector was not found."
+);
+error!(
+    extend_placeholder_target_not_found,
+    ".x {\n  @extend %y;\n}\n", "Error: The target selector was not found."
+);
+error!(
+    extend_target_not_found_in_media,
+    "@media screen {\n  .x {\n    @extend .y;\n  }\n}\n",
+    "Error: The target selector was not found."
+);
+error!(
+    extend_target_not_found_mandatory_after_optional,
+    ".x {\n  @extend .y !optional;\n}\n.z {\n  @extend .y;\n}\n",
+    "Error: The target selector was not found."
+);
+error!(
+    extend_target_not_found_same_extension_optional_and_mandatory,
+    ".x {\n  @extend .y;\n  @extend .y !optional;\n}\n",
+    "Error: The target selector was not found."
+);
+test!(
+    extend_optional_target_not_found,
+    ".x {\n  @extend .y !optional;\n}\n",
+    ""
+);
+test!(
+    extend_optional_list_target_not_found,
+    ".x {\n  @extend .y, .z !optional;\n}\n",
+    ""
+);
+test!(
+    extend_target_defined_after_extend,
+    ".x {\n  @extend .y;\n}\n.y {\n  a: b;\n}\n",
+    ".y, .x {\n  a: b;\n}\n"
+);
+test!(
+    nested_rule_in_unextended_placeholder,
+    "%a {\n  b { c: d; }\n}\n",
+    ""
+);
+test!(
+    extend_suffixed_placeholder,
+    "%a {\n  &-b { c: d; }\n}\n.e {\n  @extend %a-b;\n}\n",
+    ".e {\n  c: d;\n}\n"
+);
+test!(
+    extend_suffixed_placeholder_nested_in_class,
+    "a {\n  %b {\n    &-c { d: e; }\n  }\n}\n.f {\n  @extend %b-c;\n}\n",
+    "a .f {\n  d: e;\n}\n"
+);
+test!(
+    extend_selector_of_styles_in_media,
+    ".a {\n  @media screen { b: c; }\n}\n.d {\n  @extend .a;\n}\n",
+    "@media screen {\n  .a, .d {\n    b: c;\n  }\n}\n"
+);
+test!(
+    extend_placeholder_with_styles_in_media,
+    "%a {\n  @media screen { b: c; }\n}\n.d {\n  @extend %a;\n}\n",
+    "@media screen {\n  .d {\n    b: c;\n  }\n}\n"
+);
+test!(
+    extend_selector_of_styles_in_supports,
+    ".a {\n  @supports (b: c) { d: e; }\n}\n.f {\n  @extend .a;\n}\n",
+    "@supports (b: c) {\n  .a, .f {\n    d: e;\n  }\n}\n"
+);
+test!(
+    extend_selector_of_styles_in_unknown_at_rule,
+    ".a {\n  @foo { b: c; }\n}\n.d {\n  @extend .a;\n}\n",
+    "@foo {\n  .a, .d {\n    b: c;\n  }\n}\n"
+);
+#[test]
+fn extend_target_not_found_suggests_optional() {
+    match grass::from_string(".x { @extend .y; }".to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .contains("Use \"@extend .y !optional\" to avoid this error.")),
+    }
+}
+
+test!(
+    extend_inside_is,
+    ":is(.a, .b) .c {\n  x: y;\n}\n\n.d {\n  @extend .b;\n}\n",
+    ":is(.a, .b, .d) .c {\n  x: y;\n}\n"
+);
+test!(
+    extend_inside_where,
+    ":where(.a) {\n  x: y;\n}\n\n.d {\n  @extend .a;\n}\n",
+    ":where(.a, .d) {\n  x: y;\n}\n"
+);
+test!(
+    extend_inside_nth_child_of,
+    ":nth-child(2n+1 of .a) {\n  x: y;\n}\n\n.b {\n  @extend .a;\n}\n",
+    ":nth-child(2n+1 of .a, .b) {\n  x: y;\n}\n"
+);
+test!(
+    placeholder_inside_nth_child_of,
+    ":nth-child(2n of %a, .b) {\n  x: y;\n}\n\n:nth-child(2n of %a) {\n  x: y;\n}\n",
+    ":nth-child(2n of .b) {\n  x: y;\n}\n"
+);
+test!(
+    placeholder_inside_where,
+    ":where(%a, .b) {\n  x: y;\n}\n\n:is(%a) {\n  x: y;\n}\n",
+    ":where(.b) {\n  x: y;\n}\n"
+);
+test!(
+    extend_column_combinator,
+    ".c || .d {\n  x: y;\n}\n\n.e {\n  @extend .d;\n}\n",
+    ".c || .d, .c || .e {\n  x: y;\n}\n"
+);
+test!(
+    column_combinator_extender_is_not_woven_into_child,
+    ".a > .b {\n  x: y;\n}\n\n.c || .d {\n  @extend .b;\n}\n",
+    ".a > .b {\n  x: y;\n}\n"
+);
+test!(
+    child_extender_is_not_woven_into_column_combinator,
+    ".a || .b {\n  x: y;\n}\n\n.c > .d {\n  @extend .b;\n}\n",
+    ".a || .b {\n  x: y;\n}\n"
+);
+
 // todo: extend_loop (massive test)
 // todo: extend tests in folders
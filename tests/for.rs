@@ -168,3 +168,41 @@ error!(
     to_and_from_i32_min,
     "@for $i from -2147483648 through -2147483648 {}", "Error: -2147483648 is not an int."
 );
+test!(
+    bounds_with_same_unit,
+    "a {\n  @for $i from 1px through 3px {\n    color: $i;\n  }\n}\n",
+    "a {\n  color: 1px;\n  color: 2px;\n  color: 3px;\n}\n"
+);
+test!(
+    unit_of_from_used_when_to_is_unitless,
+    "a {\n  @for $i from 1px through 2 {\n    color: $i;\n  }\n}\n",
+    "a {\n  color: 1px;\n  color: 2px;\n}\n"
+);
+test!(
+    to_converted_to_unit_of_from,
+    "a {\n  @for $i from 95px through 1in {\n    color: $i;\n  }\n}\n",
+    "a {\n  color: 95px;\n  color: 96px;\n}\n"
+);
+test!(
+    negative_bounds_descending,
+    "a {\n  @for $i from -1 through -3 {\n    color: $i;\n  }\n}\n",
+    "a {\n  color: -1;\n  color: -2;\n  color: -3;\n}\n"
+);
+test!(
+    bounds_are_expressions,
+    "$n: 2;\na {\n  @for $i from $n - 5 to $n * 2 - 3 {\n    color: $i;\n  }\n}\n",
+    "a {\n  color: -3;\n  color: -2;\n  color: -1;\n  color: 0;\n}\n"
+);
+test!(
+    variable_declaration_without_semicolon_at_end_of_body,
+    "a {\n  $b: 0;\n  @for $i from 1 through 2 {\n    $b: $i\n  }\n  color: $b;\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+error!(
+    incompatible_units,
+    "@for $i from 1px to 3em {}", "Error: Incompatible units em and px."
+);
+error!(
+    to_is_not_a_number,
+    "@for $i from 1 through \"a\" {}", "Error: \"a\" is not a number."
+);
@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use grass::{MemoryFs, Options};
+
+fn fs(files: &[(&str, &str)]) -> MemoryFs {
+    let mut fs = MemoryFs::default();
+    for (path, contents) in files {
+        fs.add_file(path, *contents);
+    }
+    fs
+}
+
+#[test]
+fn entry_file_is_read_from_fs() {
+    let fs = fs(&[("virtual/input.scss", "a { b { color: red; } }")]);
+    assert_eq!(
+        "a b {\n  color: red;\n}\n",
+        grass::from_path("virtual/input.scss", &Options::default().fs(&fs)).unwrap()
+    );
+}
+
+#[test]
+fn imports_are_read_from_fs() {
+    let fs = fs(&[
+        ("input.scss", "@import \"lib/a\";\n@use \"lib/b\";\n"),
+        ("lib/_a.scss", "a { color: red; }"),
+        ("lib/b/_index.scss", "b { color: blue; }"),
+    ]);
+    let input = "@use \"lib/b\";\n@import \"lib/a\";\n";
+    assert_eq!(
+        "b {\n  color: blue;\n}\n\na {\n  color: red;\n}\n",
+        grass::from_string(input.to_string(), &Options::default().fs(&fs)).unwrap()
+    );
+}
+
+#[test]
+fn relative_to_importing_file() {
+    let fs = fs(&[
+        ("src/input.scss", "@import \"../lib/a\";"),
+        ("lib/_a.scss", "@import \"b\";"),
+        ("lib/b.scss", "a { color: red; }"),
+    ]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_path("src/input.scss", &Options::default().fs(&fs)).unwrap()
+    );
+}
+
+#[test]
+fn load_paths_are_looked_up_in_fs() {
+    let fs = fs(&[("node_modules/lib/_colors.scss", "$primary: red;")]);
+    let input = "@use \"colors\";\na { color: colors.$primary; }\n";
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            input.to_string(),
+            &Options::default()
+                .fs(&fs)
+                .load_path(Path::new("node_modules/lib"))
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn missing_file() {
+    let fs = fs(&[]);
+    match grass::from_string("@import \"a\";".to_string(), &Options::default().fs(&fs)) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Can't find stylesheet to import.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn ambiguous_import() {
+    let fs = fs(&[("_a.scss", ""), ("a.scss", "")]);
+    match grass::from_string("@import \"a\";".to_string(), &Options::default().fs(&fs)) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: It's not clear which file to import. Found:",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn import_cycle() {
+    let fs = fs(&[("a.scss", "@import \"./b\";"), ("b.scss", "@import \"a\";")]);
+    match grass::from_path("a.scss", &Options::default().fs(&fs)) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded: a.scss → ./b.scss → a.scss",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn import_roots_are_looked_up_in_fs() {
+    let fs = fs(&[("allowed/a.scss", "a { color: red; }"), ("b.scss", "")]);
+    let options = Options::default()
+        .fs(&fs)
+        .import_root(Path::new("./allowed"));
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string("@import \"allowed/a\";".to_string(), &options).unwrap()
+    );
+    assert!(grass::from_string("@import \"b\";".to_string(), &options).is_err());
+}
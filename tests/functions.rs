@@ -339,3 +339,92 @@ test!(
     }",
     "a {\n  color: red;\n}\n"
 );
+test!(
+    return_from_nested_control_flow,
+    "@function foo($a, $b: 2) {
+        @if $a > 1 {
+            @each $x in 1 2 3 {
+                @if $x == 2 {
+                    @return $x + $b;
+                }
+            }
+        }
+        @return 0;
+    }
+
+    a {
+        color: foo($b: 5, $a: 3);
+    }",
+    "a {\n  color: 7;\n}\n"
+);
+test!(
+    default_arg_references_earlier_arg,
+    "@function foo($a, $b: $a * 2) {
+        @return $b;
+    }
+
+    a {
+        color: foo(3);
+    }",
+    "a {\n  color: 6;\n}\n"
+);
+error!(
+    too_many_positional_args,
+    "@function foo($a) {
+        @return $a;
+    }
+
+    a {
+        color: foo(1, 2);
+    }",
+    "Error: Only 1 argument allowed, but 2 were passed."
+);
+error!(
+    unknown_named_arg,
+    "@function foo($a) {
+        @return $a;
+    }
+
+    a {
+        color: foo(1, $b: 2);
+    }",
+    "Error: No argument named $b."
+);
+error!(
+    multiple_unknown_named_args,
+    "@function foo($a) {
+        @return $a;
+    }
+
+    a {
+        color: foo(1, $d: 2, $b: 2, $c: 2);
+    }",
+    "Error: No arguments named $b, $c or $d."
+);
+error!(
+    arg_passed_by_position_and_name,
+    "@function foo($a) {
+        @return $a;
+    }
+
+    a {
+        color: foo(1, $a: 2);
+    }",
+    "Error: Argument $a was passed both by position and by name."
+);
+error!(
+    media_inside_function,
+    "@function foo() {
+        @media screen {
+            a {
+                color: red;
+            }
+        }
+        @return 1;
+    }
+
+    a {
+        color: foo();
+    }",
+    "Error: This at-rule is not allowed here."
+);
@@ -124,3 +124,67 @@ test!(
     "@use 'sass:math';\na {\n  color: call(get-function(cos, $module: math), 2);\n}\n",
     "a {\n  color: -0.4161468365;\n}\n"
 );
+test!(
+    get_function_css_is_called_as_plain_css,
+    "a {\n  color: call(get-function(foo, $css: true), 1, 2px);\n}\n",
+    "a {\n  color: foo(1, 2px);\n}\n"
+);
+test!(
+    get_function_css_ignores_sass_function,
+    "a {\n  color: call(get-function(rgb, $css: true), 1, 2, 3);\n}\n",
+    "a {\n  color: rgb(1, 2, 3);\n}\n"
+);
+test!(
+    get_function_css_inspect,
+    "a {\n  color: inspect(get-function(foo, $css: true));\n}\n",
+    "a {\n  color: get-function(\"foo\");\n}\n"
+);
+error!(
+    get_function_css_keyword_argument,
+    "a {\n  color: call(get-function(foo, $css: true), $a: 1);\n}\n",
+    "Error: Plain CSS functions don't support keyword arguments."
+);
+error!(
+    get_function_css_and_module,
+    "@use 'sass:math';\na {\n  color: get-function(cos, $css: true, $module: math);\n}\n",
+    "Error: $css and $module may not both be passed at once."
+);
+error!(
+    get_function_of_module_not_found,
+    "@use 'sass:math';\na {\n  color: get-function(foo, $module: math);\n}\n",
+    "Error: Function not found: foo"
+);
+test!(
+    call_string_builtin,
+    "a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n",
+    "a {\n  color: #010203;\n}\n"
+);
+test!(
+    call_string_user_defined,
+    "@function foo($a) {\n  @return $a * 2;\n}\na {\n  color: call(foo, 2px);\n}\n",
+    "a {\n  color: 4px;\n}\n"
+);
+test!(
+    call_string_plain_css,
+    "a {\n  color: call(foo, 2px);\n}\n",
+    "a {\n  color: foo(2px);\n}\n"
+);
+
+#[test]
+fn call_string_is_deprecated() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n".to_string()),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+
+    assert_eq!(1, stats.diagnostics.len());
+    assert_eq!("deprecated-call-string", stats.diagnostics[0].code);
+    assert_eq!(
+        "Passing a string to call() is deprecated and will be illegal in Dart Sass 2.0.0.\n\nRecommendation: call(get-function(\"rgb\"))",
+        stats.diagnostics[0].message
+    );
+}
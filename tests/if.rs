@@ -204,3 +204,46 @@ error!(
     invalid_toplevel_selector,
     "@if true { & { } }", "Error: Top-level selectors may not contain the parent selector \"&\"."
 );
+test!(
+    else_if_uppercase_if,
+    "a {\n  @if false {\n    color: red;\n  } @else IF true {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    else_if_no_space_before_parens,
+    "a {\n  @if false {\n    color: red;\n  } @else if(true) {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    deprecated_elseif,
+    "a {\n  @if false {\n    color: red;\n  } @elseif true {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    deprecated_elseif_followed_by_else,
+    "a {\n  @if false {\n    color: red;\n  } @elseif false {\n    color: blue;\n  } @else {\n    color: green;\n  }\n}\n",
+    "a {\n  color: green;\n}\n"
+);
+test!(
+    only_false_and_null_are_falsey,
+    "a {\n  @if 0 {\n    zero: truthy;\n  }\n  @if \"\" {\n    empty-string: truthy;\n  }\n  @if () {\n    empty-list: truthy;\n  }\n  @if null {\n    null: truthy;\n  } @else if false {\n    false: truthy;\n  } @else {\n    color: falsey;\n  }\n}\n",
+    "a {\n  zero: truthy;\n  empty-string: truthy;\n  empty-list: truthy;\n  color: falsey;\n}\n"
+);
+test!(
+    assignment_in_branch_updates_variable_in_enclosing_block,
+    "a {\n  $a: red;\n  @if true {\n    $a: blue;\n  }\n  color: $a;\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+test!(
+    assignment_in_else_branch_updates_global_at_root,
+    "$a: red;\n@if false {} @else {\n  $a: blue;\n}\na {\n  color: $a;\n}\n",
+    "a {\n  color: blue;\n}\n"
+);
+error!(
+    variable_declared_in_branch_is_not_visible_outside,
+    "a {\n  @if true {\n    $a: red;\n  }\n  color: $a;\n}\n", "Error: Undefined variable."
+);
+error!(
+    else_followed_by_non_if_identifier,
+    "@if false {} @else iffy {}", "Error: expected \"{\"."
+);
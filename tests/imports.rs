@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::{io::Write, path::Path};
 
 #[macro_use]
 mod macros;
@@ -85,7 +85,7 @@ fn comma_separated_import_trailing() {
     match grass::from_string(input.to_string(), &grass::Options::default()) {
         Ok(..) => panic!("did not fail"),
         Err(e) => assert_eq!(
-            "Error: Expected expression.",
+            "Error: Expected string.",
             e.to_string()
                 .chars()
                 .take_while(|c| *c != '\n')
@@ -146,6 +146,109 @@ fn chained_imports_in_directory() {
     );
 }
 
+#[test]
+fn finds_partial_index_in_directory() {
+    let input = "@import \"finds_partial_index_in_directory\";\na {\n color: $a;\n}";
+    tempfile!(
+        "_index.scss",
+        "$a: red;",
+        dir = "finds_partial_index_in_directory"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn finds_css_file() {
+    let input = "@import \"finds_css_file\";";
+    tempfile!("finds_css_file.css", "a { color: red; }");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn prefers_scss_file_to_css_file() {
+    let input = "@import \"prefers_scss_file_to_css_file\";";
+    tempfile!("prefers_scss_file_to_css_file.css", "a { color: red; }");
+    tempfile!("_prefers_scss_file_to_css_file.scss", "a { color: green; }");
+    assert_eq!(
+        "a {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn partial_and_non_partial_are_ambiguous() {
+    let input = "@import \"partial_and_non_partial_are_ambiguous\";";
+    tempfile!(
+        "partial_and_non_partial_are_ambiguous.scss",
+        "a { color: red; }"
+    );
+    tempfile!(
+        "_partial_and_non_partial_are_ambiguous.scss",
+        "a { color: red; }"
+    );
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: It's not clear which file to import. Found:\n  _partial_and_non_partial_are_ambiguous.scss\n  partial_and_non_partial_are_ambiguous.scss",
+            e.to_string().split("\n  ╷").next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn load_path_keeps_directories_of_url() {
+    tempfile!(
+        "input.scss",
+        "@import \"load_path_keeps_directories_of_url_lib/a\";\na {\n color: $a;\n}",
+        dir = "load_path_keeps_directories_of_url_input"
+    );
+    tempfile!(
+        "_a.scss",
+        "$a: red;",
+        dir = "load_path_keeps_directories_of_url_lib"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_path(
+            "load_path_keeps_directories_of_url_input/input.scss",
+            &grass::Options::default().load_path(Path::new("."))
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn nested_import_is_wrapped_in_parent_selector() {
+    let input = "a {\n  @import \"nested_import_is_wrapped_in_parent_selector\";\n}";
+    tempfile!(
+        "_nested_import_is_wrapped_in_parent_selector.scss",
+        "b { color: red; }\n& > c { color: blue; }\n@media print { d { color: green; } }"
+    );
+    assert_eq!(
+        "a b {\n  color: red;\n}\na > c {\n  color: blue;\n}\n@media print {\n  a d {\n    color: green;\n  }\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn nested_import_members_are_local() {
+    let input = "a {\n  @import \"nested_import_members_are_local\";\n  b: $a f();\n  @include m;\n}\nc {\n  d: variable-exists(a) function-exists(f) mixin-exists(m);\n}";
+    tempfile!(
+        "_nested_import_members_are_local.scss",
+        "$a: red;\n@function f() { @return 1; }\n@mixin m { e: f; }"
+    );
+    assert_eq!(
+        "a {\n  b: red 1;\n  e: f;\n}\n\nc {\n  d: false false false;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
 error!(
     missing_input_after_import,
     "@import", "Error: expected more input."
@@ -189,7 +292,6 @@ test!(
     "@import url(2..);\n"
 );
 test!(
-    #[ignore = "we currently place plain @import ahead of loud comments that precede it"]
     import_multiline_comments_everywhere,
     "  /**/  @import  /**/  url(foo)  /**/  ;",
     "/**/\n@import url(foo);\n"
@@ -208,7 +310,267 @@ test!(
     @import url(\"foo.css\");",
     "@import url(\"foo.css\");\na {\n  color: red;\n}\n"
 );
+test!(
+    plain_css_import_with_layer,
+    "@import \"foo.css\" layer;\n@import url(foo.css) layer(base);\n",
+    "@import \"foo.css\" layer;\n@import url(foo.css) layer(base);\n"
+);
+test!(
+    plain_css_import_with_supports_declaration,
+    "@import \"foo.css\" supports(display:grid);\n",
+    "@import \"foo.css\" supports(display: grid);\n"
+);
+test!(
+    plain_css_import_with_supports_condition,
+    "@import \"foo.css\" supports(not (display: grid));\n@import \"bar.css\" supports(selector(a > b));\n",
+    "@import \"foo.css\" supports(not (display: grid));\n@import \"bar.css\" supports(selector(a > b));\n"
+);
+test!(
+    plain_css_import_with_all_modifiers,
+    "@import \"foo.css\" layer(base) supports(display: grid) screen and (min-width: 100px);\n",
+    "@import \"foo.css\" layer(base) supports(display: grid) screen and (min-width: 100px);\n"
+);
+test!(
+    plain_css_import_with_media_query_list,
+    "@import \"foo.css\", \"bar.css\" print, (orientation: landscape);\n",
+    "@import \"foo.css\";\n@import \"bar.css\" print, (orientation: landscape);\n"
+);
+test!(
+    plain_css_import_modifiers_with_interpolation,
+    "$layer: base;\n$display: grid;\n@import \"foo.css\" layer(#{$layer}) supports(display: #{$display}) #{\"screen\"};\n",
+    "@import \"foo.css\" layer(base) supports(display: grid) screen;\n"
+);
+test!(
+    modifiers_make_import_plain_css,
+    "@import \"foo\" layer;\n",
+    "@import \"foo\" layer;\n"
+);
+test!(
+    plain_css_http_url,
+    "@import \"http://example.com/foo\";\n@import 'https://example.com/foo.scss';\n",
+    "@import \"http://example.com/foo\";\n@import \"https://example.com/foo.scss\";\n"
+);
+test!(
+    plain_css_url_is_case_insensitive,
+    "@import URL(foo);\n",
+    "@import url(foo);\n"
+);
+test!(
+    plain_css_url_with_media_query,
+    "@import url(foo) print and (orientation: landscape);\n",
+    "@import url(foo) print and (orientation: landscape);\n"
+);
+test!(
+    interpolated_string_is_plain_css,
+    "$name: foo;\n@import \"#{$name}\";\n",
+    "@import \"foo\";\n"
+);
+test!(
+    escaped_interpolation_is_not_plain_css,
+    "@import \"foo\\#{bar}.css\";\n",
+    "@import \"foo#{bar}.css\";\n"
+);
+test!(
+    plain_css_is_moved_after_leading_comments,
+    "/* a */\n@import \"a.css\";\n/* b */\na {\n  color: red;\n}\n@import \"b.css\";\n",
+    "/* a */\n@import \"a.css\";\n/* b */\n@import \"b.css\";\na {\n  color: red;\n}\n"
+);
+test!(
+    plain_css_in_style_rule_is_moved_to_top_of_file,
+    "a {\n  color: red;\n  @import \"foo.css\";\n}\n",
+    "@import \"foo.css\";\na {\n  color: red;\n}\n"
+);
+test!(
+    plain_css_is_moved_to_top_of_media_rule,
+    "@media screen {\n  a {\n    color: red;\n  }\n  @import url(foo);\n}\n",
+    "@media screen {\n  @import url(foo);\n  a {\n    color: red;\n  }\n}\n"
+);
+
+#[test]
+fn import_root_allows_file_inside_root() {
+    let input = "@import \"import_root_allows_file_inside_root_dir/a\";\na {\n color: $a;\n}";
+    tempfile!(
+        "a.scss",
+        "$a: red;",
+        dir = "import_root_allows_file_inside_root_dir"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &grass::Options::default()
+                .import_root(Path::new("import_root_allows_file_inside_root_dir"))
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn import_root_disallows_file_outside_root() {
+    let input = "@import \"import_root_disallows_file_outside_root\";";
+    tempfile!("import_root_disallows_file_outside_root", "$a: red;");
+    tempfile!(
+        "b.scss",
+        "",
+        dir = "import_root_disallows_file_outside_root_dir"
+    );
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default()
+            .import_root(Path::new("import_root_disallows_file_outside_root_dir")),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Can't import \"import_root_disallows_file_outside_root\": it is outside of the allowed import directories.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn empty_import_roots_disallow_all_imports() {
+    let input = "@import \"empty_import_roots_disallow_all_imports\";";
+    tempfile!("empty_import_roots_disallow_all_imports", "$a: red;");
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().import_roots(&[]),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Can't import \"empty_import_roots_disallow_all_imports\": it is outside of the allowed import directories.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn import_roots_do_not_restrict_plain_css_imports() {
+    let input = "@import \"foo.css\";";
+    assert_eq!(
+        "@import \"foo.css\";\n",
+        &grass::from_string(
+            input.to_string(),
+            &grass::Options::default().import_roots(&[])
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn import_cycle() {
+    let input = "@import \"import_cycle_a\";";
+    tempfile!("import_cycle_a.scss", "@import \"import_cycle_b\";");
+    tempfile!(
+        "import_cycle_b.scss",
+        "a {\n  color: red;\n}\n@import \"import_cycle_a\";"
+    );
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded: import_cycle_a.scss → import_cycle_b.scss → import_cycle_a.scss\n  \
+             import_cycle_a.scss 1:9  loads import_cycle_b.scss\n  \
+             import_cycle_b.scss 4:9  loads import_cycle_a.scss",
+            e.to_string().lines().take(3).collect::<Vec<&str>>().join("\n")
+        ),
+    }
+}
+
+#[test]
+fn import_cycle_of_self() {
+    let input = "@import \"import_cycle_of_self\";";
+    tempfile!(
+        "import_cycle_of_self.scss",
+        "@import \"import_cycle_of_self\";"
+    );
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded: import_cycle_of_self.scss → import_cycle_of_self.scss",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn import_cycle_ascii_arrow() {
+    let input = "@import \"import_cycle_ascii_arrow\";";
+    tempfile!(
+        "import_cycle_ascii_arrow.scss",
+        "@import \"import_cycle_ascii_arrow\";"
+    );
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().unicode_error_messages(false),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded: import_cycle_ascii_arrow.scss -> import_cycle_ascii_arrow.scss",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn import_cycle_of_entrypoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.scss");
+    let b = dir.path().join("b.scss");
+    std::fs::write(&a, "@import \"b\";").unwrap();
+    std::fs::write(&b, "@import \"a\";").unwrap();
+    match grass::from_path(a.to_str().unwrap(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            format!(
+                "Error: This file is already being loaded: {a} → {b} → {a}",
+                a = a.display(),
+                b = b.display()
+            ),
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn same_file_imported_twice_is_not_cycle() {
+    let input = "@import \"same_file_imported_twice_is_not_cycle_a\";\n@import \"same_file_imported_twice_is_not_cycle_b\";";
+    tempfile!(
+        "same_file_imported_twice_is_not_cycle_a.scss",
+        "@import \"same_file_imported_twice_is_not_cycle_b\";"
+    );
+    tempfile!(
+        "same_file_imported_twice_is_not_cycle_b.scss",
+        "a {\n  color: red;\n}"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n\na {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn stats_record_loaded_paths() {
+    let input = "@use \"stats_record_loaded_paths_b\";\n@import \"stats_record_loaded_paths_a\";";
+    tempfile!(
+        "stats_record_loaded_paths_a.scss",
+        "@import \"stats_record_loaded_paths_b\";"
+    );
+    tempfile!("_stats_record_loaded_paths_b.scss", "a {\n  color: red;\n}");
+    let stats = grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().stats(true),
+    )
+    .expect(input)
+    .stats
+    .unwrap();
+    assert_eq!(
+        vec![
+            Path::new("_stats_record_loaded_paths_b.scss"),
+            Path::new("stats_record_loaded_paths_a.scss"),
+        ],
+        stats.loaded_paths.iter().collect::<Vec<_>>()
+    );
+}
 
-// todo: edge case tests for plain css imports moved to top
 // todo: test for calling paths, e.g. `grass b\index.scss`
 // todo: test for absolute paths (how?)
@@ -0,0 +1,182 @@
+use grass::{Options, Syntax};
+
+fn indented(input: &str) -> grass::Result<String> {
+    grass::compile_string(
+        input.to_string(),
+        &Options::default().syntax(Syntax::Indented),
+    )
+}
+
+#[test]
+fn nested_rules() {
+    assert_eq!(
+        "a {\n  color: red;\n}\na b {\n  color: blue;\n}\n",
+        indented("a\n  color: red\n  b\n    color: blue\n").unwrap()
+    );
+}
+
+#[test]
+fn dedent_closes_several_blocks() {
+    assert_eq!(
+        "a b c {\n  color: red;\n}\n\nd {\n  color: blue;\n}\n",
+        indented("a\n  b\n    c\n      color: red\nd\n  color: blue\n").unwrap()
+    );
+}
+
+#[test]
+fn tabs() {
+    assert_eq!(
+        "a b {\n  color: red;\n}\n",
+        indented("a\n\tb\n\t\tcolor: red\n").unwrap()
+    );
+}
+
+#[test]
+fn blank_lines() {
+    assert_eq!(
+        "a {\n  color: red;\n  width: 1px;\n}\n",
+        indented("a\n\n  color: red\n\n\n  width: 1px\n\n").unwrap()
+    );
+}
+
+#[test]
+fn variables() {
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        indented("$color: red\na\n  color: $color\n").unwrap()
+    );
+}
+
+#[test]
+fn mixin_shorthand() {
+    assert_eq!(
+        "a {\n  border: 2px solid;\n}\n",
+        indented("=bordered($width: 1px)\n  border: $width solid\na\n  +bordered(2px)\n").unwrap()
+    );
+}
+
+#[test]
+fn content_block() {
+    assert_eq!(
+        "a b {\n  color: red;\n}\n",
+        indented("=nest\n  b\n    @content\na\n  +nest\n    color: red\n").unwrap()
+    );
+}
+
+#[test]
+fn control_flow() {
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        indented("a\n  @if false\n    color: red\n  @else\n    color: blue\n").unwrap()
+    );
+}
+
+#[test]
+fn each() {
+    assert_eq!(
+        ".a {\n  width: 1px;\n}\n\n.b {\n  width: 2px;\n}\n",
+        indented("@each $name, $width in (a: 1px, b: 2px)\n  .#{$name}\n    width: $width\n")
+            .unwrap()
+    );
+}
+
+#[test]
+fn multiline_selector_list() {
+    assert_eq!(
+        "a,\nb {\n  color: red;\n}\n",
+        indented("a,\nb\n  color: red\n").unwrap()
+    );
+}
+
+#[test]
+fn multiline_parens() {
+    assert_eq!(
+        "a {\n  color: 1 2;\n}\n",
+        indented("$map: (\n  a: 1,\n  b: 2\n)\na\n  color: map-get($map, a) map-get($map, b)\n")
+            .unwrap()
+    );
+}
+
+#[test]
+fn nested_properties() {
+    assert_eq!(
+        "a {\n  font-weight: bold;\n  font-size: 1px;\n}\n",
+        indented("a\n  font:\n    weight: bold\n    size: 1px\n").unwrap()
+    );
+}
+
+#[test]
+fn silent_comments() {
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        indented("// a comment\n  that continues\na\n  color: red // trailing\n").unwrap()
+    );
+}
+
+#[test]
+fn silent_comment_in_url_is_kept() {
+    assert_eq!(
+        "a {\n  background: url(http://example.com/a.png);\n}\n",
+        indented("a\n  background: url(http://example.com/a.png)\n").unwrap()
+    );
+}
+
+#[test]
+fn silent_comment_in_string_is_kept() {
+    assert_eq!(
+        "a {\n  content: \"a // b\";\n}\n",
+        indented("a\n  content: \"a // b\"\n").unwrap()
+    );
+}
+
+#[test]
+fn loud_comment() {
+    assert_eq!(
+        "/* a comment\n * that continues */\na {\n  color: red;\n}\n",
+        indented("/* a comment\n   that continues\na\n  color: red\n").unwrap()
+    );
+}
+
+#[test]
+fn loud_comment_already_closed() {
+    assert_eq!(
+        "/* a comment */\na {\n  color: red;\n}\n",
+        indented("/* a comment */\na\n  color: red\n").unwrap()
+    );
+}
+
+#[test]
+fn media() {
+    assert_eq!(
+        "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+        indented("@media screen\n  a\n    color: red\n").unwrap()
+    );
+}
+
+#[test]
+fn unquoted_import_of_css() {
+    assert_eq!(
+        "@import \"foo.css\";\n@import url(bar.css);\n",
+        indented("@import foo.css, url(bar.css)\n").unwrap()
+    );
+}
+
+#[test]
+fn indented_first_line() {
+    assert_eq!(
+        "Error: Indenting at the beginning of the document is illegal.",
+        indented("  a\n    color: red\n")
+            .unwrap_err()
+            .to_string()
+            .lines()
+            .next()
+            .unwrap()
+    );
+}
+
+#[test]
+fn error_points_into_source() {
+    let err = indented("a\n  color: (1 +)\n").unwrap_err();
+    let range = err.diagnostic().range.unwrap();
+    assert_eq!(2, range.start.line);
+}
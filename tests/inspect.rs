@@ -129,3 +129,23 @@ test!(
     }",
     "a {\n  color: ((a: b),);\n}\n"
 );
+test!(
+    inspect_space_list_in_space_list,
+    "a {\n  color: inspect((a b) c);\n}\n",
+    "a {\n  color: (a b) c;\n}\n"
+);
+test!(
+    inspect_comma_list_in_space_list,
+    "a {\n  color: inspect(((a, b) c, d));\n}\n",
+    "a {\n  color: (a, b) c, d;\n}\n"
+);
+test!(
+    inspect_comma_list_in_single_element_comma_list,
+    "a {\n  color: inspect(((a, b),));\n}\n",
+    "a {\n  color: ((a, b),);\n}\n"
+);
+test!(
+    inspect_bracketed_list_in_space_list,
+    "a {\n  color: inspect([a b] c);\n}\n",
+    "a {\n  color: [a b] c;\n}\n"
+);
@@ -72,7 +72,6 @@ test!(
     "a {\n  color: foo(a, 3, c);\n}\n"
 );
 test!(
-    #[ignore = "we evaluate interpolation eagerly"]
     interpolated_builtin_fn,
     "a {\n  color: uni#{t}less(1px);\n}\n",
     "a {\n  color: unitless(1px);\n}\n"
@@ -81,3 +80,91 @@ error!(
     error_message_when_at_start_of_value,
     "a {\n  color: #{2px*5px};\n}\n", "Error: 10px*px isn't a valid CSS value."
 );
+test!(
+    selector_is_reparsed,
+    "#{\".a, .b\"} {\n  c: d;\n}\n",
+    ".a, .b {\n  c: d;\n}\n"
+);
+test!(
+    selector_combinator_is_reparsed,
+    ".a {\n  #{\"> .b\"} {\n    c: d;\n  }\n}\n",
+    ".a > .b {\n  c: d;\n}\n"
+);
+test!(
+    parent_selector_is_reparsed,
+    ".a {\n  #{\"& &\"} {\n    c: d;\n  }\n}\n",
+    ".a .a {\n  c: d;\n}\n"
+);
+test!(
+    media_query_is_reparsed,
+    "@media #{\"screen and (min-width: 100px)\"} {\n  a {\n    b: c;\n  }\n}\n",
+    "@media screen and (min-width: 100px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    media_query_is_reparsed_before_merging,
+    "@media #{\"screen\"} {\n  @media #{\"(color)\"} {\n    a {\n      b: c;\n    }\n  }\n}\n",
+    "@media screen and (color) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    extend_target_is_reparsed,
+    ".a {\n  b: c;\n}\n\n.d {\n  @extend #{\".a\"};\n}\n",
+    ".a, .d {\n  b: c;\n}\n"
+);
+test!(
+    declaration_value_is_opaque,
+    "a {\n  b: #{\"1 + 2\"};\n  c: length(#{\"d e\"});\n}\n",
+    "a {\n  b: 1 + 2;\n  c: 1;\n}\n"
+);
+test!(
+    unknown_at_rule_prelude_is_opaque,
+    "@foo #{\"a + b\"} {\n  c: d;\n}\n",
+    "@foo a + b {\n  c: d;\n}\n"
+);
+test!(
+    pseudo_class_after_colon_is_selector,
+    ".a {\n  b:#{\"hover\"} {\n    c: d;\n  }\n}\n",
+    ".a b:hover {\n  c: d;\n}\n"
+);
+test!(
+    pseudo_class_after_colon_is_selector_at_root,
+    "a:#{\"hover\"} {\n  b: c;\n}\n",
+    "a:hover {\n  b: c;\n}\n"
+);
+test!(
+    declaration_value_without_space_after_colon,
+    "a {\n  b:#{\"c\"}#{\"d\"};\n  e:#{\"{\"};\n}\n",
+    "a {\n  b: cd;\n  e: {;\n}\n"
+);
+test!(
+    nested_property_after_space_and_interpolation,
+    "a {\n  font: #{\"bold\"} {\n    family: b;\n  }\n}\n",
+    "a {\n  font: bold;\n  font-family: b;\n}\n"
+);
+test!(
+    interpolated_variable_fn_name_is_plain_css_fn,
+    "$fn: unitless;\na {\n  color: #{$fn}(1px);\n}\n",
+    "a {\n  color: unitless(1px);\n}\n"
+);
+test!(
+    interpolated_color_name_is_string,
+    "a {\n  color: type-of(#{red});\n}\n",
+    "a {\n  color: string;\n}\n"
+);
+test!(
+    interpolated_keyword_is_string,
+    "a {\n  color: type-of(#{true});\n  color: type-of(#{null}x);\n}\n",
+    "a {\n  color: string;\n  color: string;\n}\n"
+);
+error!(
+    interpolated_function_name,
+    "$name: a;\n@function #{$name}() {\n  @return 1;\n}\n", "Error: Expected identifier."
+);
+error!(
+    interpolated_include_name,
+    "@mixin a {}\n$name: a;\nb {\n  @include #{$name};\n}\n", "Error: Expected identifier."
+);
+test!(
+    curly_brace_in_string_in_interpolation_without_space_after_colon,
+    "a {\n  b:#{\"}\"};\n  c: d;\n}\n",
+    "a {\n  b: };\n  c: d;\n}\n"
+);
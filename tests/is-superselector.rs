@@ -21,6 +21,26 @@ test!(
     "a {\n  color: is-superselector(\".foo .bar\", \".bar\");\n}\n",
     "a {\n  color: false;\n}\n"
 );
+test!(
+    following_sibling_and_next_sibling,
+    "a {\n  color: is-superselector(\".foo ~ .bar\", \".foo + .bar\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    following_sibling_and_column,
+    "a {\n  color: is-superselector(\".foo ~ .bar\", \".foo || .bar\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    descendant_and_column,
+    "a {\n  color: is-superselector(\".foo .bar\", \".foo || .bar\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    column_more_specific_compound,
+    "a {\n  color: is-superselector(\".foo || .bar\", \".foo || .bar.baz\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
 test!(
     two_in_sub,
     "a {\n  color: is-superselector(\"c\", \"c, d\");\n}\n",
@@ -172,19 +192,16 @@ test!(
     "a {\n  color: true;\n}\n"
 );
 test!(
-    #[ignore = "https://github.com/sass/dart-sass/issues/789"]
     simple_type_universal_namespace_and_explicit_namespace,
     "a {\n  color: is-superselector(\"*|c\", \"d|c\");\n}\n",
     "a {\n  color: true;\n}\n"
 );
 test!(
-    #[ignore = "https://github.com/sass/dart-sass/issues/789"]
     simple_type_universal_namespace_and_implicit_namespace,
     "a {\n  color: is-superselector(\"*|c\", \"c\");\n}\n",
     "a {\n  color: true;\n}\n"
 );
 test!(
-    #[ignore = "https://github.com/sass/dart-sass/issues/789"]
     simple_type_universal_namespace_and_empty_namespace,
     "a {\n  color: is-superselector(\"*|c\", \"|c\");\n}\n",
     "a {\n  color: true;\n}\n"
@@ -194,6 +211,36 @@ test!(
     "a {\n  color: is-superselector(\"*|c\", \"*|c\");\n}\n",
     "a {\n  color: true;\n}\n"
 );
+test!(
+    simple_type_explicit_namespace_and_universal_namespace_reversed,
+    "a {\n  color: is-superselector(\"d|c\", \"*|c\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    simple_universal_universal_namespace_and_explicit_namespace,
+    "a {\n  color: is-superselector(\"*|*\", \"d|c\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    simple_universal_explicit_namespace_and_same_namespace,
+    "a {\n  color: is-superselector(\"d|*\", \"d|c\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    simple_universal_explicit_namespace_and_different_namespace,
+    "a {\n  color: is-superselector(\"d|*\", \"e|c\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    simple_universal_and_type,
+    "a {\n  color: is-superselector(\"*\", \"c\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    simple_universal_and_type_with_namespace,
+    "a {\n  color: is-superselector(\"*\", \"d|c\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
 test!(
     simple_pseudo_no_args_equal,
     "a {\n  color: is-superselector(\":c\", \":c\");\n}\n",
@@ -469,6 +516,36 @@ test!(
     "a {\n  color: is-superselector(\"::-pfx-slotted(c d, e f)\", \"::-pfx-slotted(c d, e f)\");\n}\n",
     "a {\n  color: true;\n}\n"
 );
+test!(
+    psuedo_is_superset,
+    "a {\n  color: is-superselector(\":is(c, d)\", \"c\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    psuedo_is_subset,
+    "a {\n  color: is-superselector(\":is(c)\", \":is(c, d)\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    psuedo_where_superset,
+    "a {\n  color: is-superselector(\":where(c, d)\", \"c\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    psuedo_nth_child_of_superset,
+    "a {\n  color: is-superselector(\":nth-child(2n of c, d)\", \":nth-child(2n of c)\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    psuedo_nth_child_of_different_argument,
+    "a {\n  color: is-superselector(\":nth-child(2n of c)\", \":nth-child(2n+1 of c)\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    psuedo_where_in_compound,
+    "a {\n  color: is-superselector(\"c\", \"d:where(c)\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
 
 // todo: /spec/core_functions/selector/is_superselector/simple/pseudo/selector_arg/
 // :not, :matches, :nth-child, :nth-last-child
@@ -137,3 +137,48 @@ test!(
     }",
     "@keyframes foo {\n  12.5% {\n    color: red;\n  }\n}\n"
 );
+test!(
+    percentage_with_plus_sign,
+    "@keyframes foo {
+        +50% {
+            color: red;
+        }
+    }",
+    "@keyframes foo {\n  +50% {\n    color: red;\n  }\n}\n"
+);
+test!(
+    percentage_without_leading_digit,
+    "@keyframes foo {
+        .5% {
+            color: red;
+        }
+    }",
+    "@keyframes foo {\n  .5% {\n    color: red;\n  }\n}\n"
+);
+test!(
+    percentage_with_exponent,
+    "@keyframes foo {
+        1.5e1%, 1E-1% {
+            color: red;
+        }
+    }",
+    "@keyframes foo {\n  1.5e1%, 1E-1% {\n    color: red;\n  }\n}\n"
+);
+error!(
+    percentage_missing_digit_after_decimal,
+    "@keyframes foo { 1.% { color: red; } }", "Error: Expected digit."
+);
+error!(
+    escaped_selector,
+    "@keyframes foo { \\66rom { color: red; } }", "Error: Expected \"to\" or \"from\"."
+);
+error!(
+    extend_in_keyframes,
+    "@keyframes foo { from { @extend .a; } }",
+    "Error: @extend may only be used within style rules."
+);
+error!(
+    extend_in_mixin_included_in_keyframes,
+    "@mixin foo { @extend .a; } @keyframes foo { from { @include foo; } }",
+    "Error: @extend may only be used within style rules."
+);
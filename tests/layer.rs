@@ -0,0 +1,79 @@
+#[macro_use]
+mod macros;
+
+test!(
+    statement,
+    "@layer reset, base.elements, components;\n",
+    "@layer reset, base.elements, components;\n"
+);
+test!(
+    anonymous_block,
+    "@layer {\n  a {\n    color: red;\n  }\n}\n",
+    "@layer {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_block_combines_names,
+    "@layer base {\n  @layer elements {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@layer base.elements {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_block_splits_enclosing_block,
+    "@layer base {\n  a {\n    color: red;\n  }\n  @layer elements {\n    b {\n      color: red;\n    }\n  }\n  c {\n    color: red;\n  }\n}\n",
+    "@layer base {\n  a {\n    color: red;\n  }\n}\n\n@layer base.elements {\n  b {\n    color: red;\n  }\n}\n\n@layer base {\n  c {\n    color: red;\n  }\n}\n"
+);
+test!(
+    deeply_nested_blocks_combine_names,
+    "@layer a {\n  @layer b {\n    @layer c {\n      d {\n        color: red;\n      }\n    }\n  }\n}\n",
+    "@layer a.b.c {\n  d {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_block_with_interpolated_name,
+    "$name: elements;\n@layer base {\n  @layer #{$name} {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@layer base.elements {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_block_inside_anonymous_block_is_kept_nested,
+    "@layer {\n  @layer base {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@layer {\n  @layer base {\n    a {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    anonymous_block_inside_named_block_is_kept_nested,
+    "@layer base {\n  @layer {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@layer base {\n  @layer {\n    a {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    statement_inside_block_is_kept_nested,
+    "@layer base {\n  @layer reset, elements;\n}\n",
+    "@layer base {\n  @layer reset, elements;\n}\n"
+);
+test!(empty_block, "@layer base {}\n", "@layer base {}\n");
+test!(
+    inside_style_rule,
+    "a {\n  @layer base {\n    color: red;\n  }\n}\n",
+    "@layer base {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_inside_style_rule,
+    "a {\n  @layer base {\n    @layer elements {\n      &:hover {\n        color: red;\n      }\n    }\n  }\n}\n",
+    "@layer base.elements {\n  a:hover {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_with_declarations_inside_style_rule,
+    "a {\n  @layer base {\n    color: red;\n    @layer elements {\n      color: blue;\n    }\n  }\n}\n",
+    "@layer base {\n  a {\n    color: red;\n  }\n}\n@layer base.elements {\n  a {\n    color: blue;\n  }\n}\n"
+);
+test!(
+    inside_media_inside_style_rule,
+    "a {\n  @media screen {\n    @layer base {\n      color: red;\n    }\n  }\n}\n",
+    "@media screen {\n  @layer base {\n    a {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    interpolated_name,
+    "$name: base;\n@layer #{$name}.elements {\n  a {\n    color: red;\n  }\n}\n",
+    "@layer base.elements {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    at_root_inside_style_rule,
+    "a {\n  @layer base {\n    @at-root b {\n      color: red;\n    }\n  }\n}\n",
+    "@layer base {\n  b {\n    color: red;\n  }\n}\n"
+);
@@ -0,0 +1,76 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+fn line_comments(input: &str) -> String {
+    grass::from_string(
+        input.to_string(),
+        &grass::Options::default().line_comments(true),
+    )
+    .expect(input)
+}
+
+#[test]
+fn disabled_by_default() {
+    let input = "a {\n  color: red;\n}\n";
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(input.to_string(), &grass::Options::default()).unwrap()
+    );
+}
+
+#[test]
+fn style_rule() {
+    assert_eq!(
+        "/* line 2, stdin */\na {\n  color: red;\n}\n",
+        line_comments("\na {\n  color: red;\n}\n")
+    );
+}
+
+#[test]
+fn nested_style_rule() {
+    assert_eq!(
+        "/* line 1, stdin */\na {\n  color: red;\n}\n/* line 3, stdin */\na b {\n  color: blue;\n}\n",
+        line_comments("a {\n  color: red;\n  b {\n    color: blue;\n  }\n}\n")
+    );
+}
+
+#[test]
+fn style_rule_in_media_is_indented() {
+    assert_eq!(
+        "@media screen {\n  /* line 2, stdin */\n  a {\n    color: red;\n  }\n}\n",
+        line_comments("@media screen {\n  a {\n    color: red;\n  }\n}\n")
+    );
+}
+
+#[test]
+fn style_rule_from_mixin_refers_to_mixin() {
+    assert_eq!(
+        "/* line 2, stdin */\na {\n  color: red;\n}\n",
+        line_comments("@mixin foo {\n  a {\n    color: red;\n  }\n}\n\n@include foo;\n")
+    );
+}
+
+#[test]
+fn empty_style_rule_has_no_comment() {
+    assert_eq!("", line_comments("a {}\n"));
+}
+
+#[test]
+fn keyframes_selectors_have_no_comment() {
+    assert_eq!(
+        "@keyframes foo {\n  to {\n    color: red;\n  }\n}\n",
+        line_comments("@keyframes foo {\n  to {\n    color: red;\n  }\n}\n")
+    );
+}
+
+#[test]
+fn style_rule_in_imported_file() {
+    let input = "@import \"line_comments_imported\";\n";
+    tempfile!("line_comments_imported.scss", "\n\na {\n  color: red;\n}\n");
+    assert_eq!(
+        "/* line 3, line_comments_imported.scss */\na {\n  color: red;\n}\n",
+        line_comments(input)
+    );
+}
@@ -397,3 +397,78 @@ error!(
     "a {\n  color: set-nth([], 1px, a);\n}\n",
     "Error: $n: Invalid index 1px for a list with 0 elements."
 );
+test!(
+    append_map,
+    "a {\n  color: inspect(append((a: b, c: d), e));\n}\n",
+    "a {\n  color: a b, c d, e;\n}\n"
+);
+test!(
+    append_arglist,
+    "@function foo($args...) {\n  @return append($args, c);\n}\n\na {\n  color: foo(a, b);\n}\n",
+    "a {\n  color: a, b, c;\n}\n"
+);
+test!(
+    join_arglist,
+    "@function foo($args...) {\n  @return join(a b, $args);\n}\n\na {\n  color: foo(c, d);\n}\n",
+    "a {\n  color: a b c d;\n}\n"
+);
+test!(
+    zip_map,
+    "a {\n  color: inspect(zip((a: b), c d));\n}\n",
+    "a {\n  color: ((a b) c,);\n}\n"
+);
+test!(
+    slash_numbers,
+    "@use 'sass:list';\na {\n  color: list.slash(1px, 2px, 3px);\n}\n",
+    "a {\n  color: 1px / 2px / 3px;\n}\n"
+);
+test!(
+    slash_separator,
+    "@use 'sass:list';\na {\n  color: list.separator(list.slash(a, b));\n}\n",
+    "a {\n  color: slash;\n}\n"
+);
+test!(
+    slash_of_space_separated_lists,
+    "@use 'sass:list';\na {\n  color: inspect(list.slash(a b, c d));\n}\n",
+    "a {\n  color: a b / c d;\n}\n"
+);
+test!(
+    slash_inside_space_separated_list,
+    "@use 'sass:list';\na {\n  color: inspect(list.slash(a, b) c);\n}\n",
+    "a {\n  color: (a / b) c;\n}\n"
+);
+test!(
+    slash_inside_slash_separated_list,
+    "@use 'sass:list';\na {\n  color: inspect(list.slash(list.slash(a, b), c));\n}\n",
+    "a {\n  color: (a / b) / c;\n}\n"
+);
+test!(
+    slash_nth,
+    "@use 'sass:list';\na {\n  color: list.nth(list.slash(a, b, c), 2);\n}\n",
+    "a {\n  color: b;\n}\n"
+);
+test!(
+    join_separator_slash,
+    "a {\n  color: join(a b, c, $separator: slash);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+test!(
+    append_separator_slash,
+    "a {\n  color: append(a, b, $separator: slash);\n}\n",
+    "a {\n  color: a / b;\n}\n"
+);
+test!(
+    append_auto_keeps_slash,
+    "@use 'sass:list';\na {\n  color: append(list.slash(a, b), c);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+error!(
+    slash_one_element,
+    "@use 'sass:list';\na {\n  color: list.slash(a);\n}\n",
+    "Error: At least two elements are required."
+);
+error!(
+    join_separator_invalid,
+    "a {\n  color: join(a, b, $separator: foo);\n}\n",
+    "Error: $separator: Must be \"space\", \"comma\", \"slash\", or \"auto\"."
+);
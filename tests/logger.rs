@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+
+use grass::{Diagnostic, DiagnosticFormat, Logger, Severity};
+
+#[derive(Debug, Default)]
+struct Recorder {
+    messages: RefCell<Vec<(Severity, String, String)>>,
+}
+
+impl Logger for Recorder {
+    fn warn(&self, diagnostic: &Diagnostic, formatted: &str) {
+        self.messages.borrow_mut().push((
+            diagnostic.severity,
+            diagnostic.message.clone(),
+            formatted.to_owned(),
+        ));
+    }
+
+    fn debug(&self, diagnostic: &Diagnostic, formatted: &str) {
+        self.messages.borrow_mut().push((
+            diagnostic.severity,
+            diagnostic.message.clone(),
+            formatted.to_owned(),
+        ));
+    }
+}
+
+fn log(input: &str, options: grass::Options<'_>) -> Vec<(Severity, String, String)> {
+    let recorder = Recorder::default();
+    grass::from_string(input.to_string(), &options.logger(&recorder)).expect(input);
+    recorder.messages.into_inner()
+}
+
+#[test]
+fn warn_and_debug_are_logged_in_order() {
+    assert_eq!(
+        vec![
+            (
+                Severity::Debug,
+                "1".to_owned(),
+                "stdin:2 Debug: 1".to_owned()
+            ),
+            (
+                Severity::Warning,
+                "careful".to_owned(),
+                "Warning: careful\n    stdin 3:9  root stylesheet".to_owned()
+            ),
+        ],
+        log(
+            "a {\n  @debug 1;\n  @warn \"careful\";\n}\n",
+            grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn formatted_as_configured() {
+    let messages = log(
+        "@debug 1;\n",
+        grass::Options::default().diagnostic_format(DiagnosticFormat::Json),
+    );
+    assert_eq!(1, messages.len());
+    assert!(messages[0].2.starts_with("{\"severity\":\"debug\""));
+}
+
+#[test]
+fn deprecation_warnings_are_logged() {
+    let messages = log(
+        "a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n",
+        grass::Options::default(),
+    );
+    assert_eq!(1, messages.len());
+    assert_eq!(Severity::Warning, messages[0].0);
+}
+
+#[test]
+fn quiet_does_not_call_logger() {
+    assert!(log(
+        "@debug 1;\n@warn 2;\n",
+        grass::Options::default().quiet(true)
+    )
+    .is_empty());
+}
+
+#[test]
+fn null_logger() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("@warn 1;\n".to_string()),
+        &grass::Options::default().logger(&grass::NullLogger),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert_eq!(1, stats.diagnostics.len());
+}
+
+#[test]
+fn strings_are_logged_without_quotes() {
+    let messages = log(
+        "@debug \"a b\";\n@debug (\"a b\",);\n",
+        grass::Options::default(),
+    );
+    assert_eq!("a b", messages[0].1);
+    assert_eq!("(\"a b\",)", messages[1].1);
+}
+
+#[test]
+fn deprecation_warnings_from_the_same_place_are_logged_once() {
+    let input = "@for $i from 1 through 10 {\n  a {\n    color: call(\"rgb\", 1, 2, 3);\n  }\n}\n";
+    assert_eq!(1, log(input, grass::Options::default()).len());
+
+    let stats = grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert_eq!(1, stats.diagnostics.len());
+}
+
+#[test]
+fn repetitive_deprecation_warnings_are_omitted() {
+    let input = "a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n".repeat(7);
+    let messages = log(&input, grass::Options::default());
+    assert_eq!(6, messages.len());
+    assert_eq!(
+        "Warning: 2 repetitive deprecation warnings omitted.\nRun in verbose mode to see all warnings.",
+        messages[5].2
+    );
+
+    let stats = grass::compile_with_reports(
+        grass::Input::String(input),
+        &grass::Options::default().quiet(true),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert_eq!(7, stats.diagnostics.len());
+}
+
+#[test]
+fn verbose_logs_all_deprecation_warnings() {
+    let input = "a {\n  color: call(\"rgb\", 1, 2, 3);\n}\n".repeat(7);
+    let messages = log(&input, grass::Options::default().verbose(true));
+    assert_eq!(7, messages.len());
+    assert!(messages.iter().all(|message| message.1.contains("call()")));
+}
+
+#[test]
+fn warn_is_never_omitted() {
+    let messages = log(&"@warn \"careful\";\n".repeat(7), grass::Options::default());
+    assert_eq!(7, messages.len());
+}
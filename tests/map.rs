@@ -229,3 +229,140 @@ error!(
     denies_comma_separated_list_without_parens_as_key,
     "$map: (a: 1, b, c, d: e);", "Error: expected \":\"."
 );
+test!(
+    map_keys_round_trip_through_map_get,
+    "$map: (1px: a, \"b\": c, red: d);
+    a {
+        color: map-get($map, nth(map-keys($map), 1));
+        color: map-get($map, nth(map-keys($map), 2));
+        color: map-get($map, nth(map-keys($map), 3));
+        color: type-of(nth(map-keys($map), 1));
+    }",
+    "a {\n  color: a;\n  color: c;\n  color: d;\n  color: number;\n}\n"
+);
+test!(
+    map_get_with_equal_but_differently_written_key,
+    "a {\n  color: map-get((1in: a), 96px);\n  color: map-get((\"a\": b), a);\n  color: map-get((red: c), #f00);\n}\n",
+    "a {\n  color: a;\n  color: b;\n  color: c;\n}\n"
+);
+error!(
+    duplicate_key_in_function_argument,
+    "a {\n  color: map-get((foo: a, foo: b), foo);\n}\n", "Error: Duplicate key."
+);
+error!(
+    duplicate_equal_but_differently_written_keys,
+    "a {\n  $a: (1in: a, 96px: b);\n}\n", "Error: Duplicate key."
+);
+test!(
+    map_get_nested_keys,
+    "a {\n  color: map-get((a: (b: (c: d))), a, b, c);\n}\n",
+    "a {\n  color: d;\n}\n"
+);
+test!(
+    map_get_nested_keys_missing,
+    "a {\n  color: inspect(map-get((a: (b: c)), a, d, e));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    map_get_nested_keys_through_non_map,
+    "a {\n  color: inspect(map-get((a: b), a, b));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    map_has_key_nested_keys,
+    "a {\n  color: map-has-key((a: (b: null)), a, b);\n  color: map-has-key((a: (b: c)), a, c);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    map_merge_named_args,
+    "a {\n  color: inspect(map-merge($map2: (c: d), $map1: (a: b)));\n}\n",
+    "a {\n  color: (a: b, c: d);\n}\n"
+);
+test!(
+    map_merge_nested_keys,
+    "a {\n  color: inspect(map-merge((a: (b: c, d: e)), a, (d: f, g: h)));\n}\n",
+    "a {\n  color: (a: (b: c, d: f, g: h));\n}\n"
+);
+test!(
+    map_merge_nested_keys_missing,
+    "a {\n  color: inspect(map-merge((a: b), c, d, (e: f)));\n}\n",
+    "a {\n  color: (a: b, c: (d: (e: f)));\n}\n"
+);
+test!(
+    map_merge_nested_keys_replaces_non_map,
+    "a {\n  color: inspect(map-merge((a: b), a, (c: d)));\n}\n",
+    "a {\n  color: (a: (c: d));\n}\n"
+);
+error!(
+    map_merge_nested_keys_map2_non_map,
+    "a {\n  color: map-merge((a: b), a, 1);\n}\n", "Error: $map2: 1 is not a map."
+);
+test!(
+    module_get_nested,
+    "@use 'sass:map';\na {\n  color: map.get((a: (b: c)), a, b);\n}\n",
+    "a {\n  color: c;\n}\n"
+);
+test!(
+    module_set,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b, c: d), a, e));\n}\n",
+    "a {\n  color: (a: e, c: d);\n}\n"
+);
+test!(
+    module_set_named,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b), $key: c, $value: d));\n}\n",
+    "a {\n  color: (a: b, c: d);\n}\n"
+);
+test!(
+    module_set_nested,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: (b: c)), a, b, d));\n}\n",
+    "a {\n  color: (a: (b: d));\n}\n"
+);
+test!(
+    module_set_nested_creates_maps,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b), c, d, e));\n}\n",
+    "a {\n  color: (a: b, c: (d: e));\n}\n"
+);
+error!(
+    module_set_no_value,
+    "@use 'sass:map';\na {\n  color: map.set((a: b), a);\n}\n",
+    "Error: Expected $args to contain a value."
+);
+error!(
+    module_set_no_key,
+    "@use 'sass:map';\na {\n  color: map.set((a: b));\n}\n",
+    "Error: Expected $args to contain a key."
+);
+test!(
+    module_deep_merge,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-merge((a: (b: 1, c: 2), d: 3), (a: (c: 4, e: 5), f: 6)));\n}\n",
+    "a {\n  color: (a: (b: 1, c: 4, e: 5), d: 3, f: 6);\n}\n"
+);
+test!(
+    module_deep_merge_replaces_non_map,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-merge((a: 1), (a: (b: 2))));\n}\n",
+    "a {\n  color: (a: (b: 2));\n}\n"
+);
+error!(
+    module_deep_merge_non_map,
+    "@use 'sass:map';\na {\n  color: map.deep-merge((a: b), 1);\n}\n",
+    "Error: $map2: 1 is not a map."
+);
+test!(
+    module_deep_remove,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: (b: 1, c: 2), d: 3), a, b));\n}\n",
+    "a {\n  color: (a: (c: 2), d: 3);\n}\n"
+);
+test!(
+    module_deep_remove_top_level,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: b, c: d), a));\n}\n",
+    "a {\n  color: (c: d);\n}\n"
+);
+test!(
+    module_deep_remove_through_non_map,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: b), a, c));\n}\n",
+    "a {\n  color: (a: b);\n}\n"
+);
+error!(
+    module_functions_are_not_global,
+    "a {\n  color: deep-merge((a: b), (c: d));\n}\n", "Error: (a: b) isn't a valid CSS value."
+);
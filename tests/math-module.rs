@@ -588,3 +588,62 @@ test!(
     "@use 'sass:math';\na {\n  color: math.atan2(math.acos(2), 3deg);\n}\n",
     "a {\n  color: NaNdeg;\n}\n"
 );
+test!(
+    div_unitless,
+    "@use 'sass:math';\na {\n  color: math.div(10, 4);\n}\n",
+    "a {\n  color: 2.5;\n}\n"
+);
+test!(
+    div_keeps_numerator_unit,
+    "@use 'sass:math';\na {\n  color: math.div(100px, 4);\n}\n",
+    "a {\n  color: 25px;\n}\n"
+);
+test!(
+    div_same_units_cancel,
+    "@use 'sass:math';\na {\n  color: math.unit(math.div(10px, 5px));\n}\n",
+    "a {\n  color: \"\";\n}\n"
+);
+test!(
+    div_compatible_units,
+    "@use 'sass:math';\na {\n  color: math.div(1in, 1px);\n}\n",
+    "a {\n  color: 96;\n}\n"
+);
+test!(
+    div_named_args,
+    "@use 'sass:math';\na {\n  color: math.div($number2: 2, $number1: 6em);\n}\n",
+    "a {\n  color: 3em;\n}\n"
+);
+test!(
+    div_inside_list,
+    "@use 'sass:math';\na {\n  color: math.div(1, 2) math.div(3, 4);\n}\n",
+    "a {\n  color: 0.5 0.75;\n}\n"
+);
+test!(
+    div_strings,
+    "@use 'sass:math';\na {\n  color: math.div(a, b);\n}\n",
+    "a {\n  color: a/b;\n}\n"
+);
+error!(
+    div_too_many_args,
+    "@use 'sass:math';\na {\n  color: math.div(1, 2, 3);\n}\n",
+    "Error: Only 2 arguments allowed, but 3 were passed."
+);
+test!(
+    epsilon,
+    "@use 'sass:math';\na {\n  color: math.$epsilon * 10000000000000000;\n}\n",
+    "a {\n  color: 2.2204460493;\n}\n"
+);
+test!(
+    max_safe_integer,
+    "@use 'sass:math';\na {\n  color: math.$max-safe-integer;\n}\n",
+    "a {\n  color: 9007199254740991;\n}\n"
+);
+test!(
+    min_safe_integer,
+    "@use 'sass:math';\na {\n  color: math.$min-safe-integer;\n}\n",
+    "a {\n  color: -9007199254740991;\n}\n"
+);
+error!(
+    cannot_modify_epsilon,
+    "@use 'sass:math';\nmath.$epsilon: 0;\n", "Error: Cannot modify built-in variable."
+);
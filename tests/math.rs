@@ -102,12 +102,70 @@ test!(
     "a {\n  color: true;\n}\n"
 );
 test!(
+    #[cfg(feature = "random")]
     random_limit_one,
     "a {\n  color: random(1);\n}\n",
     "a {\n  color: 1;\n}\n"
 );
 test!(
+    #[cfg(feature = "random")]
     random_limit_big_one,
     "a {\n  color: random(1000000000000000001 - 1000000000000000000);\n}\n",
     "a {\n  color: 1;\n}\n"
 );
+
+#[cfg(feature = "random")]
+fn compile_with_seed(seed: u64) -> String {
+    grass::from_string(
+        "a {\n  color: random() random(100) unique-id();\n}\n".to_string(),
+        &grass::Options::default().random_seed(seed),
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg(feature = "random")]
+fn random_same_seed_is_reproducible() {
+    assert_eq!(compile_with_seed(42), compile_with_seed(42));
+}
+
+#[test]
+#[cfg(feature = "random")]
+fn random_different_seeds_differ() {
+    assert_ne!(compile_with_seed(1), compile_with_seed(2));
+}
+
+#[test]
+#[cfg(feature = "random")]
+fn unique_id_is_identifier() {
+    let css = grass::from_string(
+        "a {\n  color: unique-id();\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap();
+    let id = css
+        .trim_start_matches("a {\n  color: ")
+        .trim_end_matches(";\n}\n");
+
+    assert_eq!(7, id.len(), "{}", id);
+    assert!(id.starts_with('u'), "{}", id);
+    assert!(id.chars().all(|c| c.is_ascii_alphanumeric()), "{}", id);
+}
+
+#[test]
+#[cfg(feature = "random")]
+fn unique_id_does_not_repeat() {
+    let mut input = String::from("a {\n");
+    for _ in 0..1000 {
+        input.push_str("  color: unique-id();\n");
+    }
+    input.push_str("}\n");
+
+    let css = grass::from_string(input, &grass::Options::default()).unwrap();
+    let ids = css
+        .lines()
+        .filter_map(|line| line.strip_prefix("  color: "))
+        .collect::<std::collections::HashSet<_>>();
+
+    assert_eq!(1000, ids.len());
+}
@@ -117,3 +117,141 @@ error!(
     media_feature_missing_curly_brace_after_hash,
     "@media foo and # {}", "Error: expected \"{\"."
 );
+test!(
+    nested_media_merges_type_and_feature,
+    "@media screen {
+        @media (min-width: 1px) {
+            a {
+                b: c
+            }
+        }
+    }",
+    "@media screen and (min-width: 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_media_merges_features,
+    "@media (min-width: 1px) {
+        @media (max-width: 2px) {
+            a {
+                b: c
+            }
+        }
+    }",
+    "@media (min-width: 1px) and (max-width: 2px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_media_with_disjoint_types_is_removed,
+    "@media screen {
+        @media print {
+            a {
+                b: c
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_media_negated_and_same_type_is_removed,
+    "@media not screen {
+        @media screen {
+            a {
+                b: c
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_media_preserves_case,
+    "@media SCREEN {
+        @media screen and (color) {
+            a {
+                b: c
+            }
+        }
+    }",
+    "@media SCREEN and (color) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_media_with_unmergeable_condition_is_kept_nested,
+    "@media (a) or (b) {
+        @media (c) {
+            a {
+                b: c
+            }
+        }
+    }",
+    "@media (a) or (b) {\n  @media (c) {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(
+    nested_media_in_style_rule_splits_parent,
+    "a {
+        @media screen {
+            b: c;
+
+            @media (color) {
+                d: e;
+            }
+        }
+    }
+    @media print {
+        a {
+            b: c;
+        }
+
+        @media (color) {
+            a {
+                d: e;
+            }
+        }
+
+        f {
+            g: h;
+        }
+    }",
+    "@media screen {\n  a {\n    b: c;\n  }\n}\n@media screen and (color) {\n  a {\n    d: e;\n  }\n}\n@media print {\n  a {\n    b: c;\n  }\n}\n@media print and (color) {\n  a {\n    d: e;\n  }\n}\n@media print {\n  f {\n    g: h;\n  }\n}\n"
+);
+test!(
+    interpolated_query_is_merged,
+    "$query: \"(min-width: 1px)\";
+    @media screen {
+        @media #{$query} {
+            a {
+                b: c
+            }
+        }
+    }",
+    "@media screen and (min-width: 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    negated_condition,
+    "@media not (color) {
+        a {
+            b: c
+        }
+    }",
+    "@media not (color) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    or_condition,
+    "@media (a) or (b) {
+        a {
+            b: c
+        }
+    }",
+    "@media (a) or (b) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    media_with_only_extend_is_removed,
+    "@media print {
+        .b {
+            @extend .a;
+        }
+    }
+    @media print {
+        .a {
+            x: y;
+        }
+    }",
+    "@media print {\n  .a, .b {\n    x: y;\n  }\n}\n"
+);
@@ -0,0 +1,55 @@
+#![cfg(feature = "memory-limit")]
+
+#[global_allocator]
+static ALLOCATOR: grass::CountingAllocator = grass::CountingAllocator;
+
+#[test]
+fn runaway_loop_exceeds_memory_limit() {
+    let input = "$a: a;\n@for $i from 1 through 30 {\n  $a: $a + $a;\n}\nb {\n  color: $a;\n}\n";
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().memory_limit(1024 * 1024),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Memory limit exceeded.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn small_stylesheet_is_within_memory_limit() {
+    let input = "@for $i from 1 through 10 {\n  .a-#{$i} {\n    width: $i * 10px;\n  }\n}\n";
+    assert!(grass::from_string(
+        input.to_string(),
+        &grass::Options::default().memory_limit(16 * 1024 * 1024),
+    )
+    .is_ok());
+}
+
+#[test]
+fn no_memory_limit_by_default() {
+    let input = "$a: a;\n@for $i from 1 through 16 {\n  $a: $a + $a;\n}\nb {\n  color: str-length($a);\n}\n";
+    assert_eq!(
+        "b {\n  color: 65536;\n}\n",
+        grass::from_string(input.to_string(), &grass::Options::default()).unwrap()
+    );
+}
+
+#[test]
+fn extend_explosion_within_one_statement_exceeds_memory_limit() {
+    let targets: Vec<String> = (0..12).map(|i| format!(".a{}", i)).collect();
+    let input = format!(
+        "{} {{\n  color: red;\n}}\n.b, .c {{\n  @extend {};\n}}\n",
+        targets.concat(),
+        targets.join(", ")
+    );
+    match grass::from_string(input, &grass::Options::default().memory_limit(1024 * 1024)) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Memory limit exceeded.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
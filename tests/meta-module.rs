@@ -6,12 +6,12 @@ mod macros;
 test!(
     module_functions_builtin,
     "@use 'sass:meta';\na {\n  color: inspect(meta.module-functions(meta));\n}\n",
-    "a {\n  color: (\"feature-exists\": get-function(\"feature-exists\"), \"inspect\": get-function(\"inspect\"), \"type-of\": get-function(\"type-of\"), \"keywords\": get-function(\"keywords\"), \"global-variable-exists\": get-function(\"global-variable-exists\"), \"variable-exists\": get-function(\"variable-exists\"), \"function-exists\": get-function(\"function-exists\"), \"mixin-exists\": get-function(\"mixin-exists\"), \"content-exists\": get-function(\"content-exists\"), \"module-variables\": get-function(\"module-variables\"), \"module-functions\": get-function(\"module-functions\"), \"get-function\": get-function(\"get-function\"), \"call\": get-function(\"call\"));\n}\n"
+    "a {\n  color: (\"feature-exists\": get-function(\"feature-exists\"), \"inspect\": get-function(\"inspect\"), \"type-of\": get-function(\"type-of\"), \"keywords\": get-function(\"keywords\"), \"global-variable-exists\": get-function(\"global-variable-exists\"), \"variable-exists\": get-function(\"variable-exists\"), \"function-exists\": get-function(\"function-exists\"), \"mixin-exists\": get-function(\"mixin-exists\"), \"content-exists\": get-function(\"content-exists\"), \"module-variables\": get-function(\"module-variables\"), \"module-functions\": get-function(\"module-functions\"), \"get-function\": get-function(\"get-function\"), \"call\": get-function(\"call\"), \"get-mixin\": get-function(\"get-mixin\"), \"accepts-content\": get-function(\"accepts-content\"));\n}\n"
 );
 test!(
     module_variables_builtin,
     "@use 'sass:meta';\n@use 'sass:math';\na {\n  color: inspect(meta.module-variables(math));\n}\n",
-    "a {\n  color: (\"e\": 2.7182818285, \"pi\": 3.1415926536);\n}\n"
+    "a {\n  color: (\"e\": 2.7182818285, \"pi\": 3.1415926536, \"epsilon\": 0, \"max-safe-integer\": 9007199254740991, \"min-safe-integer\": -9007199254740991);\n}\n"
 );
 test!(
     global_var_exists_module,
@@ -28,6 +28,109 @@ error!(
     "a {\n  color: function-exists(c, d);\n}\n",
     "Error: There is no module with the namespace \"d\"."
 );
+test!(
+    get_mixin_type_of,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: meta.type-of(meta.get-mixin(foo));\n}\n",
+    "a {\n  color: mixin;\n}\n"
+);
+test!(
+    get_mixin_inspect,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: meta.inspect(meta.get-mixin(foo));\n}\n",
+    "a {\n  color: get-mixin(\"foo\");\n}\n"
+);
+test!(
+    get_mixin_equality,
+    "@use 'sass:meta';\n@mixin foo {}\n@mixin bar {}\na {\n  color: meta.get-mixin(foo) == meta.get-mixin(\"foo\");\n  color: meta.get-mixin(foo) == meta.get-mixin(bar);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    get_mixin_builtin_module,
+    "@use 'sass:meta';\na {\n  color: meta.inspect(meta.get-mixin(load-css, $module: meta));\n}\n",
+    "a {\n  color: get-mixin(\"load-css\");\n}\n"
+);
+error!(
+    get_mixin_not_found,
+    "@use 'sass:meta';\na {\n  color: meta.get-mixin(foo);\n}\n", "Error: Mixin not found: foo"
+);
+error!(
+    get_mixin_non_string_name,
+    "@use 'sass:meta';\na {\n  color: meta.get-mixin(1);\n}\n", "Error: $name: 1 is not a string."
+);
+error!(
+    get_mixin_as_css_value,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: meta.get-mixin(foo);\n}\n",
+    "Error: get-mixin(\"foo\") isn't a valid CSS value."
+);
+test!(
+    accepts_content,
+    "@use 'sass:meta';\n@mixin foo {}\n@mixin bar {\n  a {\n    @content;\n  }\n}\n@mixin baz {\n  content: \"@content\";\n}\na {\n  color: meta.accepts-content(meta.get-mixin(foo));\n  color: meta.accepts-content(meta.get-mixin(bar));\n  color: meta.accepts-content(meta.get-mixin(baz));\n}\n",
+    "a {\n  color: false;\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    accepts_content_builtin,
+    "@use 'sass:meta';\na {\n  color: meta.accepts-content(meta.get-mixin(apply, meta));\n  color: meta.accepts-content(meta.get-mixin(load-css, meta));\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+error!(
+    accepts_content_non_mixin,
+    "@use 'sass:meta';\na {\n  color: meta.accepts-content(get-function(rgb));\n}\n",
+    "Error: $mixin: get-function(\"rgb\") is not a mixin reference."
+);
+test!(
+    apply_positional_and_named_args,
+    "@use 'sass:meta';\n@mixin foo($a, $b: 2) {\n  color: $a $b;\n}\na {\n  @include meta.apply(meta.get-mixin(foo), 1);\n  @include meta.apply(meta.get-mixin(foo), 1, $b: 3);\n}\n",
+    "a {\n  color: 1 2;\n  color: 1 3;\n}\n"
+);
+test!(
+    apply_passed_to_mixin,
+    "@use 'sass:meta';\n@mixin colored {\n  color: red;\n}\n@mixin twice($mixin) {\n  @include meta.apply($mixin);\n  @include meta.apply($mixin);\n}\na {\n  @include twice(meta.get-mixin(colored));\n}\n",
+    "a {\n  color: red;\n  color: red;\n}\n"
+);
+test!(
+    apply_content_block,
+    "@use 'sass:meta';\n@mixin foo {\n  b {\n    @content;\n  }\n}\na {\n  @include meta.apply(meta.get-mixin(foo)) {\n    color: red;\n  }\n}\n",
+    "a b {\n  color: red;\n}\n"
+);
+test!(
+    apply_content_block_using,
+    "@use 'sass:meta';\n@mixin foo {\n  @content(red);\n}\na {\n  @include meta.apply(meta.get-mixin(foo)) using ($color) {\n    color: $color;\n  }\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+error!(
+    apply_content_block_not_accepted,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  @include meta.apply(meta.get-mixin(foo)) {\n    color: red;\n  }\n}\n",
+    "Error: Mixin doesn't accept a content block."
+);
+error!(
+    apply_non_mixin,
+    "@use 'sass:meta';\na {\n  @include meta.apply(foo);\n}\n",
+    "Error: $mixin: foo is not a mixin reference."
+);
+error!(
+    load_css_content_block,
+    "@use 'sass:meta';\na {\n  @include meta.load-css(\"sass:math\") {\n    color: red;\n  }\n}\n",
+    "Error: Mixin doesn't accept a content block."
+);
+
+#[test]
+fn get_mixin_module() {
+    let input = "@use \"sass:meta\";\n@use \"get_mixin_module\" as module;\na {\n @include meta.apply(meta.get-mixin(foo, $module: module));\n}";
+    tempfile!("get_mixin_module.scss", "@mixin foo {\n color: red;\n}");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn get_mixin_module_private() {
+    let input = "@use \"sass:meta\";\n@use \"get_mixin_module_private\" as module;\na {\n color: meta.get-mixin(-foo, $module: module);\n}";
+    tempfile!("get_mixin_module_private.scss", "@mixin -foo {}");
+    assert_err!(
+        "Error: Private members can't be accessed from outside their modules.",
+        input
+    );
+}
 
 #[test]
 fn mixin_exists_module() {
@@ -39,6 +142,71 @@ fn mixin_exists_module() {
     );
 }
 
+#[test]
+fn module_variables_declaration_order() {
+    let input = "@use \"sass:meta\";\n@use \"module_variables_declaration_order\" as module;\na {\n color: inspect(meta.module-variables(module));\n}";
+    tempfile!(
+        "module_variables_declaration_order.scss",
+        "$z: 1;\n$a: 2;\n$m: 3;\n$z: 4;"
+    );
+    assert_eq!(
+        "a {\n  color: (\"z\": 4, \"a\": 2, \"m\": 3);\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_variables_excludes_private() {
+    let input = "@use \"sass:meta\";\n@use \"module_variables_excludes_private\" as module;\na {\n color: inspect(meta.module-variables(module));\n}";
+    tempfile!(
+        "module_variables_excludes_private.scss",
+        "$-hidden: 1;\n$_also-hidden: 2;\n$shown: 3;"
+    );
+    assert_eq!(
+        "a {\n  color: (\"shown\": 3);\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_variables_each() {
+    let input = "@use \"sass:meta\";\n@use \"module_variables_each\" as theme;\n@each $name, $value in meta.module-variables(theme) {\n .#{$name} {\n color: $value;\n }\n}";
+    tempfile!(
+        "module_variables_each.scss",
+        "$primary: red;\n$secondary: blue;"
+    );
+    assert_eq!(
+        ".primary {\n  color: red;\n}\n\n.secondary {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_functions_declaration_order() {
+    let input = "@use \"sass:meta\";\n@use \"module_functions_declaration_order\" as module;\na {\n color: inspect(meta.module-functions(module));\n}";
+    tempfile!(
+        "module_functions_declaration_order.scss",
+        "@function zz() { @return 1; }\n@function aa() { @return 2; }\n@function -hidden() { @return 3; }"
+    );
+    assert_eq!(
+        "a {\n  color: (\"zz\": get-function(\"zz\"), \"aa\": get-function(\"aa\"));\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_functions_call() {
+    let input = "@use \"sass:meta\";\n@use \"module_functions_call\" as module;\na {\n @each $name, $function in meta.module-functions(module) {\n #{$name}: meta.call($function, 2);\n }\n}";
+    tempfile!(
+        "module_functions_call.scss",
+        "@function double($n) { @return $n * 2; }\n@function square($n) { @return $n * $n; }"
+    );
+    assert_eq!(
+        "a {\n  double: 4;\n  square: 4;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
 #[test]
 fn load_css_simple() {
     let input = "@use \"sass:meta\";\na {\n @include meta.load-css(load_css_simple);\n}";
@@ -51,7 +219,7 @@ fn load_css_simple() {
 
 #[test]
 fn load_css_explicit_args() {
-    let input = "@use \"sass:meta\";\na {\n @include meta.load-css($module: load_css_explicit_args, $with: null);\n}";
+    let input = "@use \"sass:meta\";\na {\n @include meta.load-css($url: load_css_explicit_args, $with: null);\n}";
     tempfile!("load_css_explicit_args.scss", "a { color: red; }");
     assert_eq!(
         "a a {\n  color: red;\n}\n",
@@ -63,7 +231,7 @@ fn load_css_explicit_args() {
 fn load_css_non_string_url() {
     let input = "@use \"sass:meta\";\na {\n @include meta.load-css(2);\n}";
     tempfile!("load_css_non_string_url.scss", "a { color: red; }");
-    assert_err!("Error: $module: 2 is not a string.", input);
+    assert_err!("Error: $url: 2 is not a string.", input);
 }
 
 #[test]
@@ -71,3 +239,91 @@ fn load_css_non_map_with() {
     let input = "@use \"sass:meta\";\na {\n @include meta.load-css(foo, 2);\n}";
     assert_err!("Error: $with: 2 is not a map.", input);
 }
+
+#[test]
+fn load_css_with() {
+    let input = "@use \"sass:meta\";\na {\n @include meta.load-css(load_css_with, $with: (color: blue));\n}";
+    tempfile!(
+        "load_css_with.scss",
+        "$color: red !default;\nb { color: $color; }"
+    );
+    assert_eq!(
+        "a b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn load_css_with_non_default() {
+    let input = "@use \"sass:meta\";\n@include meta.load-css(load_css_with_non_default, $with: (color: blue));";
+    tempfile!(
+        "load_css_with_non_default.scss",
+        "$color: red;\nb { color: $color; }"
+    );
+    assert_err!(
+        "Error: This variable was not declared with !default in the @used module.",
+        input
+    );
+}
+
+#[test]
+fn load_css_does_not_expose_members() {
+    let input = "@use \"sass:meta\";\n@include meta.load-css(load_css_does_not_expose_members);\na {\n  color: $color;\n}";
+    tempfile!("load_css_does_not_expose_members.scss", "$color: red;");
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn load_css_does_not_see_local_variables() {
+    let input = "@use \"sass:meta\";\n@mixin foo($color: blue) {\n  @include meta.load-css(load_css_does_not_see_local_variables);\n}\na {\n  @include foo;\n}";
+    tempfile!(
+        "load_css_does_not_see_local_variables.scss",
+        "$color: red !default;\nb { color: $color; }"
+    );
+    assert_eq!(
+        "a b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn load_css_twice_nests_in_each_rule() {
+    let input = "@use \"sass:meta\";\na {\n @include meta.load-css(load_css_twice_nests_in_each_rule);\n}\nb {\n @include meta.load-css(load_css_twice_nests_in_each_rule);\n}";
+    tempfile!(
+        "load_css_twice_nests_in_each_rule.scss",
+        "c { color: red; }"
+    );
+    assert_eq!(
+        "a c {\n  color: red;\n}\n\nb c {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn load_css_already_used_with() {
+    let input = "@use \"load_css_already_used_with\";\n@use \"sass:meta\";\n@include meta.load-css(load_css_already_used_with, $with: (color: blue));";
+    tempfile!(
+        "load_css_already_used_with.scss",
+        "$color: red !default;\nb { color: $color; }"
+    );
+    assert_err!(
+        "Error: This module was already loaded, so it can't be configured using \"with\".",
+        input
+    );
+}
+
+#[test]
+fn load_css_builtin_module() {
+    let input =
+        "@use \"sass:meta\";\n@include meta.load-css(\"sass:math\");\na {\n  color: red;\n}";
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn load_css_builtin_module_with() {
+    let input = "@use \"sass:meta\";\n@include meta.load-css(\"sass:math\", $with: (pi: 3));";
+    assert_err!("Error: Built-in modules can't be configured.", input);
+}
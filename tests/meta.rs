@@ -26,6 +26,58 @@ test!(
     "a {\n  color: if(false, 1, 2);\n}\n",
     "a {\n  color: 2;\n}\n"
 );
+test!(
+    if_only_evaluates_branch_taken,
+    "$map: null;\na {\n  color: if($map, map-get($map, key), null);\n}\n",
+    ""
+);
+test!(
+    if_recursive_function,
+    "@function fact($n) {\n  @return if($n <= 1, 1, $n * fact($n - 1));\n}\na {\n  color: fact(5);\n}\n",
+    "a {\n  color: 120;\n}\n"
+);
+test!(
+    if_nested_commas,
+    "a {\n  color: if(true, (1, 2), [3, 4]);\n}\n",
+    "a {\n  color: 1, 2;\n}\n"
+);
+test!(
+    if_trailing_comma,
+    "a {\n  color: if(false, 1, 2,);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    if_named_args_out_of_order,
+    "a {\n  color: if($if-false: 2, $if_true: 1, $condition: false);\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    if_comma_in_string,
+    "a {\n  color: if(true, \"a, b\", c);\n}\n",
+    "a {\n  color: \"a, b\";\n}\n"
+);
+test!(
+    if_rest_args,
+    "$args: true, 1, 2;\na {\n  color: if($args...);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+error!(
+    if_too_many_args,
+    "a {\n  color: if(true, 1, 2, 3);\n}\n", "Error: Only 3 arguments allowed, but 4 were passed."
+);
+error!(
+    if_missing_arg,
+    "a {\n  color: if(true, 1);\n}\n", "Error: Missing argument $if-false."
+);
+error!(
+    if_unknown_named_arg,
+    "a {\n  color: if(true, 1, $else: 2);\n}\n", "Error: No argument named $else."
+);
+error!(
+    if_arg_passed_by_position_and_name,
+    "a {\n  color: if(true, 1, $condition: false);\n}\n",
+    "Error: Argument $condition was passed both by position and by name."
+);
 test!(
     feature_exists_dbl_quoted,
     "a {\n  color: feature-exists(\"at-error\")\n}\n",
@@ -71,9 +123,7 @@ test!(
     "a {\n  color: feature-exists(units-level-3)\n}\n",
     "a {\n  color: true;\n}\n"
 );
-// Unignore as more features are added
 test!(
-    #[ignore]
     feature_exists_custom_property,
     "a {\n  color: feature-exists(custom-property)\n}\n",
     "a {\n  color: true;\n}\n"
@@ -294,3 +344,12 @@ error!(
     mixin_exists_non_string,
     "a {color: mixin-exists(12px)}", "Error: $name: 12px is not a string."
 );
+test!(
+    feature_exists_empty_string,
+    "a {\n  color: feature-exists(\"\")\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+error!(
+    feature_exists_not_string,
+    "a {\n  color: feature-exists(1)\n}\n", "Error: $feature: 1 is not a string."
+);
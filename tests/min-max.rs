@@ -2,19 +2,19 @@
 mod macros;
 
 test!(
-    min_not_evaluated_units_percent,
+    min_simplified_units_percent,
     "a {\n  color: min(1%, 2%);\n}\n",
-    "a {\n  color: min(1%, 2%);\n}\n"
+    "a {\n  color: 1%;\n}\n"
 );
 test!(
-    min_not_evaluated_units_px,
+    min_simplified_units_px,
     "a {\n  color: min(1px, 2px);\n}\n",
-    "a {\n  color: min(1px, 2px);\n}\n"
+    "a {\n  color: 1px;\n}\n"
 );
 test!(
-    min_not_evaluated_no_units,
+    min_simplified_no_units,
     "a {\n  color: min(1, 2);\n}\n",
-    "a {\n  color: min(1, 2);\n}\n"
+    "a {\n  color: 1;\n}\n"
 );
 test!(
     min_not_evaluated_incompatible_units,
@@ -44,26 +44,25 @@ error!(
     min_too_few_args,
     "a {\n  color: min();\n}\n", "Error: At least one argument must be passed."
 );
-// note: we explicitly have units in the opposite order of `dart-sass`.
-// see https://github.com/sass/dart-sass/issues/766
-error!(
-    min_incompatible_units,
-    "$a: 1px;\n$b: 2%;\na {\n  color: min($a, $b);\n}\n", "Error: Incompatible units px and %."
+test!(
+    min_not_evaluated_variable_incompatible_units,
+    "$a: 1px;\n$b: 2%;\na {\n  color: min($a, $b);\n}\n",
+    "a {\n  color: min(1px, 2%);\n}\n"
 );
 test!(
-    max_not_evaluated_units_percent,
+    max_simplified_units_percent,
     "a {\n  color: max(1%, 2%);\n}\n",
-    "a {\n  color: max(1%, 2%);\n}\n"
+    "a {\n  color: 2%;\n}\n"
 );
 test!(
-    max_not_evaluated_units_px,
+    max_simplified_units_px,
     "a {\n  color: max(1px, 2px);\n}\n",
-    "a {\n  color: max(1px, 2px);\n}\n"
+    "a {\n  color: 2px;\n}\n"
 );
 test!(
-    max_not_evaluated_no_units,
+    max_simplified_no_units,
     "a {\n  color: max(1, 2);\n}\n",
-    "a {\n  color: max(1, 2);\n}\n"
+    "a {\n  color: 2;\n}\n"
 );
 test!(
     max_not_evaluated_incompatible_units,
@@ -98,10 +97,48 @@ error!(
     max_too_few_args,
     "a {\n  color: max();\n}\n", "Error: At least one argument must be passed."
 );
-// note: we explicitly have units in the opposite order of `dart-sass`.
-// see https://github.com/sass/dart-sass/issues/766
-error!(
-    max_incompatible_units,
-    "$a: 1px;\n$b: 2%;\na {\n  color: max($a, $b);\n}\n", "Error: Incompatible units px and %."
+test!(
+    max_not_evaluated_variable_incompatible_units,
+    "$a: 1px;\n$b: 2%;\na {\n  color: max($a, $b);\n}\n",
+    "a {\n  color: max(1px, 2%);\n}\n"
+);
+test!(
+    max_evaluated_variable_and_unitless_number,
+    "$a: 1px;\n$b: 2px;\na {\n  color: max($a, $b, 0);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    min_not_evaluated_variable_in_arithmetic,
+    "$a: 10px;\na {\n  color: min(100% - $a, 50%);\n}\n",
+    "a {\n  color: min(100% - 10px, 50%);\n}\n"
+);
+test!(
+    min_simplified_calc_and_variable,
+    "$b: 2px;\na {\n  color: min(calc(1), $b);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    min_nested_calc_is_unwrapped,
+    "a {\n  color: min(calc(1px + 2%), 3px);\n}\n",
+    "a {\n  color: min(1px + 2%, 3px);\n}\n"
+);
+test!(
+    min_not_evaluated_nested_env,
+    "a {\n  color: min(env(safe-area-inset-left), 1px);\n}\n",
+    "a {\n  color: min(env(safe-area-inset-left), 1px);\n}\n"
+);
+test!(
+    min_not_evaluated_nested_var_with_variable_fallback,
+    "$a: 10px;\na {\n  color: min(1px, var(--x, $a));\n}\n",
+    "a {\n  color: min(1px, var(--x, 10px));\n}\n"
+);
+test!(
+    min_not_evaluated_nested_clamp,
+    "a {\n  color: min(clamp(1px, 2%, 3px), 4vh);\n}\n",
+    "a {\n  color: min(clamp(1px, 2%, 3px), 4vh);\n}\n"
+);
+test!(
+    max_not_evaluated_nested_min,
+    "a {\n  color: max(min(1px, 2%), 3vh);\n}\n",
+    "a {\n  color: max(min(1px, 2%), 3vh);\n}\n"
 );
-// todo: special functions, min(calc(1), $b);
@@ -0,0 +1,79 @@
+/// Asserts that compiling `input` gives `output` both with and without mixin
+/// caching enabled
+macro_rules! cache_test {
+    ($func:ident, $input:expr, $output:expr) => {
+        #[test]
+        fn $func() {
+            let uncached = grass::from_string($input.to_string(), &grass::Options::default())
+                .expect(concat!("failed to parse on ", $input));
+            let cached = grass::from_string(
+                $input.to_string(),
+                &grass::Options::default().cache_mixins(true),
+            )
+            .expect(concat!("failed to parse on ", $input));
+            assert_eq!(String::from($output), uncached);
+            assert_eq!(uncached, cached);
+        }
+    };
+}
+
+cache_test!(
+    same_arguments,
+    "@mixin m($a) {\n  color: $a;\n}\n\na {\n  @include m(red);\n}\n\nb {\n  @include m(red);\n}\n",
+    "a {\n  color: red;\n}\n\nb {\n  color: red;\n}\n"
+);
+cache_test!(
+    different_arguments,
+    "@mixin m($a) {\n  color: $a;\n}\n\na {\n  @include m(red);\n  @include m(blue);\n}\n",
+    "a {\n  color: red;\n  color: blue;\n}\n"
+);
+cache_test!(
+    different_parent_selectors,
+    "@mixin m {\n  &:hover {\n    color: red;\n  }\n}\n\na {\n  @include m;\n}\n\nb {\n  @include m;\n}\n",
+    "a:hover {\n  color: red;\n}\n\nb:hover {\n  color: red;\n}\n"
+);
+cache_test!(
+    different_media_queries,
+    "@mixin m {\n  color: red;\n}\n\na {\n  @include m;\n}\n\n@media screen {\n  a {\n    @include m;\n  }\n}\n",
+    "a {\n  color: red;\n}\n@media screen {\n  a {\n    color: red;\n  }\n}\n"
+);
+cache_test!(
+    default_argument_reads_global,
+    "$color: red;\n\n@mixin m($a: $color) {\n  color: $a;\n}\n\na {\n  @include m;\n  $color: blue !global;\n  @include m;\n}\n",
+    "a {\n  color: red;\n  color: blue;\n}\n"
+);
+cache_test!(
+    reads_global_variable,
+    "$color: red;\n\n@mixin m {\n  color: $color;\n}\n\na {\n  @include m;\n  $color: blue !global;\n  @include m;\n}\n",
+    "a {\n  color: red;\n  color: blue;\n}\n"
+);
+cache_test!(
+    local_variables_and_loops,
+    "@mixin m($n) {\n  $width: $n * 10px;\n  @each $side in top, bottom {\n    margin-#{$side}: $width;\n  }\n}\n\na {\n  @include m(1);\n}\n\nb {\n  @include m(1);\n}\n",
+    "a {\n  margin-top: 10px;\n  margin-bottom: 10px;\n}\n\nb {\n  margin-top: 10px;\n  margin-bottom: 10px;\n}\n"
+);
+cache_test!(
+    sets_global_variable,
+    "$count: 0;\n\n@mixin m {\n  $count: $count + 1 !global;\n  width: $count;\n}\n\na {\n  @include m;\n  @include m;\n}\n",
+    "a {\n  width: 1;\n  width: 2;\n}\n"
+);
+cache_test!(
+    calls_user_defined_function,
+    "$count: 0;\n\n@function next() {\n  $count: $count + 1 !global;\n  @return $count;\n}\n\n@mixin m {\n  width: next();\n}\n\na {\n  @include m;\n  @include m;\n}\n",
+    "a {\n  width: 1;\n  width: 2;\n}\n"
+);
+cache_test!(
+    content_block,
+    "@mixin m {\n  @content;\n}\n\na {\n  @include m {\n    color: red;\n  }\n  @include m {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: red;\n  color: blue;\n}\n"
+);
+cache_test!(
+    extend_in_mixin,
+    "%p {\n  color: red;\n}\n\n@mixin m {\n  @extend %p;\n}\n\na {\n  @include m;\n}\n\nb {\n  @include m;\n}\n",
+    "b, a {\n  color: red;\n}\n"
+);
+cache_test!(
+    cached_rule_is_extended,
+    "@mixin m {\n  .c {\n    color: red;\n  }\n}\n\na {\n  @include m;\n  @include m;\n}\n\nd {\n  @extend .c;\n}\n",
+    "a .c, a d {\n  color: red;\n}\na .c, a d {\n  color: red;\n}\n"
+);
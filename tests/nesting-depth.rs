@@ -0,0 +1,69 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+#[test]
+fn max_nesting_depth_per_file() {
+    tempfile!("nesting_depth_per_file.scss", "a { b { c { d: e; } } }");
+    let stats = grass::compile_with_reports(
+        grass::Input::String("a { @import \"nesting_depth_per_file\"; } f { g: h; }".to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert_eq!(stats.max_nesting_depth["stdin"], 1);
+    assert_eq!(stats.max_nesting_depth["nesting_depth_per_file.scss"], 4);
+}
+
+#[test]
+fn max_nesting_depth_with_mixin() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("@mixin foo { b { c { d: e; } } } a { @include foo; }".to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert_eq!(stats.max_nesting_depth["stdin"], 3);
+}
+
+#[test]
+fn no_style_rules_is_omitted() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("$a: b;".to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+    assert!(stats.max_nesting_depth.is_empty());
+}
+
+#[test]
+fn nesting_within_limit() {
+    let options = grass::Options::default().max_nesting_depth(2);
+    assert_eq!(
+        "a b {\n  c: d;\n}\n",
+        grass::from_string("a { b { c: d; } }".to_string(), &options).unwrap()
+    );
+}
+
+#[test]
+fn nesting_exceeds_limit() {
+    let options = grass::Options::default().max_nesting_depth(2);
+    match grass::from_string("a { b { c { d: e; } } }".to_string(), &options) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Style rules may not be nested more than 2 levels deep.",
+            e.to_string()
+                .chars()
+                .take_while(|c| *c != '\n')
+                .collect::<String>()
+        ),
+    }
+}
@@ -0,0 +1,122 @@
+use grass::Number;
+
+fn parse(s: &str) -> Number {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parses_integer() {
+    assert_eq!(parse("12"), 12);
+    assert_eq!(parse("-12"), -12);
+    assert_eq!(parse("+12"), 12);
+}
+
+#[test]
+fn parses_decimal() {
+    assert_eq!("0.5", parse(".5").to_string());
+    assert_eq!("-1.25", parse("-1.25").to_string());
+}
+
+#[test]
+fn parses_exponent() {
+    assert_eq!(parse("1.5e3"), 1500);
+    assert_eq!("0.0015", parse("1.5E-3").to_string());
+}
+
+#[test]
+fn parses_more_digits_than_fit_in_i64() {
+    assert_eq!(
+        "123456789012345678901234567890",
+        parse("123456789012345678901234567890").to_string()
+    );
+}
+
+#[test]
+fn parse_errors() {
+    for s in &["", "-", "1.", "1.a", "a", "1e", "1e-", "1px"] {
+        assert_eq!(
+            "Expected digit.",
+            s.parse::<Number>().unwrap_err().to_string(),
+            "{}",
+            s
+        );
+    }
+    assert_eq!(
+        "Exponent too large.",
+        "1e100".parse::<Number>().unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Exponent too negative.",
+        "1e-100".parse::<Number>().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn is_int() {
+    assert!(parse("3.0").is_int());
+    assert!(parse("1.5e1").is_int());
+    assert!(!parse("1.5").is_int());
+}
+
+#[test]
+fn to_i64() {
+    assert_eq!(Some(3), parse("3.0").to_i64());
+    assert_eq!(None, parse("3.5").to_i64());
+    assert_eq!(None, parse("123456789012345678901234567890").to_i64());
+}
+
+#[test]
+fn to_f64() {
+    assert_eq!(0.25, parse(".25").to_f64());
+}
+
+#[test]
+fn arithmetic_is_exact() {
+    assert_eq!(parse("0.3"), parse("0.1") + parse("0.2"));
+    assert_eq!(parse("0.1"), parse("0.3") - parse("0.2"));
+    assert_eq!(parse("0.75"), parse("1.5") * parse("0.5"));
+    assert_eq!(-Number::from(3), -3);
+}
+
+#[test]
+fn checked_div() {
+    assert_eq!(
+        Some(parse("2.5")),
+        Number::from(5).checked_div(&Number::from(2))
+    );
+    assert_eq!(None, Number::from(5).checked_div(&Number::from(0)));
+}
+
+#[test]
+fn displays_like_dart_sass() {
+    let third = Number::from(1).checked_div(&Number::from(3)).unwrap();
+    assert_eq!("0.3333333333", third.to_string());
+    let two_thirds = Number::from(2).checked_div(&Number::from(3)).unwrap();
+    assert_eq!("0.6666666667", two_thirds.to_string());
+    assert_eq!("1", parse("0.99999999999").to_string());
+    assert_eq!("-0.5", parse("-.5").to_string());
+}
+
+#[test]
+fn matches_compiled_output() {
+    let css = grass::from_string(
+        "a { width: 1/3 * 2.5em; }".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap();
+    let n = Number::from(1).checked_div(&Number::from(3)).unwrap() * parse("2.5");
+    assert_eq!(format!("a {{\n  width: {}em;\n}}\n", n), css);
+}
+
+#[test]
+fn to_string_with_precision() {
+    let third = Number::from(1).checked_div(&Number::from(3)).unwrap();
+    assert_eq!("0.333", third.to_string_with_precision(3));
+    assert_eq!("0.3333333333", third.to_string());
+}
+
+#[test]
+fn ordering() {
+    assert!(parse("1.5") < parse("2"));
+    assert!(parse("1.5") > 1);
+}
@@ -189,3 +189,85 @@ error!(
     scientific_notation_too_negative,
     "a {\n  color: 1e-100;\n}\n", "Error: Exponent too negative."
 );
+test!(
+    third_times_three_is_exactly_one,
+    "a {\n  color: (1/3) * 3 == 1;\n  width: (1/3) * 3;\n}\n",
+    "a {\n  color: true;\n  width: 1;\n}\n"
+);
+test!(
+    decimal_addition_is_exact,
+    "a {\n  color: 0.1 + 0.2 == 0.3;\n  width: 0.1 + 0.2;\n}\n",
+    "a {\n  color: true;\n  width: 0.3;\n}\n"
+);
+test!(
+    two_thirds_rounds_last_digit,
+    "a {\n  color: (2/3);\n}\n",
+    "a {\n  color: 0.6666666667;\n}\n"
+);
+
+fn compile_with_precision(input: &str, precision: usize) -> String {
+    grass::from_string(
+        input.to_string(),
+        &grass::Options::default().precision(precision),
+    )
+    .unwrap()
+}
+
+#[test]
+fn lower_precision() {
+    assert_eq!(
+        "a {\n  color: 0.33333;\n  width: 0.66667px;\n  height: 1.2;\n}\n",
+        compile_with_precision(
+            "a {\n  color: (1/3);\n  width: (2px/3);\n  height: 1.2;\n}\n",
+            5
+        )
+    );
+}
+
+#[test]
+fn higher_precision() {
+    assert_eq!(
+        "a {\n  color: 0.333333333333333;\n}\n",
+        compile_with_precision("a {\n  color: (1/3);\n}\n", 15)
+    );
+}
+
+#[test]
+fn precision_rounds_half_up() {
+    assert_eq!(
+        "a {\n  color: 1.01;\n  width: -1.01;\n}\n",
+        compile_with_precision("a {\n  color: 1.005;\n  width: -1.005;\n}\n", 2)
+    );
+}
+
+#[test]
+fn zero_precision_rounds_to_integer() {
+    assert_eq!(
+        "a {\n  color: 1;\n  width: 0;\n  height: 3px;\n}\n",
+        compile_with_precision(
+            "a {\n  color: (2/3);\n  width: -0.4;\n  height: 2.5px;\n}\n",
+            0
+        )
+    );
+}
+
+#[test]
+fn precision_does_not_affect_equality() {
+    assert_eq!(
+        "a {\n  color: false;\n}\n",
+        compile_with_precision("a {\n  color: 1.001 == 1;\n}\n", 2)
+    );
+}
+
+#[test]
+fn precision_is_reset_after_compiling() {
+    compile_with_precision("a {\n  color: (1/3);\n}\n", 2);
+    assert_eq!(
+        "a {\n  color: 0.3333333333;\n}\n",
+        grass::from_string(
+            "a {\n  color: (1/3);\n}\n".to_string(),
+            &grass::Options::default()
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,187 @@
+use grass::{MemoryFs, Options, Syntax};
+
+fn compile_css(input: &str) -> Result<String, String> {
+    grass::compile_string(input.to_string(), &Options::default().syntax(Syntax::Css))
+        .map_err(|err| err.to_string().chars().take_while(|c| *c != '\n').collect())
+}
+
+#[test]
+fn passes_through_css() {
+    assert_eq!(
+        Ok("a {\n  color: rgb(1, 2, 3);\n  margin: 0 -1px;\n  font: 12px/1.5 sans-serif;\n  b: var(--x);\n}\n@media (min-width: 100px) {\n  a {\n    b: c;\n  }\n}\n".to_string()),
+        compile_css("a { color: rgb(1, 2, 3); margin: 0 -1px; font: 12px/1.5 sans-serif; b: var(--x) }\n@media (min-width: 100px) { a { b: c } }")
+    );
+}
+
+#[test]
+fn keyword_operators_are_identifiers() {
+    assert_eq!(
+        Ok("a {\n  b: c and d or not e;\n}\n".to_string()),
+        compile_css("a { b: c and d or not e }")
+    );
+}
+
+#[test]
+fn import_is_always_plain() {
+    assert_eq!(
+        Ok("@import \"library\";\n".to_string()),
+        compile_css("@import \"library\";")
+    );
+}
+
+#[test]
+fn loud_comment_is_not_interpolated() {
+    assert_eq!(Ok("/* #{a} */\n".to_string()), compile_css("/* #{a} */"));
+}
+
+#[test]
+fn variable_declaration() {
+    assert_eq!(
+        Err("Error: Sass variables aren't allowed in plain CSS.".to_string()),
+        compile_css("$a: red;")
+    );
+}
+
+#[test]
+fn variable_reference() {
+    assert_eq!(
+        Err("Error: Sass variables aren't allowed in plain CSS.".to_string()),
+        compile_css("a { color: $a; }")
+    );
+}
+
+#[test]
+fn sass_at_rule() {
+    assert_eq!(
+        Err("Error: This at-rule isn't allowed in plain CSS.".to_string()),
+        compile_css("@if true { a { color: red; } }")
+    );
+}
+
+#[test]
+fn use_rule() {
+    assert_eq!(
+        Err("Error: This at-rule isn't allowed in plain CSS.".to_string()),
+        compile_css("@use \"sass:math\";")
+    );
+}
+
+#[test]
+fn interpolation() {
+    assert_eq!(
+        Err("Error: Interpolation isn't allowed in plain CSS.".to_string()),
+        compile_css("a { color: #{red}; }")
+    );
+}
+
+#[test]
+fn silent_comment() {
+    assert_eq!(
+        Err("Error: Silent comments aren't allowed in plain CSS.".to_string()),
+        compile_css("// a\na { color: red; }")
+    );
+}
+
+#[test]
+fn nested_declaration() {
+    assert_eq!(
+        Err("Error: Nested declarations aren't allowed in plain CSS.".to_string()),
+        compile_css("a { font: { family: serif; } }")
+    );
+}
+
+#[test]
+fn placeholder_selector() {
+    assert_eq!(
+        Err("Error: Placeholder selectors aren't allowed in plain CSS.".to_string()),
+        compile_css("%a { color: red; }")
+    );
+}
+
+#[test]
+fn sass_function() {
+    assert_eq!(
+        Err("Error: This function isn't allowed in plain CSS.".to_string()),
+        compile_css("a { color: lighten(red, 10%); }")
+    );
+}
+
+#[test]
+fn module_function() {
+    assert_eq!(
+        Err("Error: Module namespaces aren't allowed in plain CSS.".to_string()),
+        compile_css("a { width: math.div(1, 2); }")
+    );
+}
+
+#[test]
+fn operator() {
+    assert_eq!(
+        Err("Error: Operators aren't allowed in plain CSS.".to_string()),
+        compile_css("a { width: 1px + 2px; }")
+    );
+}
+
+#[test]
+fn parentheses() {
+    assert_eq!(
+        Err("Error: Parentheses aren't allowed in plain CSS.".to_string()),
+        compile_css("a { width: (1px); }")
+    );
+}
+
+#[test]
+fn use_css_file() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("library.css", "a { color: red; }");
+
+    let css = grass::compile_string(
+        "@use \"library\";\nb { color: blue; }".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n", css);
+}
+
+#[test]
+fn use_css_file_with_variable() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("library.css", "$a: red;");
+
+    let err = grass::compile_string(
+        "@use \"library.css\";".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Error: Sass variables aren't allowed in plain CSS."));
+}
+
+#[test]
+fn import_css_file_with_mixin() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("library.css", "a { @include b; }");
+
+    let err = grass::compile_string(
+        "@mixin b { color: red; }\n@import \"library\";".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Error: This at-rule isn't allowed in plain CSS."));
+}
+
+#[test]
+fn scss_is_unaffected_by_imported_css() {
+    let mut fs = MemoryFs::default();
+    fs.add_file("library.css", "a { color: red; }");
+
+    let css = grass::compile_string(
+        "@import \"library\";\n$b: blue;\nb { color: $b; }".to_string(),
+        &Options::default().fs(&fs),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n", css);
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "plugins")]
+
+use std::{
+    env,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    path::Path,
+    process::Command,
+    ptr,
+};
+
+use grass::{Options, Plugin, PluginFunction};
+
+unsafe extern "C" fn double(
+    argc: usize,
+    argv: *const *const c_char,
+    is_error: *mut bool,
+) -> *mut c_char {
+    if argc != 1 {
+        *is_error = true;
+        return CString::new("double() takes exactly one argument.")
+            .unwrap()
+            .into_raw();
+    }
+    let arg = CStr::from_ptr(*argv).to_str().unwrap();
+    CString::new(format!("({}) * 2", arg)).unwrap().into_raw()
+}
+
+unsafe extern "C" fn join_args(
+    argc: usize,
+    argv: *const *const c_char,
+    _: *mut bool,
+) -> *mut c_char {
+    let args = (0..argc)
+        .map(|i| CStr::from_ptr(*argv.add(i)).to_str().unwrap())
+        .collect::<Vec<_>>();
+    CString::new(format!("\"{}\"", args.join("|")))
+        .unwrap()
+        .into_raw()
+}
+
+unsafe extern "C" fn free(result: *mut c_char) {
+    drop(CString::from_raw(result));
+}
+
+fn plugin() -> Plugin {
+    let functions = [
+        PluginFunction {
+            name: b"double\0".as_ptr().cast(),
+            call: Some(double),
+            free: Some(free),
+        },
+        PluginFunction {
+            name: b"join_args\0".as_ptr().cast(),
+            call: Some(join_args),
+            free: Some(free),
+        },
+        PluginFunction {
+            name: ptr::null(),
+            call: None,
+            free: None,
+        },
+    ];
+    unsafe { Plugin::from_raw(functions.as_ptr()) }.unwrap()
+}
+
+fn compile(input: &str, plugin: &Plugin) -> grass::Result<String> {
+    grass::from_string(input.to_string(), &Options::default().plugin(plugin))
+}
+
+#[test]
+fn calls_plugin_function() {
+    assert_eq!(
+        "a {\n  width: 20px;\n}\n",
+        compile("a {\n  width: double(10px);\n}\n", &plugin()).unwrap()
+    );
+}
+
+#[test]
+fn arguments_are_inspected() {
+    assert_eq!(
+        "a {\n  color: \"1px|a|(b: c)|1 2\";\n}\n",
+        compile(
+            "a {\n  color: join-args(1px, a, (b: c), 1 2);\n}\n",
+            &plugin()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn plugin_error_is_sass_error() {
+    match compile("a {\n  width: double(1, 2);\n}\n", &plugin()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: double() takes exactly one argument.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn user_defined_function_takes_precedence() {
+    assert_eq!(
+        "a {\n  width: 1;\n}\n",
+        compile(
+            "@function double($a) {\n  @return 1;\n}\na {\n  width: double(10px);\n}\n",
+            &plugin()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn plugin_function_reference() {
+    assert_eq!(
+        "a {\n  width: 4;\n  exists: true;\n}\n",
+        compile(
+            "a {\n  width: call(get-function(double), 2);\n  exists: function-exists(double);\n}\n",
+            &plugin()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn plugin_functions_are_not_global() {
+    assert_eq!(
+        "a {\n  width: double(10px);\n}\n",
+        grass::from_string(
+            "a {\n  width: double(10px);\n}\n".to_string(),
+            &Options::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn load_missing_library() {
+    assert!(Plugin::load("/does/not/exist.so").is_err());
+}
+
+#[test]
+fn load_plugin_from_dynamic_library() {
+    let dir = tempfile::tempdir().unwrap();
+    let lib = dir.path().join(format!(
+        "{}double{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    ));
+
+    let status = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+        .args(&["--crate-type", "cdylib", "--edition", "2018", "-o"])
+        .arg(&lib)
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/plugins/double.rs"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let plugin = Plugin::load(&lib).unwrap();
+    assert_eq!(vec!["double"], plugin.function_names().collect::<Vec<_>>());
+    assert_eq!(
+        "a {\n  width: 20px;\n}\n",
+        compile("a {\n  width: double(10px);\n}\n", &plugin).unwrap()
+    );
+}
@@ -0,0 +1,62 @@
+//! A plugin exporting a single function, `double()`, built as a dynamic
+//! library by `tests/plugins.rs` to test loading plugins with `Plugin::load`
+
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    ptr,
+};
+
+#[repr(C)]
+pub struct GrassPluginFunction {
+    name: *const c_char,
+    call: Option<unsafe extern "C" fn(usize, *const *const c_char, *mut bool) -> *mut c_char>,
+    free: Option<unsafe extern "C" fn(*mut c_char)>,
+}
+
+struct Functions([GrassPluginFunction; 2]);
+
+// SAFETY: the functions are never modified, and only point to static data
+unsafe impl Sync for Functions {}
+
+static FUNCTIONS: Functions = Functions([
+    GrassPluginFunction {
+        name: b"double\0" as *const u8 as *const c_char,
+        call: Some(double),
+        free: Some(free),
+    },
+    GrassPluginFunction {
+        name: ptr::null(),
+        call: None,
+        free: None,
+    },
+]);
+
+unsafe extern "C" fn double(
+    argc: usize,
+    argv: *const *const c_char,
+    is_error: *mut bool,
+) -> *mut c_char {
+    if argc != 1 {
+        *is_error = true;
+        return CString::new("double() takes exactly one argument.")
+            .unwrap()
+            .into_raw();
+    }
+    let arg = CStr::from_ptr(*argv).to_str().unwrap();
+    CString::new(format!("({}) * 2", arg)).unwrap().into_raw()
+}
+
+unsafe extern "C" fn free(result: *mut c_char) {
+    drop(CString::from_raw(result));
+}
+
+#[no_mangle]
+pub extern "C" fn grass_plugin_abi_version() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn grass_plugin_functions() -> *const GrassPluginFunction {
+    FUNCTIONS.0.as_ptr()
+}
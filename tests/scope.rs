@@ -79,3 +79,74 @@ test!(
     }",
     "a a {\n  color: true;\n  color: true;\n}\n"
 );
+test!(
+    local_variable_shadows_global_when_global_is_reassigned,
+    "$a: 1;
+    a {
+        $a: 2;
+        $a: 3 !global;
+        color: $a;
+    }
+    b {
+        color: $a;
+    }",
+    "a {\n  color: 2;\n}\n\nb {\n  color: 3;\n}\n"
+);
+test!(
+    default_in_nested_scope_sees_global,
+    "$a: 1;
+    a {
+        $a: 2 !default;
+        color: $a;
+    }",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    default_in_nested_scope_sees_shadowing_null_local,
+    "a {
+        $a: null;
+        b {
+            $a: 2 !default;
+        }
+        color: $a;
+    }",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    root_control_flow_updates_loop_variable_before_global,
+    "$a: 1;
+    @each $a in 5 {
+        $a: 2;
+        a {
+            color: $a;
+        }
+    }
+    b {
+        color: $a;
+    }",
+    "a {\n  color: 2;\n}\n\nb {\n  color: 1;\n}\n"
+);
+test!(
+    nested_control_flow_does_not_update_global,
+    "$a: 1;
+    a {
+        @if true {
+            $a: 2;
+        }
+        color: $a;
+    }",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    return_from_loop_leaves_loop_scope,
+    "a {
+        @function foo() {
+            @each $i in 1 2 {
+                @return $i;
+            }
+        }
+        color: foo();
+        color: variable-exists(i);
+    }",
+    "a {\n  color: 1;\n  color: false;\n}\n"
+);
@@ -58,11 +58,12 @@ error!(
 error!(
     parent_in_second_arg,
     "a {\n  color: selector-append(\"c\", \"&\");\n}\n",
-    "Error: Parent selectors aren't allowed here."
+    "Error: $selectors: Parent selectors aren't allowed here."
 );
 error!(
     malformed_selector_in_first_arg,
-    "a {\n  color: selector-append(\"[c\", \".d\");\n}\n", "Error: expected more input."
+    "a {\n  color: selector-append(\"[c\", \".d\");\n}\n",
+    "Error: $selectors: expected more input."
 );
 error!(
     invalid_type_in_first_arg,
@@ -296,11 +296,61 @@ test!(
     "a {\n  color: selector-extend(\":not(.c)\", \".c\", \".d:matches(.e, .f)\");\n}\n",
     "a {\n  color: :not(.c):not(.d:matches(.e, .f));\n}\n"
 );
+test!(
+    simple_pseudo_idempotent_not_and_is_list,
+    "a {\n  color: selector-extend(\":not(.c)\", \".c\", \":is(.d, .e)\");\n}\n",
+    "a {\n  color: :not(.c):not(.d):not(.e);\n}\n"
+);
+test!(
+    simple_pseudo_idempotent_not_and_where_list,
+    "a {\n  color: selector-extend(\":not(.c)\", \".c\", \":where(.d .e, .f .g)\");\n}\n",
+    "a {\n  color: :not(.c):not(.d .e):not(.f .g);\n}\n"
+);
+test!(
+    simple_pseudo_idempotent_is_and_is_list,
+    "a {\n  color: selector-extend(\":is(.c)\", \".c\", \":is(.d, .e)\");\n}\n",
+    "a {\n  color: :is(.c, .d, .e);\n}\n"
+);
+test!(
+    simple_pseudo_idempotent_where_and_where_list,
+    "a {\n  color: selector-extend(\":where(.c)\", \".c\", \":where(.d, .e)\");\n}\n",
+    "a {\n  color: :where(.c, .d, .e);\n}\n"
+);
+test!(
+    simple_pseudo_nth_child_of,
+    "a {\n  color: selector-extend(\":nth-child(2n of .c)\", \".c\", \".d\");\n}\n",
+    "a {\n  color: :nth-child(2n of .c, .d);\n}\n"
+);
+test!(
+    simple_pseudo_nth_last_child_of,
+    "a {\n  color: selector-extend(\":nth-last-child(2n+1 of .c)\", \".c\", \".d\");\n}\n",
+    "a {\n  color: :nth-last-child(2n+1 of .c, .d);\n}\n"
+);
 test!(
     simple_pseudo_idempotent_not_and_not_in_extender,
     "a {\n  color: selector-extend(\":not(.c)\", \".c\", \":not(.d)\");\n}\n",
     "a {\n  color: :not(.c);\n}\n"
 );
+error!(
+    parent_in_selector,
+    "a {\n  color: selector-extend(\"&\", \"c\", \"d\");\n}\n",
+    "Error: $selector: Parent selectors aren't allowed here."
+);
+test!(
+    trims_more_than_one_hundred_selectors,
+    "$extenders: \".c\";\n@for $i from 1 through 100 {\n  $extenders: append($extenders, \".c.b#{$i}\", comma);\n}\na {\n  color: selector-extend(\".a\", \".a\", $extenders);\n}\n",
+    "a {\n  color: .a, .c;\n}\n"
+);
+error!(
+    complex_extendee,
+    "a {\n  color: selector-extend(\"c\", \"c d\", \"e\");\n}\n",
+    "Error: Can't extend complex selector c d."
+);
+error!(
+    malformed_extender,
+    "a {\n  color: selector-extend(\"c\", \"c\", \"[d\");\n}\n",
+    "Error: $extender: expected more input."
+);
 // todo: https://github.com/sass/sass-spec/blob/master/spec/core_functions/selector/extend/simple/pseudo/selector/idempotent.hrx
 // (starting at line 113)
 // todo: https://github.com/sass/sass-spec/tree/master/spec/core_functions/selector/extend/simple/pseudo/selector/
@@ -0,0 +1,87 @@
+use grass::SelectorMapping;
+
+fn selector_map(input: &str) -> Vec<SelectorMapping> {
+    grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().selector_map(true),
+    )
+    .expect(input)
+    .selector_map
+    .unwrap()
+}
+
+#[test]
+fn nested_rule_maps_to_resolved_selector() {
+    let map = selector_map("a {\n  color: red;\n  b {\n    color: red;\n  }\n}\n");
+    assert_eq!(
+        map,
+        vec![
+            SelectorMapping {
+                file: "stdin".to_owned(),
+                line: 1,
+                column: 1,
+                source: "a".to_owned(),
+                output: "a".to_owned(),
+            },
+            SelectorMapping {
+                file: "stdin".to_owned(),
+                line: 3,
+                column: 3,
+                source: "b".to_owned(),
+                output: "a b".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn parent_selector_and_interpolation_are_kept_in_source() {
+    let map = selector_map("a {\n  &:hover, .b #{\"c\"} {\n    color: red;\n  }\n}\n");
+    assert_eq!(map[1].source, "&:hover, .b #{\"c\"}");
+    assert_eq!(map[1].output, "a:hover, a .b c");
+}
+
+#[test]
+fn output_includes_extensions() {
+    let map = selector_map("a {\n  color: red;\n}\n\n.b {\n  @extend a;\n}\n");
+    assert_eq!(map[0].output, "a, .b");
+    assert_eq!(map[1].output, ".b");
+}
+
+#[test]
+fn extension_added_after_rule_is_reflected() {
+    let map = selector_map("%a {\n  color: red;\n}\n\n.b {\n  @extend %a;\n}\n");
+    assert_eq!(map[0].source, "%a");
+    assert_eq!(map[0].output, ".b");
+}
+
+#[test]
+fn unextended_placeholder_has_empty_output() {
+    let map = selector_map("%a {\n  color: red;\n}\n");
+    assert_eq!(map[0].output, "");
+}
+
+#[test]
+fn rule_inside_mixin_points_to_mixin() {
+    let map =
+        selector_map("@mixin foo {\n  .c {\n    color: red;\n  }\n}\n\na {\n  @include foo;\n}\n");
+    assert_eq!(map[1].line, 2);
+    assert_eq!(map[1].source, ".c");
+    assert_eq!(map[1].output, "a .c");
+}
+
+#[test]
+fn css_output_is_unchanged() {
+    let input = "a {\n  b {\n    color: red;\n  }\n}\n";
+    assert_eq!(
+        grass::compile_with_reports(
+            grass::Input::String(input.to_string()),
+            &grass::Options::default(),
+            grass::ReportOptions::default().selector_map(true)
+        )
+        .unwrap()
+        .css,
+        grass::from_string(input.to_string(), &grass::Options::default()).unwrap()
+    );
+}
@@ -91,6 +91,16 @@ test!(
     "a {\n  color: selector-parse(\"b > c > d\");\n}\n",
     "a {\n  color: b > c > d;\n}\n"
 );
+test!(
+    is_with_selector_list,
+    "a {\n  color: selector-parse(\":is(b, c)\");\n}\n",
+    "a {\n  color: :is(b, c);\n}\n"
+);
+test!(
+    where_with_selector_list,
+    "a {\n  color: selector-parse(\":where(b c, d)\");\n}\n",
+    "a {\n  color: :where(b c, d);\n}\n"
+);
 test!(
     comma_and_space_list,
     "a {\n  color: selector-parse(\"b c, d e, f g\");\n}\n",
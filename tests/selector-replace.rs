@@ -36,3 +36,38 @@ test!(
     "a {\n  color: selector-replace(\"c, d\", \"d\", \"e\");\n}\n",
     "a {\n  color: c, e;\n}\n"
 );
+test!(
+    multiple_originals,
+    "a {\n  color: selector-replace(\"c.d\", \".d, c\", \"e\");\n}\n",
+    "a {\n  color: e;\n}\n"
+);
+test!(
+    list_of_strings,
+    "a {\n  color: selector-replace((\"c\", \"d\"), \"c\", \"e\");\n}\n",
+    "a {\n  color: e, d;\n}\n"
+);
+error!(
+    parent_in_selector,
+    "a {\n  color: selector-replace(\"&\", \"c\", \"d\");\n}\n",
+    "Error: $selector: Parent selectors aren't allowed here."
+);
+error!(
+    parent_in_original,
+    "a {\n  color: selector-replace(\"c\", \"&\", \"d\");\n}\n",
+    "Error: $original: Parent selectors aren't allowed here."
+);
+error!(
+    parent_in_replacement,
+    "a {\n  color: selector-replace(\"c\", \"c\", \"&\");\n}\n",
+    "Error: $replacement: Parent selectors aren't allowed here."
+);
+error!(
+    complex_original,
+    "a {\n  color: selector-replace(\"c\", \"c d\", \"e\");\n}\n",
+    "Error: Can't extend complex selector c d."
+);
+error!(
+    invalid_replacement_type,
+    "a {\n  color: selector-replace(\"c\", \"c\", 1);\n}\n",
+    "Error: $replacement: 1 is not a valid selector: it must be a string, a list of strings, or a list of lists of strings."
+);
@@ -41,6 +41,16 @@ test!(
     "a {\n  color: selector-unify(\"[a]\", \"[a]\");\n}\n",
     "a {\n  color: [a];\n}\n"
 );
+test!(
+    attribute_quoted_and_unquoted_value,
+    "a {\n  color: selector-unify(\"[a='b']\", \"[a=b]\");\n}\n",
+    "a {\n  color: [a=b];\n}\n"
+);
+test!(
+    attribute_different_modifier,
+    "a {\n  color: selector-unify(\"[a=b i]\", \"[a=b]\");\n}\n",
+    "a {\n  color: [a=b i][a=b];\n}\n"
+);
 test!(
     attribute_distinct,
     "a {\n  color: selector-unify(\"[a]\", \"[b]\");\n}\n",
@@ -81,6 +91,21 @@ test!(
     "a {\n  color: selector-unify(\"%a\", \"%b\");\n}\n",
     "a {\n  color: %a%b;\n}\n"
 );
+test!(
+    type_different_namespaces,
+    "a {\n  color: selector-unify(\"a|x\", \"b|x\");\n}\n",
+    ""
+);
+test!(
+    type_universal_namespace_and_explicit_namespace,
+    "a {\n  color: selector-unify(\"*|x\", \"a|x\");\n}\n",
+    "a {\n  color: a|x;\n}\n"
+);
+test!(
+    type_empty_namespace_and_implicit_namespace,
+    "a {\n  color: selector-unify(\"|x\", \"x\");\n}\n",
+    ""
+);
 test!(
     universal_and_namespace,
     "a {\n  color: selector-unify(\"*\", \"a|b\");\n}\n",
@@ -579,12 +604,10 @@ error!(
     "Error: $selector2: Parent selectors aren't allowed here."
 );
 error!(
-    #[ignore = "we don't include the name of the arg in the error message"]
     malformed_selector_in_first_arg,
     "a {\n  color: selector-unify(\"[c\", \"c\");\n}\n", "Error: $selector1: expected more input."
 );
 error!(
-    #[ignore = "we don't include the name of the arg in the error message"]
     malformed_selector_in_second_arg,
     "a {\n  color: selector-unify(\"c\", \"[c\");\n}\n", "Error: $selector2: expected more input."
 );
@@ -658,6 +681,36 @@ test!(
     "a {\n  color: inspect(selector-unify(\"::c(@#$)\", \"::c(*&^)\"));\n}\n",
     "a {\n  color: null;\n}\n"
 );
+test!(
+    column_combinator_same,
+    "a {\n  color: selector-unify(\".c || .d\", \".c || .d\");\n}\n",
+    "a {\n  color: .c || .d;\n}\n"
+);
+test!(
+    column_combinator_superselector,
+    "a {\n  color: selector-unify(\".c.e || .d\", \".c || .f\");\n}\n",
+    "a {\n  color: .c.e || .d.f;\n}\n"
+);
+test!(
+    column_combinator_different,
+    "a {\n  color: inspect(selector-unify(\".c || .d\", \".e || .f\"));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    column_combinator_and_child,
+    "a {\n  color: inspect(selector-unify(\".c || .d\", \".e > .f\"));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    column_combinator_and_descendant,
+    "a {\n  color: inspect(selector-unify(\".c || .d\", \".e .f\"));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    column_combinator_and_compound,
+    "a {\n  color: selector-unify(\".c || .d\", \".e\");\n}\n",
+    "a {\n  color: .c || .d.e;\n}\n"
+);
 test!(
     simple_pseudo_arg_matches_same_selector_arg,
     "a {\n  color: selector-unify(\":matches(.c)\", \":matches(.c)\");\n}\n",
@@ -142,6 +142,26 @@ test!(
     "[*|foo] {\n  color: red;\n}\n",
     "[*|foo] {\n  color: red;\n}\n"
 );
+test!(
+    selector_attribute_named_namespace,
+    "[ns|foo^=bar] {\n  color: red;\n}\n",
+    "[ns|foo^=bar] {\n  color: red;\n}\n"
+);
+test!(
+    selector_attribute_empty_namespace,
+    "[|foo*=bar] {\n  color: red;\n}\n",
+    "[|foo*=bar] {\n  color: red;\n}\n"
+);
+test!(
+    selector_attribute_modifier_after_quoted_value,
+    "[attr=\"val\"i] {\n  color: red;\n}\n",
+    "[attr=val i] {\n  color: red;\n}\n"
+);
+test!(
+    selector_attribute_chooses_quotes_for_value,
+    "[attr=\"\\\"val\\\"\"] {\n  color: red;\n}\n",
+    "[attr='\"val\"'] {\n  color: red;\n}\n"
+);
 error!(
     selector_attribute_missing_equal,
     "[a~b] {\n  color: red;\n}\n", "Error: expected \"=\"."
@@ -325,6 +345,40 @@ test!(
     "a {\n&__b {\n  color: red;\n}\n}\n",
     "a__b {\n  color: red;\n}\n"
 );
+test!(
+    parent_suffix_beginning_with_digit,
+    ".a {\n  &1 {\n    color: red;\n  }\n}\n",
+    ".a1 {\n  color: red;\n}\n"
+);
+test!(
+    parent_suffix_beginning_with_escaped_digit,
+    ".a {\n  &\\31 {\n    color: red;\n  }\n}\n",
+    ".a1 {\n  color: red;\n}\n"
+);
+test!(
+    parent_suffix_on_pseudo_class,
+    ".a:hover {\n  &-b {\n    color: red;\n  }\n}\n",
+    ".a:hover-b {\n  color: red;\n}\n"
+);
+test!(
+    parent_suffix_on_each_complex_selector,
+    ".a .b, .c {\n  &-d {\n    color: red;\n  }\n}\n",
+    ".a .b-d, .c-d {\n  color: red;\n}\n"
+);
+test!(
+    parent_selector_on_both_sides_of_combinator,
+    ".a, .b {\n  & + & {\n    color: red;\n  }\n}\n",
+    ".a + .a, .a + .b, .b + .a, .b + .b {\n  color: red;\n}\n"
+);
+error!(
+    parent_suffix_on_universal_selector,
+    "* {\n  &-a {\n    color: red;\n  }\n}\n", "Error: Invalid parent selector \"*\""
+);
+error!(
+    toplevel_parent_selector_in_media,
+    "@media screen {\n  & {\n    color: red;\n  }\n}\n",
+    "Error: Top-level selectors may not contain the parent selector \"&\"."
+);
 test!(
     selector_interpolation_addition,
     "#{\"foo\" + \" bar\"}baz {color: red;}",
@@ -397,9 +451,34 @@ test!(
 );
 test!(
     combinator_alone,
-    "a {\n  + {\n    b {\n      color: red;\n  }\n}\n",
+    "a {\n  + {\n    b {\n      color: red;\n    }\n  }\n}\n",
     "a + b {\n  color: red;\n}\n"
 );
+test!(
+    combinator_column,
+    "col.selected || td {\n  color: red;\n}\n",
+    "col.selected || td {\n  color: red;\n}\n"
+);
+test!(
+    combinator_column_no_whitespace,
+    "a||b {\n  color: red;\n}\n",
+    "a || b {\n  color: red;\n}\n"
+);
+test!(
+    combinator_column_after_universal,
+    "*||b {\n  color: red;\n}\n",
+    "* || b {\n  color: red;\n}\n"
+);
+test!(
+    combinator_column_after_namespaced_type,
+    "ns|a || b {\n  color: red;\n}\n",
+    "ns|a || b {\n  color: red;\n}\n"
+);
+test!(
+    combinator_column_preceding,
+    "a {\n  || b {\n    color: red;\n  }\n}\n",
+    "a || b {\n  color: red;\n}\n"
+);
 test!(
     simple_multiple_newline,
     "a,\nb {\n  color: red;\n}\n",
@@ -713,6 +792,30 @@ test!(
     ":nth-child(2n+1 of b, c) {\n  color: &;\n}\n",
     ":nth-child(2n+1 of b, c) {\n  color: :nth-child(2n+1 of b, c);\n}\n"
 );
+test!(
+    a_n_of,
+    ":nth-child(2n of b) {\n  color: &;\n}\n",
+    ":nth-child(2n of b) {\n  color: :nth-child(2n of b);\n}\n"
+);
+test!(
+    a_n_plus_b_of_with_whitespace,
+    ":nth-last-child( -n + 3  of  b , c ) {\n  color: &;\n}\n",
+    ":nth-last-child(-n+3 of b, c) {\n  color: :nth-last-child(-n+3 of b, c);\n}\n"
+);
+test!(
+    even_of,
+    ":nth-child(even of li.important) {\n  color: &;\n}\n",
+    ":nth-child(even of li.important) {\n  color: :nth-child(even of li.important);\n}\n"
+);
+test!(
+    parent_selector_in_of,
+    "a {\n  :nth-child(odd of &) {\n    color: red;\n  }\n}\n",
+    ":nth-child(odd of a) {\n  color: red;\n}\n"
+);
+error!(
+    a_n_plus_b_followed_by_ident_other_than_of,
+    ":nth-child(2n foo b) {\n  color: red;\n}\n", "Error: Expected \"of\"."
+);
 test!(
     a_n_plus_b_n_number_alone,
     ":nth-child(5) {\n  color: &;\n}\n",
@@ -845,3 +948,29 @@ error!(
     denies_optional_in_selector,
     "a !optional {}", "Error: expected \"{\"."
 );
+test!(
+    line_break_in_media_is_indented,
+    "@media screen {\n  a,\n  b {\n    color: red;\n  }\n}\n",
+    "@media screen {\n  a,\n  b {\n    color: red;\n  }\n}\n"
+);
+
+#[test]
+fn selector_line_breaks_disabled() {
+    let input = "a,\nb {\n  c,\n  d {\n    color: red;\n  }\n}\n";
+    let options = grass::Options::default().selector_line_breaks(false);
+    assert_eq!(
+        "a c, a d, b c, b d {\n  color: red;\n}\n",
+        grass::from_string(input.to_string(), &options).unwrap()
+    );
+}
+
+#[test]
+fn selector_line_breaks_disabled_in_media_and_extend() {
+    let input =
+        "@media screen {\n  a,\n  b {\n    color: red;\n  }\n}\n\nc,\nd {\n  @extend a;\n}\n";
+    let options = grass::Options::default().selector_line_breaks(false);
+    assert_eq!(
+        "@media screen {\n  a, c, d, b {\n    color: red;\n  }\n}\n",
+        grass::from_string(input.to_string(), &options).unwrap()
+    );
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "serde")]
+
+use grass::{Exports, SassValue, Separator};
+
+fn exports(input: &str) -> Exports {
+    grass::compile_with_reports(
+        grass::Input::String(input.to_string()),
+        &grass::Options::default(),
+        grass::ReportOptions::default().exports(true),
+    )
+    .expect(input)
+    .exports
+    .unwrap()
+}
+
+fn number(value: f64, unit: &str) -> SassValue {
+    SassValue::Number {
+        value,
+        unit: unit.to_string(),
+    }
+}
+
+#[test]
+fn serialize_number() {
+    assert_eq!(
+        r#"{"number":{"value":1.5,"unit":"px"}}"#,
+        serde_json::to_string(&number(1.5, "px")).unwrap()
+    );
+}
+
+#[test]
+fn serialize_null_and_bool() {
+    assert_eq!(
+        r#""null""#,
+        serde_json::to_string(&SassValue::Null).unwrap()
+    );
+    assert_eq!(
+        r#"{"bool":true}"#,
+        serde_json::to_string(&SassValue::Bool(true)).unwrap()
+    );
+}
+
+#[test]
+fn deserialize_list() {
+    let value: SassValue = serde_json::from_str(
+        r#"{"list": {"items": [{"number": {"value": 1, "unit": ""}}, {"string": {"text": "a", "quoted": true}}], "separator": "comma", "bracketed": false}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        SassValue::List {
+            items: vec![
+                number(1.0, ""),
+                SassValue::String {
+                    text: "a".to_string(),
+                    quoted: true,
+                }
+            ],
+            separator: Separator::Comma,
+            bracketed: false,
+        },
+        value
+    );
+}
+
+#[test]
+fn map_round_trips() {
+    let exports = exports("$a: (small: 4px, large: [8px 16px], color: #f00);\n");
+    let value = &exports.root.variables["a"];
+
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(*value, serde_json::from_str::<SassValue>(&json).unwrap());
+}
+
+#[test]
+fn exports_round_trip() {
+    let exports = exports("$a: 1px;\n$b: calc(1px + 2%);\n$c: get-function(\"lighten\");\n");
+
+    let json = serde_json::to_string(&exports).unwrap();
+    assert_eq!(exports, serde_json::from_str::<Exports>(&json).unwrap());
+    assert_eq!(
+        r#"{"root":{"variables":{"a":{"number":{"value":1.0,"unit":"px"}},"b":{"calculation":"calc(1px + 2%)"},"c":{"function":"lighten"}}},"modules":{}}"#,
+        json
+    );
+}
@@ -0,0 +1,33 @@
+#[test]
+fn shorthand_conflicts_do_not_change_output() {
+    let input = "@mixin reset {
+        margin: 0;
+    }
+
+    a {
+        margin-top: 1px;
+        @include reset;
+        padding: 0;
+        padding-left: 2px;
+    }";
+    let options = grass::Options::default().warn_shorthand_conflicts(true);
+    assert_eq!(
+        "a {\n  margin-top: 1px;\n  margin: 0;\n  padding: 0;\n  padding-left: 2px;\n}\n",
+        grass::from_string(input.to_string(), &options).unwrap()
+    );
+}
+
+#[test]
+fn shorthand_conflicts_in_nested_properties() {
+    let input = "a {
+        border-top-width: 1px;
+        border: {
+            style: solid;
+        }
+    }";
+    let options = grass::Options::default().warn_shorthand_conflicts(true);
+    assert_eq!(
+        "a {\n  border-top-width: 1px;\n  border-style: solid;\n}\n",
+        grass::from_string(input.to_string(), &options).unwrap()
+    );
+}
@@ -11,3 +11,32 @@ test!(
     "a {\n  color: simple-selectors(\".foo.bar.baz\");\n}\n",
     "a {\n  color: .foo, .bar, .baz;\n}\n"
 );
+test!(
+    type_and_pseudo_with_selector,
+    "a {\n  color: simple-selectors(\"a:not(.b, .c)[d]\");\n}\n",
+    "a {\n  color: a, :not(.b, .c), [d];\n}\n"
+);
+test!(
+    single_simple_selector,
+    "a {\n  color: simple-selectors(\".foo\");\n}\n",
+    "a {\n  color: .foo;\n}\n"
+);
+error!(
+    complex_selector,
+    "a {\n  color: simple-selectors(\".foo .bar\");\n}\n",
+    "Error: $selector: expected no more input."
+);
+error!(
+    selector_list,
+    "a {\n  color: simple-selectors(\".foo, .bar\");\n}\n",
+    "Error: $selector: expected no more input."
+);
+error!(
+    leading_combinator,
+    "a {\n  color: simple-selectors(\"> .foo\");\n}\n", "Error: $selector: expected selector."
+);
+error!(
+    parent_selector,
+    "a {\n  color: simple-selectors(\"&\");\n}\n",
+    "Error: $selector: Parent selectors aren't allowed here."
+);
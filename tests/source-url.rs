@@ -0,0 +1,135 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+fn webpack(url: &str) -> String {
+    format!("webpack:///{}", url)
+}
+
+#[test]
+fn rewrites_url_in_error_message() {
+    let err = grass::from_string(
+        "a {\n  color: ;\n}\n".to_string(),
+        &grass::Options::default().source_url_rewriter(&webpack),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        "webpack:///stdin:2:3",
+        err.to_string().lines().last().unwrap()
+    );
+    assert_eq!(Some("webpack:///stdin"), err.diagnostic().file.as_deref());
+}
+
+#[test]
+fn relative_url_in_error_message_is_prefixed() {
+    let strip = |url: &str| url.trim_start_matches("std").to_owned();
+    let err = grass::from_string(
+        "a {\n  color: ;\n}\n".to_string(),
+        &grass::Options::default().source_url_rewriter(&strip),
+    )
+    .unwrap_err();
+
+    assert_eq!("./in:2:3", err.to_string().lines().last().unwrap());
+}
+
+#[test]
+fn rewrites_url_of_warnings() {
+    let stats = grass::compile_with_reports(
+        grass::Input::String("a {\n  b {\n    @warn foo;\n  }\n}\n".to_string()),
+        &grass::Options::default()
+            .quiet(true)
+            .source_url_rewriter(&webpack),
+        grass::ReportOptions::default().stats(true),
+    )
+    .unwrap()
+    .stats
+    .unwrap();
+
+    assert_eq!(
+        Some("webpack:///stdin"),
+        stats.diagnostics[0].file.as_deref()
+    );
+    assert_eq!(
+        vec!["webpack:///stdin"],
+        stats.max_nesting_depth.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rewrites_url_in_selector_map() {
+    let mappings = grass::compile_with_reports(
+        grass::Input::String("a {\n  color: red;\n}\n".to_string()),
+        &grass::Options::default().source_url_rewriter(&webpack),
+        grass::ReportOptions::default().selector_map(true),
+    )
+    .unwrap()
+    .selector_map
+    .unwrap();
+
+    assert_eq!("webpack:///stdin", mappings[0].file);
+}
+
+#[test]
+fn rewrites_url_of_imported_file() {
+    let input = "@import \"source_url_imported\";\n";
+    tempfile!("source_url_imported.scss", "a {\n  color: ;\n}\n");
+
+    let err = grass::from_string(
+        input.to_string(),
+        &grass::Options::default().source_url_rewriter(&webpack),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        Some("webpack:///source_url_imported.scss"),
+        err.diagnostic().file.as_deref()
+    );
+}
+
+#[test]
+fn rewrites_url_of_used_file() {
+    let input = "@use \"source_url_used\";\n";
+    tempfile!("source_url_used.scss", "a {\n  color: ;\n}\n");
+
+    let err = grass::from_string(
+        input.to_string(),
+        &grass::Options::default().source_url_rewriter(&webpack),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        Some("webpack:///source_url_used.scss"),
+        err.diagnostic().file.as_deref()
+    );
+}
+
+// the determinism audit compiles each stylesheet several times
+#[cfg(not(feature = "determinism-audit"))]
+#[test]
+fn rewriter_is_called_for_each_loaded_file() {
+    let input = "@import \"source_url_called_each\";\n@import \"source_url_called_each\";\n";
+    tempfile!("source_url_called_each.scss", "a {\n  color: red;\n}\n");
+
+    let calls = std::cell::RefCell::new(Vec::new());
+    let record = |url: &str| {
+        calls.borrow_mut().push(url.to_owned());
+        url.to_owned()
+    };
+
+    grass::from_string(
+        input.to_string(),
+        &grass::Options::default().source_url_rewriter(&record),
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            "stdin",
+            "source_url_called_each.scss",
+            "source_url_called_each.scss"
+        ],
+        *calls.borrow()
+    );
+}
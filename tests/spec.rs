@@ -0,0 +1,332 @@
+#![cfg(feature = "spec")]
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use grass::MemoryFs;
+
+/// A single spec: a directory containing an input stylesheet, along with the
+/// CSS or error it is expected to produce
+struct Case {
+    /// The path of the spec relative to the root of the suite, such as
+    /// `core_functions/color/rgb/one_arg/alpha`
+    name: String,
+
+    /// The files of the archive or directory the spec is in, keyed by their
+    /// path within it
+    files: Rc<BTreeMap<String, String>>,
+
+    /// The path of the input stylesheet within `files`
+    input: String,
+
+    expected: Expected,
+}
+
+enum Expected {
+    Output(String),
+
+    /// The first line of the error message
+    Error(String),
+}
+
+enum Outcome {
+    Pass,
+    Fail(String),
+}
+
+/// Splits an [HRX archive](https://github.com/google/hrx) into its files,
+/// keyed by their path within the archive
+fn parse_hrx(archive: &str) -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+
+    let boundary = match archive.find('>') {
+        Some(end) if archive.starts_with("<=") => &archive[..=end],
+        _ => return files,
+    };
+
+    for entry in archive[boundary.len()..].split(&format!("\n{}", boundary)) {
+        let (header, contents) = match entry.find('\n') {
+            Some(idx) => (&entry[..idx], &entry[idx + 1..]),
+            None => (entry, ""),
+        };
+
+        let path = header.trim();
+
+        // entries without a path are comments
+        if path.is_empty() || path.ends_with('/') {
+            continue;
+        }
+
+        files.insert(path.to_owned(), contents.to_owned());
+    }
+
+    files
+}
+
+/// Whether `options.yml` marks the spec as not yet passing, or not applicable,
+/// in `dart-sass`
+fn is_ignored_for_dart_sass(options: &str) -> bool {
+    let mut key = "";
+    for line in options.lines() {
+        let line = line.trim();
+        if line.starts_with(':') {
+            key = line;
+        } else if (key == ":todo:" || key == ":ignore_for:") && line == "- dart-sass" {
+            return true;
+        }
+    }
+    false
+}
+
+/// Finds the specs in `files`, which are every directory that contains an
+/// input stylesheet and an expected output or error
+fn cases_in(name: &str, files: BTreeMap<String, String>) -> Vec<Case> {
+    let files = Rc::new(files);
+    let mut cases = Vec::new();
+
+    for input in files.keys() {
+        let dir = match input.strip_suffix("input.scss") {
+            Some(dir) => dir,
+            None => match input.strip_suffix("input.sass") {
+                Some(dir) => dir,
+                None => continue,
+            },
+        };
+        if !(dir.is_empty() || dir.ends_with('/')) {
+            continue;
+        }
+
+        if files
+            .get(&format!("{}options.yml", dir))
+            .map_or(false, |options| is_ignored_for_dart_sass(options))
+        {
+            continue;
+        }
+
+        let expected = if let Some(output) = files
+            .get(&format!("{}output-dart-sass.css", dir))
+            .or_else(|| files.get(&format!("{}output.css", dir)))
+        {
+            Expected::Output(output.clone())
+        } else if let Some(error) = files
+            .get(&format!("{}error-dart-sass", dir))
+            .or_else(|| files.get(&format!("{}error", dir)))
+        {
+            Expected::Error(first_line(error))
+        } else {
+            continue;
+        };
+
+        cases.push(Case {
+            name: format!("{}/{}", name, dir.trim_end_matches('/'))
+                .trim_end_matches('/')
+                .to_owned(),
+            files: Rc::clone(&files),
+            input: input.clone(),
+            expected,
+        });
+    }
+
+    cases
+}
+
+/// Collects the files within `dir`, keyed by their path relative to `root`
+fn read_dir_recursive(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            read_dir_recursive(root, &path, files);
+        } else if let Ok(contents) = fs::read_to_string(&path) {
+            let relative = path.strip_prefix(root).unwrap();
+            files.insert(relative.to_string_lossy().replace('\\', "/"), contents);
+        }
+    }
+}
+
+/// Finds every spec beneath `dir`, whether in an HRX archive or a directory
+fn collect_cases(root: &Path, dir: &Path, cases: &mut Vec<Case>) {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+
+    let relative = dir.strip_prefix(root).unwrap().to_string_lossy();
+
+    // a directory containing an input stylesheet is a single spec, even if
+    // its expected output is nested within it
+    if entries
+        .iter()
+        .any(|path| path.ends_with("input.scss") || path.ends_with("input.sass"))
+    {
+        let mut files = BTreeMap::new();
+        read_dir_recursive(dir, dir, &mut files);
+        cases.extend(cases_in(&relative, files));
+        return;
+    }
+
+    for path in entries {
+        if path.is_dir() {
+            collect_cases(root, &path, cases);
+        } else if path.extension().map_or(false, |ext| ext == "hrx") {
+            let name = path.strip_prefix(root).unwrap().with_extension("");
+            let archive = fs::read_to_string(&path).unwrap();
+            cases.extend(cases_in(&name.to_string_lossy(), parse_hrx(&archive)));
+        }
+    }
+}
+
+fn run(case: &Case) -> Outcome {
+    let mut fs = MemoryFs::default();
+    for (path, contents) in case.files.iter() {
+        fs.add_file(path, contents.as_str());
+    }
+    let options = grass::Options::default().fs(&fs).quiet(true);
+
+    let actual =
+        match panic::catch_unwind(AssertUnwindSafe(|| grass::from_path(&case.input, &options))) {
+            Ok(result) => result.map_err(|e| first_line(&e.to_string())),
+            Err(..) => return Outcome::Fail("grass panicked".to_owned()),
+        };
+
+    match (actual, &case.expected) {
+        (Ok(actual), Expected::Output(expected)) if actual.trim() == expected.trim() => {
+            Outcome::Pass
+        }
+        (Err(actual), Expected::Error(expected)) if &actual == expected => Outcome::Pass,
+        (Ok(actual), Expected::Output(expected)) => Outcome::Fail(format!(
+            "expected:\n{}\nfound:\n{}",
+            expected.trim_end(),
+            actual.trim_end()
+        )),
+        (Err(actual), Expected::Output(..)) => Outcome::Fail(format!("unexpected {}", actual)),
+        (Ok(..), Expected::Error(expected)) => {
+            Outcome::Fail(format!("expected {}, but compiled successfully", expected))
+        }
+        (Err(actual), Expected::Error(expected)) => {
+            Outcome::Fail(format!("expected {}\nfound {}", expected, actual))
+        }
+    }
+}
+
+fn first_line(s: &str) -> String {
+    s.lines().next().unwrap_or_default().trim_end().to_owned()
+}
+
+fn expected_failures_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/spec-expected-failures.txt")
+}
+
+/// The specs that are known to fail, read from `tests/spec-expected-failures.txt`
+///
+/// Each line names a spec or a directory of specs. Blank lines and lines
+/// starting with `#` are ignored.
+fn expected_failures() -> Vec<String> {
+    fs::read_to_string(expected_failures_path())
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.trim_end_matches('/').to_owned())
+        .collect()
+}
+
+fn is_listed(name: &str, list: &[String]) -> bool {
+    list.iter().any(|entry| {
+        name == entry || (name.starts_with(entry.as_str()) && name[entry.len()..].starts_with('/'))
+    })
+}
+
+#[test]
+fn spec() {
+    let root = env::var_os("SASS_SPEC").map_or_else(
+        || Path::new(env!("CARGO_MANIFEST_DIR")).join("sass-spec/spec"),
+        PathBuf::from,
+    );
+    if !root.is_dir() {
+        eprintln!(
+            "skipping spec test: set `SASS_SPEC` to the `spec` directory of a sass-spec checkout"
+        );
+        return;
+    }
+
+    let mut cases = Vec::new();
+    collect_cases(&root, &root, &mut cases);
+
+    if let Ok(filter) = env::var("GRASS_SPEC_FILTER") {
+        cases.retain(|case| case.name.starts_with(&filter));
+    }
+
+    let expected_failures = expected_failures();
+
+    let mut passed = 0;
+    let mut unexpected_failures = Vec::new();
+    let mut unexpected_passes = Vec::new();
+
+    for case in &cases {
+        let expected_to_fail = is_listed(&case.name, &expected_failures);
+        match run(case) {
+            Outcome::Pass => {
+                passed += 1;
+                if expected_to_fail {
+                    unexpected_passes.push(&case.name);
+                }
+            }
+            Outcome::Fail(reason) => {
+                if !expected_to_fail {
+                    unexpected_failures.push((&case.name, reason));
+                }
+            }
+        }
+    }
+
+    for (name, reason) in &unexpected_failures {
+        eprintln!("--- {}\n{}\n", name, reason);
+    }
+
+    // record the new failures as expected, e.g. when first adding the list or
+    // updating sass-spec
+    if env::var_os("GRASS_SPEC_BLESS").is_some() && !unexpected_failures.is_empty() {
+        let mut list = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(expected_failures_path())
+            .unwrap();
+        for (name, _) in &unexpected_failures {
+            writeln!(list, "{}", name).unwrap();
+        }
+        unexpected_failures.clear();
+    }
+
+    // specs listed individually can be removed from the list once they pass
+    for name in &unexpected_passes {
+        if expected_failures.contains(*name) {
+            eprintln!("now passing: {}", name);
+        }
+    }
+
+    eprintln!(
+        "PASSING: {}\nFAILING: {}\nTOTAL: {}\nCOMPATIBILITY: {:.2}%",
+        passed,
+        cases.len() - passed,
+        cases.len(),
+        if cases.is_empty() {
+            0.0
+        } else {
+            passed as f64 / cases.len() as f64 * 100.0
+        }
+    );
+
+    assert!(
+        unexpected_failures.is_empty(),
+        "{} specs failed that are not listed in tests/spec-expected-failures.txt",
+        unexpected_failures.len()
+    );
+}
@@ -4,7 +4,7 @@ mod macros;
 test!(
     calc_whitespace,
     "a {\n  color: calc(       1      );\n}\n",
-    "a {\n  color: calc( 1 );\n}\n"
+    "a {\n  color: 1;\n}\n"
 );
 test!(
     calc_newline,
@@ -17,9 +17,9 @@ test!(
     "a {\n  color: calc(1, 2, a, b, c);\n}\n"
 );
 test!(
-    calc_does_not_evaluate_arithmetic,
+    calc_simplifies_arithmetic,
     "a {\n  color: calc(1 + 2);\n}\n",
-    "a {\n  color: calc(1 + 2);\n}\n"
+    "a {\n  color: 3;\n}\n"
 );
 test!(
     calc_evaluates_interpolated_arithmetic,
@@ -42,19 +42,19 @@ test!(
     "a {\n  color: calc((((()))));\n}\n"
 );
 test!(
-    calc_invalid_arithmetic,
+    calc_partially_simplifies_incompatible_units,
     "a {\n  color: calc(2px + 2px + 5%);\n}\n",
-    "a {\n  color: calc(2px + 2px + 5%);\n}\n"
+    "a {\n  color: calc(4px + 5%);\n}\n"
 );
 test!(
     calc_uppercase,
-    "a {\n  color: CALC(1 + 1);\n}\n",
-    "a {\n  color: calc(1 + 1);\n}\n"
+    "a {\n  color: CALC(1px + 1%);\n}\n",
+    "a {\n  color: calc(1px + 1%);\n}\n"
 );
 test!(
     calc_mixed_casing,
-    "a {\n  color: cAlC(1 + 1);\n}\n",
-    "a {\n  color: calc(1 + 1);\n}\n"
+    "a {\n  color: cAlC(1px + 1%);\n}\n",
+    "a {\n  color: calc(1px + 1%);\n}\n"
 );
 test!(
     calc_browser_prefixed,
@@ -81,6 +81,73 @@ test!(
     "a {\n  color: calc(#);\n}\n",
     "a {\n  color: calc(#);\n}\n"
 );
+test!(
+    calc_substitutes_variable,
+    "$a: 10px;\na {\n  color: calc(100% - $a);\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_simplifies_arithmetic_with_variable,
+    "$a: 1px;\na {\n  color: calc($a + 2px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+error!(
+    calc_undefined_variable,
+    "a {\n  color: calc($a);\n}\n", "Error: Undefined variable."
+);
+test!(
+    calc_type_of,
+    "a {\n  color: type-of(calc(1px + 2%));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    calc_in_variable,
+    "$a: calc(1px + 2%);\na {\n  color: $a;\n}\n",
+    "a {\n  color: calc(1px + 2%);\n}\n"
+);
+test!(
+    calc_equality,
+    "a {\n  color: calc(1px + 2%) == calc(1px + 2%);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    calc_concatenated_with_string,
+    "a {\n  color: \"a\" + calc(1px + 2%);\n}\n",
+    "a {\n  color: \"acalc(1px + 2%)\";\n}\n"
+);
+error!(
+    calc_plus_number,
+    "a {\n  color: calc(1px + 2%) + 1;\n}\n", "Error: Undefined operation \"calc(1px + 2%) + 1\"."
+);
+error!(
+    number_minus_calc,
+    "a {\n  color: 1 - calc(1px + 2%);\n}\n", "Error: Undefined operation \"1 - calc(1px + 2%)\"."
+);
+test!(
+    clamp_simplifies_arguments,
+    "a {\n  color: clamp(1px + 2px, 50%, 3px);\n}\n",
+    "a {\n  color: clamp(3px, 50%, 3px);\n}\n"
+);
+test!(
+    clamp_substitutes_variables,
+    "$min: 1rem;\n$max: 3rem;\na {\n  color: clamp($min, 2vw, $max);\n}\n",
+    "a {\n  color: clamp(1rem, 2vw, 3rem);\n}\n"
+);
+test!(
+    clamp_type_of,
+    "a {\n  color: type-of(clamp(1px, 2%, 3px));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    min_type_of,
+    "a {\n  color: type-of(min(1px, 2%));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    element_does_not_substitute_variables,
+    "a {\n  color: element($a);\n}\n",
+    "a {\n  color: element($a);\n}\n"
+);
 test!(
     element_whitespace,
     "a {\n  color: element(       1      );\n}\n",
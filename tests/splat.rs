@@ -72,3 +72,25 @@ error!(
     }",
     "Error: 1 is not a string in (1: red)."
 );
+test!(
+    splat_map_followed_by_named_arg,
+    "@function foo($a, $b) {
+        @return $a $b;
+    }
+
+    a {
+        color: foo((b: 2)..., $a: 1);
+    }",
+    "a {\n  color: 1 2;\n}\n"
+);
+test!(
+    splat_list_followed_by_splat_map,
+    "@function foo($a, $b, $c) {
+        @return $a $b $c;
+    }
+
+    a {
+        color: foo((1, 2)..., (c: 3)...);
+    }",
+    "a {\n  color: 1 2 3;\n}\n"
+);
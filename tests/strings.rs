@@ -239,3 +239,83 @@ test!(
     "a {\n  color: \"#foo\";\n}\n",
     "a {\n  color: \"#foo\";\n}\n"
 );
+test!(
+    str_index_counts_code_points,
+    "a {\n  color: str-index(\"a😀b\", b);\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    str_insert_between_double_width_chars,
+    "a {\n  color: str-insert(\"😀😀\", \"X\", -2);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"😀X😀\";\n}\n"
+);
+test!(
+    str_slice_double_width_chars,
+    "a {\n  color: str-slice(\"a😀b😀\", 2, -2);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"😀b\";\n}\n"
+);
+test!(
+    uppercase_only_converts_ascii,
+    "a {\n  color: to-upper-case(\"aé\");\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"Aé\";\n}\n"
+);
+test!(
+    lowercase_only_converts_ascii,
+    "a {\n  color: to-lower-case(\"ÀB\");\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"Àb\";\n}\n"
+);
+test!(
+    quote_preserves_escape_in_identifier,
+    "a {\n  color: quote(a\\a b);\n}\n",
+    "a {\n  color: \"a\\\\a b\";\n}\n"
+);
+test!(
+    quote_escaped_quote_in_identifier,
+    "a {\n  color: quote(\\\"x);\n}\n",
+    "a {\n  color: '\\\\\"x';\n}\n"
+);
+test!(
+    unquote_preserves_backslash,
+    "a {\n  color: unquote(\"\\\\61\");\n}\n",
+    "a {\n  color: \\61;\n}\n"
+);
+test!(
+    quote_unquote_round_trips_escapes,
+    "a {\n  color: quote(unquote(\"a\\\\\\\"b\"));\n}\n",
+    "a {\n  color: 'a\\\\\"b';\n}\n"
+);
+test!(
+    module_split,
+    "@use 'sass:string';\na {\n  color: inspect(string.split(\"a b c\", \" \"));\n}\n",
+    "a {\n  color: [\"a\", \"b\", \"c\"];\n}\n"
+);
+test!(
+    module_split_unquoted,
+    "@use 'sass:string';\na {\n  color: inspect(string.split(a-b-c, \"-\"));\n}\n",
+    "a {\n  color: [a, b, c];\n}\n"
+);
+test!(
+    module_split_limit,
+    "@use 'sass:string';\na {\n  color: inspect(string.split(\"a b c\", \" \", 1));\n}\n",
+    "a {\n  color: [\"a\", \"b c\"];\n}\n"
+);
+test!(
+    module_split_empty_separator,
+    "@use 'sass:string';\na {\n  color: inspect(string.split(abc, \"\"));\n}\n",
+    "a {\n  color: [a, b, c];\n}\n"
+);
+test!(
+    module_split_empty_string,
+    "@use 'sass:string';\na {\n  color: inspect(string.split(\"\", \",\"));\n}\n",
+    "a {\n  color: [];\n}\n"
+);
+error!(
+    module_split_limit_zero,
+    "@use 'sass:string';\na {\n  color: string.split(\"a b\", \" \", 0);\n}\n",
+    "Error: $limit: Must be 1 or greater, was 0."
+);
+test!(
+    module_index,
+    "@use 'sass:string';\na {\n  color: string.index(abcd, c);\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
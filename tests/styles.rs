@@ -33,7 +33,7 @@ test!(
 );
 test!(
     removes_empty_outer_styles,
-    "a {\n  b {\n    color: red;\n  }\n",
+    "a {\n  b {\n    color: red;\n  }\n}\n",
     "a b {\n  color: red;\n}\n"
 );
 test!(removes_empty_styles, "a {}\n", "");
@@ -198,3 +198,43 @@ test!(
     "a {\n  color  /**/  : red;\n}\n",
     "a {\n  color: red;\n}\n"
 );
+test!(
+    declarations_after_nested_rule_are_hoisted,
+    "a {\n  b: c;\n  d {\n    e: f;\n  }\n  g: h;\n}\n",
+    "a {\n  b: c;\n  g: h;\n}\na d {\n  e: f;\n}\n"
+);
+test!(
+    nested_rules_emitted_in_source_order,
+    "a {\n  b {\n    c {\n      x: 1;\n    }\n    y: 2;\n  }\n  z {\n    w: 3;\n  }\n}\n",
+    "a b {\n  y: 2;\n}\na b c {\n  x: 1;\n}\na z {\n  w: 3;\n}\n"
+);
+test!(
+    declarations_from_mixin_keep_include_order,
+    "@mixin m {\n  x: 1;\n  y {\n    z: 2;\n  }\n  w: 3;\n}\na {\n  b: c;\n  @include m;\n  g: h;\n}\n",
+    "a {\n  b: c;\n  x: 1;\n  w: 3;\n  g: h;\n}\na y {\n  z: 2;\n}\n"
+);
+test!(
+    declarations_from_content_block_keep_content_order,
+    "@mixin m {\n  @content;\n  q: r;\n}\na {\n  b: c;\n  @include m {\n    d {\n      e: f;\n    }\n    s: t;\n  }\n  g: h;\n}\n",
+    "a {\n  b: c;\n  s: t;\n  q: r;\n  g: h;\n}\na d {\n  e: f;\n}\n"
+);
+test!(
+    rules_from_repeated_mixin_are_not_merged,
+    "@mixin m {\n  b {\n    c: d;\n  }\n}\na {\n  @include m;\n  e {\n    f: g;\n  }\n  @include m;\n}\n",
+    "a b {\n  c: d;\n}\na e {\n  f: g;\n}\na b {\n  c: d;\n}\n"
+);
+test!(
+    declarations_from_control_flow_keep_order,
+    "a {\n  @if true {\n    b: c;\n    d {\n      e: f;\n    }\n  }\n  g: h;\n  @each $i in 1, 2 {\n    i#{$i}: $i;\n    .x#{$i} {\n      y: $i;\n    }\n  }\n}\n",
+    "a {\n  b: c;\n  g: h;\n  i1: 1;\n  i2: 2;\n}\na d {\n  e: f;\n}\na .x1 {\n  y: 1;\n}\na .x2 {\n  y: 2;\n}\n"
+);
+test!(
+    nested_rules_and_at_rules_interleaved,
+    "a {\n  b {\n    c: d;\n  }\n  @media screen {\n    e: f;\n  }\n  g {\n    h: i;\n  }\n}\n",
+    "a b {\n  c: d;\n}\n@media screen {\n  a {\n    e: f;\n  }\n}\na g {\n  h: i;\n}\n"
+);
+test!(
+    childless_at_rule_stays_in_place,
+    "a {\n  b: c;\n  @foo bar;\n  d: e;\n}\n",
+    "a {\n  b: c;\n  @foo bar;\n  d: e;\n}\n"
+);
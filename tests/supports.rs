@@ -14,3 +14,160 @@ test!(
     }",
     "@supports (a: b) {\n  a {\n    color: red;\n  }\n}\na {\n  color: green;\n}\n"
 );
+test!(
+    declaration_value_is_evaluated,
+    "@supports (a: 1 + 1) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (a: 2) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    declaration_name_is_evaluated,
+    "$prop: width;
+    @supports ($prop: 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (width: 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    interpolated_declaration,
+    "$value: 2px;
+    @supports (a: #{$value}) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (a: 2px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    interpolated_condition,
+    "@supports #{\"(a: b)\"} {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (a: b) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    whitespace_is_normalized,
+    "@supports   (a:b)
+      and   (c :d) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (a: b) and (c: d) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    not_condition,
+    "@supports not (a: b) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports not (a: b) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_conditions,
+    "@supports ((a: b) or (c: d)) and (not (e: f)) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports ((a: b) or (c: d)) and (not (e: f)) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    negated_nested_condition,
+    "@supports not ((a: b) and (c: d)) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports not ((a: b) and (c: d)) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    function_condition,
+    "@supports selector(a > #{b}) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports selector(a > b) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    custom_property_value_is_not_evaluated,
+    "@supports (--a: 1 + 1) {
+        a {
+            b: c;
+        }
+    }",
+    "@supports (--a: 1 + 1) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_in_style_rule,
+    "a {
+        @supports (x: y) {
+            b: c;
+
+            d {
+                e: f;
+            }
+        }
+    }",
+    "@supports (x: y) {\n  a {\n    b: c;\n  }\n  a d {\n    e: f;\n  }\n}\n"
+);
+test!(
+    nested_in_media_in_style_rule,
+    "@media screen {
+        a {
+            @supports (x: y) {
+                b: c;
+            }
+        }
+    }",
+    "@media screen {\n  @supports (x: y) {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(
+    nested_supports_are_not_merged,
+    "@supports (a: b) {
+        @supports (c: d) {
+            a {
+                b: c;
+            }
+        }
+    }",
+    "@supports (a: b) {\n  @supports (c: d) {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(
+    empty_body_is_not_emitted,
+    "@supports (a: b) {}\n\na {\n  b: c;\n}\n",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    body_of_empty_style_rules_is_not_emitted,
+    "@supports (a: b) {\n  a {}\n}\n",
+    ""
+);
+test!(
+    nested_in_media_with_empty_body_is_not_emitted,
+    "@media screen {\n  @supports (a: b) {\n    a {}\n  }\n}\n",
+    ""
+);
+test!(
+    body_with_only_comment_is_emitted,
+    "@supports (a: b) {\n  /* c */\n}\n",
+    "@supports (a: b) {\n  /* c */\n}\n"
+);
+error!(
+    mixed_operators_without_parens,
+    "@supports (a: b) and (c: d) or (e: f) {}", "Error: expected \"{\"."
+);
+error!(empty_condition, "@supports {}", "Error: Expected \"not\".");
+error!(
+    missing_closing_paren,
+    "@supports (a: b {}", "Error: expected \")\"."
+);
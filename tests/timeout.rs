@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+#[test]
+fn infinite_while_loop_times_out() {
+    let input = "$a: 0;\n@while true {\n  $a: $a + 1;\n}\n";
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().timeout(Duration::from_millis(50)),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Compilation timed out.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn infinite_mixin_loop_times_out() {
+    let input =
+        "@mixin foo {\n  @while true {\n    a {\n      color: red;\n    }\n  }\n}\n@include foo;\n";
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().timeout(Duration::from_millis(50)),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Compilation timed out.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn fast_compilation_is_within_timeout() {
+    let input = "@for $i from 1 through 10 {\n  .a-#{$i} {\n    width: $i * 10px;\n  }\n}\n";
+    assert!(grass::from_string(
+        input.to_string(),
+        &grass::Options::default().timeout(Duration::from_secs(60)),
+    )
+    .is_ok());
+}
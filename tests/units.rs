@@ -181,7 +181,6 @@ error!(
     "a {\n  color: (1 / 1em);\n}\n", "Error: 1em^-1 isn't a valid CSS value."
 );
 error!(
-    #[ignore = "non-comparable inverse units"]
     display_single_div_with_non_comparable_numerator,
     "a {\n  color: (1px / 1em);\n}\n", "Error: 1px/em isn't a valid CSS value."
 );
@@ -198,6 +197,87 @@ error!(
     display_single_div_with_none_numerator_percent,
     "a {\n  color: (35 / 7%);\n}\n", "Error: 5%^-1 isn't a valid CSS value."
 );
+error!(
+    display_div_with_none_numerator_and_mul_denominator,
+    "a {\n  color: 1 / (1em * 1px);\n}\n", "Error: 1(em*px)^-1 isn't a valid CSS value."
+);
+error!(
+    display_mul_numerator_and_denominator,
+    "a {\n  color: 1px * 1px / 1s / 1s;\n}\n", "Error: 1px*px/s*s isn't a valid CSS value."
+);
+error!(
+    add_compound_units_converts,
+    "a {\n  color: (1in * 1s) + (96px * 1000ms);\n}\n", "Error: 2in*s isn't a valid CSS value."
+);
+error!(
+    add_incompatible_compound_units,
+    "a {\n  color: (1px * 1s) + (1px * 1px);\n}\n", "Error: Incompatible units px*px and px*s."
+);
+test!(
+    unit_div_non_comparable,
+    "a {\n  color: unit(1px / 1s);\n}\n",
+    "a {\n  color: \"px/s\";\n}\n"
+);
+test!(
+    unit_div_mul_by_denominator,
+    "a {\n  color: (1px / 1s) * 2s;\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    unit_mul_div_cancels_numerator,
+    "a {\n  color: (6px * 1s) / 2s;\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    unit_mul_div_cancels_compatible_numerator,
+    "a {\n  color: 1px * 1s / 1ms;\n}\n",
+    "a {\n  color: 1000px;\n}\n"
+);
+test!(
+    unit_mul_div_cancels_compatible_denominator,
+    "a {\n  color: (1px / 1ms) * 1s;\n}\n",
+    "a {\n  color: 1000px;\n}\n"
+);
+test!(
+    unit_div_mul_by_same_unit,
+    "a {\n  color: unit((1px * 1px) / 1px);\n}\n",
+    "a {\n  color: \"px\";\n}\n"
+);
+test!(
+    unit_mul_compound_both_sides,
+    "a {\n  color: unit((1em / 1s) * (1s / 1px));\n}\n",
+    "a {\n  color: \"em/px\";\n}\n"
+);
+test!(
+    compound_units_equal_after_conversion,
+    "a {\n  color: (1in * 1s) == (96px * 1000ms);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    compound_units_comparable,
+    "a {\n  color: comparable(1px * 1s, 1in * 1ms);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    compound_units_not_comparable,
+    "a {\n  color: comparable(1px * 1s, 1px * 1px);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    in_plus_px_equals_in,
+    "a {\n  color: 1in + 96px == 2in;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    modulo_converts_units,
+    "a {\n  color: 1in % 50px;\n}\n",
+    "a {\n  color: 0.4791666667in;\n}\n"
+);
+test!(
+    x_plus_dpi,
+    "a {\n  color: 1x + 96dpi;\n}\n",
+    "a {\n  color: 2x;\n}\n"
+);
 
 macro_rules! test_unit_addition {
     ($u1:ident, $u2:ident, $out:literal) => {
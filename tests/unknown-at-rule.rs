@@ -33,3 +33,89 @@ test!(
     "@foo (a: b) {\n  a {\n    color: red;\n  }\n}\na {\n  color: green;\n}\n"
 );
 test!(contains_multiline_comment, "@foo /**/;\n", "@foo;\n");
+test!(
+    no_body_with_params_followed_by_rule,
+    "@foo bar;\na {\n  color: red;\n}\n",
+    "@foo bar;\na {\n  color: red;\n}\n"
+);
+test!(
+    no_body_inside_style_rule,
+    "a {\n  @foo bar;\n  color: red;\n}\n",
+    "a {\n  @foo bar;\n  color: red;\n}\n"
+);
+test!(
+    no_body_params_contain_quoted_semicolon,
+    "a {\n  @foo \"b;{c\";\n}\n",
+    "a {\n  @foo \"b;{c\";\n}\n"
+);
+test!(empty_body, "@foo {}\n", "@foo {}\n");
+test!(
+    empty_body_inside_style_rule,
+    "a {\n  @foo {}\n}\n",
+    "@foo {}\n"
+);
+test!(
+    font_face_inside_style_rule,
+    "a {\n  @font-face {\n    font-family: x;\n  }\n  color: red;\n}\n",
+    "a {\n  color: red;\n}\n@font-face {\n  font-family: x;\n}\n"
+);
+test!(
+    font_face_from_mixin_inside_style_rule,
+    "@mixin font {\n  @font-face {\n    font-family: x;\n    src: url(x.woff);\n  }\n}\n\na {\n  b {\n    @include font;\n  }\n}\n",
+    "@font-face {\n  font-family: x;\n  src: url(x.woff);\n}\n"
+);
+test!(
+    font_face_from_mixin_inside_media,
+    "@mixin font {\n  @font-face {\n    font-family: x;\n  }\n\n  b {\n    color: red;\n  }\n}\n\n@media screen {\n  a {\n    @include font;\n  }\n}\n",
+    "@media screen {\n  @font-face {\n    font-family: x;\n  }\n  a b {\n    color: red;\n  }\n}\n"
+);
+test!(
+    font_face_nested_style_rule_keeps_parent_selector,
+    "a {\n  @font-face {\n    font-family: x;\n\n    b {\n      color: red;\n    }\n  }\n}\n",
+    "@font-face {\n  font-family: x;\n  a b {\n    color: red;\n  }\n}\n"
+);
+test!(
+    quoted_params_are_interpolated,
+    "$a: b;\n@foo \"#{$a}\" 'c#{1 + 1}';\n",
+    "@foo \"b\" \"c2\";\n"
+);
+test!(
+    silent_comment_in_params,
+    "@foo bar // baz\n  qux;\n",
+    "@foo bar qux;\n"
+);
+test!(
+    loud_comment_in_params,
+    "@foo bar /* baz */ qux;\n",
+    "@foo bar /* baz */ qux;\n"
+);
+test!(
+    url_in_params,
+    "@foo url(http://example.com) {\n  a: b;\n}\n",
+    "@foo url(http://example.com) {\n  a: b;\n}\n"
+);
+test!(
+    body_order_is_kept,
+    "@foo {\n  a {\n    b: c;\n  }\n  d: e;\n}\n",
+    "@foo {\n  a {\n    b: c;\n  }\n  d: e;\n}\n"
+);
+test!(
+    no_body_inside_unknown_at_rule_inside_style_rule,
+    "a {\n  @foo {\n    @bar;\n    b: c;\n  }\n}\n",
+    "@foo {\n  a {\n    @bar;\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_inside_style_rule,
+    "a {\n  @foo {\n    @bar {\n      b: c;\n    }\n  }\n}\n",
+    "@foo {\n  @bar {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(
+    media_inside_unknown_at_rule_inside_style_rule,
+    "a {\n  @foo {\n    @media screen {\n      b: c;\n    }\n  }\n}\n",
+    "@foo {\n  @media screen {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(
+    inside_media_inside_style_rule,
+    "a {\n  @media screen {\n    @foo {\n      b: c;\n    }\n  }\n}\n",
+    "@media screen {\n  @foo {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
@@ -337,3 +337,164 @@ fn use_variable_declaration_between_use() {
         &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
     );
 }
+
+#[test]
+fn use_outside_import_root() {
+    let input = "@use \"use_outside_import_root\";";
+    tempfile!("use_outside_import_root.scss", "$a: red;");
+    match grass::from_string(input.to_string(), &grass::Options::default().import_roots(&[])) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: Can't import \"use_outside_import_root\": it is outside of the allowed import directories.",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn module_function_uses_module_variables() {
+    let input = "@use \"module_function_uses_module_variables\" as m;\n$a: outer;\na {\n  color: m.get();\n}\n";
+    tempfile!(
+        "module_function_uses_module_variables.scss",
+        "$a: inner;\n@function get() {\n  @return $a;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: inner;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_function_uses_private_module_variables() {
+    let input =
+        "@use \"module_function_uses_private_module_variables\" as m;\na {\n  color: m.get();\n}\n";
+    tempfile!(
+        "module_function_uses_private_module_variables.scss",
+        "$-a: red;\n@function get() {\n  @return $-a;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_function_sees_reassigned_module_variable() {
+    let input = "@use \"module_function_sees_reassigned_module_variable\" as m;\nm.$a: blue;\na {\n  color: m.get();\n}\n";
+    tempfile!(
+        "module_function_sees_reassigned_module_variable.scss",
+        "$a: red;\n@function get() {\n  @return $a;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_function_calls_private_module_function() {
+    let input =
+        "@use \"module_function_calls_private_module_function\" as m;\na {\n  color: m.get(3);\n}\n";
+    tempfile!(
+        "module_function_calls_private_module_function.scss",
+        "@function -double($n) {\n  @return $n * 2;\n}\n@function get($n) {\n  @if $n <= 0 {\n    @return 0;\n  }\n  @return -double($n) + get($n - 1);\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: 12;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_function_assigns_global_module_variable() {
+    let input = "@use \"module_function_assigns_global_module_variable\" as m;\n$a: outer;\na {\n  b: m.set(inner);\n  c: m.$a;\n  d: $a;\n}\n";
+    tempfile!(
+        "module_function_assigns_global_module_variable.scss",
+        "$a: null;\n@function set($value) {\n  $a: $value !global;\n  @return $value;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  b: inner;\n  c: inner;\n  d: outer;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_function_reference_uses_module_variables() {
+    let input = "@use \"module_function_reference_uses_module_variables\" as m;\n$a: outer;\na {\n  color: call(get-function(get, $module: m));\n}\n";
+    tempfile!(
+        "module_function_reference_uses_module_variables.scss",
+        "$a: inner;\n@function get() {\n  @return $a;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: inner;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn use_cycle() {
+    let input = "@use \"use_cycle_a\";";
+    tempfile!("use_cycle_a.scss", "@use \"use_cycle_b\";");
+    tempfile!("use_cycle_b.scss", "@use \"use_cycle_a\";");
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: This file is already being loaded: use_cycle_a.scss → use_cycle_b.scss → use_cycle_a.scss",
+            e.to_string().lines().next().unwrap()
+        ),
+    }
+}
+
+#[test]
+fn module_is_only_evaluated_once() {
+    let input = "@use \"module_is_only_evaluated_once_a\";\n@use \"module_is_only_evaluated_once_b\";\n@use \"./module_is_only_evaluated_once_a\" as a2;\n";
+    tempfile!(
+        "module_is_only_evaluated_once_a.scss",
+        "a {\n  color: red;\n}\n"
+    );
+    tempfile!(
+        "module_is_only_evaluated_once_b.scss",
+        "@use \"module_is_only_evaluated_once_a\";\nb {\n  color: blue;\n}\n"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_loaded_twice_shares_variables() {
+    let input = "@use \"module_loaded_twice_shares_variables\" as a;\n@use \"./module_loaded_twice_shares_variables\" as b;\na.$a: green;\nc {\n  color: b.$a;\n}\n";
+    tempfile!("module_loaded_twice_shares_variables.scss", "$a: red;");
+    assert_eq!(
+        "c {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn configure_module_that_was_already_loaded() {
+    let input = "@use \"configure_module_that_was_already_loaded\";\n@use \"./configure_module_that_was_already_loaded\" as b with ($a: green);\n";
+    tempfile!(
+        "configure_module_that_was_already_loaded.scss",
+        "$a: red !default;"
+    );
+    assert_err!(
+        "Error: This module was already loaded, so it can't be configured using \"with\".",
+        input
+    );
+}
+
+#[test]
+fn default_namespace_is_basename_of_url() {
+    let input = "@use \"default_namespace_is_basename_of_url/a\";\nb {\n  color: a.$a;\n}\n";
+    tempfile!(
+        "_a.scss",
+        "$a: red;",
+        dir = "default_namespace_is_basename_of_url"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
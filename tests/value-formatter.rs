@@ -0,0 +1,120 @@
+use grass::{SassValue, ValueFormatter};
+
+#[derive(Debug)]
+struct RgbColors;
+
+impl ValueFormatter for RgbColors {
+    fn format(&self, value: &SassValue) -> Option<String> {
+        match value {
+            SassValue::Color {
+                red,
+                green,
+                blue,
+                alpha,
+            } if *alpha == 1.0 => Some(format!("rgb({}, {}, {})", red, green, blue)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AssetUrls;
+
+impl ValueFormatter for AssetUrls {
+    fn format(&self, value: &SassValue) -> Option<String> {
+        match value {
+            SassValue::String {
+                text,
+                quoted: false,
+            } if text.starts_with("url(") => Some(text.replace("url(", "url(/assets/")),
+            _ => None,
+        }
+    }
+}
+
+fn format(input: &str, formatter: &dyn ValueFormatter) -> String {
+    grass::from_string(
+        input.to_string(),
+        &grass::Options::default().value_formatter(formatter),
+    )
+    .expect(input)
+}
+
+#[test]
+fn replaces_value() {
+    assert_eq!(
+        "a {\n  color: rgb(255, 0, 0);\n}\n",
+        format("a {\n  color: red;\n}\n", &RgbColors)
+    );
+}
+
+#[test]
+fn declined_values_are_unchanged() {
+    assert_eq!(
+        "a {\n  color: rgba(255, 0, 0, 0.5);\n  width: 1px;\n}\n",
+        format(
+            "a {\n  color: rgba(red, 0.5);\n  width: 1px;\n}\n",
+            &RgbColors
+        )
+    );
+}
+
+#[test]
+fn replaces_items_of_lists() {
+    assert_eq!(
+        "a {\n  border: 1px solid rgb(0, 0, 255), 2px dashed rgb(0, 128, 0);\n}\n",
+        format(
+            "a {\n  border: 1px solid blue, 2px dashed green;\n}\n",
+            &RgbColors
+        )
+    );
+}
+
+#[test]
+fn replaces_values_of_nested_properties() {
+    assert_eq!(
+        "a {\n  border-color: rgb(255, 0, 0);\n}\n",
+        format("a {\n  border: {\n    color: red;\n  }\n}\n", &RgbColors)
+    );
+}
+
+#[test]
+fn replaces_values_from_mixins() {
+    assert_eq!(
+        "a {\n  color: rgb(255, 0, 0);\n}\n",
+        format(
+            "@mixin foo {\n  color: red;\n}\n\na {\n  @include foo;\n}\n",
+            &RgbColors
+        )
+    );
+}
+
+#[test]
+fn replaces_values_in_keyframes() {
+    assert_eq!(
+        "@keyframes foo {\n  to {\n    color: rgb(255, 0, 0);\n  }\n}\n",
+        format(
+            "@keyframes foo {\n  to {\n    color: red;\n  }\n}\n",
+            &RgbColors
+        )
+    );
+}
+
+#[test]
+fn rewrites_urls() {
+    assert_eq!(
+        "a {\n  background: url(/assets/a.png) no-repeat;\n}\n",
+        format("a {\n  background: url(a.png) no-repeat;\n}\n", &AssetUrls)
+    );
+}
+
+#[test]
+fn does_not_affect_variables() {
+    assert_eq!(
+        "a {\n  color: rgb(255, 0, 0);\n  b: true;\n}\n",
+        format(
+            "$a: red;\n\na {\n  color: $a;\n  b: type-of($a) == color;\n}\n",
+            &RgbColors
+        )
+    );
+}
@@ -0,0 +1,124 @@
+use std::{fs, io};
+
+use grass::{write_css, WriteOptions};
+
+const CSS: &str = "a {\n  color: red;\n}\n";
+
+#[test]
+fn writes_new_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+
+    write_css(&path, CSS, &WriteOptions::default()).unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn overwrites_existing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+    fs::write(&path, "a much longer stylesheet that is entirely replaced").unwrap();
+
+    write_css(&path, CSS, &WriteOptions::default()).unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn missing_directory_is_error_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("css/a.css");
+
+    let err = write_css(&path, CSS, &WriteOptions::default()).unwrap_err();
+
+    assert_eq!(io::ErrorKind::NotFound, err.kind());
+}
+
+#[test]
+fn create_dirs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("css/min/a.css");
+
+    write_css(&path, CSS, &WriteOptions::default().create_dirs(true)).unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn no_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+    fs::write(&path, "b {}").unwrap();
+
+    let err = write_css(&path, CSS, &WriteOptions::default().overwrite(false)).unwrap_err();
+
+    assert_eq!(io::ErrorKind::AlreadyExists, err.kind());
+    assert_eq!("b {}", fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn no_overwrite_writes_new_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+
+    write_css(&path, CSS, &WriteOptions::default().overwrite(false)).unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn atomic_replaces_file_and_leaves_no_temporary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+    fs::write(&path, "b {}").unwrap();
+
+    write_css(&path, CSS, &WriteOptions::default().atomic(true)).unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+    assert_eq!(1, fs::read_dir(dir.path()).unwrap().count());
+}
+
+#[test]
+fn atomic_no_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a.css");
+    fs::write(&path, "b {}").unwrap();
+
+    let err = write_css(
+        &path,
+        CSS,
+        &WriteOptions::default().atomic(true).overwrite(false),
+    )
+    .unwrap_err();
+
+    assert_eq!(io::ErrorKind::AlreadyExists, err.kind());
+    assert_eq!("b {}", fs::read_to_string(&path).unwrap());
+    assert_eq!(1, fs::read_dir(dir.path()).unwrap().count());
+}
+
+#[test]
+fn atomic_missing_directory_leaves_no_temporary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("css/a.css");
+
+    let err = write_css(&path, CSS, &WriteOptions::default().atomic(true)).unwrap_err();
+
+    assert_eq!(io::ErrorKind::NotFound, err.kind());
+    assert_eq!(0, fs::read_dir(dir.path()).unwrap().count());
+}
+
+#[test]
+fn atomic_create_dirs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("css/a.css");
+
+    write_css(
+        &path,
+        CSS,
+        &WriteOptions::default().atomic(true).create_dirs(true),
+    )
+    .unwrap();
+
+    assert_eq!(CSS, fs::read_to_string(&path).unwrap());
+}